@@ -100,11 +100,23 @@ impl HttpEngine for ActixEngine {
 
         let server = ActixHttpServer::new(move || {
             let endpoint = endpoint.clone();
-            ActixApp::new()
+            let mut app = ActixApp::new()
                 .app_data(Data::new(ctx.clone()))
                 .route(&endpoint, web::post().to(post_handler))
                 .route(&endpoint, web::delete().to(delete_handler))
-                .route(&endpoint, web::get().to(get_handler))
+                .route(&endpoint, web::get().to(get_handler));
+            // The probes and the admin endpoint sit outside the MCP endpoint,
+            // and each path is `None` unless the server enables it.
+            if let Some(path) = ctx.liveness_path() {
+                app = app.route(path, web::get().to(liveness_handler));
+            }
+            if let Some(path) = ctx.readiness_path() {
+                app = app.route(path, web::get().to(readiness_handler));
+            }
+            if let Some(path) = ctx.admin_path() {
+                app = app.route(path, web::get().to(admin_handler));
+            }
+            app
         })
         .bind(addr.as_str())
         .map_err(|e| Error::new(ErrorCode::InternalError, e.to_string()))?
@@ -151,6 +163,18 @@ async fn post_handler(
             builder.streaming(stream)
         }
         StreamResponse::Complete(resp) => ActixEngine::adapt_response(resp),
+        StreamResponse::Body { headers, body } => {
+            let mut builder = ActixHttpResponse::Ok();
+            for (name, value) in headers.iter() {
+                if let (Ok(n), Ok(v)) = (
+                    actix_web::http::header::HeaderName::from_bytes(name.as_str().as_bytes()),
+                    actix_web::http::header::HeaderValue::from_bytes(value.as_bytes()),
+                ) {
+                    builder.append_header((n, v));
+                }
+            }
+            builder.streaming(body)
+        }
     }
 }
 
@@ -188,10 +212,41 @@ async fn get_handler(
             builder.streaming(stream)
         }
         StreamResponse::Complete(resp) => ActixEngine::adapt_response(resp),
+        StreamResponse::Body { headers, body } => {
+            let mut builder = ActixHttpResponse::Ok();
+            for (name, value) in headers.iter() {
+                if let (Ok(n), Ok(v)) = (
+                    actix_web::http::header::HeaderName::from_bytes(name.as_str().as_bytes()),
+                    actix_web::http::header::HeaderValue::from_bytes(value.as_bytes()),
+                ) {
+                    builder.append_header((n, v));
+                }
+            }
+            builder.streaming(body)
+        }
     }
 }
 
 /// Translate a neva engine-adapter `Error` into a 500 actix response.
+async fn liveness_handler() -> ActixHttpResponse {
+    ActixEngine::adapt_response(handlers::handle_liveness())
+}
+
+async fn readiness_handler(ctx: Data<HttpContext>) -> ActixHttpResponse {
+    ActixEngine::adapt_response(handlers::handle_readiness(&ctx))
+}
+
+async fn admin_handler(
+    ctx: Data<HttpContext>,
+    req: ActixHttpRequest,
+    body: ActixBytes,
+) -> ActixHttpResponse {
+    match ActixEngine::adapt_request((req, body)).await {
+        Ok(req) => ActixEngine::adapt_response(handlers::handle_admin(req, &ctx).await),
+        Err(e) => internal_error(e),
+    }
+}
+
 fn internal_error(err: Error) -> ActixHttpResponse {
     ActixHttpResponse::InternalServerError().body(err.to_string())
 }
@@ -205,7 +260,9 @@ async fn hello(name: String) -> String {
 async fn main() {
     tracing_subscriber::fmt::init();
 
-    let http = HttpServer::from_engine("127.0.0.1:3000", ActixEngine).with_endpoint("/mcp");
+    let http = HttpServer::from_engine("127.0.0.1:3000", ActixEngine)
+        .with_endpoint("/mcp")
+        .with_health_probes(|probes| probes);
 
     App::new()
         .with_options(|opt| opt.with_name("Actix Example Server").set_http(http))
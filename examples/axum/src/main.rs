@@ -22,7 +22,7 @@ use axum::{
         IntoResponse, Response,
         sse::{Event, KeepAlive, Sse},
     },
-    routing::post,
+    routing::{get, post},
 };
 use http_body_util::BodyExt;
 use neva::prelude::*;
@@ -88,12 +88,22 @@ impl HttpEngine for AxumEngine {
         let addr = ctx.addr().to_owned();
         let endpoint = ctx.endpoint().to_owned();
 
-        let app = Router::new()
-            .route(
-                &endpoint,
-                post(post_handler).get(get_handler).delete(delete_handler),
-            )
-            .with_state(ctx);
+        let mut app = Router::new().route(
+            &endpoint,
+            post(post_handler).get(get_handler).delete(delete_handler),
+        );
+        // The probes and the admin endpoint sit outside the MCP endpoint, and
+        // each path is `None` unless the server enables it.
+        if let Some(path) = ctx.liveness_path() {
+            app = app.route(path, get(liveness_handler));
+        }
+        if let Some(path) = ctx.readiness_path() {
+            app = app.route(path, get(readiness_handler));
+        }
+        if let Some(path) = ctx.admin_path() {
+            app = app.route(path, get(admin_handler));
+        }
+        let app = app.with_state(ctx);
 
         let listener = tokio::net::TcpListener::bind(&addr)
             .await
@@ -124,6 +134,11 @@ async fn post_handler(State(ctx): State<HttpContext>, req: axum::http::Request<B
             response
         }
         StreamResponse::Complete(resp) => AxumEngine::adapt_response(resp),
+        StreamResponse::Body { headers, body } => {
+            let mut response = Body::from_stream(body).into_response();
+            response.headers_mut().extend(headers);
+            response
+        }
     }
 }
 
@@ -148,6 +163,26 @@ async fn get_handler(State(ctx): State<HttpContext>, req: http::Request<Body>) -
             response
         }
         StreamResponse::Complete(resp) => AxumEngine::adapt_response(resp),
+        StreamResponse::Body { headers, body } => {
+            let mut response = Body::from_stream(body).into_response();
+            response.headers_mut().extend(headers);
+            response
+        }
+    }
+}
+
+async fn liveness_handler() -> Response {
+    AxumEngine::adapt_response(handlers::handle_liveness())
+}
+
+async fn readiness_handler(State(ctx): State<HttpContext>) -> Response {
+    AxumEngine::adapt_response(handlers::handle_readiness(&ctx))
+}
+
+async fn admin_handler(State(ctx): State<HttpContext>, req: http::Request<Body>) -> Response {
+    match AxumEngine::adapt_request(req).await {
+        Ok(req) => AxumEngine::adapt_response(handlers::handle_admin(req, &ctx).await),
+        Err(e) => internal_error(e),
     }
 }
 
@@ -169,7 +204,9 @@ async fn hello(name: String) -> String {
 async fn main() {
     tracing_subscriber::fmt::init();
 
-    let http = HttpServer::from_engine("127.0.0.1:3000", AxumEngine).with_endpoint("/mcp");
+    let http = HttpServer::from_engine("127.0.0.1:3000", AxumEngine)
+        .with_endpoint("/mcp")
+        .with_health_probes(|probes| probes);
 
     App::new()
         .with_options(|opt| opt.with_name("Axum Example Server").set_http(http))
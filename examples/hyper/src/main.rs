@@ -17,7 +17,8 @@
 use std::convert::Infallible;
 
 use bytes::Bytes;
-use http_body_util::{BodyExt, Empty, Full, StreamBody, combinators::BoxBody};
+use futures_util::StreamExt;
+use http_body_util::{BodyExt, Empty, Full, StreamBody, combinators::UnsyncBoxBody};
 use hyper::{
     Method,
     body::{Frame, Incoming},
@@ -29,9 +30,10 @@ use neva::prelude::*;
 use tokio_util::sync::CancellationToken;
 
 /// Boxed body type used uniformly by every response this engine builds --
-/// `BoxBody` lets the SSE-streaming branch and the buffered-JSON branch
-/// share a single response type.
-type BoxedBody = BoxBody<Bytes, Infallible>;
+/// `UnsyncBoxBody` lets the SSE-streaming branch and the buffered-JSON branch
+/// share a single response type. The error is what aborts a streamed JSON
+/// body that cannot be completed.
+type BoxedBody = UnsyncBoxBody<Bytes, std::io::Error>;
 
 /// HTTP engine backed by raw [hyper](https://docs.rs/hyper).
 ///
@@ -63,7 +65,7 @@ impl HttpEngine for HyperEngine {
         let (parts, body) = resp.into_parts();
         let boxed = Full::new(body)
             .map_err(|never: Infallible| match never {})
-            .boxed();
+            .boxed_unsync();
         http::Response::from_parts(parts, boxed)
     }
 
@@ -115,6 +117,23 @@ impl HttpEngine for HyperEngine {
 
 /// Per-request dispatch: gate on the configured endpoint path and method.
 async fn dispatch(req: http::Request<Incoming>, ctx: HttpContext) -> http::Response<BoxedBody> {
+    // The probes and the admin endpoint sit outside the MCP endpoint, and
+    // each path is `None` unless the server enables it.
+    if req.method() == Method::GET {
+        let path = Some(req.uri().path());
+        if path == ctx.liveness_path() {
+            return HyperEngine::adapt_response(handlers::handle_liveness());
+        }
+        if path == ctx.readiness_path() {
+            return HyperEngine::adapt_response(handlers::handle_readiness(&ctx));
+        }
+        if path == ctx.admin_path() {
+            return match HyperEngine::adapt_request(req).await {
+                Ok(req) => HyperEngine::adapt_response(handlers::handle_admin(req, &ctx).await),
+                Err(_) => status_only(http::StatusCode::INTERNAL_SERVER_ERROR),
+            };
+        }
+    }
     if req.uri().path() != ctx.endpoint() {
         return status_only(http::StatusCode::NOT_FOUND);
     }
@@ -128,7 +147,9 @@ async fn dispatch(req: http::Request<Incoming>, ctx: HttpContext) -> http::Respo
             };
             match outcome {
                 StreamResponse::Stream { headers, stream } => {
-                    let body = StreamBody::new(stream).boxed();
+                    let body = StreamBody::new(stream)
+                        .map_err(|never: Infallible| match never {})
+                        .boxed_unsync();
                     let mut resp = http::Response::builder()
                         .status(http::StatusCode::OK)
                         .header(http::header::CONTENT_TYPE, "text/event-stream")
@@ -140,6 +161,7 @@ async fn dispatch(req: http::Request<Incoming>, ctx: HttpContext) -> http::Respo
                     resp
                 }
                 StreamResponse::Complete(resp) => HyperEngine::adapt_response(resp),
+                StreamResponse::Body { headers, body } => body_response(headers, body),
             }
         }
         Method::DELETE => handlers::dispatch_delete::<HyperEngine>(req, &ctx)
@@ -152,7 +174,9 @@ async fn dispatch(req: http::Request<Incoming>, ctx: HttpContext) -> http::Respo
             };
             match outcome {
                 StreamResponse::Stream { headers, stream } => {
-                    let body = StreamBody::new(stream).boxed();
+                    let body = StreamBody::new(stream)
+                        .map_err(|never: Infallible| match never {})
+                        .boxed_unsync();
                     let mut resp = http::Response::builder()
                         .status(http::StatusCode::OK)
                         .header(http::header::CONTENT_TYPE, "text/event-stream")
@@ -164,16 +188,28 @@ async fn dispatch(req: http::Request<Incoming>, ctx: HttpContext) -> http::Respo
                     resp
                 }
                 StreamResponse::Complete(resp) => HyperEngine::adapt_response(resp),
+                StreamResponse::Body { headers, body } => body_response(headers, body),
             }
         }
         _ => status_only(http::StatusCode::METHOD_NOT_ALLOWED),
     }
 }
 
+/// A `200 OK` whose JSON body is written as neva produces it.
+fn body_response(headers: http::HeaderMap, body: BodyStream) -> http::Response<BoxedBody> {
+    let body = StreamBody::new(body.map(|chunk| chunk.map(Frame::data))).boxed_unsync();
+    let mut resp = http::Response::builder()
+        .status(http::StatusCode::OK)
+        .body(body)
+        .expect("valid response");
+    resp.headers_mut().extend(headers);
+    resp
+}
+
 fn status_only(status: http::StatusCode) -> http::Response<BoxedBody> {
     let body = Empty::<Bytes>::new()
         .map_err(|never| match never {})
-        .boxed();
+        .boxed_unsync();
     http::Response::builder()
         .status(status)
         .body(body)
@@ -189,7 +225,9 @@ async fn hello(name: String) -> String {
 async fn main() {
     tracing_subscriber::fmt::init();
 
-    let http = HttpServer::from_engine("127.0.0.1:3000", HyperEngine).with_endpoint("/mcp");
+    let http = HttpServer::from_engine("127.0.0.1:3000", HyperEngine)
+        .with_endpoint("/mcp")
+        .with_health_probes(|probes| probes);
 
     App::new()
         .with_options(|opt| opt.with_name("Hyper Example Server").set_http(http))
@@ -17,12 +17,16 @@ async fn resource_meta(name: String) -> ResourceContents {
 }
 
 #[resource(uri = "file://{name}")]
-async fn resource_data(uri: Uri, name: String) -> ResourceContents {
-    // get resource from somewhere
+async fn resource_data(uri: Uri, name: String) -> BlobStream {
+    // Read the resource from somewhere as it is sent, rather than into memory
+    // first: any `AsyncRead` or stream of byte chunks will do.
+    use tokio::io::AsyncReadExt;
+    let reader = tokio::io::repeat(b'x').take(SIZE);
 
-    ResourceContents::new(uri.clone())
-        .with_title(name.clone())
-        .with_blob("large file")
+    BlobStream::new(uri, reader)
+        .with_size(SIZE)
+        .with_title(name)
+        .with_mime("application/octet-stream")
 }
 
 #[tool]
@@ -35,12 +39,15 @@ async fn get_file_info(ctx: Context, name: String) -> Result<Content, Error> {
         .next()
         .ok_or_else(missing)
         .and_then(|r| r.json::<Resource>())
-        .map(|r| Content::link(r))
+        .map(Content::link)
 }
 
+/// The size of every file this server serves, in bytes.
+const SIZE: u64 = 64 * 1024 * 1024;
+
 fn get_res_info(uri: Uri, name: String) -> Resource {
     Resource::new(uri, name)
-        .with_size(100000)
+        .with_size(SIZE as usize)
         .with_mime("application/octet-stream")
         .with_descr("Large file")
 }
@@ -52,6 +59,7 @@ async fn main() {
             opt.with_name("Large resource example server")
                 .with_default_http()
         })
+        .with_max_blob_size(256 * 1024 * 1024)
         .run()
         .await;
 }
@@ -125,6 +125,28 @@ async fn route(
         ));
     }
 
+    // The probes and the admin endpoint: outside the authorization below,
+    // and each path is `None` unless the server enables it.
+    if method == http::Method::GET {
+        let path = Some(path.as_str());
+        if path == ctx.liveness_path() {
+            return Ok(HyperEngine::adapt_response(handlers::handle_liveness()));
+        }
+        if path == ctx.readiness_path() {
+            return Ok(HyperEngine::adapt_response(handlers::handle_readiness(
+                &ctx,
+            )));
+        }
+        if path == ctx.admin_path() {
+            let Ok(req) = HyperEngine::adapt_request(req).await else {
+                return Ok(status(http::StatusCode::INTERNAL_SERVER_ERROR));
+            };
+            return Ok(HyperEngine::adapt_response(
+                handlers::handle_admin(req, &ctx).await,
+            ));
+        }
+    }
+
     if path != ctx.endpoint() {
         return Ok(status(http::StatusCode::NOT_FOUND));
     }
@@ -157,6 +179,8 @@ async fn route(
         }
         http::Method::GET => match handlers::handle_get_sse::<HyperEngine>(neutral, &ctx).await {
             StreamResponse::Complete(resp) => HyperEngine::adapt_response(resp),
+            // Only a `POST` reply is ever a streamed JSON body.
+            StreamResponse::Body { .. } => status(http::StatusCode::INTERNAL_SERVER_ERROR),
             StreamResponse::Stream { headers, stream } => {
                 let body = StreamBody::new(stream.map(|event| Ok(Frame::data(event))));
                 let mut resp = http::Response::builder()
@@ -12,12 +12,13 @@ use crate::error::{Error, ErrorCode};
 use crate::middleware::{MwContext, Next, make_fn::make_mw};
 use crate::transport::{Receiver, Sender, Transport};
 use crate::types::{
-    CallToolRequestParams, CallToolResponse, CompleteResult, FromHandlerArgs,
-    GetPromptRequestParams, GetPromptResult, IntoResponse, ListPromptsRequestParams,
-    ListPromptsResult, ListResourceTemplatesRequestParams, ListResourceTemplatesResult,
-    ListResourcesRequestParams, ListResourcesResult, ListToolsRequestParams, ListToolsResult,
-    Message, MessageBatch, MessageEnvelope, Prompt, PromptHandler, ReadResourceRequestParams,
-    ReadResourceResult, Request, Resource, ResourceTemplate, Response, Tool, ToolHandler, Uri,
+    CallToolRequestParams, CompleteRequestParams, CompleteResult, FromHandlerArgs,
+    GetPromptRequestParams, GetPromptResult, IntoResponse, IntoToolResponse,
+    ListPromptsRequestParams, ListPromptsResult, ListResourceTemplatesRequestParams,
+    ListResourceTemplatesResult, ListResourcesRequestParams, ListResourcesResult,
+    ListToolsRequestParams, ListToolsResult, Message, MessageBatch, MessageEnvelope, Prompt,
+    PromptHandler, ReadResourceRequestParams, ReadResourceResult, Request, Resource,
+    ResourceTemplate, Response, Tool, ToolHandler, Uri,
     notification::{CancelledNotificationParams, Notification},
     resource::template::ResourceFunc,
 };
@@ -35,7 +36,6 @@ use crate::types::{SubscribeRequestParams, UnsubscribeRequestParams};
 #[cfg(not(feature = "legacy-spec"))]
 use tokio_util::sync::CancellationToken;
 
-#[cfg(not(feature = "legacy-spec"))]
 use self::shutdown::DEFAULT_SHUTDOWN_DRAIN;
 
 #[cfg(not(feature = "legacy-spec"))]
@@ -71,23 +71,33 @@ use volga_di::{Container, ContainerBuilder};
 mod collection;
 mod commands;
 pub mod context;
+#[cfg(feature = "directory")]
+pub mod directory;
 mod dispatch;
 #[cfg(not(feature = "legacy-spec"))]
 pub mod extension;
 mod greeter;
 pub(crate) mod handler;
+#[cfg(feature = "manifest")]
+pub mod manifest;
 #[cfg(not(feature = "legacy-spec"))]
 mod mrtr;
 #[cfg(not(feature = "legacy-spec"))]
 pub mod mrtr_store;
 #[cfg(not(feature = "legacy-spec"))]
 pub mod notification_bus;
+#[cfg(feature = "openapi")]
+pub mod openapi;
 pub mod options;
 pub mod shutdown;
+#[cfg(feature = "http-server")]
+mod status;
 #[cfg(not(feature = "legacy-spec"))]
 pub(crate) mod subscriptions;
+#[cfg(feature = "server-validation")]
+mod validation;
 
-pub use shutdown::ShutdownHandle;
+pub use shutdown::{ShutdownHandle, ShutdownSummary};
 
 const DEFAULT_PAGE_SIZE: usize = 10;
 
@@ -112,10 +122,18 @@ pub struct App {
     /// [`ShutdownHandle`] the caller kept.
     shutdown: ShutdownHandle,
 
-    /// Ceiling on the wait for live subscriptions to answer before the
-    /// transport is torn down. See [`DEFAULT_SHUTDOWN_DRAIN`].
-    #[cfg(not(feature = "legacy-spec"))]
+    /// Ceiling on the wait for in-flight work to finish before the transport
+    /// is torn down. See [`DEFAULT_SHUTDOWN_DRAIN`].
     shutdown_drain: std::time::Duration,
+
+    /// Where prompts, static resources and tool metadata are loaded from,
+    /// if anywhere.
+    #[cfg(feature = "manifest")]
+    manifest: Option<manifest::ManifestOptions>,
+
+    /// Directories served as resources.
+    #[cfg(feature = "directory")]
+    directories: Vec<directory::DirectoryOptions>,
 }
 
 impl Debug for App {
@@ -142,8 +160,11 @@ impl App {
             #[cfg(feature = "di")]
             container: ContainerBuilder::new(),
             shutdown: ShutdownHandle::new(),
-            #[cfg(not(feature = "legacy-spec"))]
             shutdown_drain: DEFAULT_SHUTDOWN_DRAIN,
+            #[cfg(feature = "manifest")]
+            manifest: None,
+            #[cfg(feature = "directory")]
+            directories: Vec::new(),
         };
 
         #[cfg(feature = "legacy-spec")]
@@ -266,13 +287,9 @@ impl App {
     /// still stops on Ctrl+C.
     ///
     /// Await [`run`](Self::run) to know the server actually finished: this
-    /// only requests the stop.
-    #[cfg_attr(
-        not(feature = "legacy-spec"),
-        doc = "
-Under MCP 2026-07-28 that gap is where live `subscriptions/listen` streams get
-answered, bounded by [`with_shutdown_drain`](Self::with_shutdown_drain)."
-    )]
+    /// only requests the stop. That gap is the drain, bounded by
+    /// [`with_shutdown_drain`](Self::with_shutdown_drain), and
+    /// [`ShutdownHandle::summary`] reports what it left behind.
     ///
     /// # Example
     /// ```no_run
@@ -318,32 +335,128 @@ answered, bounded by [`with_shutdown_drain`](Self::with_shutdown_drain)."
         self
     }
 
-    /// Caps how long shutdown waits for live `subscriptions/listen` streams to
-    /// answer before the transport is torn down anyway.
+    /// Caps how long shutdown waits for in-flight work to finish before the
+    /// transport is torn down anyway.
+    ///
+    /// From the moment shutdown is requested new requests are refused; the
+    /// drain is for the ones already running, for running tasks, and under
+    /// MCP 2026-07-28 for live `subscriptions/listen` streams to send their
+    /// final notice and answer. Requests still running when it ends are
+    /// abandoned, and tasks are failed with the shutdown as their reason --
+    /// both are counted in the [`ShutdownSummary`].
     ///
-    /// This is a ceiling, not a delay: the wait ends the moment the last
-    /// result is queued, and is skipped outright when no subscription is open.
-    /// Raise it for a server whose subscriptions have deep buffers to flush;
-    /// `Duration::ZERO` opts out and restores an abrupt close.
+    /// This is a ceiling, not a delay: the wait ends the moment nothing is
+    /// running, which is immediate for an idle server. Raise it for a server
+    /// whose requests or tasks take longer to finish; `Duration::ZERO` opts
+    /// out and restores an abrupt close.
     ///
     /// Default: 2 seconds.
     ///
     /// # Example
     /// ```no_run
-    /// # #[cfg(not(feature = "legacy-spec"))] {
     /// use neva::App;
     /// use std::time::Duration;
     ///
     /// let app = App::new()
     ///     .with_shutdown_drain(Duration::from_secs(5));
-    /// # }
     /// ```
-    #[cfg(not(feature = "legacy-spec"))]
     pub fn with_shutdown_drain(mut self, drain: std::time::Duration) -> Self {
         self.shutdown_drain = drain;
         self
     }
 
+    /// Loads prompts, static resources and tool metadata from a manifest
+    /// directory, and keeps them in step with it while the server runs.
+    ///
+    /// Manifest prompts and resources are registered the way
+    /// [`App::map_prompt`] and [`App::add_resource`] register theirs; tool
+    /// entries describe tools registered in code. A change to the directory is
+    /// applied as a diff, with the `list_changed` notifications it calls for --
+    /// which is why watching turns `list_changed` on. See the
+    /// [`manifest`](crate::app::manifest) module for the file format.
+    ///
+    /// Default: no manifest.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use neva::App;
+    ///
+    /// let app = App::new()
+    ///     .with_manifest(|manifest| manifest.with_dir("./prompts"));
+    /// ```
+    #[cfg(feature = "manifest")]
+    pub fn with_manifest<F>(mut self, config: F) -> Self
+    where
+        F: FnOnce(manifest::ManifestOptions) -> manifest::ManifestOptions,
+    {
+        self.manifest = Some(config(Default::default()));
+        self
+    }
+
+    /// Serves the files under `root` as resources.
+    ///
+    /// Every file is listed with its size and MIME type and read as
+    /// `file://` followed by its path under `root`; text is read as text and
+    /// anything else streamed as a blob. Reads never leave `root`, symlinks
+    /// included. Changes on disk are picked up while the server runs and
+    /// reach clients as `list_changed` and `resources/updated` -- which is
+    /// why watching turns both on. See the
+    /// [`directory`](crate::app::directory) module for the patterns `config`
+    /// takes.
+    ///
+    /// Call it again to serve more directories, each under a prefix of its
+    /// own. A root that does not exist at startup is not served.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use neva::App;
+    ///
+    /// let app = App::new()
+    ///     .with_directory("./docs", |dir| dir.with_include("*.md"));
+    /// ```
+    #[cfg(feature = "directory")]
+    pub fn with_directory<F>(mut self, root: impl Into<std::path::PathBuf>, config: F) -> Self
+    where
+        F: FnOnce(directory::DirectoryOptions) -> directory::DirectoryOptions,
+    {
+        self.directories
+            .push(config(directory::DirectoryOptions::new(root.into())));
+        self
+    }
+
+    /// Registers a tool for every operation of an OpenAPI 3.x document,
+    /// calling the API it describes over HTTP.
+    ///
+    /// Each tool takes the operation's parameters and request body as its
+    /// arguments and is annotated after its HTTP method. See the
+    /// [`openapi`](crate::app::openapi) module for how schemas and names are
+    /// derived, and [`OpenApiOptions`](openapi::OpenApiOptions) for what
+    /// `config` sets -- the base URL and the headers sent, the caller's
+    /// identity among them.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use neva::App;
+    /// use neva::app::openapi::OpenApi;
+    ///
+    /// # fn main() -> Result<(), neva::error::Error> {
+    /// let api = OpenApi::load("./billing.json")?;
+    /// let app = App::new()
+    ///     .with_openapi(api, |api| api.with_operations(["getInvoice", "listInvoices"]));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "openapi")]
+    pub fn with_openapi<F>(mut self, api: openapi::OpenApi, config: F) -> Self
+    where
+        F: FnOnce(openapi::OpenApiOptions) -> openapi::OpenApiOptions,
+    {
+        for tool in api.tools(config(Default::default())) {
+            self.options.add_tool(tool);
+        }
+        self
+    }
+
     /// Run the MCP server
     ///
     /// # Example
@@ -367,6 +480,21 @@ answered, bounded by [`with_shutdown_drain`](Self::with_shutdown_drain)."
         // macro-registered tools are not in the collection before it.
         self.validate_arg_names();
 
+        // After register_methods() too, so code-registered names are known and
+        // keep precedence, and before the greeting so it lists what the
+        // manifest added.
+        #[cfg(feature = "manifest")]
+        let manifest = self
+            .manifest
+            .take()
+            .and_then(|config| manifest::ManifestWatcher::start(config, &mut self.options));
+        #[cfg(feature = "directory")]
+        let mut directories = Vec::new();
+        #[cfg(feature = "directory")]
+        for config in std::mem::take(&mut self.directories) {
+            directories.extend(directory::DirectoryWatcher::start(config, &mut self.options).await);
+        }
+
         // ORDERING CONSTRAINT: must execute after register_methods() so macro-registered
         // tools/prompts are present; must execute before self.options.transport() consumes
         // `proto` and before ServerRuntime::new() transitions collections to Runtime state
@@ -431,14 +559,27 @@ answered, bounded by [`with_shutdown_drain`](Self::with_shutdown_drain)."
         let greeted = self.greeting;
 
         let mut transport = self.options.transport();
+
+        // The HTTP transport's readiness probe answers from this: not ready
+        // until the runtime below is dispatching, and not ready again from the
+        // moment shutdown is requested -- well before the transport goes down.
+        #[cfg(feature = "http-server")]
+        let status = transport.server_status();
+        #[cfg(feature = "http-server")]
+        if let Some(status) = &status {
+            status.watch_shutdown(self.shutdown.token());
+        }
+
         let cancellation_token = transport.start();
 
         // Shutdown arrives here -- from an OS signal, or from a
         // `ShutdownHandle` the caller kept -- and is relayed to the transport
         // rather than being the transport's own token. What happens in between
-        // is the drain, below.
+        // is the drain, below. The dispatch path watches it directly, so new
+        // requests are refused from the moment it fires.
         let shutdown = self.shutdown.token();
         self.wait_for_shutdown_signal(shutdown.clone());
+        self.options.set_draining_token(shutdown);
 
         // Long-lived requests (`subscriptions/listen`) end one phase ahead of
         // the transport: they watch a token of their own, so their
@@ -450,18 +591,6 @@ answered, bounded by [`with_shutdown_drain`](Self::with_shutdown_drain)."
         #[cfg(not(feature = "legacy-spec"))]
         self.options.set_shutdown_token(subscriptions_token.clone());
 
-        #[cfg(not(feature = "legacy-spec"))]
-        Self::relay_shutdown(
-            shutdown,
-            subscriptions_token,
-            cancellation_token.clone(),
-            self.options.subscriptions().clone(),
-            self.options.in_flight(),
-            self.shutdown_drain,
-        );
-        #[cfg(feature = "legacy-spec")]
-        Self::relay_shutdown(shutdown, cancellation_token.clone());
-
         // With a notification bus installed, every subscribable notification --
         // this instance's own included -- comes back through the bus, and this
         // task is what turns it into a delivery to the subscribers this
@@ -511,6 +640,41 @@ answered, bounded by [`with_shutdown_drain`](Self::with_shutdown_drain)."
             #[cfg(feature = "di")]
             self.container.build(),
         );
+        #[cfg(feature = "http-server")]
+        if let Some(status) = status {
+            status.accept(Self::status_reporter(runtime.options()));
+        }
+
+        // Relayed once the runtime exists, since the drain reads its options.
+        // A signal that fired in the meantime is not lost: the relay starts
+        // from an already-cancelled token.
+        Self::relay_shutdown(
+            self.shutdown,
+            cancellation_token.clone(),
+            #[cfg(not(feature = "legacy-spec"))]
+            subscriptions_token,
+            runtime.options(),
+            self.shutdown_drain,
+        );
+
+        // Changes are applied through a context of their own, like a handler
+        // would apply them, so they notify clients the same way.
+        #[cfg(feature = "manifest")]
+        if let Some(manifest) = manifest {
+            #[cfg(feature = "http-server")]
+            let ctx = runtime.context(None, Default::default(), None);
+            #[cfg(not(feature = "http-server"))]
+            let ctx = runtime.context(None);
+            manifest.spawn(ctx, cancellation_token.clone());
+        }
+        #[cfg(feature = "directory")]
+        for directory in directories {
+            #[cfg(feature = "http-server")]
+            let ctx = runtime.context(None, Default::default(), None);
+            #[cfg(not(feature = "http-server"))]
+            let ctx = runtime.context(None);
+            directory.spawn(ctx, cancellation_token.clone());
+        }
         loop {
             tokio::select! {
                 biased;
@@ -690,6 +854,59 @@ answered, bounded by [`with_shutdown_drain`](Self::with_shutdown_drain)."
         self
     }
 
+    /// Sets the maximum size (bytes) of a [`BlobStream`](crate::types::BlobStream)
+    /// a resource handler may return.
+    ///
+    /// A blob that declares a larger size is answered with an error before
+    /// anything is sent; one of unknown size fails once it reads past the
+    /// limit. The limit also bounds the memory a blob takes where the response
+    /// has to be whole before it is sent.
+    ///
+    /// Default: no limit
+    ///
+    /// # Example
+    /// ```no_run
+    /// use neva::App;
+    ///
+    /// let app = App::new()
+    ///     .with_max_blob_size(512 * 1024 * 1024);
+    /// ```
+    pub fn with_max_blob_size(mut self, bytes: u64) -> Self {
+        self.options.set_max_blob_size(bytes);
+        self
+    }
+
+    /// Sets the policy that decides how an error a tool returns reaches the
+    /// client: as a [`CallToolResponse`](crate::types::CallToolResponse) with
+    /// `isError` set, which the model sees, or as a JSON-RPC error, which the
+    /// client handles.
+    ///
+    /// The policy is given the [`ErrorKind`](crate::error::ErrorKind) of each
+    /// `Err` a tool returns. An error in the call itself -- an unknown tool,
+    /// arguments that do not fit its schema -- is always a protocol error.
+    ///
+    /// Default: [`ToolErrorMode::for_kind`](crate::types::ToolErrorMode::for_kind)
+    ///
+    /// # Example
+    /// ```no_run
+    /// use neva::prelude::*;
+    /// use neva::error::ErrorKind;
+    ///
+    /// // Hide internal failures from the model
+    /// let app = App::new()
+    ///     .with_tool_error_policy(|kind| match kind {
+    ///         ErrorKind::Internal => ToolErrorMode::Protocol,
+    ///         kind => ToolErrorMode::for_kind(kind),
+    ///     });
+    /// ```
+    pub fn with_tool_error_policy<F>(mut self, policy: F) -> Self
+    where
+        F: Fn(&crate::error::ErrorKind) -> crate::types::ToolErrorMode + Send + Sync + 'static,
+    {
+        self.options.set_tool_error_policy(Arc::new(policy));
+        self
+    }
+
     /// Sets the maximum encoded `requestState` size (bytes). When a round-trip
     /// would emit a larger blob, the server returns an error result instead
     /// (MCP 2026-07-28).
@@ -951,7 +1168,7 @@ answered, bounded by [`with_shutdown_drain`](Self::with_shutdown_drain)."
     pub fn map_tool<F, R, Args>(&mut self, name: impl Into<String>, handler: F) -> &mut Tool
     where
         F: ToolHandler<Args, Output = R>,
-        R: Into<CallToolResponse> + Send + 'static,
+        R: IntoToolResponse + Send + 'static,
         Args: FromHandlerArgs<CallToolRequestParams> + Send + Sync + 'static,
     {
         self.options.add_tool(Tool::new(name, handler))
@@ -1040,6 +1257,9 @@ answered, bounded by [`with_shutdown_drain`](Self::with_shutdown_drain)."
 
     /// Maps an MCP resource read request to a specific function
     ///
+    /// It replaces the default listing, and with it the resources that
+    /// templates [enumerate](ResourceTemplate::with_enumerator).
+    ///
     /// # Example
     /// ```no_run
     /// use neva::{App, types::{Resource, ListResourcesRequestParams}};
@@ -1074,6 +1294,10 @@ answered, bounded by [`with_shutdown_drain`](Self::with_shutdown_drain)."
 
     /// Maps a completion request
     ///
+    /// It replaces the default completion, which offers the values a
+    /// template [enumerates](ResourceTemplate::with_enumerator) for its
+    /// variables.
+    ///
     /// # Example
     /// ```no_run
     /// use neva::{App, types::{CompleteRequestParams, CompleteResult}};
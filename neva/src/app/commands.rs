@@ -9,6 +9,8 @@
 //! `map_handler` replaces any of them under the same method name.
 
 use super::*;
+#[cfg(not(feature = "tasks"))]
+use crate::types::CallToolResponse;
 
 impl App {
     /// Connection initialization handler (legacy handshake).
@@ -30,9 +32,23 @@ impl App {
     }
 
     /// Completion request handler
-    pub(super) async fn completion() -> CompleteResult {
-        // return default as its non-optional capability so far
-        CompleteResult::default()
+    pub(super) async fn completion(
+        options: RuntimeMcpOptions,
+        params: CompleteRequestParams,
+    ) -> Result<CompleteResult, Error> {
+        // only resource templates that enumerate their resources complete
+        // out of the box; it is a non-optional capability so far
+        let template = match params.r#ref.uri.as_deref() {
+            Some(uri) => options.resource_template_by_uri(uri).await,
+            None => None,
+        };
+        let Some(template) = template else {
+            return Ok(CompleteResult::default());
+        };
+        let completion = template
+            .complete(&params.arg.name, &params.arg.value)
+            .await?;
+        Ok(CompleteResult::new(completion))
     }
 
     /// Tools request handler
@@ -57,16 +73,16 @@ impl App {
     pub(super) async fn resources(
         options: RuntimeMcpOptions,
         params: ListResourcesRequestParams,
-    ) -> ListResourcesResult {
+    ) -> Result<ListResourcesResult, Error> {
         let (resources, next_cursor) = options
             .list_resources_page(params.cursor, DEFAULT_PAGE_SIZE)
-            .await;
+            .await?;
 
-        ListResourcesResult {
+        Ok(ListResourcesResult {
             resources,
             next_cursor,
             ..Default::default()
-        }
+        })
     }
 
     /// Resource templates request handler
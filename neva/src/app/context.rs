@@ -69,14 +69,24 @@ pub(crate) type ToolOrTaskResponse = Either<CreateTaskResult, CallToolResponse>;
 
 type RequestHandlers = HashMap<String, RequestHandler<Response>>;
 
+mod agent;
 mod effects;
 mod interaction;
 mod listen;
 mod primitives;
+mod progress;
+mod sandbox;
 mod tasks;
 
+pub use agent::{AgentLoop, AgentRun};
 #[cfg(not(feature = "legacy-spec"))]
 pub(crate) use effects::MrtrCtx;
+pub use progress::Progress;
+#[cfg(feature = "legacy-spec")]
+pub(crate) use sandbox::RootsCache;
+#[cfg(feature = "directory")]
+pub(crate) use sandbox::percent_decode;
+pub use sandbox::{Sandbox, SandboxedPath};
 #[cfg(all(not(feature = "legacy-spec"), feature = "tasks"))]
 pub use tasks::TaskContext;
 #[cfg(all(not(feature = "legacy-spec"), feature = "tasks"))]
@@ -165,6 +175,13 @@ pub struct Context {
     /// Represents a timeout for the current request
     timeout: Duration,
 
+    /// The `progressToken` the current request carried, if any.
+    pub(crate) progress_token: Option<crate::types::ProgressToken>,
+
+    /// The client's roots, loaded before a tool that takes a
+    /// [`SandboxedPath`] runs.
+    pub(crate) sandbox: Option<Sandbox>,
+
     /// Execution substrate for this dispatch (set by the server dispatch layer:
     /// `Mrtr` for a stateless elicitable call, `Task` for a background
     /// task-augmented call, `None` otherwise).
@@ -223,7 +240,6 @@ impl ServerRuntime {
 
     /// Provides the counter of messages currently inside the middleware
     /// pipeline, which the shutdown drain waits on.
-    #[cfg(not(feature = "legacy-spec"))]
     pub(crate) fn in_flight(&self) -> Arc<std::sync::atomic::AtomicUsize> {
         self.options.in_flight()
     }
@@ -251,6 +267,8 @@ impl ServerRuntime {
             sender: self.sender.clone(),
             options: self.options.clone(),
             timeout: self.options.request_timeout,
+            progress_token: None,
+            sandbox: None,
             #[cfg(not(feature = "legacy-spec"))]
             exec: ExecMode::None,
             #[cfg(not(feature = "legacy-spec"))]
@@ -276,6 +294,8 @@ impl ServerRuntime {
             sender: self.sender.clone(),
             options: self.options.clone(),
             timeout: self.options.request_timeout,
+            progress_token: None,
+            sandbox: None,
             #[cfg(not(feature = "legacy-spec"))]
             exec: ExecMode::None,
             #[cfg(not(feature = "legacy-spec"))]
@@ -555,7 +575,7 @@ impl Context {
     /// a round trip the local case does not otherwise pay; the default is no
     /// bus, which delivers straight to the local registry.
     #[inline]
-    async fn send_notification(
+    pub(super) async fn send_notification(
         &mut self,
         method: &str,
         params: Option<serde_json::Value>,
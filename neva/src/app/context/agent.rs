@@ -0,0 +1,333 @@
+//! An agentic sampling loop: the client's model calls this server's tools
+//! until it has an answer.
+//!
+//! Each step samples the conversation so far, runs the tools the model asked
+//! for, and appends their results for the next step. Under MCP 2026-07-28 each
+//! step is its own MRTR input request and its tool results are memoized, so a
+//! re-run replays the steps already taken rather than repeating them; the
+//! legacy profile sends a `sampling/createMessage` request per step.
+
+use super::*;
+use crate::types::ProgressToken;
+use crate::types::sampling::{
+    CreateMessageRequestParams, CreateMessageResult, SamplingMessage, StopReason, ToolChoice,
+};
+use tokio_util::sync::CancellationToken;
+
+/// The step budget of an [`AgentLoop`] unless set otherwise.
+const DEFAULT_MAX_STEPS: usize = 8;
+
+/// A sampling loop that lets the client's model call this server's tools.
+///
+/// # Example
+/// ```no_run
+/// # #[cfg(all(feature = "server-macros", not(feature = "legacy-spec")))] {
+/// use neva::prelude::*;
+/// use neva::app::context::AgentLoop;
+/// use neva::types::sampling::CreateMessageRequestParams;
+///
+/// #[tool]
+/// async fn plan_trip(mut ctx: Context, city: String) -> Result<String, Error> {
+///     let params = CreateMessageRequestParams::new()
+///         .with_message(format!("Plan a day in {city} around the weather."))
+///         .with_max_tokens(1024);
+///     let agent = AgentLoop::new(params)
+///         .with_tools(["get_weather", "find_museums"])
+///         .with_max_steps(4);
+///
+///     # #[allow(deprecated)]
+///     let run = ctx.run_agent("plan", agent).await?;
+///     Ok(run.text())
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AgentLoop {
+    params: CreateMessageRequestParams,
+    tools: Vec<String>,
+    max_steps: usize,
+    progress: Option<ProgressToken>,
+    cancel: Option<CancellationToken>,
+}
+
+/// What an [`AgentLoop`] ended with.
+#[derive(Debug, Clone)]
+pub struct AgentRun {
+    /// The model's last response.
+    pub message: CreateMessageResult,
+
+    /// The whole conversation: the request's messages, then every response
+    /// and tool result in order, ending with [`Self::message`].
+    pub transcript: Vec<SamplingMessage>,
+
+    /// How many times the model was sampled.
+    pub steps: usize,
+
+    /// Whether the budget ran out while the model still asked for tools, so
+    /// that [`Self::message`] is a tool request rather than an answer.
+    pub exhausted: bool,
+}
+
+impl AgentLoop {
+    /// Creates a loop that starts from `params`: its messages, system prompt,
+    /// model preferences and any tools it already carries.
+    pub fn new(params: CreateMessageRequestParams) -> Self {
+        Self {
+            params,
+            tools: Vec::new(),
+            max_steps: DEFAULT_MAX_STEPS,
+            progress: None,
+            cancel: None,
+        }
+    }
+
+    /// Offers the model these tools of this server, by name.
+    ///
+    /// The model may call only the tools it was offered: these and those the
+    /// request already carries. A call to any other tool is answered with an
+    /// error result rather than run. Names this server has no tool for are
+    /// left out.
+    ///
+    /// Default: only the tools the request carries.
+    pub fn with_tools<T, I>(mut self, names: I) -> Self
+    where
+        T: Into<String>,
+        I: IntoIterator<Item = T>,
+    {
+        self.tools.extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets how many times the model may be sampled.
+    ///
+    /// The last step offers no tools, so that the model answers with what it
+    /// has. A budget of 0 is taken as 1.
+    ///
+    /// Default: 8
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps.max(1);
+        self
+    }
+
+    /// Reports each finished step as progress on `token`, out of the step
+    /// budget.
+    ///
+    /// Progress is sent as `notifications/progress` on the request's stream,
+    /// like [`Context::progress`] does. A step replayed on a later MRTR round
+    /// is reported again.
+    ///
+    /// Default: no progress is reported.
+    pub fn with_progress(mut self, token: ProgressToken) -> Self {
+        self.progress = Some(token);
+        self
+    }
+
+    /// Stops the loop with a [`RequestCancelled`](ErrorCode::RequestCancelled)
+    /// error once `token` is cancelled, abandoning a step in flight.
+    ///
+    /// Default: the loop runs until the model answers or the budget is spent.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+}
+
+impl AgentRun {
+    /// The text of the model's last response.
+    pub fn text(&self) -> String {
+        self.message
+            .text()
+            .map(|text| text.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl Context {
+    /// Runs an [`AgentLoop`]: samples the client's model, runs the tools it
+    /// asks for, and samples again with their results until the model
+    /// answers or the step budget is spent (MRTR, MCP 2026-07-28).
+    ///
+    /// Each step is an input request keyed `{key}/{step}`, and its tool
+    /// results are memoized under `{key}/{step}/tools`, so the steps already
+    /// taken replay on every round without calling the model or the tools
+    /// again. Every answer and memo rides in `requestState`, so a long loop
+    /// may need a larger [`App::with_max_state_bytes`](crate::App::with_max_state_bytes).
+    ///
+    /// # Deprecated on arrival
+    /// Built on [`Self::sample`], and deprecated with it.
+    #[cfg(not(feature = "legacy-spec"))]
+    #[deprecated(
+        note = "sampling is deprecated in MCP 2026-07-28; it returns as an MRTR input-request kind only for migration"
+    )]
+    pub async fn run_agent(
+        &mut self,
+        key: impl Into<String>,
+        agent: AgentLoop,
+    ) -> Result<AgentRun, Error> {
+        self.drive_agent(&key.into(), agent).await
+    }
+
+    /// Runs an [`AgentLoop`]: samples the client's model, runs the tools it
+    /// asks for, and samples again with their results until the model
+    /// answers or the step budget is spent.
+    #[cfg(feature = "legacy-spec")]
+    pub async fn run_agent(&mut self, agent: AgentLoop) -> Result<AgentRun, Error> {
+        self.drive_agent("", agent).await
+    }
+
+    async fn drive_agent(&mut self, key: &str, agent: AgentLoop) -> Result<AgentRun, Error> {
+        let AgentLoop {
+            mut params,
+            tools,
+            max_steps,
+            progress,
+            cancel,
+        } = agent;
+        let cancel = cancel.unwrap_or_default();
+        let progress = match progress {
+            Some(token) => self.progress_on(token).with_total(max_steps as f64),
+            None => Progress::disabled(),
+        };
+
+        if !tools.is_empty() {
+            let found = self.find_tools(tools.iter().map(String::as_str)).await;
+            params.tools.get_or_insert_with(Vec::new).extend(found);
+            params.tool_choice.get_or_insert_with(ToolChoice::auto);
+        }
+        let offered: Vec<String> = params
+            .tools
+            .iter()
+            .flatten()
+            .map(|tool| tool.name.clone())
+            .collect();
+
+        let mut transcript = std::mem::take(&mut params.messages);
+        for step in 0..max_steps {
+            let mut request = params.clone();
+            request.messages = transcript.clone();
+            if step + 1 == max_steps && !offered.is_empty() {
+                request.tool_choice = Some(ToolChoice::none());
+            }
+
+            let message = cancellable(&cancel, self.agent_step(key, step, request)).await?;
+            transcript.push(SamplingMessage {
+                role: message.role,
+                content: message.content.clone(),
+            });
+            progress.report((step + 1) as f64).await;
+
+            let uses: Vec<ToolUse> = message.tools().cloned().collect();
+            let finished = uses.is_empty() || message.stop_reason != Some(StopReason::ToolUse);
+            if finished || step + 1 == max_steps {
+                return Ok(AgentRun {
+                    exhausted: !finished,
+                    steps: step + 1,
+                    message,
+                    transcript,
+                });
+            }
+
+            let calls = self.agent_tools(key, step, uses, &offered);
+            let results = cancellable(&cancel, calls).await?;
+            let results = results
+                .into_iter()
+                .fold(SamplingMessage::user(), |message, result| {
+                    message.with(result)
+                });
+            transcript.push(results);
+        }
+        unreachable!("the last step always returns")
+    }
+
+    /// Samples one step of an agent loop.
+    async fn agent_step(
+        &mut self,
+        _key: &str,
+        _step: usize,
+        params: CreateMessageRequestParams,
+    ) -> Result<CreateMessageResult, Error> {
+        #[cfg(not(feature = "legacy-spec"))]
+        #[allow(deprecated)]
+        {
+            self.sample(format!("{_key}/{_step}"), params).await
+        }
+        #[cfg(feature = "legacy-spec")]
+        {
+            self.sample(params).await
+        }
+    }
+
+    /// Runs the tools one step of an agent loop asked for, refusing those the
+    /// model was not offered.
+    async fn agent_tools(
+        &self,
+        _key: &str,
+        _step: usize,
+        uses: Vec<ToolUse>,
+        offered: &[String],
+    ) -> Result<Vec<ToolResult>, Error> {
+        let calls = async {
+            let calls = uses.into_iter().map(|tool| async move {
+                if offered.contains(&tool.name) {
+                    self.use_tool(tool).await
+                } else {
+                    let err = Error::new(
+                        ErrorCode::InvalidParams,
+                        format!("tool `{}` is not available to this model", tool.name),
+                    );
+                    ToolResult::error(tool.id, err)
+                }
+            });
+            Ok(futures_util::future::join_all(calls).await)
+        };
+        #[cfg(not(feature = "legacy-spec"))]
+        {
+            self.memo(format!("{_key}/{_step}/tools"), calls).await
+        }
+        #[cfg(feature = "legacy-spec")]
+        {
+            calls.await
+        }
+    }
+}
+
+/// Awaits `fut` unless `cancel` fires first.
+async fn cancellable<T>(
+    cancel: &CancellationToken,
+    fut: impl Future<Output = Result<T, Error>>,
+) -> Result<T, Error> {
+    tokio::select! {
+        biased;
+        _ = cancel.cancelled() => Err(Error::new(
+            ErrorCode::RequestCancelled,
+            "the agent loop was cancelled",
+        )),
+        res = fut => res,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn a_cancelled_loop_abandons_its_step() {
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+
+        let res: Result<(), Error> = cancellable(&cancel, std::future::pending()).await;
+        assert_eq!(res.unwrap_err().code, ErrorCode::RequestCancelled);
+    }
+
+    #[test]
+    fn it_builds_an_agent_loop() {
+        let agent = AgentLoop::new(CreateMessageRequestParams::new())
+            .with_tools(["a", "b"])
+            .with_max_steps(0);
+
+        assert_eq!(agent.tools, ["a", "b"]);
+        assert_eq!(agent.max_steps, 1);
+        assert!(agent.progress.is_none());
+    }
+}
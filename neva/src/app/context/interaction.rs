@@ -340,7 +340,7 @@ impl Context {
     /// [`Self::list_roots`]: every input kind rides the same MRTR substrate,
     /// so only the envelope and the result type differ.
     #[cfg(not(feature = "legacy-spec"))]
-    fn request_input<T: serde::de::DeserializeOwned>(
+    pub(super) fn request_input<T: serde::de::DeserializeOwned>(
         &self,
         key: impl Into<String>,
         request: crate::types::mrtr::InputRequest,
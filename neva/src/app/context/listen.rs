@@ -19,11 +19,12 @@ impl Context {
     /// answers the long-lived `subscriptions/listen` request with its
     /// graceful-close result.
     ///
-    /// On the shutdown path the answer is delivered, not merely attempted: the
-    /// signal ends subscriptions one phase ahead of the transport, waits for
-    /// the results they produce to reach the outbound channel, and only then
-    /// tears the writers down -- and the writers drain what is queued before
-    /// they exit. See [`App::run`](crate::App::run) and
+    /// On the shutdown path the answer is delivered, not merely attempted, and
+    /// preceded by a warning-level `notifications/message` saying the server
+    /// is shutting down: the signal ends subscriptions one phase ahead of the
+    /// transport, waits for the results they produce to reach the outbound
+    /// channel, and only then tears the writers down -- and the writers drain
+    /// what is queued before they exit. See [`App::run`](crate::App::run) and
     /// [`App::with_shutdown_drain`](crate::App::with_shutdown_drain), which
     /// caps that wait; a server whose subscriptions cannot flush inside it
     /// still closes abruptly, which is what the spec tells a client to treat
@@ -70,10 +71,18 @@ impl Context {
         // subscriptions' own, cancelled a phase before the transport's, which
         // is what leaves room for the result below to be written.
         let shutdown = self.options.shutdown_token();
-        tokio::select! {
-            _ = token.cancelled() => {},
-            _ = sink.closed() => {},
-            _ = shutdown.cancelled() => {},
+        let closing = tokio::select! {
+            _ = token.cancelled() => false,
+            _ = sink.closed() => false,
+            _ = shutdown.cancelled() => true,
+        };
+
+        // A server-initiated close says why before it answers, so a client
+        // can tell a shutdown from a subscription it will get back by
+        // reconnecting to the same instance. Best-effort, like every other
+        // delivery onto the stream.
+        if closing {
+            let _ = sink.try_send(crate::app::shutdown::shutdown_notice());
         }
 
         // Deregistering and dropping this end closes the subscription's own
@@ -174,6 +183,8 @@ mod subscription_sink_tests {
             sender: TransportProtoSender::None,
             options: Arc::new(McpOptions::default()),
             timeout: Duration::from_secs(5),
+            progress_token: None,
+            sandbox: None,
             exec: ExecMode::None,
             client_capabilities: Default::default(),
             #[cfg(feature = "di")]
@@ -9,6 +9,7 @@
 //! entering a registered handler.
 
 use super::*;
+use crate::types::{Content, ResourceContents};
 
 impl Context {
     /// Returns a list of all available tools
@@ -166,10 +167,69 @@ impl Context {
         self.clone().read_resource(params).await
     }
 
+    /// Returns a resource link [`Content`] for the registered resource at
+    /// `uri`, carrying its name, title, description, MIME type and size.
+    ///
+    /// Fails with a "resource not found" error if no resource is registered
+    /// at `uri`; a URI served by a template has no [`Resource`] to link to.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # #[cfg(feature = "server")] {
+    /// use neva::prelude::*;
+    ///
+    /// let mut app = App::new();
+    /// app.map_tool("latest_report", |ctx: Context| async move {
+    ///     ctx.resource_link("file:///reports/latest.pdf").await
+    /// });
+    /// # }
+    /// ```
+    pub async fn resource_link(&self, uri: impl Into<Uri>) -> Result<Content, Error> {
+        let uri = uri.into();
+        match self.options.resources.get(&uri).await {
+            Some(resource) => Ok(Content::link(resource)),
+            None => Err(Error::resource_not_found(&uri)),
+        }
+    }
+
+    /// Reads the resource at `uri` and returns its contents as embedded
+    /// resource [`Content`]s, one for each of its contents.
+    ///
+    /// Reads it like [`Self::resource`] does, so a URI served by a template
+    /// works too. A blob read from a stream is read into memory, within the
+    /// server's [`max blob size`](crate::App::with_max_blob_size).
+    ///
+    /// # Example
+    /// ```no_run
+    /// # #[cfg(feature = "server")] {
+    /// use neva::prelude::*;
+    ///
+    /// let mut app = App::new();
+    /// app.map_tool("attach_guide", |ctx: Context| async move {
+    ///     ctx.embed_resource("docs://guide").await
+    /// });
+    /// # }
+    /// ```
+    pub async fn embed_resource(&self, uri: impl Into<Uri>) -> Result<Vec<Content>, Error> {
+        let result = self.resource(uri).await?;
+        let mut contents = Vec::with_capacity(result.contents.len());
+        for content in result.contents {
+            let content = match content {
+                ResourceContents::Stream(blob) => blob.into_contents().await?.into(),
+                content => content,
+            };
+            contents.push(Content::resource(content));
+        }
+        Ok(contents)
+    }
+
     /// Adds a new resource and notifies clients
     pub async fn add_resource(&mut self, res: impl Into<Resource>) -> Result<(), Error> {
         let res: Resource = res.into();
-        self.options.resources.insert(res.name.clone(), res).await?;
+        self.options
+            .resources
+            .insert(res.uri.to_string(), res)
+            .await?;
 
         if self.options.is_resource_list_changed_supported() {
             self.send_notification(crate::types::resource::commands::LIST_CHANGED, None)
@@ -341,6 +401,10 @@ impl Context {
             return Err(Error::new(ErrorCode::InternalError, conflict));
         }
 
+        #[cfg(feature = "server-validation")]
+        if let Some(validators) = &self.options.argument_validation {
+            validators.forget(&tool.name);
+        }
         self.options.tools.insert(tool.name.clone(), tool).await?;
 
         if self.options.is_tools_list_changed_supported() {
@@ -353,7 +417,12 @@ impl Context {
 
     /// Removes a tool and notifies clients
     pub async fn remove_tool(&mut self, name: impl Into<String>) -> Result<Option<Tool>, Error> {
-        let removed = self.options.tools.remove(&name.into()).await?;
+        let name = name.into();
+        #[cfg(feature = "server-validation")]
+        if let Some(validators) = &self.options.argument_validation {
+            validators.forget(&name);
+        }
+        let removed = self.options.tools.remove(&name).await?;
 
         if removed.is_some() && self.options.is_tools_list_changed_supported() {
             self.send_notification(crate::types::tool::commands::LIST_CHANGED, None)
@@ -369,6 +438,14 @@ impl Context {
         params: ReadResourceRequestParams,
     ) -> Result<ReadResourceResult, Error> {
         let opt = self.options.clone();
+        #[cfg(feature = "manifest")]
+        if let Some(contents) = opt.manifest_contents.get(&*params.uri) {
+            return Ok(ReadResourceResult::new().with_content(contents.clone()));
+        }
+        #[cfg(feature = "directory")]
+        if let Some(directory) = opt.directories.iter().find(|dir| dir.owns(&params.uri)) {
+            return directory.read(&params.uri, opt.max_blob_size()).await;
+        }
         match opt.read_resource(&params.uri) {
             Some((handler, args)) => {
                 #[cfg(feature = "http-server")]
@@ -379,17 +456,22 @@ impl Context {
                         template.as_ref().and_then(|t| t.permissions.as_deref()),
                     )
                 }?;
-                handler
+                let mut result = handler
                     .call(params.with_args(args).with_context(self).into())
-                    .await
+                    .await?;
+                for content in result.contents.iter_mut() {
+                    if let crate::types::ResourceContents::Stream(blob) = content {
+                        blob.limit(opt.max_blob_size())?;
+                    }
+                }
+                Ok(result)
             }
             // The spec's SHOULD: name the URI that was not found in
             // `error.data.uri`. A caller that fanned several reads onto one
             // connection otherwise cannot tell which of them this refers to
             // without matching on the request id, and an intermediary logging
             // the error has nothing to log.
-            _ => Err(Error::from(ErrorCode::RESOURCE_NOT_FOUND)
-                .with_data(serde_json::json!({ "uri": params.uri.to_string() }))),
+            _ => Err(Error::resource_not_found(&params.uri)),
         }
     }
 
@@ -418,7 +500,12 @@ impl Context {
             Some(tool) => {
                 #[cfg(feature = "http-server")]
                 self.validate_claims(tool.roles.as_deref(), tool.permissions.as_deref())?;
-                tool.call(params.with_context(self)).await
+                #[cfg(feature = "server-validation")]
+                if let Some(validators) = &self.options.argument_validation {
+                    validators.validate(&tool, params.args.as_ref())?;
+                }
+                let ctx = self.with_roots_for(&tool).await?;
+                tool.call(params.with_context(ctx)).await
             }
         }
     }
@@ -459,6 +546,8 @@ mod runtime_registration_tests {
             // the whole point of the paths under test.
             options: McpOptions::default().into_runtime(),
             timeout: Duration::from_secs(5),
+            progress_token: None,
+            sandbox: None,
             #[cfg(not(feature = "legacy-spec"))]
             exec: ExecMode::None,
             #[cfg(not(feature = "legacy-spec"))]
@@ -0,0 +1,379 @@
+//! A typed progress reporter for the current request.
+//!
+//! [`Context::progress`] sends `notifications/progress` straight to the
+//! request's stream, so unlike a `progress` tracing event it needs neither the
+//! `tracing` feature nor the notification layer. A request that carried no
+//! `progressToken` gets a reporter that does nothing, so a handler reports
+//! unconditionally and leaves it to the client whether anything is sent.
+
+use super::*;
+use crate::types::ProgressToken;
+use crate::types::notification::ProgressNotification;
+use std::time::Instant;
+
+/// Reports progress of the current request to the client.
+///
+/// Values only ever move forward: a report below what was already sent is
+/// raised to it, since the spec has progress increase with every notification.
+///
+/// # Example
+/// ```no_run
+/// use neva::prelude::*;
+///
+/// async fn import(ctx: Context, files: Vec<String>) -> Result<(), Error> {
+///     let progress = ctx
+///         .progress()
+///         .with_total(files.len() as f64)
+///         .with_min_interval(std::time::Duration::from_millis(100));
+///
+///     for (i, file) in files.iter().enumerate() {
+///         // ... import `file` ...
+///         progress.report_with_message((i + 1) as f64, format!("imported {file}")).await;
+///     }
+///     progress.finish().await;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Progress {
+    /// Where the notifications go; `None` when the request carried no token.
+    reporter: Option<Arc<Reporter>>,
+
+    /// This reporter's own total, in its own units.
+    total: Option<f64>,
+
+    /// For a sub-progress: where it starts and how much of the root's
+    /// progress it covers, both in the root's units.
+    range: Option<(f64, f64)>,
+}
+
+/// State shared by a root progress and all of its sub-progresses.
+struct Reporter {
+    token: ProgressToken,
+    session_id: Option<uuid::Uuid>,
+    sender: TransportProtoSender,
+    state: std::sync::Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    /// The root's total, carried on every notification.
+    total: Option<f64>,
+
+    /// How often at most a notification goes out; `None` sends every report.
+    min_interval: Option<Duration>,
+
+    /// The highest progress reported so far, sent or not, in root units.
+    current: f64,
+
+    /// When the last notification went out.
+    sent_at: Option<Instant>,
+
+    /// The latest report held back by [`Self::min_interval`].
+    pending: Option<ProgressNotification>,
+}
+
+impl Debug for Progress {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Progress")
+            .field("token", &self.reporter.as_ref().map(|r| &r.token))
+            .field("total", &self.total)
+            .field("range", &self.range)
+            .finish()
+    }
+}
+
+impl Context {
+    /// Returns a reporter for the progress of the current request.
+    ///
+    /// The reporter sends to the `progressToken` the request carried; without
+    /// one it does nothing. Each call returns an independent reporter, so take
+    /// one per operation and use [`Progress::sub`] for its parts.
+    pub fn progress(&self) -> Progress {
+        match &self.progress_token {
+            Some(token) => self.progress_on(token.clone()),
+            None => Progress::disabled(),
+        }
+    }
+
+    /// Returns a reporter that sends to `token` on the current request's stream.
+    pub(crate) fn progress_on(&self, token: ProgressToken) -> Progress {
+        Progress {
+            reporter: Some(Arc::new(Reporter {
+                token,
+                session_id: self.session_id,
+                sender: self.sender.clone(),
+                state: Default::default(),
+            })),
+            total: None,
+            range: None,
+        }
+    }
+}
+
+impl Progress {
+    /// Creates a reporter that reports nothing.
+    pub fn disabled() -> Self {
+        Self {
+            reporter: None,
+            total: None,
+            range: None,
+        }
+    }
+
+    /// Returns `true` if the request carried a progress token, i.e. if reports
+    /// are sent at all.
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.reporter.is_some()
+    }
+
+    /// Sets the total that reported values are out of.
+    ///
+    /// On a sub-progress it only rescales its values onto the part of the
+    /// parent it covers.
+    ///
+    /// Default: none for the root; the covered amount for a sub-progress
+    pub fn with_total(mut self, total: f64) -> Self {
+        self.total = Some(total);
+        if let (None, Some(reporter)) = (self.range, &self.reporter) {
+            reporter.state().total = Some(total);
+        }
+        self
+    }
+
+    /// Coalesces reports that come faster than `interval`: only the latest of
+    /// them is sent, by the first report after the interval has passed, or by
+    /// [`Self::flush`] or [`Self::finish`]. A report that completes the total
+    /// is always sent.
+    ///
+    /// Applies to the root and all of its sub-progresses.
+    ///
+    /// Default: every report is sent
+    pub fn with_min_interval(self, interval: Duration) -> Self {
+        if let Some(reporter) = &self.reporter {
+            reporter.state().min_interval = Some(interval);
+        }
+        self
+    }
+
+    /// Reports `value` out of this reporter's total.
+    pub async fn report(&self, value: f64) {
+        self.send(value, None).await
+    }
+
+    /// Reports `value` out of this reporter's total, with a message describing
+    /// where the operation is.
+    pub async fn report_with_message(&self, value: f64, message: impl Into<String>) {
+        self.send(value, Some(message.into())).await
+    }
+
+    /// Returns a reporter for a part of this operation that covers `amount`
+    /// of this reporter's units, starting from the progress reported so far.
+    ///
+    /// The sub-progress reports in its own units, out of `amount` unless given
+    /// a total of its own with [`Self::with_total`], and its reports move this
+    /// reporter's progress within that share. Sub-progresses nest.
+    pub fn sub(&self, amount: f64) -> Progress {
+        let Some(reporter) = &self.reporter else {
+            return Self::disabled();
+        };
+        let start = reporter.state().current;
+        Self {
+            reporter: Some(reporter.clone()),
+            total: Some(amount),
+            range: Some((start, amount * self.scale())),
+        }
+    }
+
+    /// Sends a report held back by [`Self::with_min_interval`], if there is one.
+    pub async fn flush(&self) {
+        let Some(reporter) = &self.reporter else {
+            return;
+        };
+        let pending = {
+            let mut state = reporter.state();
+            let pending = state.pending.take();
+            if pending.is_some() {
+                state.sent_at = Some(Instant::now());
+            }
+            pending
+        };
+        if let Some(notification) = pending {
+            reporter.deliver(notification).await;
+        }
+    }
+
+    /// Completes this reporter: reports its total if it has one, then sends
+    /// whatever is still held back.
+    pub async fn finish(self) {
+        if let Some(total) = self.total {
+            self.report(total).await;
+        }
+        self.flush().await;
+    }
+
+    async fn send(&self, value: f64, message: Option<String>) {
+        let Some(reporter) = &self.reporter else {
+            return;
+        };
+        let notification = {
+            let mut state = reporter.state();
+            state.current = state.current.max(self.to_root(value));
+
+            let mut notification = reporter.token.notify(state.current, state.total);
+            notification.message = message;
+
+            let complete = state.total.is_some_and(|total| state.current >= total);
+            let due = match (state.min_interval, state.sent_at) {
+                (Some(interval), Some(sent_at)) => sent_at.elapsed() >= interval,
+                _ => true,
+            };
+            if !(due || complete) {
+                state.pending = Some(notification);
+                return;
+            }
+            state.pending = None;
+            state.sent_at = Some(Instant::now());
+            notification
+        };
+        reporter.deliver(notification).await;
+    }
+
+    /// How many root units one of this reporter's units is.
+    #[inline]
+    fn scale(&self) -> f64 {
+        match (self.range, self.total) {
+            (Some((_, span)), Some(total)) if total > 0.0 => span / total,
+            (Some(_), _) => 0.0,
+            (None, _) => 1.0,
+        }
+    }
+
+    /// Maps `value` in this reporter's units onto the root's.
+    #[inline]
+    fn to_root(&self, value: f64) -> f64 {
+        match self.range {
+            Some((start, span)) => start + (value * self.scale()).clamp(0.0, span),
+            None => value,
+        }
+    }
+}
+
+impl Reporter {
+    #[inline]
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Sends a notification on the request's stream: the `POST` response body
+    /// it was registered with under MCP 2026-07-28, the transport otherwise.
+    ///
+    /// Best-effort, like the notification layer: a full or closed stream drops
+    /// the report rather than failing the request.
+    async fn deliver(&self, progress: ProgressNotification) {
+        let mut notification: Notification = progress.into();
+        notification.session_id = self.session_id;
+
+        #[cfg(all(feature = "http-server", not(feature = "legacy-spec")))]
+        if let Some(sink) = self
+            .session_id
+            .and_then(|id| crate::types::notification::sink::get(&id))
+        {
+            let _ = sink.try_send(Message::Notification(notification));
+            return;
+        }
+
+        let _ = self.sender.clone().send(notification.into()).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::stdio::StdIoSender;
+    use tokio::sync::mpsc::Receiver;
+
+    fn progress() -> (Progress, Receiver<Message>) {
+        let (sender, rx) = StdIoSender::channel();
+        let reporter = Reporter {
+            token: ProgressToken::Number(1),
+            session_id: None,
+            sender: TransportProtoSender::Stdio(sender),
+            state: Default::default(),
+        };
+        let progress = Progress {
+            reporter: Some(Arc::new(reporter)),
+            total: None,
+            range: None,
+        };
+        (progress, rx)
+    }
+
+    fn sent(rx: &mut Receiver<Message>) -> Vec<ProgressNotification> {
+        std::iter::from_fn(|| rx.try_recv().ok())
+            .filter_map(|msg| match msg {
+                Message::Notification(n) => serde_json::from_value(n.params?).ok(),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn it_reports_values_and_messages() {
+        let (progress, mut rx) = progress();
+        let progress = progress.with_total(10.0);
+        progress.report(2.0).await;
+        progress.report_with_message(5.0, "halfway").await;
+        progress.report(4.0).await;
+
+        let sent = sent(&mut rx);
+        let values: Vec<_> = sent.iter().map(|p| p.progress).collect();
+        assert_eq!(values, [2.0, 5.0, 5.0]);
+        assert_eq!(sent[1].total, Some(10.0));
+        assert_eq!(sent[1].message.as_deref(), Some("halfway"));
+    }
+
+    #[tokio::test]
+    async fn it_maps_nested_sub_progress_onto_the_root() {
+        let (progress, mut rx) = progress();
+        let progress = progress.with_total(100.0);
+        progress.report(20.0).await;
+
+        let download = progress.sub(50.0).with_total(4.0);
+        download.report(2.0).await;
+        let unpack = download.sub(2.0);
+        unpack.report(1.0).await;
+        download.finish().await;
+
+        let values: Vec<_> = sent(&mut rx).iter().map(|p| p.progress).collect();
+        assert_eq!(values, [20.0, 45.0, 57.5, 70.0]);
+    }
+
+    #[tokio::test]
+    async fn it_coalesces_reports_within_the_interval() {
+        let (progress, mut rx) = progress();
+        let progress = progress
+            .with_total(10.0)
+            .with_min_interval(Duration::from_secs(60));
+        for value in 1..=5 {
+            progress.report(value as f64).await;
+        }
+        progress.flush().await;
+        progress.report(10.0).await;
+
+        let values: Vec<_> = sent(&mut rx).iter().map(|p| p.progress).collect();
+        assert_eq!(values, [1.0, 5.0, 10.0]);
+    }
+
+    #[tokio::test]
+    async fn a_disabled_reporter_sends_nothing() {
+        let progress = Progress::disabled().with_total(3.0);
+        assert!(!progress.is_enabled());
+        progress.sub(1.0).report(1.0).await;
+        progress.finish().await;
+    }
+}
@@ -0,0 +1,456 @@
+//! Confining the paths a tool is handed to the client's roots.
+//!
+//! A client lists the directories a server may work in as `file://` roots.
+//! [`Sandbox`] holds them canonicalized and resolves a user-supplied path
+//! against them: a relative path is taken under a root, and the result --
+//! symlinks and `..` resolved -- must still lie inside one. A tool that takes a
+//! [`SandboxedPath`] argument gets that check done before it runs:
+//!
+//! ```no_run
+//! use neva::prelude::*;
+//!
+//! let mut app = App::new();
+//! app.map_tool("read_file", |path: SandboxedPath| async move {
+//!     std::fs::read_to_string(&path).map_err(Error::from)
+//! });
+//! ```
+//!
+//! The roots are the client's, so the check assumes the client and the server
+//! see the same filesystem -- as they do over stdio. It holds at the time of
+//! the call: a tree that changes underneath a tool can still surprise it.
+//!
+//! Under the legacy profile the roots are asked for once per session and kept
+//! until the client sends `notifications/roots/list_changed`. Under MCP
+//! 2026-07-28 they are an MRTR input -- a deprecated kind, kept for migration
+//! -- and the client answers with the call that needs them, so there is
+//! nothing to keep between calls.
+
+use super::*;
+use crate::types::{
+    helpers::extract::{Payload, RequestArgument, Source},
+    root::Root,
+};
+use serde::Deserialize;
+use std::{
+    ops::Deref,
+    path::{Path, PathBuf},
+};
+
+/// The input key a call's roots are requested under.
+#[cfg(not(feature = "legacy-spec"))]
+const ROOTS_KEY: &str = "roots";
+
+/// The paths a tool may touch: the client's `file://` roots, canonicalized.
+///
+/// # Example
+/// ```no_run
+/// use neva::prelude::*;
+///
+/// async fn write_note(mut ctx: Context, name: String, text: String) -> Result<(), Error> {
+///     let sandbox = ctx.sandbox().await?;
+///     let path = sandbox.resolve(format!("notes/{name}.md"))?;
+///     std::fs::write(path, text).map_err(Error::from)
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Sandbox {
+    roots: Arc<[PathBuf]>,
+}
+
+/// A tool argument holding a path that lies inside one of the client's roots.
+///
+/// Sent as a string, and resolved by [`Sandbox::resolve`] before the handler
+/// runs: a path outside every root fails the call as invalid params. The
+/// client's roots are fetched first for a tool that takes one.
+#[derive(Debug, Clone)]
+pub struct SandboxedPath(PathBuf);
+
+impl Sandbox {
+    /// Creates a [`Sandbox`] from the client's `roots`.
+    ///
+    /// Only `file://` roots that exist on this machine are kept; the rest
+    /// admit nothing.
+    pub fn new<I, R>(roots: I) -> Self
+    where
+        I: IntoIterator<Item = R>,
+        R: Into<Root>,
+    {
+        let roots = roots
+            .into_iter()
+            .filter_map(|root| root_path(&root.into().uri))
+            .filter_map(|path| path.canonicalize().ok())
+            .filter(|path| path.is_dir())
+            .collect::<Vec<_>>();
+        Self {
+            roots: roots.into(),
+        }
+    }
+
+    /// Returns the canonical directories this sandbox admits.
+    #[inline]
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    /// Whether `path`, already canonical, lies inside one of the roots.
+    #[inline]
+    pub fn contains(&self, path: impl AsRef<Path>) -> bool {
+        let path = path.as_ref();
+        self.roots.iter().any(|root| path.starts_with(root))
+    }
+
+    /// Resolves `path` to a canonical path inside one of the roots.
+    ///
+    /// A relative path is taken under the first root it exists in, or under
+    /// the first root at all when it exists in none -- a file a tool is about
+    /// to create. A part of the path that does not exist yet may not contain
+    /// `..`, nor be a dangling symlink.
+    pub fn resolve(&self, path: impl AsRef<Path>) -> Result<PathBuf, Error> {
+        let path = path.as_ref();
+        if self.roots.is_empty() {
+            return Err(outside(path, "the client has no `file://` roots"));
+        }
+        if path.is_absolute() {
+            return canonical(path)
+                .filter(|resolved| self.contains(resolved))
+                .ok_or_else(|| outside(path, "is outside the client's roots"));
+        }
+
+        let mut fallback = None;
+        for root in self.roots.iter() {
+            let Some(resolved) = canonical(&root.join(path)) else {
+                continue;
+            };
+            if !resolved.starts_with(root) {
+                continue;
+            }
+            if resolved.exists() {
+                return Ok(resolved);
+            }
+            fallback.get_or_insert(resolved);
+        }
+        fallback.ok_or_else(|| outside(path, "is outside the client's roots"))
+    }
+}
+
+impl SandboxedPath {
+    /// Returns the resolved path.
+    #[inline]
+    pub fn as_path(&self) -> &Path {
+        &self.0
+    }
+
+    /// Unwraps the resolved path.
+    #[inline]
+    pub fn into_inner(self) -> PathBuf {
+        self.0
+    }
+}
+
+impl Deref for SandboxedPath {
+    type Target = Path;
+
+    #[inline]
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for SandboxedPath {
+    #[inline]
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl RequestArgument for SandboxedPath {
+    type Error = Error;
+
+    #[inline]
+    fn extract(payload: Payload<'_>) -> Result<Self, Self::Error> {
+        let (value, meta) = payload.expect_scoped();
+        let path = String::deserialize(value)?;
+        meta.as_ref()
+            .and_then(|meta| meta.context.as_ref())
+            .and_then(|ctx| ctx.sandbox.as_ref())
+            .ok_or(Error::new(
+                ErrorCode::InternalError,
+                "the client's roots were not loaded for this call",
+            ))?
+            .resolve(path)
+            .map(Self)
+    }
+
+    #[inline]
+    fn source() -> Source {
+        Source::Scoped
+    }
+}
+
+impl Context {
+    /// Returns the client's roots as a [`Sandbox`].
+    ///
+    /// Under the legacy profile the roots are requested once per session and
+    /// kept until the client reports they changed. Under MCP 2026-07-28 they
+    /// are requested as an MRTR input, like [`Self::elicit`].
+    pub async fn sandbox(&mut self) -> Result<Sandbox, Error> {
+        if let Some(sandbox) = &self.sandbox {
+            return Ok(sandbox.clone());
+        }
+
+        #[cfg(feature = "legacy-spec")]
+        let sandbox = {
+            let roots = &self.options.roots;
+            if let Some(sandbox) = roots.get(self.session_id) {
+                return Ok(sandbox);
+            }
+            let epoch = roots.epoch();
+            let sandbox = Sandbox::new(self.list_roots().await?.roots);
+            self.options
+                .roots
+                .insert(self.session_id, epoch, sandbox.clone());
+            sandbox
+        };
+        #[cfg(not(feature = "legacy-spec"))]
+        #[allow(deprecated)]
+        let sandbox = Sandbox::new(
+            self.request_input::<crate::types::root::ListRootsResult>(
+                ROOTS_KEY,
+                crate::types::mrtr::InputRequest::Roots(Default::default()),
+                "roots",
+            )?
+            .roots,
+        );
+
+        Ok(sandbox)
+    }
+
+    /// Loads the client's roots into the context when `tool` takes an
+    /// argument resolved against them.
+    pub(super) async fn with_roots_for(mut self, tool: &Tool) -> Result<Self, Error> {
+        if tool.reads_roots {
+            self.sandbox = Some(self.sandbox().await?);
+        }
+        Ok(self)
+    }
+}
+
+/// The sandboxes of the sessions whose roots are known, until the roots change.
+#[cfg(feature = "legacy-spec")]
+#[derive(Debug, Default)]
+pub(crate) struct RootsCache {
+    sandboxes: dashmap::DashMap<Option<uuid::Uuid>, Sandbox>,
+
+    /// Bumped on every change, so roots fetched before one are not kept.
+    epoch: std::sync::atomic::AtomicU64,
+}
+
+#[cfg(feature = "legacy-spec")]
+impl RootsCache {
+    /// How many sessions are kept before the cache starts over. A session can
+    /// end without a word, so nothing else would bound it.
+    const CAPACITY: usize = 1024;
+
+    #[inline]
+    fn get(&self, session_id: Option<uuid::Uuid>) -> Option<Sandbox> {
+        self.sandboxes.get(&session_id).map(|entry| entry.clone())
+    }
+
+    #[inline]
+    fn epoch(&self) -> u64 {
+        self.epoch.load(std::sync::atomic::Ordering::Acquire)
+    }
+
+    /// Keeps `sandbox` for the session, unless the roots changed since `epoch`.
+    fn insert(&self, session_id: Option<uuid::Uuid>, epoch: u64, sandbox: Sandbox) {
+        if self.epoch() != epoch {
+            return;
+        }
+        if self.sandboxes.len() >= Self::CAPACITY {
+            self.sandboxes.clear();
+        }
+        self.sandboxes.insert(session_id, sandbox);
+    }
+
+    /// Forgets the session's roots; the next call that needs them asks again.
+    pub(crate) fn invalidate(&self, session_id: Option<uuid::Uuid>) {
+        self.epoch.fetch_add(1, std::sync::atomic::Ordering::AcqRel);
+        self.sandboxes.remove(&session_id);
+    }
+}
+
+/// The local path a `file://` root URI names.
+fn root_path(uri: &str) -> Option<PathBuf> {
+    let rest = uri.strip_prefix("file://")?;
+    // `file:///dir` and `file://localhost/dir` name the same directory.
+    let rest = rest.strip_prefix("localhost").unwrap_or(rest);
+    if !rest.starts_with('/') {
+        return None;
+    }
+    let path = percent_decode(rest)?;
+    // `file:///C:/dir` on Windows.
+    #[cfg(windows)]
+    let path = match path.strip_prefix('/') {
+        Some(drive) if drive.as_bytes().get(1) == Some(&b':') => drive.to_owned(),
+        _ => path,
+    };
+    Some(PathBuf::from(path))
+}
+
+/// Decodes the `%XX` escapes in a URI path.
+pub(crate) fn percent_decode(path: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut rest = path.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Canonicalizes `path`, the part of it that does not exist yet included.
+///
+/// That part is appended as it is, so it may not step out with `..` -- which
+/// leaves it without a file name -- nor name anything at all: an entry that
+/// exists but cannot be canonicalized is a dangling symlink, and whatever it
+/// points to is not known to be inside.
+fn canonical(path: &Path) -> Option<PathBuf> {
+    let mut existing = path;
+    let mut missing = Vec::new();
+    let mut resolved = loop {
+        match existing.canonicalize() {
+            Ok(resolved) => break resolved,
+            Err(_) if existing.symlink_metadata().is_ok() => return None,
+            Err(_) => {
+                missing.push(existing.file_name()?);
+                existing = existing.parent()?;
+            }
+        }
+    };
+    resolved.extend(missing.into_iter().rev());
+    Some(resolved)
+}
+
+#[inline]
+fn outside(path: &Path, reason: &str) -> Error {
+    let path = path.display().to_string();
+    Error::new(ErrorCode::InvalidParams, format!("`{path}` {reason}"))
+        .with_data(serde_json::json!({ "path": path }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            let dir = std::env::temp_dir().join(format!("neva-sandbox-{}", uuid::Uuid::new_v4()));
+            std::fs::create_dir_all(dir.join("work/src")).unwrap();
+            std::fs::create_dir_all(dir.join("secret")).unwrap();
+            std::fs::write(dir.join("work/src/main.rs"), "fn main() {}").unwrap();
+            std::fs::write(dir.join("secret/key"), "hunter2").unwrap();
+            Self(dir.canonicalize().unwrap())
+        }
+
+        fn sandbox(&self) -> Sandbox {
+            let uri = format!("file://{}", self.0.join("work").display());
+            Sandbox::new([(uri, "work")])
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn it_resolves_paths_inside_the_roots() {
+        let dir = TempDir::new();
+        let sandbox = dir.sandbox();
+        let work = dir.0.join("work");
+
+        assert_eq!(sandbox.roots(), std::slice::from_ref(&work));
+        assert_eq!(
+            sandbox.resolve("src/main.rs").unwrap(),
+            work.join("src/main.rs")
+        );
+        assert_eq!(
+            sandbox.resolve(work.join("src/../src/main.rs")).unwrap(),
+            work.join("src/main.rs")
+        );
+        // Not there yet: a file the tool is about to create.
+        assert_eq!(
+            sandbox.resolve("src/new/lib.rs").unwrap(),
+            work.join("src/new/lib.rs")
+        );
+    }
+
+    #[test]
+    fn it_rejects_escapes() {
+        let dir = TempDir::new();
+        let sandbox = dir.sandbox();
+
+        let err = sandbox.resolve("../secret/key").unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidParams);
+        assert!(sandbox.resolve(dir.0.join("secret/key")).is_err());
+        assert!(sandbox.resolve("src/missing/../../../secret/key").is_err());
+        assert!(Sandbox::default().resolve("src/main.rs").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn it_follows_symlinks_before_deciding() {
+        let dir = TempDir::new();
+        let sandbox = dir.sandbox();
+        let work = dir.0.join("work");
+        std::os::unix::fs::symlink(dir.0.join("secret"), work.join("shortcut")).unwrap();
+        std::os::unix::fs::symlink(dir.0.join("secret/new"), work.join("dangling")).unwrap();
+        std::os::unix::fs::symlink(work.join("src"), work.join("sources")).unwrap();
+
+        assert!(sandbox.resolve("shortcut/key").is_err());
+        assert!(sandbox.resolve("dangling").is_err());
+        assert_eq!(
+            sandbox.resolve("sources/main.rs").unwrap(),
+            work.join("src/main.rs")
+        );
+    }
+
+    #[cfg(feature = "legacy-spec")]
+    #[test]
+    fn it_forgets_roots_when_they_change() {
+        let cache = RootsCache::default();
+        cache.insert(None, cache.epoch(), Sandbox::default());
+        assert!(cache.get(None).is_some());
+        cache.invalidate(None);
+        assert!(cache.get(None).is_none());
+
+        // Fetched before the change and stored after it: not kept.
+        let epoch = cache.epoch();
+        cache.invalidate(None);
+        cache.insert(None, epoch, Sandbox::default());
+        assert!(cache.get(None).is_none());
+    }
+
+    #[test]
+    fn it_reads_file_root_uris() {
+        assert_eq!(
+            root_path("file:///home/me/my%20project"),
+            Some(PathBuf::from("/home/me/my project"))
+        );
+        assert_eq!(
+            root_path("file://localhost/srv"),
+            Some(PathBuf::from("/srv"))
+        );
+        assert_eq!(root_path("https://example.com/repo"), None);
+        assert_eq!(root_path("file://server/share"), None);
+    }
+}
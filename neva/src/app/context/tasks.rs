@@ -91,6 +91,10 @@ impl Context {
             Some(tool) => {
                 #[cfg(feature = "http-server")]
                 self.validate_claims(tool.roles.as_deref(), tool.permissions.as_deref())?;
+                #[cfg(feature = "server-validation")]
+                if let Some(validators) = &self.options.argument_validation {
+                    validators.validate(&tool, params.args.as_ref())?;
+                }
 
                 let task_support = tool.task_support();
                 if let Some(task_meta) = params.task {
@@ -124,9 +128,10 @@ impl Context {
 
                     tokio::spawn(async move {
                         tokio::select! {
-                            result = tool.call(params
-                                .with_task(&task_id)
-                                .with_context(ctx)) => {
+                            result = async {
+                                let ctx = ctx.with_roots_for(&tool).await?;
+                                tool.call(params.with_task(&task_id).with_context(ctx)).await
+                            } => {
                                 // The outcome is stored *before* the status
                                 // flips, so a `tasks/get` that observes a
                                 // terminal status always sees the matching
@@ -171,9 +176,8 @@ impl Context {
                         "Tool required task augmented call",
                     ))
                 } else {
-                    tool.call(params.with_context(self))
-                        .await
-                        .map(Either::Right)
+                    let ctx = self.with_roots_for(&tool).await?;
+                    tool.call(params.with_context(ctx)).await.map(Either::Right)
                 }
             }
         }
@@ -0,0 +1,896 @@
+//! A directory tree served as resources.
+//!
+//! [`App::with_directory`](crate::App::with_directory) lists every file under a
+//! root as a [`Resource`] -- with its size and a MIME type guessed from its
+//! extension -- and answers `resources/read` for them under a URI prefix,
+//! `file://` unless told otherwise:
+//!
+//! ```no_run
+//! use neva::App;
+//!
+//! let app = App::new()
+//!     .with_directory("./docs", |dir| dir
+//!         .with_include("*.md")
+//!         .with_exclude("drafts/**"));
+//! // `./docs/guide/intro.md` is listed and read as `file://guide/intro.md`
+//! ```
+//!
+//! Text files up to 8 MiB are read as text and everything else as a blob,
+//! streamed from disk as a [`BlobStream`] rather than read whole. Either way a
+//! file over the server's
+//! [`max blob size`](crate::App::with_max_blob_size) is refused. A read never leaves the
+//! root: a URI with `..` in it is refused, and so is a symlink that points
+//! outside.
+//!
+//! While the server runs, the tree is watched -- through inotify on Linux,
+//! by polling elsewhere -- and a change reaches clients through the usual
+//! `list_changed` and `resources/updated` notifications.
+
+use super::{
+    context::{Context, percent_decode},
+    options::McpOptions,
+};
+use crate::error::{Error, ErrorCode};
+use crate::types::{
+    BlobResourceContents, BlobStream, ReadResourceResult, Resource, ResourceContents,
+    ResourceTemplate, TextResourceContents, Uri,
+};
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio_util::sync::CancellationToken;
+
+const DEFAULT_URI_PREFIX: &str = "file://";
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long a burst of changes is given to settle before the tree is
+/// rescanned: saving a file is several events, and one rescan covers them all.
+const SETTLE: Duration = Duration::from_millis(50);
+
+/// How much of a file of no known type is looked at to tell text from binary.
+const SNIFF_LEN: usize = 8 * 1024;
+
+/// The largest text file read whole; a larger one is streamed as a blob.
+const MAX_TEXT_LEN: u64 = 8 * 1024 * 1024;
+
+/// Which files under a directory are served, and how it is watched.
+///
+/// Configured with [`App::with_directory`](crate::App::with_directory).
+///
+/// # Example
+/// ```no_run
+/// use neva::App;
+///
+/// let app = App::new()
+///     .with_directory("./artifacts", |dir| dir
+///         .with_uri_prefix("artifacts://")
+///         .with_include("**/*.tar.gz")
+///         .with_exclude("tmp/**"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct DirectoryOptions {
+    root: PathBuf,
+    uri_prefix: String,
+    include: Vec<String>,
+    exclude: Vec<String>,
+    poll_interval: Duration,
+    watch: bool,
+}
+
+impl DirectoryOptions {
+    pub(crate) fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            uri_prefix: DEFAULT_URI_PREFIX.into(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            watch: true,
+        }
+    }
+
+    /// Serves a file at `prefix` followed by its path under the root.
+    ///
+    /// Give every directory an [`App`](crate::App) serves a prefix of its own.
+    ///
+    /// Default: `file://`
+    pub fn with_uri_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.uri_prefix = prefix.into();
+        self
+    }
+
+    /// Serves only the files that match `pattern`; call it again to serve the
+    /// files matching any of several.
+    ///
+    /// Patterns match a file's path under the root, `/`-separated: `*` and
+    /// `?` stay within one segment and `**` spans any number of them. A
+    /// pattern without a `/` matches the file name at any depth.
+    ///
+    /// Default: every file
+    pub fn with_include(mut self, pattern: impl Into<String>) -> Self {
+        self.include.push(pattern.into());
+        self
+    }
+
+    /// Leaves out the files that match `pattern`, even if included. A
+    /// directory that matches is not looked into at all.
+    ///
+    /// Patterns read as in [`Self::with_include`].
+    pub fn with_exclude(mut self, pattern: impl Into<String>) -> Self {
+        self.exclude.push(pattern.into());
+        self
+    }
+
+    /// Checks the tree for changes every `interval` where inotify is not
+    /// available.
+    ///
+    /// Default: 1 second
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Lists the tree once, at startup, and never again. Reads still see what
+    /// is on disk.
+    pub fn without_watch(mut self) -> Self {
+        self.watch = false;
+        self
+    }
+}
+
+/// A directory being served: where it is and which of its files are.
+#[derive(Debug)]
+pub(crate) struct Directory {
+    /// The root, canonical, so a resolved path can be checked against it.
+    root: PathBuf,
+    prefix: String,
+    include: Vec<Glob>,
+    exclude: Vec<Glob>,
+}
+
+/// What a file is listed with, and what a change to it shows up in.
+#[derive(Debug, Clone, PartialEq)]
+struct FileInfo {
+    size: u64,
+    modified: Option<SystemTime>,
+}
+
+/// The served files under a directory by their path under its root, and the
+/// directories that were looked into.
+#[derive(Debug, Default)]
+struct Scan {
+    files: BTreeMap<String, FileInfo>,
+    dirs: Vec<PathBuf>,
+}
+
+impl Directory {
+    fn new(config: &DirectoryOptions) -> std::io::Result<Self> {
+        Ok(Self {
+            root: config.root.canonicalize()?,
+            prefix: config.uri_prefix.clone(),
+            include: config.include.iter().map(|p| Glob::new(p)).collect(),
+            exclude: config.exclude.iter().map(|p| Glob::new(p)).collect(),
+        })
+    }
+
+    /// Whether `uri` is under this directory's prefix.
+    #[inline]
+    pub(crate) fn owns(&self, uri: &Uri) -> bool {
+        uri.starts_with(&self.prefix)
+    }
+
+    /// Whether the file at `path` under the root is served.
+    fn admits(&self, path: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|glob| glob.matches(path)))
+            && !self.exclude.iter().any(|glob| glob.matches(path))
+    }
+
+    fn uri(&self, path: &str) -> String {
+        format!("{}{}", self.prefix, encode(path))
+    }
+
+    fn resource(&self, path: &str, info: &FileInfo) -> Resource {
+        let mut resource = Resource::new(self.uri(path), path);
+        resource.mime = mime(path);
+        resource.size = Some(info.size as usize);
+        resource
+    }
+
+    fn template(&self) -> ResourceTemplate {
+        let mut template =
+            ResourceTemplate::new(format!("{}{{path}}", self.prefix), self.prefix.as_str());
+        template.descr = Some(format!("Files under {}", self.root.display()));
+        template
+    }
+
+    /// Finds the file `uri` names, refusing a path that leaves the root.
+    fn resolve(&self, uri: &Uri) -> Result<PathBuf, Error> {
+        let path = uri
+            .strip_prefix(&self.prefix)
+            .and_then(percent_decode)
+            .ok_or_else(|| not_found(uri))?;
+        if !Path::new(&path)
+            .components()
+            .all(|part| matches!(part, Component::Normal(_)))
+        {
+            return Err(Error::new(
+                ErrorCode::InvalidParams,
+                format!("`{uri}` is not a path under the served directory"),
+            ));
+        }
+        if !self.admits(&path) {
+            return Err(not_found(uri));
+        }
+        // Canonical, so a symlink is followed before the check rather than
+        // after it.
+        match self.root.join(&path).canonicalize() {
+            Ok(file) if file.starts_with(&self.root) && file.is_file() => Ok(file),
+            _ => Err(not_found(uri)),
+        }
+    }
+
+    /// Reads the file `uri` names: whole if it is text of at most
+    /// [`MAX_TEXT_LEN`] bytes and within `max_blob_size`, as a [`BlobStream`]
+    /// otherwise.
+    pub(crate) async fn read(
+        &self,
+        uri: &Uri,
+        max_blob_size: Option<u64>,
+    ) -> Result<ReadResourceResult, Error> {
+        let path = self.resolve(uri)?;
+        let mime = mime(&path.to_string_lossy());
+        let uri = uri.to_string();
+
+        let text = match mime.as_deref() {
+            Some(mime) => is_text(mime),
+            None => looks_like_text(&path).await,
+        };
+        let file = tokio::fs::File::open(&path).await.map_err(read_failed)?;
+        let size = file.metadata().await.map_err(read_failed)?.len();
+        // Over the limit, the blob below refuses it.
+        if text && size <= MAX_TEXT_LEN.min(max_blob_size.unwrap_or(u64::MAX)) {
+            use tokio::io::AsyncReadExt;
+
+            // Only as much as was measured, should the file grow meanwhile.
+            let mut bytes = Vec::with_capacity(size as usize);
+            file.take(size)
+                .read_to_end(&mut bytes)
+                .await
+                .map_err(read_failed)?;
+            let contents: ResourceContents = match String::from_utf8(bytes) {
+                Ok(text) => TextResourceContents::new(uri, text)
+                    .with_mime(mime.unwrap_or_else(|| "text/plain".into()))
+                    .into(),
+                Err(err) => {
+                    let blob = BlobResourceContents::new(uri, err.into_bytes());
+                    match mime {
+                        Some(mime) => blob.with_mime(mime).into(),
+                        None => blob.into(),
+                    }
+                }
+            };
+            return Ok(ReadResourceResult::new().with_content(contents));
+        }
+
+        let mut blob = BlobStream::new(uri, file).with_size(size);
+        if let Some(mime) = mime {
+            blob = blob.with_mime(mime);
+        }
+        blob.limit(max_blob_size)?;
+        Ok(ReadResourceResult::new().with_content(blob))
+    }
+
+    /// Walks the tree. A symlink is served if it leads to a file under the
+    /// root, and never looked into if it leads to a directory, so the walk
+    /// cannot loop.
+    fn scan(&self) -> Scan {
+        let mut scan = Scan::default();
+        let mut pending = vec![(self.root.clone(), String::new())];
+        while let Some((dir, prefix)) = pending.pop() {
+            let Ok(entries) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            scan.dirs.push(dir);
+            for entry in entries.flatten() {
+                let Some(name) = entry.file_name().to_str().map(str::to_owned) else {
+                    continue;
+                };
+                let path = format!("{prefix}{name}");
+                let Ok(kind) = entry.file_type() else {
+                    continue;
+                };
+                if kind.is_dir() {
+                    if !self.exclude.iter().any(|glob| glob.matches(&path)) {
+                        pending.push((entry.path(), format!("{path}/")));
+                    }
+                    continue;
+                }
+                if !self.admits(&path) {
+                    continue;
+                }
+                let meta = if kind.is_symlink() {
+                    match entry.path().canonicalize() {
+                        Ok(target) if target.starts_with(&self.root) => target.metadata(),
+                        _ => continue,
+                    }
+                } else {
+                    entry.metadata()
+                };
+                if let Ok(meta) = meta.and_then(|meta| {
+                    meta.is_file()
+                        .then_some(meta)
+                        .ok_or(std::io::ErrorKind::InvalidInput.into())
+                }) {
+                    let info = FileInfo {
+                        size: meta.len(),
+                        modified: meta.modified().ok(),
+                    };
+                    scan.files.insert(path, info);
+                }
+            }
+        }
+        scan
+    }
+}
+
+/// Keeps a running server's listing in step with a directory.
+#[derive(Debug)]
+pub(crate) struct DirectoryWatcher {
+    directory: Arc<Directory>,
+    files: BTreeMap<String, FileInfo>,
+    dirs: Vec<PathBuf>,
+    poll_interval: Duration,
+}
+
+impl DirectoryWatcher {
+    /// Lists the directory in a server that is about to start. Returns the
+    /// watcher that keeps it current, unless watching is turned off.
+    ///
+    /// The first walk of the tree runs on the blocking pool, like the rescans.
+    pub(crate) async fn start(config: DirectoryOptions, options: &mut McpOptions) -> Option<Self> {
+        let directory = match Directory::new(&config) {
+            Ok(directory) => Arc::new(directory),
+            Err(err) => {
+                warn(format_args!("not serving {}: {err}", config.root.display()));
+                return None;
+            }
+        };
+
+        let walked = directory.clone();
+        let Ok(scan) = tokio::task::spawn_blocking(move || walked.scan()).await else {
+            warn(format_args!(
+                "not serving {}: scanning it panicked",
+                config.root.display()
+            ));
+            return None;
+        };
+        for (path, info) in &scan.files {
+            options.add_resource(directory.resource(path, info));
+        }
+        options.add_directory(directory.clone(), directory.template());
+
+        if !config.watch {
+            return None;
+        }
+        options.announce_resource_changes();
+        Some(Self {
+            directory,
+            files: scan.files,
+            dirs: scan.dirs,
+            poll_interval: config.poll_interval,
+        })
+    }
+
+    /// Watches the tree until `token` fires, applying each change through
+    /// `ctx`.
+    pub(crate) fn spawn(mut self, mut ctx: Context, token: CancellationToken) {
+        tokio::spawn(async move {
+            let mut changes = Changes::new(&self.directory.root, self.poll_interval);
+            changes.watch(&std::mem::take(&mut self.dirs));
+            loop {
+                tokio::select! {
+                    biased;
+                    _ = token.cancelled() => break,
+                    _ = changes.next() => {}
+                }
+
+                let directory = self.directory.clone();
+                let Ok(scan) = tokio::task::spawn_blocking(move || directory.scan()).await else {
+                    warn(format_args!(
+                        "the directory watcher stopped: scanning {} panicked",
+                        self.directory.root.display()
+                    ));
+                    break;
+                };
+                changes.watch(&scan.dirs);
+                self.apply(&mut ctx, scan.files).await;
+            }
+        });
+    }
+
+    /// Applies what changed since the last scan: one `list_changed` for the
+    /// files that came and went, and a `resources/updated` for each one that
+    /// changed in place.
+    async fn apply(&mut self, ctx: &mut Context, next: BTreeMap<String, FileInfo>) {
+        let prev = std::mem::replace(&mut self.files, next);
+        let resources = &ctx.options.resources;
+        let mut list_changed = false;
+
+        for path in prev.keys().filter(|path| !self.files.contains_key(*path)) {
+            let uri = Uri::from(self.directory.uri(path));
+            report(resources.remove(&uri).await);
+            list_changed = true;
+        }
+
+        let mut updated = Vec::new();
+        for (path, info) in &self.files {
+            let old = prev.get(path);
+            if old == Some(info) {
+                continue;
+            }
+            let resource = self.directory.resource(path, info);
+            updated.extend(old.map(|_| resource.uri.clone()));
+            list_changed |= old.is_none();
+            report(resources.insert(resource.uri.to_string(), resource).await);
+        }
+
+        if list_changed && ctx.options.is_resource_list_changed_supported() {
+            report(
+                ctx.send_notification(crate::types::resource::commands::LIST_CHANGED, None)
+                    .await,
+            );
+        }
+        if ctx.options.is_resource_subscription_supported() {
+            for uri in updated {
+                report(ctx.resource_updated(uri).await);
+            }
+        }
+    }
+}
+
+/// Where the watcher hears that something under the root may have changed.
+enum Changes {
+    #[cfg(target_os = "linux")]
+    Inotify(inotify::Watch),
+    Poll(tokio::time::Interval),
+}
+
+impl Changes {
+    fn new(root: &Path, poll_interval: Duration) -> Self {
+        #[cfg(target_os = "linux")]
+        match inotify::Watch::new() {
+            Ok(watch) => return Self::Inotify(watch),
+            Err(err) => warn(format_args!(
+                "cannot watch {} with inotify, polling it instead: {err}",
+                root.display()
+            )),
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        let _ = root;
+        let mut ticker = tokio::time::interval(poll_interval.max(Duration::from_millis(1)));
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        // The first tick is immediate, and startup has just listed.
+        ticker.reset();
+        Self::Poll(ticker)
+    }
+
+    /// Watches `dirs` too, if changes come from inotify: the ones a scan
+    /// looked into, so a directory created since is watched from then on.
+    fn watch(&mut self, _dirs: &[PathBuf]) {
+        #[cfg(target_os = "linux")]
+        if let Self::Inotify(watch) = self {
+            for dir in _dirs {
+                watch.add(dir);
+            }
+        }
+    }
+
+    /// Waits until the tree may have changed.
+    async fn next(&mut self) {
+        match self {
+            #[cfg(target_os = "linux")]
+            Self::Inotify(watch) => watch.next().await,
+            Self::Poll(ticker) => {
+                ticker.tick().await;
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod inotify {
+    use super::SETTLE;
+    use nix::sys::inotify::{AddWatchFlags, InitFlags, Inotify};
+    use std::os::fd::{AsFd, AsRawFd, RawFd};
+    use std::path::Path;
+    use tokio::io::unix::AsyncFd;
+
+    /// An inotify instance on the runtime's reactor.
+    pub(super) struct Watch(AsyncFd<Fd>);
+
+    struct Fd(Inotify);
+
+    impl AsRawFd for Fd {
+        #[inline]
+        fn as_raw_fd(&self) -> RawFd {
+            self.0.as_fd().as_raw_fd()
+        }
+    }
+
+    impl Watch {
+        pub(super) fn new() -> std::io::Result<Self> {
+            let inotify = Inotify::init(InitFlags::IN_NONBLOCK | InitFlags::IN_CLOEXEC)?;
+            Ok(Self(AsyncFd::new(Fd(inotify))?))
+        }
+
+        /// Watches `dir` for files coming, going and changing. Watching a
+        /// directory twice is one watch; a removed one drops its own.
+        pub(super) fn add(&self, dir: &Path) {
+            let mask = AddWatchFlags::IN_CREATE
+                | AddWatchFlags::IN_DELETE
+                | AddWatchFlags::IN_CLOSE_WRITE
+                | AddWatchFlags::IN_MODIFY
+                | AddWatchFlags::IN_MOVED_FROM
+                | AddWatchFlags::IN_MOVED_TO
+                | AddWatchFlags::IN_ATTRIB;
+            if let Err(err) = self.0.get_ref().0.add_watch(dir, mask) {
+                super::warn(format_args!("cannot watch {}: {err}", dir.display()));
+            }
+        }
+
+        /// Waits for an event, then for the burst it starts to settle.
+        pub(super) async fn next(&mut self) {
+            if self.read().await.is_err() {
+                // An instance that cannot be read is not coming back; wait
+                // for shutdown rather than spin.
+                std::future::pending::<()>().await;
+            }
+            tokio::time::sleep(SETTLE).await;
+            while self.0.get_ref().0.read_events().is_ok() {}
+        }
+
+        async fn read(&self) -> std::io::Result<()> {
+            loop {
+                let mut ready = self.0.readable().await?;
+                match ready.try_io(|fd| fd.get_ref().0.read_events().map_err(Into::into)) {
+                    Ok(events) => return events.map(drop),
+                    Err(_would_block) => continue,
+                }
+            }
+        }
+    }
+}
+
+/// A pattern from [`DirectoryOptions::with_include`] or
+/// [`DirectoryOptions::with_exclude`], split into segments.
+#[derive(Debug)]
+struct Glob(Vec<String>);
+
+impl Glob {
+    fn new(pattern: &str) -> Self {
+        let pattern = pattern.trim_start_matches("./").trim_start_matches('/');
+        let mut segments: Vec<String> = pattern.split('/').map(str::to_owned).collect();
+        if segments.len() == 1 {
+            segments.insert(0, "**".into());
+        }
+        Self(segments)
+    }
+
+    fn matches(&self, path: &str) -> bool {
+        let path: Vec<&str> = path.split('/').collect();
+        matches_segments(&self.0, &path)
+    }
+}
+
+fn matches_segments(pattern: &[String], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first == "**" => {
+            (0..=path.len()).any(|skip| matches_segments(rest, &path[skip..]))
+        }
+        Some((first, rest)) => path
+            .split_first()
+            .is_some_and(|(name, path)| matches_name(first, name) && matches_segments(rest, path)),
+    }
+}
+
+/// Matches one segment against a pattern of literal characters, `?` and `*`.
+fn matches_name(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((sp, sn)) => {
+                    p = sp + 1;
+                    n = sn + 1;
+                    star = Some((sp, sn + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Percent-encodes a path for a URI, leaving `/` as it is.
+fn encode(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/".contains(&byte) {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    out
+}
+
+/// The MIME type a file's extension suggests, if it suggests one.
+#[inline]
+fn mime(path: &str) -> Option<String> {
+    mime_guess::from_path(path)
+        .first()
+        .map(|mime| mime.essence_str().to_owned())
+}
+
+/// Whether a file of no known type is text, going by its first few KiB: valid
+/// UTF-8 -- but for a character cut off at the end -- with no NUL in it.
+async fn looks_like_text(path: &Path) -> bool {
+    use tokio::io::AsyncReadExt;
+
+    let Ok(file) = tokio::fs::File::open(path).await else {
+        return false;
+    };
+    let mut head = Vec::with_capacity(SNIFF_LEN);
+    if file
+        .take(SNIFF_LEN as u64)
+        .read_to_end(&mut head)
+        .await
+        .is_err()
+    {
+        return false;
+    }
+    !head.contains(&0)
+        && match std::str::from_utf8(&head) {
+            Ok(_) => true,
+            Err(err) => err.error_len().is_none(),
+        }
+}
+
+/// Whether a file of `mime` is read as text.
+fn is_text(mime: &str) -> bool {
+    let Some((kind, subtype)) = mime.split_once('/') else {
+        return false;
+    };
+    kind == "text"
+        || matches!(
+            subtype,
+            "json" | "xml" | "javascript" | "x-sh" | "x-toml" | "x-yaml" | "yaml" | "toml"
+        )
+        || subtype.ends_with("+json")
+        || subtype.ends_with("+xml")
+}
+
+#[inline]
+fn not_found(uri: &Uri) -> Error {
+    Error::resource_not_found(uri)
+}
+
+#[inline]
+fn read_failed(err: std::io::Error) -> Error {
+    Error::new(
+        ErrorCode::InternalError,
+        format!("Failed to read the resource: {err}"),
+    )
+}
+
+#[inline]
+fn report<T>(result: Result<T, Error>) {
+    if let Err(err) = result {
+        warn(format_args!("cannot apply a directory change: {err}"));
+    }
+}
+
+#[inline]
+fn warn(_message: std::fmt::Arguments<'_>) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(logger = "neva", "{_message}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            let dir = std::env::temp_dir().join(format!("neva-directory-{}", uuid::Uuid::new_v4()));
+            std::fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+
+        fn write(&self, name: &str, contents: impl AsRef<[u8]>) {
+            let path = self.0.join(name);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn directory(
+        dir: &TempDir,
+        config: impl FnOnce(DirectoryOptions) -> DirectoryOptions,
+    ) -> Directory {
+        Directory::new(&config(DirectoryOptions::new(dir.0.clone()))).unwrap()
+    }
+
+    fn assert_not_found(read: Result<ReadResourceResult, Error>) {
+        let err = read.unwrap_err();
+        assert_eq!(err.code, ErrorCode::RESOURCE_NOT_FOUND);
+        assert!(err.data.is_some_and(|data| data["uri"].is_string()));
+    }
+
+    #[test]
+    fn it_matches_globs() {
+        let glob = Glob::new("*.md");
+        assert!(glob.matches("readme.md"));
+        assert!(glob.matches("guide/intro.md"));
+        assert!(!glob.matches("readme.md.bak"));
+
+        let glob = Glob::new("docs/**/*.rs");
+        assert!(glob.matches("docs/a.rs"));
+        assert!(glob.matches("docs/x/y/a.rs"));
+        assert!(!glob.matches("src/a.rs"));
+
+        let glob = Glob::new("drafts/**");
+        assert!(glob.matches("drafts/a"));
+        assert!(glob.matches("drafts"));
+        assert!(!glob.matches("drafts.md"));
+
+        assert!(Glob::new("file?.txt").matches("file1.txt"));
+        assert!(!Glob::new("a/*").matches("a/b/c"));
+    }
+
+    #[test]
+    fn it_round_trips_paths_through_uris() {
+        let path = "notes/a b%c.md";
+        assert_eq!(encode(path), "notes/a%20b%25c.md");
+        assert_eq!(percent_decode(&encode(path)).as_deref(), Some(path));
+        assert_eq!(percent_decode("bad%2"), None);
+    }
+
+    #[test]
+    fn it_lists_what_the_patterns_admit() {
+        let dir = TempDir::new();
+        dir.write("readme.md", "# hi");
+        dir.write("guide/intro.md", "intro");
+        dir.write("guide/logo.png", [0x89, b'P', b'N', b'G']);
+        dir.write("drafts/wip.md", "wip");
+
+        let directory = directory(&dir, |d| d.with_include("*.md").with_exclude("drafts"));
+        let scan = directory.scan();
+        let files: Vec<_> = scan.files.keys().map(String::as_str).collect();
+        assert_eq!(files, ["guide/intro.md", "readme.md"]);
+        assert!(!scan.dirs.iter().any(|dir| dir.ends_with("drafts")));
+
+        let resource = directory.resource("guide/intro.md", &scan.files["guide/intro.md"]);
+        assert_eq!(&*resource.uri, "file://guide/intro.md");
+        assert_eq!(resource.size, Some(5));
+        assert_eq!(resource.mime.as_deref(), Some("text/markdown"));
+    }
+
+    #[tokio::test]
+    async fn it_reads_text_as_text_and_the_rest_as_a_stream() {
+        let dir = TempDir::new();
+        dir.write("notes.txt", "plain");
+        dir.write("data", "no extension, still text");
+        dir.write("image.png", [0x89, b'P', b'N', b'G', 0]);
+        let directory = directory(&dir, |d| d);
+
+        let text = directory
+            .read(&"file://notes.txt".into(), None)
+            .await
+            .unwrap();
+        assert_eq!(text.contents[0].text(), Some("plain"));
+        assert_eq!(text.contents[0].mime(), Some("text/plain"));
+
+        let sniffed = directory.read(&"file://data".into(), None).await.unwrap();
+        assert_eq!(sniffed.contents[0].text(), Some("no extension, still text"));
+
+        let mut image = directory
+            .read(&"file://image.png".into(), None)
+            .await
+            .unwrap();
+        let ResourceContents::Stream(blob) = image.contents.remove(0) else {
+            panic!("expected a streamed blob");
+        };
+        assert_eq!(blob.size(), Some(5));
+        let blob = blob.into_contents().await.unwrap();
+        assert_eq!(&blob.blob[..], [0x89, b'P', b'N', b'G', 0]);
+        assert_eq!(blob.mime.as_deref(), Some("image/png"));
+
+        let err = directory
+            .read(&"file://image.png".into(), Some(4))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidRequest);
+    }
+
+    #[tokio::test]
+    async fn it_reads_text_whole_only_within_the_limit() {
+        let dir = TempDir::new();
+        dir.write("notes.txt", "plain");
+        let directory = directory(&dir, |d| d);
+
+        let err = directory
+            .read(&"file://notes.txt".into(), Some(4))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidRequest);
+        assert_eq!(err.data().unwrap()["size"], 5);
+
+        let text = directory
+            .read(&"file://notes.txt".into(), Some(5))
+            .await
+            .unwrap();
+        assert_eq!(text.contents[0].text(), Some("plain"));
+    }
+
+    #[tokio::test]
+    async fn it_never_reads_outside_the_root() {
+        let outside = TempDir::new();
+        outside.write("secret.txt", "secret");
+        let dir = TempDir::new();
+        dir.write("public/ok.txt", "ok");
+        dir.write("skipped.log", "log");
+        let directory = directory(&dir, |d| d.with_exclude("*.log"));
+
+        for uri in [
+            "file://../secret.txt",
+            "file://public/../../secret.txt",
+            "file:///etc/passwd",
+        ] {
+            let err = directory.read(&uri.into(), None).await.unwrap_err();
+            assert_eq!(err.code, ErrorCode::InvalidParams, "{uri}");
+            assert!(err.to_string().contains("not a path under"), "{uri}: {err}");
+        }
+        assert_not_found(directory.read(&"file://skipped.log".into(), None).await);
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(outside.0.join("secret.txt"), dir.0.join("escape.txt"))
+                .unwrap();
+            std::os::unix::fs::symlink(dir.0.join("public/ok.txt"), dir.0.join("inside.txt"))
+                .unwrap();
+
+            assert_not_found(directory.read(&"file://escape.txt".into(), None).await);
+            let read = directory
+                .read(&"file://inside.txt".into(), None)
+                .await
+                .unwrap();
+            assert_eq!(read.contents[0].text(), Some("ok"));
+
+            let files: Vec<_> = directory.scan().files.into_keys().collect();
+            assert_eq!(files, ["inside.txt", "public/ok.txt"]);
+        }
+    }
+}
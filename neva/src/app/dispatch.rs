@@ -36,7 +36,6 @@ impl App {
     pub(super) async fn execute(msg: Message, runtime: ServerRuntime) {
         // Held for the whole pipeline, so the shutdown drain can tell a
         // response that is queued from one that is still being produced.
-        #[cfg(not(feature = "legacy-spec"))]
         let _in_flight = InFlightGuard::enter(runtime.in_flight());
         // Closing the request notification sink here -- once the *whole*
         // middleware pipeline has run -- is what lets a request-scoped SSE POST
@@ -63,7 +62,6 @@ impl App {
         // `ServerRuntime::execute`, so they never close the shared sink early):
         // the request-scoped SSE response stays open until every inner request
         // and its middleware have finished. See `App::execute`.
-        #[cfg(not(feature = "legacy-spec"))]
         let _in_flight = InFlightGuard::enter(runtime.in_flight());
         #[cfg(all(not(feature = "legacy-spec"), feature = "http-server"))]
         let _sink_guard = RequestSinkGuard(batch_session_id);
@@ -276,6 +274,23 @@ impl App {
         let session_id = req.session_id;
         let full_id = req.full_id();
 
+        // Once shutdown is requested nothing new starts: the drain is for the
+        // work already under way, and a request started now would only be
+        // abandoned at its deadline. Responses and notifications still flow --
+        // an in-flight handler may be waiting on one -- and so do the requests
+        // that finish earlier work: an MRTR continuation, a task poll.
+        if runtime.options().is_draining() && !shutdown::continues_earlier_work(&req) {
+            runtime
+                .options()
+                .rejected
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            let mut resp = Response::error(req_id, shutdown::shutting_down());
+            if let Some(session_id) = session_id {
+                resp = resp.set_session_id(session_id);
+            }
+            return resp;
+        }
+
         // MRTR pre-capture: method + salient params (the params that identify
         // this request, see `salient_params`), needed after `req`/`context` are
         // moved into `handler.call`.
@@ -347,14 +362,15 @@ impl App {
         #[cfg(feature = "di")]
         let context = context.with_scope(scope);
 
+        let mut context = context;
+        context.progress_token = req.meta().and_then(|meta| meta.progress_token);
+
         let options = runtime.options();
         let handlers = runtime.request_handlers();
         let token = options.track_request(&full_id);
 
         // MRTR seed: decode/verify any incoming `requestState`, merge this
         // round's `inputResponses`, and attach the replay state to the context.
-        #[cfg(not(feature = "legacy-spec"))]
-        let mut context = context;
         // Declared per request, so it belongs on every dispatch and not just the
         // MRTR ones: a task-augmented call asks the same caller for the same
         // kinds of input, and a handler that skips asking when the caller cannot
@@ -486,7 +502,7 @@ impl App {
                 }
             }
         } else {
-            Err(Error::from(ErrorCode::MethodNotFound))
+            Err(Error::method_not_found(&req.method))
         };
 
         // MRTR interception: if the handler requested input (recorded in the
@@ -643,6 +659,10 @@ impl App {
                 #[cfg(feature = "tracing")]
                 notification.write();
             }
+            #[cfg(feature = "legacy-spec")]
+            crate::types::root::commands::LIST_CHANGED => {
+                runtime.options().roots.invalidate(notification.session_id);
+            }
             _ => {}
         }
     }
@@ -726,10 +746,8 @@ pub(super) fn opens_subscription(msg: &Message) -> bool {
 /// The count is what makes "nothing in flight" mean "every response produced so
 /// far is already queued on the transport sender": the terminal middleware
 /// awaits that send before it returns, so the guard outlives it.
-#[cfg(not(feature = "legacy-spec"))]
 struct InFlightGuard(Arc<std::sync::atomic::AtomicUsize>);
 
-#[cfg(not(feature = "legacy-spec"))]
 impl InFlightGuard {
     #[inline]
     fn enter(counter: Arc<std::sync::atomic::AtomicUsize>) -> Self {
@@ -738,7 +756,6 @@ impl InFlightGuard {
     }
 }
 
-#[cfg(not(feature = "legacy-spec"))]
 impl Drop for InFlightGuard {
     #[inline]
     fn drop(&mut self) {
@@ -819,12 +836,9 @@ async fn param_header_error(
     let mirrored = param_headers::extract(&declared, &args);
 
     let mismatch = |header: &str, stated: &str, body: &str| {
-        Some(Error::new(
-            ErrorCode::HeaderMismatch,
-            format!(
-                "Header mismatch: {header} header value {stated:?} does not match body value {body:?}"
-            ),
-        ))
+        Some(Error::header_mismatch(format!(
+            "Header mismatch: {header} header value {stated:?} does not match body value {body:?}"
+        )))
     };
 
     for header in &declared {
@@ -838,27 +852,22 @@ async fn param_header_error(
         match (stated, body) {
             (None, None) => {}
             (None, Some(body)) => {
-                return Some(Error::new(
-                    ErrorCode::HeaderMismatch,
-                    format!("Missing {name} header for the mirrored argument {body:?}"),
-                ));
+                return Some(Error::header_mismatch(format!(
+                    "Missing {name} header for the mirrored argument {body:?}"
+                )));
             }
             (Some(stated), None) => {
-                return Some(Error::new(
-                    ErrorCode::HeaderMismatch,
-                    format!(
-                        "{name} header sent as {stated:?}, but the call carries no such argument"
-                    ),
-                ));
+                return Some(Error::header_mismatch(format!(
+                    "{name} header sent as {stated:?}, but the call carries no such argument"
+                )));
             }
             (Some(stated), Some(body)) => match decode_header_value(stated) {
                 Some(decoded) if decoded == body => {}
                 Some(decoded) => return mismatch(&name, &decoded, body),
                 None => {
-                    return Some(Error::new(
-                        ErrorCode::HeaderMismatch,
-                        format!("Malformed {name} header value"),
-                    ));
+                    return Some(Error::header_mismatch(format!(
+                        "Malformed {name} header value"
+                    )));
                 }
             },
         }
@@ -0,0 +1,1289 @@
+//! Prompts, static resources and tool metadata read from a manifest directory.
+//!
+//! Some of what a server offers is content, not code: prompt wording, a style
+//! guide served as a resource, the description a tool is advertised with. A
+//! manifest lets whoever writes that content ship it as files next to the
+//! server instead of as Rust. Every JSON file in the directory -- and, with the
+//! `manifest-yaml` and `manifest-toml` features, every YAML and TOML file --
+//! holds any of three lists:
+//!
+//! ```yaml
+//! prompts:
+//!   - name: review
+//!     description: Reviews a change
+//!     arguments:
+//!       - name: lang
+//!         required: true
+//!     messages:
+//!       - role: user
+//!         text: "Review this {{lang}} change for correctness."
+//! resources:
+//!   - uri: docs://style
+//!     name: style
+//!     mimeType: text/markdown
+//!     file: docs/style.md
+//! tools:
+//!   - name: deploy
+//!     description: Deploys the current build to staging
+//!     annotations:
+//!       destructiveHint: true
+//! ```
+//!
+//! Prompts and resources are registered exactly as
+//! [`App::map_prompt`](crate::App::map_prompt) and
+//! [`App::add_resource`](crate::App::add_resource) register theirs. Tool
+//! entries only describe tools registered in code; a manifest cannot add
+//! behaviour. Whatever code registers under a name wins over a manifest entry
+//! claiming the same one.
+//!
+//! A resource's `file` is relative to the directory and must stay under it.
+//!
+//! While the server runs, the manifests and the files they name are polled and
+//! a change is applied as a diff through [`Context`], so clients hear about it through the usual
+//! `list_changed` and `resources/updated` notifications. A file that fails to
+//! parse keeps its last good content in service -- a typo in one prompt does
+//! not take the others down with it.
+
+use super::{
+    context::Context,
+    handler::{Handler, HandlerParams},
+    options::McpOptions,
+};
+use crate::error::{Error, ErrorCode};
+use crate::shared::BoxFuture;
+use crate::types::{
+    BlobResourceContents, GetPromptResult, Prompt, PromptArgument, PromptMessage, Resource,
+    ResourceContents, Role, TextResourceContents, Tool, ToolAnnotations,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio_util::sync::CancellationToken;
+
+const DEFAULT_DIR: &str = "manifest";
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Where a manifest is read from and how it is watched.
+///
+/// Configured with [`App::with_manifest`](crate::App::with_manifest).
+///
+/// # Example
+/// ```no_run
+/// use neva::App;
+/// use std::time::Duration;
+///
+/// let app = App::new()
+///     .with_manifest(|manifest| manifest
+///         .with_dir("./content")
+///         .with_poll_interval(Duration::from_secs(5)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ManifestOptions {
+    dir: PathBuf,
+    poll_interval: Duration,
+    watch: bool,
+}
+
+impl Default for ManifestOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            dir: PathBuf::from(DEFAULT_DIR),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            watch: true,
+        }
+    }
+}
+
+impl ManifestOptions {
+    /// Reads the manifest from `dir`.
+    ///
+    /// Manifest files are the directory's own; subdirectories are only read
+    /// for the files a resource names.
+    ///
+    /// Default: `./manifest`
+    pub fn with_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dir = dir.into();
+        self
+    }
+
+    /// Checks the directory for changes every `interval`.
+    ///
+    /// Default: 1 second
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Reads the manifest once, at startup, and never again.
+    pub fn without_watch(mut self) -> Self {
+        self.watch = false;
+        self
+    }
+}
+
+/// One manifest file as written.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ManifestFile {
+    prompts: Vec<PromptEntry>,
+    resources: Vec<ResourceEntry>,
+    tools: Vec<ToolEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PromptEntry {
+    name: String,
+    title: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    arguments: Vec<ArgumentEntry>,
+    messages: Vec<MessageEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ArgumentEntry {
+    name: String,
+    description: Option<String>,
+    #[serde(default)]
+    required: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MessageEntry {
+    #[serde(default = "user_role")]
+    role: Role,
+    text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+struct ResourceEntry {
+    uri: String,
+    name: String,
+    title: Option<String>,
+    description: Option<String>,
+    mime_type: Option<String>,
+    /// Inline content. Exactly one of this and `file` is set.
+    text: Option<String>,
+    /// Content read from a file, relative to the manifest directory.
+    file: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ToolEntry {
+    name: String,
+    title: Option<String>,
+    description: Option<String>,
+    /// Kept as written and checked against [`ToolAnnotations`] on load, so
+    /// two loads can be compared.
+    annotations: Option<Value>,
+}
+
+#[inline]
+fn user_role() -> Role {
+    Role::User
+}
+
+/// A resource together with the content it serves.
+#[derive(Debug, Clone, PartialEq)]
+struct StaticResource {
+    entry: ResourceEntry,
+    body: Body,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Body {
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+/// Everything the manifest directory defines, merged across its files.
+#[derive(Debug, Default, Clone, PartialEq)]
+struct Manifest {
+    prompts: BTreeMap<String, PromptEntry>,
+    resources: BTreeMap<String, StaticResource>,
+    tools: BTreeMap<String, ToolEntry>,
+}
+
+/// A file's successfully parsed content.
+#[derive(Debug)]
+struct Loaded {
+    prompts: Vec<PromptEntry>,
+    resources: Vec<StaticResource>,
+    tools: Vec<ToolEntry>,
+}
+
+/// Reads the manifest directory and notices when it changes.
+#[derive(Debug)]
+struct Loader {
+    dir: PathBuf,
+    /// The last good content of every manifest file, by path. Sorted, so a
+    /// later path wins a name two files define.
+    files: BTreeMap<PathBuf, Loaded>,
+    /// The files the manifests' resources name, as of the last load.
+    referenced: BTreeSet<PathBuf>,
+    /// The manifests and the files they name, with their modification time
+    /// and size, as of the last load.
+    fingerprint: Vec<(PathBuf, Option<SystemTime>, u64)>,
+}
+
+impl Loader {
+    fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            files: BTreeMap::new(),
+            referenced: BTreeSet::new(),
+            fingerprint: Vec::new(),
+        }
+    }
+
+    /// Loads the manifest if a manifest, or a file one names, changed since
+    /// the last load.
+    fn poll(&mut self) -> Option<Manifest> {
+        if self.fingerprint() == self.fingerprint {
+            return None;
+        }
+        let manifest = self.load();
+        // Taken again: the load may have found files to watch it did not
+        // know about before.
+        self.fingerprint = self.fingerprint();
+        Some(manifest)
+    }
+
+    /// Loads every manifest file, keeping the last good content of the ones
+    /// that fail to parse.
+    fn load(&mut self) -> Manifest {
+        let paths = match manifest_paths(&self.dir) {
+            Ok(paths) => paths,
+            Err(err) => {
+                warn(format_args!(
+                    "cannot read the manifest directory {}: {err}",
+                    self.dir.display()
+                ));
+                Vec::new()
+            }
+        };
+
+        self.files.retain(|path, _| paths.contains(path));
+        self.referenced.clear();
+        for path in paths {
+            match self.read(&path) {
+                Ok(loaded) => {
+                    self.files.insert(path, loaded);
+                }
+                Err(err) => warn(format_args!(
+                    "skipping manifest {}{}: {}",
+                    path.display(),
+                    if self.files.contains_key(&path) {
+                        ", keeping its last good content"
+                    } else {
+                        ""
+                    },
+                    err
+                )),
+            }
+        }
+
+        self.merge()
+    }
+
+    fn read(&mut self, path: &Path) -> Result<Loaded, Error> {
+        let source = std::fs::read_to_string(path).map_err(invalid)?;
+        let file = parse(path, &source)?;
+        // Watched even if reading them fails below, so the manifest is
+        // loaded again once they can be read.
+        self.referenced.extend(
+            file.resources
+                .iter()
+                .filter_map(|entry| entry.file.as_ref())
+                .map(|file| self.dir.join(file)),
+        );
+
+        for prompt in &file.prompts {
+            check_prompt(prompt)?;
+        }
+        for tool in &file.tools {
+            if let Some(annotations) = &tool.annotations {
+                serde_json::from_value::<ToolAnnotations>(annotations.clone()).map_err(|err| {
+                    invalid(format!(
+                        "tool `{}` has invalid annotations: {err}",
+                        tool.name
+                    ))
+                })?;
+            }
+        }
+        let resources = file
+            .resources
+            .into_iter()
+            .map(|entry| self.resolve(entry))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Loaded {
+            prompts: file.prompts,
+            resources,
+            tools: file.tools,
+        })
+    }
+
+    /// Reads the content a resource entry points at.
+    fn resolve(&self, mut entry: ResourceEntry) -> Result<StaticResource, Error> {
+        let body = match (entry.text.take(), &entry.file) {
+            (Some(text), None) => Body::Text(text),
+            (None, Some(file)) => {
+                let path = self.confine(&entry.uri, file)?;
+                let bytes = std::fs::read(path).map_err(|err| {
+                    invalid(format!(
+                        "resource `{}` cannot read {}: {err}",
+                        entry.uri,
+                        file.display()
+                    ))
+                })?;
+                match String::from_utf8(bytes) {
+                    Ok(text) => Body::Text(text),
+                    Err(err) => Body::Blob(err.into_bytes()),
+                }
+            }
+            _ => {
+                return Err(invalid(format!(
+                    "resource `{}` must set exactly one of `text` and `file`",
+                    entry.uri
+                )));
+            }
+        };
+        Ok(StaticResource { entry, body })
+    }
+
+    /// Returns the path of the file a resource names, if it is under the
+    /// manifest directory. Canonical, so a symlink is followed before the
+    /// check rather than after it.
+    fn confine(&self, uri: &str, file: &Path) -> Result<PathBuf, Error> {
+        let cannot_read = |err| {
+            invalid(format!(
+                "resource `{uri}` cannot read {}: {err}",
+                file.display()
+            ))
+        };
+        let root = self.dir.canonicalize().map_err(cannot_read)?;
+        let path = self.dir.join(file).canonicalize().map_err(cannot_read)?;
+        if !path.starts_with(&root) {
+            return Err(invalid(format!(
+                "resource `{uri}` names {}, which is not under the manifest directory",
+                file.display()
+            )));
+        }
+        Ok(path)
+    }
+
+    /// The manifests and the files they name with their modification time
+    /// and size, in path order. Cheap enough to take on every poll; a file
+    /// that is missing is there with neither.
+    fn fingerprint(&self) -> Vec<(PathBuf, Option<SystemTime>, u64)> {
+        let manifests = manifest_paths(&self.dir).unwrap_or_default();
+        let mut files: Vec<_> = manifests
+            .into_iter()
+            .chain(self.referenced.iter().cloned())
+            .map(|path| match std::fs::metadata(&path) {
+                Ok(meta) => (path, meta.modified().ok(), meta.len()),
+                Err(_) => (path, None, 0),
+            })
+            .collect();
+        files.sort();
+        files
+    }
+
+    /// Merges the files in path order; a later file wins a name an earlier
+    /// one defines too.
+    fn merge(&self) -> Manifest {
+        let mut manifest = Manifest::default();
+        let mut origin = HashMap::new();
+
+        for (path, loaded) in &self.files {
+            for prompt in &loaded.prompts {
+                note_duplicate(&mut origin, "prompt", &prompt.name, path);
+                manifest.prompts.insert(prompt.name.clone(), prompt.clone());
+            }
+            for resource in &loaded.resources {
+                note_duplicate(&mut origin, "resource", &resource.entry.uri, path);
+                manifest
+                    .resources
+                    .insert(resource.entry.uri.clone(), resource.clone());
+            }
+            for tool in &loaded.tools {
+                note_duplicate(&mut origin, "tool", &tool.name, path);
+                manifest.tools.insert(tool.name.clone(), tool.clone());
+            }
+        }
+        manifest
+    }
+}
+
+/// What code registered before the manifest was first applied. The manifest
+/// never takes over a prompt or resource from it, and a tool whose manifest
+/// entry goes away is put back the way code described it.
+#[derive(Debug, Default)]
+struct Registry {
+    applied: Manifest,
+    prompts: HashSet<String>,
+    resources: HashSet<String>,
+    tools: HashMap<String, ToolMeta>,
+}
+
+#[derive(Debug, Clone)]
+struct ToolMeta {
+    title: Option<String>,
+    descr: Option<String>,
+    annotations: Option<ToolAnnotations>,
+}
+
+impl Registry {
+    fn new(options: &McpOptions) -> Self {
+        Self {
+            applied: Manifest::default(),
+            prompts: options.prompts.as_ref().keys().cloned().collect(),
+            resources: options.resources.as_ref().keys().cloned().collect(),
+            tools: options
+                .tools
+                .as_ref()
+                .iter()
+                .map(|(name, tool)| {
+                    let meta = ToolMeta {
+                        title: tool.title.clone(),
+                        descr: tool.descr.clone(),
+                        annotations: tool.annotations.clone(),
+                    };
+                    (name.clone(), meta)
+                })
+                .collect(),
+        }
+    }
+
+    /// Drops what the manifest may not define: names code already took, and
+    /// tools code never registered.
+    fn admit(&self, mut manifest: Manifest) -> Manifest {
+        manifest.prompts.retain(|name, _| {
+            let free = !self.prompts.contains(name);
+            if !free {
+                warn(format_args!(
+                    "manifest prompt `{name}` ignored: a prompt of that name is registered in code"
+                ));
+            }
+            free
+        });
+        manifest.resources.retain(|uri, _| {
+            let free = !self.resources.contains(uri);
+            if !free {
+                warn(format_args!(
+                    "manifest resource `{uri}` ignored: a resource with that URI is registered in code"
+                ));
+            }
+            free
+        });
+        manifest.tools.retain(|name, _| {
+            let known = self.tools.contains_key(name);
+            if !known {
+                warn(format_args!(
+                    "manifest tool `{name}` ignored: a manifest only describes tools registered in code"
+                ));
+            }
+            known
+        });
+        manifest
+    }
+
+    /// Describes `tool` by its manifest entry, falling back field by field to
+    /// what code said.
+    fn describe(&self, tool: &mut Tool, entry: Option<&ToolEntry>) {
+        let Some(code) = self.tools.get(&tool.name) else {
+            return;
+        };
+        tool.title = entry
+            .and_then(|entry| entry.title.clone())
+            .or_else(|| code.title.clone());
+        tool.descr = entry
+            .and_then(|entry| entry.description.clone())
+            .or_else(|| code.descr.clone());
+        tool.annotations = entry
+            .and_then(|entry| entry.annotations.clone())
+            .and_then(|annotations| serde_json::from_value(annotations).ok())
+            .or_else(|| code.annotations.clone());
+    }
+
+    /// Applies the manifest to a server that is not running yet.
+    fn register(&mut self, options: &mut McpOptions, manifest: Manifest) {
+        let manifest = self.admit(manifest);
+        for prompt in manifest.prompts.values() {
+            options.add_prompt(prompt.to_prompt());
+        }
+        for resource in manifest.resources.values() {
+            options
+                .manifest_contents
+                .insert(resource.entry.uri.clone(), resource.contents());
+            options.add_resource(resource.to_resource());
+        }
+        for (name, entry) in &manifest.tools {
+            if let Some(tool) = options.tools.as_mut().get_mut(name) {
+                self.describe(tool, Some(entry));
+            }
+        }
+        self.applied = manifest;
+    }
+
+    /// Applies what changed since the last load to a running server. Each
+    /// change goes through `ctx` so the notifications it calls for go out.
+    async fn apply(&mut self, ctx: &mut Context, next: Manifest) {
+        let next = self.admit(next);
+        let prev = std::mem::take(&mut self.applied);
+
+        for name in prev.prompts.keys() {
+            if !next.prompts.contains_key(name) {
+                report(ctx.remove_prompt(name.as_str()).await);
+            }
+        }
+        for (name, prompt) in &next.prompts {
+            if prev.prompts.get(name) != Some(prompt) {
+                report(ctx.add_prompt(prompt.to_prompt()).await);
+            }
+        }
+
+        for uri in prev.resources.keys() {
+            if !next.resources.contains_key(uri) {
+                ctx.options.manifest_contents.remove(uri);
+                report(ctx.remove_resource(uri.as_str()).await);
+            }
+        }
+        for (uri, resource) in &next.resources {
+            let old = prev.resources.get(uri);
+            if old == Some(resource) {
+                continue;
+            }
+            ctx.options
+                .manifest_contents
+                .insert(uri.clone(), resource.contents());
+            if old.map(|old| &old.entry) != Some(&resource.entry) {
+                report(ctx.add_resource(resource.to_resource()).await);
+            }
+            if old.is_some_and(|old| old.body != resource.body)
+                && ctx.options.is_resource_subscription_supported()
+            {
+                report(ctx.resource_updated(uri.as_str()).await);
+            }
+        }
+
+        for name in prev.tools.keys().chain(next.tools.keys()) {
+            let entry = next.tools.get(name);
+            if prev.tools.get(name) == entry {
+                continue;
+            }
+            if let Some(mut tool) = ctx.options.get_tool(name).await {
+                self.describe(&mut tool, entry);
+                report(ctx.add_tool(tool).await);
+            }
+        }
+
+        self.applied = next;
+    }
+}
+
+/// Keeps a running server in step with its manifest directory.
+#[derive(Debug)]
+pub(crate) struct ManifestWatcher {
+    loader: Loader,
+    registry: Registry,
+    poll_interval: Duration,
+}
+
+impl ManifestWatcher {
+    /// Loads the manifest into a server that is about to start. Returns the
+    /// watcher that keeps it current, unless watching is turned off.
+    pub(crate) fn start(config: ManifestOptions, options: &mut McpOptions) -> Option<Self> {
+        if !config.dir.is_dir() {
+            warn(format_args!(
+                "the manifest directory {} does not exist; it is picked up once it does",
+                config.dir.display()
+            ));
+        }
+
+        let mut loader = Loader::new(config.dir);
+        let mut registry = Registry::new(options);
+        let manifest = loader.poll().unwrap_or_default();
+        registry.register(options, manifest);
+
+        if !config.watch {
+            return None;
+        }
+        // A watched manifest changes the lists at will, so clients are told
+        // they can expect to hear about it.
+        options.announce_list_changes();
+        Some(Self {
+            loader,
+            registry,
+            poll_interval: config.poll_interval,
+        })
+    }
+
+    /// Polls the directory until `token` fires, applying each change through
+    /// `ctx`.
+    pub(crate) fn spawn(self, mut ctx: Context, token: CancellationToken) {
+        let Self {
+            mut loader,
+            mut registry,
+            poll_interval,
+        } = self;
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(poll_interval.max(Duration::from_millis(1)));
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            // The first tick is immediate, and startup has just loaded.
+            ticker.tick().await;
+
+            loop {
+                tokio::select! {
+                    biased;
+                    _ = token.cancelled() => break,
+                    _ = ticker.tick() => {}
+                }
+
+                let polled = tokio::task::spawn_blocking(move || {
+                    let next = loader.poll();
+                    (loader, next)
+                })
+                .await;
+                let Ok((returned, next)) = polled else {
+                    warn(format_args!(
+                        "the manifest watcher stopped: loading the manifest panicked"
+                    ));
+                    break;
+                };
+                loader = returned;
+
+                if let Some(next) = next {
+                    registry.apply(&mut ctx, next).await;
+                }
+            }
+        });
+    }
+}
+
+/// Serves a manifest prompt: its messages, with the request's arguments
+/// substituted for their `{{placeholders}}`.
+#[derive(Debug)]
+struct Template(PromptEntry);
+
+impl Handler<GetPromptResult> for Template {
+    fn call(&self, params: HandlerParams) -> BoxFuture<'_, Result<GetPromptResult, Error>> {
+        let HandlerParams::Prompt(params, _) = params else {
+            unreachable!()
+        };
+        Box::pin(async move { self.render(&params.args.unwrap_or_default()) })
+    }
+}
+
+impl Template {
+    fn render(&self, args: &HashMap<String, Value>) -> Result<GetPromptResult, Error> {
+        if let Some(missing) = self
+            .0
+            .arguments
+            .iter()
+            .find(|arg| arg.required && !args.contains_key(&arg.name))
+        {
+            return Err(Error::new(
+                ErrorCode::InvalidParams,
+                format!("missing required argument `{}`", missing.name),
+            ));
+        }
+
+        let messages = self
+            .0
+            .messages
+            .iter()
+            .map(|message| {
+                let text = substitute(&message.text, |name| match args.get(name) {
+                    Some(Value::String(value)) => value.clone(),
+                    Some(Value::Null) | None => String::new(),
+                    Some(value) => value.to_string(),
+                });
+                PromptMessage::new(message.role).with(text)
+            })
+            .collect();
+
+        Ok(GetPromptResult {
+            descr: self.0.description.clone(),
+            messages,
+        })
+    }
+}
+
+impl PromptEntry {
+    fn to_prompt(&self) -> Prompt {
+        let args = self
+            .arguments
+            .iter()
+            .map(|arg| PromptArgument {
+                name: arg.name.clone(),
+                descr: arg.description.clone(),
+                required: Some(arg.required),
+            })
+            .collect();
+
+        let mut prompt = Prompt::from_handler(&self.name, args, Arc::new(Template(self.clone())));
+        prompt.title = self.title.clone();
+        prompt.descr = self.description.clone();
+        prompt
+    }
+}
+
+impl StaticResource {
+    fn mime(&self) -> Option<String> {
+        match (&self.entry.mime_type, &self.body) {
+            (Some(mime), _) => Some(mime.clone()),
+            (None, Body::Text(_)) => Some("text/plain".into()),
+            (None, Body::Blob(_)) => None,
+        }
+    }
+
+    fn to_resource(&self) -> Resource {
+        let mut resource = Resource::new(self.entry.uri.as_str(), self.entry.name.as_str());
+        resource.title = self.entry.title.clone();
+        resource.descr = self.entry.description.clone();
+        resource.mime = self.mime();
+        resource.size = Some(match &self.body {
+            Body::Text(text) => text.len(),
+            Body::Blob(blob) => blob.len(),
+        });
+        resource
+    }
+
+    fn contents(&self) -> ResourceContents {
+        let uri = self.entry.uri.as_str();
+        match (&self.body, self.mime()) {
+            (Body::Text(text), mime) => TextResourceContents::new(uri, text.as_str())
+                .with_mime(mime.unwrap_or_default())
+                .into(),
+            (Body::Blob(blob), Some(mime)) => BlobResourceContents::new(uri, blob.clone())
+                .with_mime(mime)
+                .into(),
+            (Body::Blob(blob), None) => BlobResourceContents::new(uri, blob.clone()).into(),
+        }
+    }
+}
+
+/// Replaces every `{{name}}` in `text` with `value(name)`. Whitespace inside
+/// the braces is ignored; an unclosed `{{` is left as written.
+fn substitute(text: &str, mut value: impl FnMut(&str) -> String) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        out.push_str(&value(rest[start + 2..start + 2 + len].trim()));
+        rest = &rest[start + 2 + len + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Refuses a prompt whose arguments collide or whose messages name an
+/// argument it does not declare -- both would otherwise only show up as a
+/// wrong rendering on a client.
+fn check_prompt(prompt: &PromptEntry) -> Result<(), Error> {
+    let mut declared = HashSet::new();
+    if let Some(duplicate) = prompt
+        .arguments
+        .iter()
+        .find(|arg| !declared.insert(arg.name.as_str()))
+    {
+        return Err(invalid(format!(
+            "prompt `{}` declares the argument `{}` twice",
+            prompt.name, duplicate.name
+        )));
+    }
+
+    let mut undeclared = None;
+    for message in &prompt.messages {
+        substitute(&message.text, |name| {
+            if !declared.contains(name) {
+                undeclared.get_or_insert_with(|| name.to_owned());
+            }
+            String::new()
+        });
+    }
+    match undeclared {
+        Some(name) => Err(invalid(format!(
+            "prompt `{}` uses `{{{{{name}}}}}`, which is not one of its arguments",
+            prompt.name
+        ))),
+        None => Ok(()),
+    }
+}
+
+fn parse(path: &Path, source: &str) -> Result<ManifestFile, Error> {
+    if source.trim().is_empty() {
+        return Ok(ManifestFile::default());
+    }
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => serde_json::from_str(source).map_err(invalid),
+        #[cfg(feature = "manifest-yaml")]
+        Some("yaml" | "yml") => serde_yaml::from_str(source).map_err(invalid),
+        #[cfg(feature = "manifest-toml")]
+        Some("toml") => toml::from_str(source).map_err(invalid),
+        _ => Err(invalid("not a manifest format this build reads")),
+    }
+}
+
+/// Whether `path` is a manifest file this build reads.
+fn is_manifest(path: &Path) -> bool {
+    let ext = path.extension().and_then(|ext| ext.to_str());
+    matches!(ext, Some("json"))
+        || (cfg!(feature = "manifest-yaml") && matches!(ext, Some("yaml" | "yml")))
+        || (cfg!(feature = "manifest-toml") && matches!(ext, Some("toml")))
+}
+
+/// The manifest files directly in `dir`, in path order.
+fn manifest_paths(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut paths: Vec<_> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && is_manifest(path))
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+fn note_duplicate<'a>(
+    origin: &mut HashMap<(&'static str, &'a str), &'a Path>,
+    kind: &'static str,
+    name: &'a str,
+    path: &'a Path,
+) {
+    match origin.insert((kind, name), path) {
+        Some(earlier) if earlier == path => warn(format_args!(
+            "manifest {kind} `{name}` is defined twice in {}; the latter wins",
+            path.display()
+        )),
+        Some(earlier) => warn(format_args!(
+            "manifest {kind} `{name}` is defined in both {} and {}; the latter wins",
+            earlier.display(),
+            path.display()
+        )),
+        None => {}
+    }
+}
+
+#[inline]
+fn invalid(err: impl ToString) -> Error {
+    Error::new(ErrorCode::InternalError, err.to_string())
+}
+
+#[inline]
+fn report<T>(result: Result<T, Error>) {
+    if let Err(err) = result {
+        warn(format_args!("cannot apply a manifest change: {err}"));
+    }
+}
+
+#[inline]
+fn warn(_message: std::fmt::Arguments<'_>) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(logger = "neva", "{_message}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::GetPromptRequestParams;
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            let dir = std::env::temp_dir().join(format!("neva-manifest-{}", uuid::Uuid::new_v4()));
+            std::fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+
+        fn write(&self, name: &str, contents: impl AsRef<[u8]>) {
+            let path = self.0.join(name);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn prompt(json: Value) -> PromptEntry {
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn greeting() -> PromptEntry {
+        prompt(serde_json::json!({
+            "name": "greet",
+            "arguments": [
+                { "name": "name", "required": true },
+                { "name": "mood" }
+            ],
+            "messages": [
+                { "text": "Say hello to {{ name }}{{mood}}." },
+                { "role": "assistant", "text": "Hello, {{name}}!" }
+            ]
+        }))
+    }
+
+    fn message_text(message: &PromptMessage) -> String {
+        serde_json::to_value(&message.content).unwrap()["text"]
+            .as_str()
+            .unwrap()
+            .to_owned()
+    }
+
+    #[test]
+    fn it_renders_a_prompt_template() {
+        let template = Template(greeting());
+        let args = HashMap::from([("name".to_owned(), Value::from("Ada"))]);
+
+        let result = template.render(&args).unwrap();
+
+        assert_eq!(result.messages.len(), 2);
+        assert_eq!(message_text(&result.messages[0]), "Say hello to Ada.");
+        assert_eq!(result.messages[1].role, Role::Assistant);
+        assert_eq!(message_text(&result.messages[1]), "Hello, Ada!");
+    }
+
+    #[test]
+    fn it_requires_required_arguments() {
+        let err = Template(greeting()).render(&HashMap::new()).unwrap_err();
+
+        assert_eq!(err.code, ErrorCode::InvalidParams);
+        assert!(err.to_string().contains("`name`"));
+    }
+
+    #[tokio::test]
+    async fn it_serves_the_template_as_a_prompt() {
+        let prompt = greeting().to_prompt();
+        let mut params = GetPromptRequestParams::new("greet");
+        params.args = Some(HashMap::from([("name".to_owned(), Value::from("Ada"))]));
+
+        let result = prompt.call(params).await.unwrap();
+
+        assert_eq!(message_text(&result.messages[1]), "Hello, Ada!");
+        assert_eq!(prompt.args.as_ref().map(Vec::len), Some(2));
+        assert!(prompt.arg_name_conflict().is_none());
+    }
+
+    #[test]
+    fn it_refuses_undeclared_placeholders() {
+        let entry = prompt(serde_json::json!({
+            "name": "greet",
+            "messages": [{ "text": "Hello, {{name}}!" }]
+        }));
+
+        let err = check_prompt(&entry).unwrap_err();
+
+        assert!(err.to_string().contains("{{name}}"));
+    }
+
+    #[test]
+    fn it_refuses_duplicate_arguments() {
+        let entry = prompt(serde_json::json!({
+            "name": "greet",
+            "arguments": [{ "name": "name" }, { "name": "name" }],
+            "messages": []
+        }));
+
+        assert!(check_prompt(&entry).is_err());
+    }
+
+    #[test]
+    fn it_leaves_an_unclosed_placeholder_as_written() {
+        assert_eq!(substitute("a {{b}} {{c", |_| "x".into()), "a x {{c");
+    }
+
+    #[test]
+    fn it_loads_only_when_something_changed() {
+        let dir = TempDir::new();
+        dir.write("prompts.json", r#"{ "prompts": [] }"#);
+        let mut loader = Loader::new(dir.0.clone());
+
+        assert!(loader.poll().is_some());
+        assert!(loader.poll().is_none());
+
+        dir.write(
+            "prompts.json",
+            r#"{ "prompts": [{ "name": "a", "messages": [] }] }"#,
+        );
+        let manifest = loader.poll().expect("a changed file is reloaded");
+
+        assert!(manifest.prompts.contains_key("a"));
+    }
+
+    #[test]
+    fn it_keeps_the_last_good_content_of_a_broken_file() {
+        let dir = TempDir::new();
+        dir.write(
+            "a.json",
+            r#"{ "prompts": [{ "name": "a", "messages": [] }] }"#,
+        );
+        dir.write(
+            "b.json",
+            r#"{ "prompts": [{ "name": "b", "messages": [] }] }"#,
+        );
+        let mut loader = Loader::new(dir.0.clone());
+        loader.poll().unwrap();
+
+        dir.write("a.json", r#"{ "prompts": [{ "name": "a", "messages": ["#);
+        dir.write(
+            "b.json",
+            r#"{ "prompts": [{ "name": "b", "title": "B", "messages": [] }] }"#,
+        );
+        let manifest = loader.poll().unwrap();
+
+        assert!(manifest.prompts.contains_key("a"));
+        assert_eq!(manifest.prompts["b"].title.as_deref(), Some("B"));
+    }
+
+    #[test]
+    fn it_forgets_a_removed_file() {
+        let dir = TempDir::new();
+        dir.write(
+            "a.json",
+            r#"{ "prompts": [{ "name": "a", "messages": [] }] }"#,
+        );
+        let mut loader = Loader::new(dir.0.clone());
+        loader.poll().unwrap();
+
+        std::fs::remove_file(dir.0.join("a.json")).unwrap();
+
+        assert!(loader.poll().unwrap().prompts.is_empty());
+    }
+
+    #[test]
+    fn a_later_file_wins_a_name() {
+        let dir = TempDir::new();
+        dir.write(
+            "a.json",
+            r#"{ "prompts": [{ "name": "p", "title": "A", "messages": [] }] }"#,
+        );
+        dir.write(
+            "b.json",
+            r#"{ "prompts": [{ "name": "p", "title": "B", "messages": [] }] }"#,
+        );
+
+        let manifest = Loader::new(dir.0.clone()).poll().unwrap();
+
+        assert_eq!(manifest.prompts["p"].title.as_deref(), Some("B"));
+    }
+
+    #[test]
+    fn it_reads_resource_contents() {
+        let dir = TempDir::new();
+        dir.write("docs/style.md", "# Style");
+        dir.write("docs/logo.bin", [0xff, 0xfe, 0x00]);
+        dir.write(
+            "resources.json",
+            r#"{ "resources": [
+                { "uri": "docs://style", "name": "style", "mimeType": "text/markdown", "file": "docs/style.md" },
+                { "uri": "docs://logo", "name": "logo", "file": "docs/logo.bin" },
+                { "uri": "docs://motd", "name": "motd", "text": "Hi" }
+            ] }"#,
+        );
+
+        let manifest = Loader::new(dir.0.clone()).poll().unwrap();
+
+        let style = &manifest.resources["docs://style"];
+        assert_eq!(style.body, Body::Text("# Style".into()));
+        assert_eq!(style.to_resource().mime.as_deref(), Some("text/markdown"));
+        assert_eq!(style.to_resource().size, Some(7));
+        assert_eq!(
+            manifest.resources["docs://logo"].body,
+            Body::Blob(vec![0xff, 0xfe, 0x00])
+        );
+        assert_eq!(
+            manifest.resources["docs://motd"]
+                .to_resource()
+                .mime
+                .as_deref(),
+            Some("text/plain")
+        );
+    }
+
+    #[test]
+    fn it_refuses_a_file_outside_the_directory() {
+        let dir = TempDir::new();
+        let outside = TempDir::new();
+        outside.write("secret.txt", "hunter2");
+        let escaping = Path::new("..")
+            .join(outside.0.file_name().unwrap())
+            .join("secret.txt");
+        dir.write(
+            "resources.json",
+            serde_json::json!({ "resources": [
+                { "uri": "docs://up", "name": "up", "file": escaping },
+                { "uri": "docs://abs", "name": "abs", "file": outside.0.join("secret.txt") }
+            ] })
+            .to_string(),
+        );
+
+        assert!(
+            Loader::new(dir.0.clone())
+                .poll()
+                .unwrap()
+                .resources
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn it_watches_the_files_a_manifest_names_and_nothing_else() {
+        let dir = TempDir::new();
+        dir.write("docs/style.md", "# Style");
+        dir.write(
+            "resources.json",
+            r#"{ "resources": [{ "uri": "docs://style", "name": "style", "file": "docs/style.md" }] }"#,
+        );
+        let mut loader = Loader::new(dir.0.clone());
+        loader.poll().unwrap();
+
+        dir.write("docs/unrelated.md", "# Unrelated");
+        assert!(loader.poll().is_none());
+
+        dir.write("docs/style.md", "# Style, revised");
+        let manifest = loader.poll().expect("a named file's change is reloaded");
+
+        assert_eq!(
+            manifest.resources["docs://style"].body,
+            Body::Text("# Style, revised".into())
+        );
+    }
+
+    #[test]
+    fn a_resource_needs_exactly_one_source() {
+        let dir = TempDir::new();
+        dir.write(
+            "resources.json",
+            r#"{ "resources": [{ "uri": "docs://none", "name": "none" }] }"#,
+        );
+
+        assert!(
+            Loader::new(dir.0.clone())
+                .poll()
+                .unwrap()
+                .resources
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn it_refuses_unknown_fields() {
+        let dir = TempDir::new();
+        dir.write(
+            "prompts.json",
+            r#"{ "prompts": [{ "name": "a", "mesages": [] }] }"#,
+        );
+
+        assert!(
+            Loader::new(dir.0.clone())
+                .poll()
+                .unwrap()
+                .prompts
+                .is_empty()
+        );
+    }
+
+    #[cfg(feature = "manifest-yaml")]
+    #[test]
+    fn it_reads_yaml() {
+        let dir = TempDir::new();
+        dir.write(
+            "prompts.yaml",
+            "prompts:\n  - name: review\n    messages:\n      - text: Review it\n",
+        );
+
+        let manifest = Loader::new(dir.0.clone()).poll().unwrap();
+
+        assert_eq!(manifest.prompts["review"].messages[0].role, Role::User);
+    }
+
+    #[cfg(feature = "manifest-toml")]
+    #[test]
+    fn it_reads_toml() {
+        let dir = TempDir::new();
+        dir.write(
+            "tools.toml",
+            "[[tools]]\nname = \"deploy\"\ndescription = \"Deploys\"\n\n[tools.annotations]\ndestructiveHint = true\n",
+        );
+
+        let manifest = Loader::new(dir.0.clone()).poll().unwrap();
+
+        assert_eq!(
+            manifest.tools["deploy"].annotations,
+            Some(serde_json::json!({ "destructiveHint": true }))
+        );
+    }
+
+    #[test]
+    fn code_keeps_its_names_and_tools_fall_back_to_code() {
+        let dir = TempDir::new();
+        dir.write(
+            "manifest.json",
+            r#"{
+                "prompts": [
+                    { "name": "coded", "title": "From the manifest", "messages": [] },
+                    { "name": "written", "messages": [] }
+                ],
+                "tools": [
+                    { "name": "deploy", "description": "From the manifest" },
+                    { "name": "missing", "description": "Nobody" }
+                ]
+            }"#,
+        );
+        let mut options = McpOptions::default();
+        options.add_prompt(Prompt::new("coded", || async { ("coded", Role::User) }));
+        options
+            .add_tool(Tool::new("deploy", || async { "deployed" }))
+            .with_title("Deploy");
+
+        let watcher =
+            ManifestWatcher::start(ManifestOptions::default().with_dir(&dir.0), &mut options)
+                .expect("watched by default");
+
+        let prompts = options.prompts.as_ref();
+        assert!(prompts["coded"].title.is_none());
+        assert!(prompts.contains_key("written"));
+        let tools = options.tools.as_ref();
+        assert_eq!(tools["deploy"].descr.as_deref(), Some("From the manifest"));
+        assert_eq!(tools["deploy"].title.as_deref(), Some("Deploy"));
+        assert!(!tools.contains_key("missing"));
+        assert!(options.is_prompts_list_changed_supported());
+
+        let mut deploy = tools["deploy"].clone();
+        watcher.registry.describe(&mut deploy, None);
+        assert!(deploy.descr.is_none());
+        assert_eq!(deploy.title.as_deref(), Some("Deploy"));
+    }
+
+    #[test]
+    fn an_unwatched_manifest_leaves_list_changed_alone() {
+        let dir = TempDir::new();
+        let mut options = McpOptions::default();
+
+        let watcher = ManifestWatcher::start(
+            ManifestOptions::default().with_dir(&dir.0).without_watch(),
+            &mut options,
+        );
+
+        assert!(watcher.is_none());
+        assert!(!options.is_prompts_list_changed_supported());
+    }
+}
@@ -182,16 +182,13 @@ pub(super) fn build_input_required(
         .iter()
         .find(|(_, request)| !arc.client_capabilities.allows(request))
     {
-        return Err(Error::new(
-            ErrorCode::MissingRequiredClientCapability,
+        return Err(Error::missing_client_capability(
             format!(
                 "server requested `{}` but the client did not declare support",
                 request.method()
             ),
-        )
-        .with_data(serde_json::json!({
-            "requiredCapabilities": arc.client_capabilities.requiring(request),
-        })));
+            arc.client_capabilities.requiring(request),
+        ));
     }
 
     let memos = arc.memos.lock().map(|m| m.clone()).unwrap_or_default();
@@ -0,0 +1,1066 @@
+//! Tools generated from an OpenAPI document.
+//!
+//! [`App::with_openapi`](crate::App::with_openapi) registers one [`Tool`] per
+//! operation of an OpenAPI 3.x document, and a call to it is made over HTTP to
+//! the API the document describes:
+//!
+//! ```no_run
+//! use neva::App;
+//! use neva::app::openapi::OpenApi;
+//!
+//! # fn main() -> Result<(), neva::error::Error> {
+//! let api = OpenApi::load("./petstore.json")?;
+//! let app = App::new().with_openapi(api, |api| api
+//!     .with_base_url("https://petstore.internal/v1")
+//!     .with_tool_prefix("pets_"));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! A tool is named after the operation's `operationId` -- or its method and
+//! path when it has none -- and takes the operation's parameters as arguments
+//! under their own names, with the request body under `body`. A parameter
+//! whose name another location already took is prefixed with its location, as
+//! in `header_id`. Schemas from `components` travel along as `$defs`.
+//!
+//! The first `2xx` response with a JSON schema becomes the `outputSchema`; a
+//! schema that is not an object is published wrapped as `{ "result": ... }`,
+//! and the response is wrapped the same way. The method sets the annotations:
+//! `GET`, `HEAD` and `OPTIONS` are read-only, `PUT` and `DELETE` idempotent.
+//!
+//! A response outside `2xx`, or a request that never got one, is a tool error
+//! the model sees -- the API failing is the tool failing, not the protocol.
+
+use super::handler::{Handler, HandlerParams};
+use crate::error::{Error, ErrorCode};
+use crate::shared::BoxFuture;
+use crate::types::{CallToolResponse, Content, Tool, ToolAnnotations};
+use http::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Client, Method};
+use serde_json::{Map, Value, json};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{Debug, Formatter};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(feature = "http-server")]
+use crate::auth::Claims;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// How many `$ref` hops are followed before a reference is given up on.
+const MAX_REF_HOPS: usize = 16;
+
+/// A parsed OpenAPI 3.x document.
+#[derive(Debug, Clone)]
+pub struct OpenApi {
+    doc: Arc<Value>,
+}
+
+/// Which operations of an [`OpenApi`] become tools, and how they are called.
+///
+/// See [`App::with_openapi`](crate::App::with_openapi).
+pub struct OpenApiOptions {
+    base_url: Option<String>,
+    prefix: String,
+    operations: Option<HashSet<String>>,
+    headers: HeaderMap,
+    timeout: Duration,
+    #[cfg(feature = "http-server")]
+    forward_authorization: bool,
+    #[cfg(feature = "http-server")]
+    claims_auth: Option<ClaimsAuth>,
+}
+
+#[cfg(feature = "http-server")]
+type ClaimsAuth = Arc<dyn Fn(&dyn Claims) -> HeaderMap + Send + Sync>;
+
+impl Debug for OpenApiOptions {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut dbg = f.debug_struct("OpenApiOptions");
+        dbg.field("base_url", &self.base_url)
+            .field("prefix", &self.prefix)
+            .field("operations", &self.operations)
+            .field("timeout", &self.timeout);
+        #[cfg(feature = "http-server")]
+        dbg.field("forward_authorization", &self.forward_authorization)
+            .field("claims_auth", &self.claims_auth.is_some());
+        dbg.finish()
+    }
+}
+
+impl Default for OpenApiOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            base_url: None,
+            prefix: String::new(),
+            operations: None,
+            headers: HeaderMap::new(),
+            timeout: DEFAULT_TIMEOUT,
+            #[cfg(feature = "http-server")]
+            forward_authorization: false,
+            #[cfg(feature = "http-server")]
+            claims_auth: None,
+        }
+    }
+}
+
+impl OpenApi {
+    /// Parses an OpenAPI document from JSON.
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        Self::from_value(serde_json::from_str(json)?)
+    }
+
+    /// Parses an OpenAPI document from YAML.
+    #[cfg(feature = "openapi-yaml")]
+    pub fn from_yaml(yaml: &str) -> Result<Self, Error> {
+        let doc = serde_yaml::from_str(yaml).map_err(|err| {
+            Error::new(
+                ErrorCode::InvalidParams,
+                format!("invalid OpenAPI document: {err}"),
+            )
+        })?;
+        Self::from_value(doc)
+    }
+
+    /// Reads an OpenAPI document from a file: YAML for a `.yaml` or `.yml`
+    /// file, JSON otherwise.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            #[cfg(feature = "openapi-yaml")]
+            Some("yaml" | "yml") => Self::from_yaml(&text),
+            #[cfg(not(feature = "openapi-yaml"))]
+            Some("yaml" | "yml") => Err(Error::new(
+                ErrorCode::InvalidParams,
+                "reading a YAML OpenAPI document needs the `openapi-yaml` feature",
+            )),
+            _ => Self::from_json(&text),
+        }
+    }
+
+    /// Wraps an already parsed OpenAPI document.
+    ///
+    /// Fails unless the document declares an `openapi` version of `3.x`.
+    pub fn from_value(doc: Value) -> Result<Self, Error> {
+        match doc.get("openapi").and_then(Value::as_str) {
+            Some(version) if version.starts_with("3.") => Ok(Self { doc: Arc::new(doc) }),
+            Some(version) => Err(Error::new(
+                ErrorCode::InvalidParams,
+                format!("OpenAPI {version} is not supported; only 3.x is"),
+            )),
+            None => Err(Error::new(
+                ErrorCode::InvalidParams,
+                "not an OpenAPI document: the `openapi` version is missing",
+            )),
+        }
+    }
+
+    /// Builds the tools for the operations `options` selects.
+    pub(crate) fn tools(&self, options: OpenApiOptions) -> Vec<Tool> {
+        let base_url = options.base_url.clone().or_else(|| self.server_url());
+        if base_url.is_none() {
+            warn(format_args!(
+                "the OpenAPI document names no absolute server URL; its tools fail until \
+                 one is set with `with_base_url`"
+            ));
+        }
+        let client = Client::builder()
+            .timeout(options.timeout)
+            .build()
+            .unwrap_or_default();
+        let shared = Arc::new(Shared {
+            base_url,
+            client,
+            options,
+        });
+
+        let Some(paths) = self.doc.get("paths").and_then(Value::as_object) else {
+            return Vec::new();
+        };
+        let mut tools = Vec::new();
+        for (path, item) in paths {
+            let item = self.resolve(item);
+            for method in METHODS {
+                let Some(op) = item.get(method) else {
+                    continue;
+                };
+                let name = op
+                    .get("operationId")
+                    .and_then(Value::as_str)
+                    .map(sanitize)
+                    .unwrap_or_else(|| sanitize(&format!("{method}_{path}")));
+                if shared
+                    .options
+                    .operations
+                    .as_ref()
+                    .is_some_and(|ops| !ops.contains(&name))
+                {
+                    continue;
+                }
+                let name = format!("{}{name}", shared.options.prefix);
+                tools.push(self.tool(name, method, path, item, op, &shared));
+            }
+        }
+        tools
+    }
+
+    fn tool(
+        &self,
+        name: String,
+        method: &str,
+        path: &str,
+        item: &Value,
+        op: &Value,
+        shared: &Arc<Shared>,
+    ) -> Tool {
+        let mut schemas = Schemas::new(&self.doc);
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        let mut params = Vec::new();
+
+        for param in self.parameters(item, op) {
+            let (Some(param_name), Some(location)) = (
+                param.get("name").and_then(Value::as_str),
+                param
+                    .get("in")
+                    .and_then(Value::as_str)
+                    .and_then(Location::parse),
+            ) else {
+                continue;
+            };
+            let property = if properties.contains_key(param_name) {
+                format!("{}_{param_name}", location.as_str())
+            } else {
+                param_name.to_owned()
+            };
+            let mut schema = param
+                .get("schema")
+                .or_else(|| first_content_schema(param.get("content")))
+                .map(|schema| schemas.convert(schema))
+                .unwrap_or_else(|| json!({ "type": "string" }));
+            if let (Some(descr), Some(schema)) = (param.get("description"), schema.as_object_mut())
+            {
+                schema.entry("description").or_insert_with(|| descr.clone());
+            }
+            let is_required =
+                location == Location::Path || param.get("required") == Some(&Value::Bool(true));
+            if is_required {
+                required.push(Value::String(property.clone()));
+            }
+            properties.insert(property.clone(), schema);
+            params.push(Param {
+                name: param_name.to_owned(),
+                location,
+                property,
+                required: is_required,
+            });
+        }
+
+        let body = op.get("requestBody").map(|body| self.resolve(body));
+        let body = body.and_then(|body| {
+            let content = body.get("content")?.as_object()?;
+            let (content_type, media) = content
+                .iter()
+                .find(|(content_type, _)| is_json(content_type))
+                .or_else(|| content.iter().next())?;
+            let property = if properties.contains_key("body") {
+                "requestBody"
+            } else {
+                "body"
+            };
+            let mut schema = match media.get("schema") {
+                Some(schema) if is_json(content_type) => schemas.convert(schema),
+                _ => json!({ "type": "string" }),
+            };
+            if let (Some(descr), Some(schema)) = (body.get("description"), schema.as_object_mut()) {
+                schema.entry("description").or_insert_with(|| descr.clone());
+            }
+            if body.get("required") == Some(&Value::Bool(true)) {
+                required.push(Value::String(property.to_owned()));
+            }
+            properties.insert(property.to_owned(), schema);
+            Some(Body {
+                content_type: content_type.clone(),
+                property: property.to_owned(),
+            })
+        });
+
+        let mut input_schema = json!({ "type": "object", "properties": properties });
+        if !required.is_empty() {
+            input_schema["required"] = Value::Array(required);
+        }
+        schemas.attach(&mut input_schema);
+
+        let mut output_schema = None;
+        let mut wrap_output = false;
+        if let Some(schema) = self.response_schema(op) {
+            let mut schemas = Schemas::new(&self.doc);
+            let is_object = self.resolve(schema).get("type") == Some(&json!("object"));
+            let mut schema = schemas.convert(schema);
+            if !is_object {
+                wrap_output = true;
+                schema = json!({
+                    "type": "object",
+                    "properties": { "result": schema },
+                    "required": ["result"],
+                });
+            }
+            schemas.attach(&mut schema);
+            output_schema = Some(schema);
+        }
+
+        let endpoint = Endpoint {
+            method: Method::from_bytes(method.to_ascii_uppercase().as_bytes())
+                .unwrap_or(Method::GET),
+            path: path.to_owned(),
+            params,
+            body,
+            wrap_output,
+            shared: shared.clone(),
+        };
+
+        let mut tool = Tool::with_handler(name, Arc::new(endpoint));
+        tool.input_schema = schema_of(input_schema);
+        tool.output_schema = output_schema.map(schema_of);
+        tool.title = op.get("summary").and_then(Value::as_str).map(str::to_owned);
+        tool.descr = op
+            .get("description")
+            .or_else(|| op.get("summary"))
+            .and_then(Value::as_str)
+            .map(str::to_owned);
+        tool.annotations = Some(annotations(method));
+        tool
+    }
+
+    /// The operation's parameters, path-level ones included unless the
+    /// operation overrides them.
+    fn parameters<'a>(&'a self, item: &'a Value, op: &'a Value) -> Vec<&'a Value> {
+        let list = |value: &'a Value| -> Vec<&'a Value> {
+            value
+                .get("parameters")
+                .and_then(Value::as_array)
+                .map(|params| params.iter().map(|param| self.resolve(param)).collect())
+                .unwrap_or_default()
+        };
+        let key = |param: &Value| (param.get("name").cloned(), param.get("in").cloned());
+        let own = list(op);
+        let mut params = list(item)
+            .into_iter()
+            .filter(|shared| !own.iter().any(|param| key(param) == key(shared)))
+            .collect::<Vec<_>>();
+        params.extend(own);
+        params
+    }
+
+    /// The JSON schema of the first `2xx` response -- or the default one --
+    /// that has one.
+    fn response_schema<'a>(&'a self, op: &'a Value) -> Option<&'a Value> {
+        let responses = op.get("responses")?.as_object()?;
+        let mut codes = responses
+            .keys()
+            .filter(|code| code.starts_with('2'))
+            .collect::<Vec<_>>();
+        codes.sort();
+        codes
+            .into_iter()
+            .chain(responses.keys().filter(|code| *code == "default"))
+            .find_map(|code| {
+                let response = self.resolve(&responses[code]);
+                response
+                    .get("content")?
+                    .as_object()?
+                    .iter()
+                    .find(|(content_type, _)| is_json(content_type))?
+                    .1
+                    .get("schema")
+            })
+    }
+
+    /// The first server URL that is absolute, with its variables at their
+    /// defaults.
+    fn server_url(&self) -> Option<String> {
+        self.doc
+            .get("servers")?
+            .as_array()?
+            .iter()
+            .find_map(|server| {
+                let mut url = server.get("url")?.as_str()?.to_owned();
+                if let Some(vars) = server.get("variables").and_then(Value::as_object) {
+                    for (var, def) in vars {
+                        if let Some(default) = def.get("default").and_then(Value::as_str) {
+                            url = url.replace(&format!("{{{var}}}"), default);
+                        }
+                    }
+                }
+                (url.starts_with("http://") || url.starts_with("https://")).then_some(url)
+            })
+    }
+
+    /// Follows `value` through any local `$ref` to what it names.
+    fn resolve<'a>(&'a self, mut value: &'a Value) -> &'a Value {
+        for _ in 0..MAX_REF_HOPS {
+            let Some(target) = value
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|reference| reference.strip_prefix('#'))
+                .and_then(|pointer| self.doc.pointer(pointer))
+            else {
+                break;
+            };
+            value = target;
+        }
+        value
+    }
+}
+
+impl OpenApiOptions {
+    /// Sets the URL the API is called at.
+    ///
+    /// Default: the first absolute URL among the document's `servers`.
+    pub fn with_base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = Some(url.into());
+        self
+    }
+
+    /// Sets a prefix for every tool name, to keep two APIs apart.
+    ///
+    /// Default: no prefix.
+    pub fn with_tool_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Registers tools only for the operations named, by `operationId` -- or
+    /// by the name the tool would get, for an operation without one.
+    ///
+    /// Default: every operation.
+    pub fn with_operations<I, S>(mut self, operations: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.operations = Some(operations.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sends a header with every call, such as an API key.
+    pub fn with_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Sets how long a call waits for the API.
+    ///
+    /// Default: 30 seconds.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Passes the caller's own `Authorization` header on to the API.
+    ///
+    /// Default: not passed on.
+    #[cfg(feature = "http-server")]
+    pub fn with_forwarded_authorization(mut self) -> Self {
+        self.forward_authorization = true;
+        self
+    }
+
+    /// Derives headers for the API from the caller's [`Claims`], such as a
+    /// subject header an internal service trusts.
+    ///
+    /// Not called for a caller without claims.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use neva::app::openapi::OpenApiOptions;
+    /// use http::{HeaderMap, HeaderValue};
+    ///
+    /// fn config(api: OpenApiOptions) -> OpenApiOptions {
+    ///     api.with_auth_from_claims(|claims| {
+    ///         let mut headers = HeaderMap::new();
+    ///         if let Some(sub) = claims.subject().and_then(|sub| HeaderValue::from_str(sub).ok()) {
+    ///             headers.insert("x-user", sub);
+    ///         }
+    ///         headers
+    ///     })
+    /// }
+    /// ```
+    #[cfg(feature = "http-server")]
+    pub fn with_auth_from_claims<F>(mut self, auth: F) -> Self
+    where
+        F: Fn(&dyn Claims) -> HeaderMap + Send + Sync + 'static,
+    {
+        self.claims_auth = Some(Arc::new(auth));
+        self
+    }
+}
+
+/// What every tool of one document shares.
+struct Shared {
+    base_url: Option<String>,
+    client: Client,
+    options: OpenApiOptions,
+}
+
+/// One operation, as a tool handler.
+struct Endpoint {
+    method: Method,
+    path: String,
+    params: Vec<Param>,
+    body: Option<Body>,
+    wrap_output: bool,
+    shared: Arc<Shared>,
+}
+
+struct Param {
+    name: String,
+    location: Location,
+    property: String,
+    required: bool,
+}
+
+struct Body {
+    content_type: String,
+    property: String,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Location {
+    Path,
+    Query,
+    Header,
+    Cookie,
+}
+
+impl Location {
+    fn parse(location: &str) -> Option<Self> {
+        match location {
+            "path" => Some(Self::Path),
+            "query" => Some(Self::Query),
+            "header" => Some(Self::Header),
+            "cookie" => Some(Self::Cookie),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Path => "path",
+            Self::Query => "query",
+            Self::Header => "header",
+            Self::Cookie => "cookie",
+        }
+    }
+}
+
+impl Handler<CallToolResponse> for Endpoint {
+    #[inline]
+    fn call(&self, params: HandlerParams) -> BoxFuture<'_, Result<CallToolResponse, Error>> {
+        let HandlerParams::Tool(params, _) = params else {
+            unreachable!()
+        };
+        Box::pin(async move {
+            let args = params.args.unwrap_or_default();
+            let ctx = params.meta.and_then(|meta| meta.context);
+            self.send(args, ctx).await
+        })
+    }
+}
+
+impl Endpoint {
+    async fn send(
+        &self,
+        args: HashMap<String, Value>,
+        ctx: Option<super::context::Context>,
+    ) -> Result<CallToolResponse, Error> {
+        let shared = &self.shared;
+        let Some(base_url) = &shared.base_url else {
+            return Err(Error::new(
+                ErrorCode::InternalError,
+                "the OpenAPI document names no absolute server URL; set one with `with_base_url`",
+            ));
+        };
+
+        let mut path = self.path.clone();
+        let mut query = Vec::new();
+        let mut headers = shared.options.headers.clone();
+        let mut cookies = Vec::new();
+        for param in &self.params {
+            let Some(value) = args.get(&param.property).filter(|value| !value.is_null()) else {
+                if param.required {
+                    return Err(Error::new(
+                        ErrorCode::InvalidParams,
+                        format!("missing required argument `{}`", param.property),
+                    ));
+                }
+                continue;
+            };
+            match param.location {
+                Location::Path => {
+                    // Encoding leaves these as they are, and the API would
+                    // read them as a step within or out of the path.
+                    let value = scalar(value);
+                    if value == "." || value == ".." {
+                        return Err(Error::new(
+                            ErrorCode::InvalidParams,
+                            format!("argument `{}` cannot be `{value}`", param.property),
+                        ));
+                    }
+                    path = path.replace(&format!("{{{}}}", param.name), &encode(&value));
+                }
+                Location::Query => match value {
+                    Value::Array(items) => {
+                        query.extend(items.iter().map(|item| (param.name.clone(), scalar(item))))
+                    }
+                    value => query.push((param.name.clone(), scalar(value))),
+                },
+                Location::Header => {
+                    let name = HeaderName::from_bytes(param.name.as_bytes());
+                    let value = HeaderValue::from_str(&scalar(value));
+                    match (name, value) {
+                        (Ok(name), Ok(value)) => {
+                            headers.insert(name, value);
+                        }
+                        _ => {
+                            return Err(Error::new(
+                                ErrorCode::InvalidParams,
+                                format!("argument `{}` is not a valid header", param.property),
+                            ));
+                        }
+                    }
+                }
+                Location::Cookie => {
+                    let value = scalar(value);
+                    if !is_cookie_value(&value) {
+                        return Err(Error::new(
+                            ErrorCode::InvalidParams,
+                            format!("argument `{}` is not a valid cookie value", param.property),
+                        ));
+                    }
+                    cookies.push(format!("{}={value}", param.name));
+                }
+            }
+        }
+        if !cookies.is_empty()
+            && let Ok(cookie) = HeaderValue::from_str(&cookies.join("; "))
+        {
+            headers.insert(http::header::COOKIE, cookie);
+        }
+        #[cfg(feature = "http-server")]
+        if let Some(ctx) = &ctx {
+            self.forward_auth(ctx, &mut headers);
+        }
+        #[cfg(not(feature = "http-server"))]
+        let _ = ctx;
+
+        let mut url = format!("{}{path}", base_url.trim_end_matches('/'));
+        for (i, (name, value)) in query.iter().enumerate() {
+            url.push(if i == 0 { '?' } else { '&' });
+            url.push_str(&encode(name));
+            url.push('=');
+            url.push_str(&encode(value));
+        }
+        let mut request = shared
+            .client
+            .request(self.method.clone(), &url)
+            .headers(headers);
+        if let Some(body) = &self.body
+            && let Some(value) = args.get(&body.property).filter(|value| !value.is_null())
+        {
+            request = match value {
+                Value::String(text) if !is_json(&body.content_type) => request
+                    .header(http::header::CONTENT_TYPE, &body.content_type)
+                    .body(text.clone()),
+                value => request.json(value),
+            };
+        }
+
+        let call = format!("{} {}", self.method, self.path);
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(err) => return Ok(failed(format!("`{call}` failed: {err}"))),
+        };
+        let status = response.status();
+        let text = match response.text().await {
+            Ok(text) => text,
+            Err(err) => return Ok(failed(format!("`{call}` failed: {err}"))),
+        };
+        if !status.is_success() {
+            return Ok(failed(format!("`{call}` returned {status}: {text}")));
+        }
+
+        let mut result = CallToolResponse::new(Content::text(text.clone()));
+        if let Ok(value) = serde_json::from_str::<Value>(&text) {
+            result.struct_content = Some(match self.wrap_output {
+                true => json!({ "result": value }),
+                false => value,
+            });
+        }
+        Ok(result)
+    }
+
+    /// Adds the headers the caller's identity passes on to the API.
+    #[cfg(feature = "http-server")]
+    fn forward_auth(&self, ctx: &super::context::Context, headers: &mut HeaderMap) {
+        let options = &self.shared.options;
+        if options.forward_authorization
+            && let Some(auth) = ctx.headers.get(http::header::AUTHORIZATION)
+        {
+            headers.insert(http::header::AUTHORIZATION, auth.clone());
+        }
+        if let (Some(auth), Some(claims)) = (&options.claims_auth, &ctx.claims) {
+            headers.extend(auth(claims.as_ref()));
+        }
+    }
+}
+
+/// Converts OpenAPI schemas to JSON Schema, collecting the components they
+/// reference as `$defs`.
+struct Schemas<'a> {
+    doc: &'a Value,
+    defs: BTreeMap<String, Value>,
+    pending: Vec<String>,
+}
+
+impl<'a> Schemas<'a> {
+    fn new(doc: &'a Value) -> Self {
+        Self {
+            doc,
+            defs: BTreeMap::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Rewrites component references to `$defs` and OpenAPI 3.0's `nullable`
+    /// to a `null` type.
+    fn convert(&mut self, schema: &Value) -> Value {
+        match schema {
+            Value::Object(map) => {
+                let mut out = Map::with_capacity(map.len());
+                for (key, value) in map {
+                    match (key.as_str(), value) {
+                        ("$ref", Value::String(reference)) => {
+                            let local = match reference.strip_prefix("#/components/schemas/") {
+                                Some(name) => {
+                                    self.pending.push(name.to_owned());
+                                    format!("#/$defs/{name}")
+                                }
+                                None => reference.clone(),
+                            };
+                            out.insert(key.clone(), Value::String(local));
+                        }
+                        ("nullable", _) => {}
+                        _ => {
+                            out.insert(key.clone(), self.convert(value));
+                        }
+                    }
+                }
+                if map.get("nullable") == Some(&Value::Bool(true))
+                    && let Some(Value::String(ty)) = out.get("type")
+                {
+                    let ty = json!([ty, "null"]);
+                    out.insert("type".into(), ty);
+                }
+                Value::Object(out)
+            }
+            Value::Array(items) => {
+                Value::Array(items.iter().map(|item| self.convert(item)).collect())
+            }
+            value => value.clone(),
+        }
+    }
+
+    /// Adds the components referenced so far -- and those they reference --
+    /// to `schema` as `$defs`.
+    fn attach(mut self, schema: &mut Value) {
+        while let Some(name) = self.pending.pop() {
+            if self.defs.contains_key(&name) {
+                continue;
+            }
+            let component = self
+                .doc
+                .pointer(&format!("/components/schemas/{name}"))
+                .map(|component| self.convert(component))
+                .unwrap_or_else(|| json!({}));
+            self.defs.insert(name, component);
+        }
+        if !self.defs.is_empty() {
+            schema["$defs"] = Value::Object(self.defs.into_iter().collect());
+        }
+    }
+}
+
+/// The tool annotations an HTTP method implies.
+fn annotations(method: &str) -> ToolAnnotations {
+    let annotations = ToolAnnotations::new().with_open_world(true);
+    match method {
+        "get" | "head" | "options" => annotations.with_readonly(true),
+        "put" => annotations.with_idempotent(true).with_destructive(false),
+        "delete" => annotations.with_idempotent(true).with_destructive(true),
+        _ => annotations,
+    }
+}
+
+fn schema_of(schema: Value) -> crate::types::ToolInputSchema {
+    serde_json::from_value(schema).unwrap_or_default()
+}
+
+fn first_content_schema(content: Option<&Value>) -> Option<&Value> {
+    content?.as_object()?.values().next()?.get("schema")
+}
+
+#[inline]
+fn is_json(content_type: &str) -> bool {
+    let essence = content_type.split(';').next().unwrap_or_default().trim();
+    essence == "application/json" || essence.ends_with("+json")
+}
+
+/// A tool name made of the characters MCP allows in one.
+fn sanitize(name: &str) -> String {
+    let name = name
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '_' | '-' | '.' => c,
+            _ => '_',
+        })
+        .collect::<String>();
+    let name = name.trim_matches('_');
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// A parameter value as it goes into a URL or a header.
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// Whether `value` is made of the characters RFC 6265 allows in a cookie
+/// value -- none of which ends the cookie or starts another.
+fn is_cookie_value(value: &str) -> bool {
+    value
+        .bytes()
+        .all(|byte| matches!(byte, 0x21 | 0x23..=0x2B | 0x2D..=0x3A | 0x3C..=0x5B | 0x5D..=0x7E))
+}
+
+/// Percent-encodes a path segment or a query parameter.
+fn encode(segment: &str) -> String {
+    let mut out = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    out
+}
+
+#[inline]
+fn failed(message: String) -> CallToolResponse {
+    CallToolResponse::error(Error::new(ErrorCode::InternalError, message))
+}
+
+#[inline]
+fn warn(_message: std::fmt::Arguments<'_>) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(logger = "neva", "{_message}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn petstore() -> OpenApi {
+        OpenApi::from_value(json!({
+            "openapi": "3.0.3",
+            "servers": [{ "url": "https://{env}.example.com/v1", "variables": { "env": { "default": "api" } } }],
+            "paths": {
+                "/pets/{petId}": {
+                    "parameters": [{ "name": "petId", "in": "path", "required": true, "schema": { "type": "integer" } }],
+                    "get": {
+                        "operationId": "getPet",
+                        "summary": "Find a pet",
+                        "parameters": [{ "name": "petId", "in": "header", "schema": { "type": "string" } }],
+                        "responses": {
+                            "200": { "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } } } }
+                        }
+                    },
+                    "delete": { "responses": { "204": { "description": "gone" } } }
+                },
+                "/pets": {
+                    "get": {
+                        "operationId": "listPets",
+                        "parameters": [{ "$ref": "#/components/parameters/limit" }],
+                        "responses": {
+                            "200": { "content": { "application/json": { "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Pet" } } } } }
+                        }
+                    },
+                    "put": {
+                        "operationId": "putPet",
+                        "requestBody": {
+                            "required": true,
+                            "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } } }
+                        },
+                        "responses": {}
+                    }
+                }
+            },
+            "components": {
+                "parameters": {
+                    "limit": { "name": "limit", "in": "query", "schema": { "type": "integer", "nullable": true } }
+                },
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "properties": { "name": { "type": "string" }, "owner": { "$ref": "#/components/schemas/Owner" } }
+                    },
+                    "Owner": { "type": "object", "properties": { "pets": { "type": "array", "items": { "$ref": "#/components/schemas/Pet" } } } }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn tool<'a>(tools: &'a [Tool], name: &str) -> &'a Tool {
+        tools.iter().find(|tool| tool.name == name).unwrap()
+    }
+
+    fn schema(schema: &crate::types::ToolInputSchema) -> Value {
+        serde_json::to_value(schema).unwrap()
+    }
+
+    #[test]
+    fn it_builds_a_tool_per_operation() {
+        let tools = petstore().tools(OpenApiOptions::default());
+        let mut names = tools
+            .iter()
+            .map(|tool| tool.name.as_str())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, ["delete_pets_petId", "getPet", "listPets", "putPet"]);
+
+        let get = tool(&tools, "getPet");
+        assert_eq!(get.title.as_deref(), Some("Find a pet"));
+        let input = schema(&get.input_schema);
+        assert_eq!(input["properties"]["petId"]["type"], "integer");
+        assert_eq!(input["properties"]["header_petId"]["type"], "string");
+        assert_eq!(input["required"], json!(["petId"]));
+
+        let output = schema(get.output_schema.as_ref().unwrap());
+        assert_eq!(output["$ref"], "#/$defs/Pet");
+        assert!(output["$defs"]["Owner"].is_object());
+    }
+
+    #[test]
+    fn it_wraps_non_object_outputs_and_reads_bodies() {
+        let tools = petstore().tools(OpenApiOptions::default());
+
+        let list = tool(&tools, "listPets");
+        let input = schema(&list.input_schema);
+        assert_eq!(
+            input["properties"]["limit"]["type"],
+            json!(["integer", "null"])
+        );
+        let output = schema(list.output_schema.as_ref().unwrap());
+        assert_eq!(output["properties"]["result"]["type"], "array");
+
+        let put = tool(&tools, "putPet");
+        let input = schema(&put.input_schema);
+        assert_eq!(input["properties"]["body"]["$ref"], "#/$defs/Pet");
+        assert_eq!(input["required"], json!(["body"]));
+        assert!(put.output_schema.is_none());
+    }
+
+    #[test]
+    fn it_annotates_by_method() {
+        let tools = petstore().tools(OpenApiOptions::default());
+        let annotations = |name| tool(&tools, name).annotations.clone().unwrap();
+
+        assert_eq!(annotations("getPet").readonly, Some(true));
+        assert_eq!(annotations("putPet").idempotent, Some(true));
+        assert_eq!(annotations("delete_pets_petId").destructive, Some(true));
+        assert_eq!(annotations("delete_pets_petId").idempotent, Some(true));
+    }
+
+    #[test]
+    fn it_selects_and_prefixes_operations() {
+        let tools = petstore().tools(
+            OpenApiOptions::default()
+                .with_operations(["getPet"])
+                .with_tool_prefix("pets_"),
+        );
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].name, "pets_getPet");
+        assert_eq!(
+            petstore().server_url().as_deref(),
+            Some("https://api.example.com/v1")
+        );
+    }
+
+    #[tokio::test]
+    async fn it_refuses_arguments_it_cannot_send_safely() {
+        let api = OpenApi::from_value(json!({
+            "openapi": "3.0.3",
+            "servers": [{ "url": "http://127.0.0.1:9" }],
+            "paths": {
+                "/files/{name}": {
+                    "get": {
+                        "operationId": "getFile",
+                        "parameters": [
+                            { "name": "name", "in": "path", "required": true },
+                            { "name": "rev", "in": "query", "required": true },
+                            { "name": "x-tenant", "in": "header", "required": true },
+                            { "name": "session", "in": "cookie" }
+                        ],
+                        "responses": {}
+                    }
+                }
+            }
+        }))
+        .unwrap();
+        let tools = api.tools(OpenApiOptions::default());
+        let get = tool(&tools, "getFile");
+        let call = |args: Value| {
+            get.call(crate::types::CallToolRequestParams::new("getFile").with_args(args))
+        };
+        let args = |name: &str, session: &str| json!({ "name": name, "rev": "1", "x-tenant": "t", "session": session });
+
+        for name in [".", ".."] {
+            let err = call(args(name, "s")).await.unwrap_err();
+            assert_eq!(err.code, ErrorCode::InvalidParams, "{name}");
+        }
+
+        let err = call(args("a", "s; admin=1")).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidParams);
+        assert!(err.to_string().contains("cookie"));
+
+        for missing in ["rev", "x-tenant"] {
+            let mut args = args("a", "s");
+            args.as_object_mut().unwrap().remove(missing);
+            let err = call(args).await.unwrap_err();
+            assert_eq!(err.code, ErrorCode::InvalidParams, "{missing}");
+            assert!(err.to_string().contains(missing));
+        }
+    }
+
+    #[test]
+    fn it_refuses_other_documents() {
+        assert!(OpenApi::from_value(json!({ "swagger": "2.0" })).is_err());
+        assert!(OpenApi::from_value(json!({ "openapi": "2.0" })).is_err());
+        assert!(OpenApi::from_json("{").is_err());
+    }
+}
@@ -1,6 +1,7 @@
 //! MCP server options
 
 use crate::app::{collection::Collection, handler::RequestHandler};
+use crate::transport::record::{Peer, Recorder, Tap};
 #[cfg(feature = "http-server")]
 use crate::transport::{HttpEngine, HttpServer};
 use crate::transport::{StdIoServer, TransportProto};
@@ -8,7 +9,6 @@ use dashmap::DashMap;
 #[cfg(feature = "legacy-spec")]
 use dashmap::DashSet;
 use std::fmt::{Debug, Formatter};
-#[cfg(not(feature = "legacy-spec"))]
 use std::sync::atomic::AtomicUsize;
 use std::{sync::Arc, time::Duration};
 use tokio_util::sync::CancellationToken;
@@ -37,7 +37,6 @@ use crate::types::{ServerTasksCapability, Task};
 #[cfg(all(feature = "tracing", feature = "legacy-spec"))]
 use tracing_subscriber::{Registry, filter::LevelFilter, reload::Handle};
 
-#[cfg(any(feature = "tasks", all(feature = "tracing", feature = "legacy-spec")))]
 use crate::error::Error;
 #[cfg(all(feature = "tracing", feature = "legacy-spec"))]
 use crate::error::ErrorCode;
@@ -65,6 +64,21 @@ pub struct McpOptions {
     /// A flat map of resource templates, where the _key_ is a resource template name
     pub(super) resources_templates: Collection<ResourceTemplate>,
 
+    /// What the static resources a manifest defines read as, where the _key_
+    /// is a resource URI
+    #[cfg(feature = "manifest")]
+    pub(crate) manifest_contents: DashMap<String, crate::types::ResourceContents>,
+
+    /// Directories served as resources, each answering reads under its own
+    /// URI prefix
+    #[cfg(feature = "directory")]
+    pub(crate) directories: Vec<std::sync::Arc<crate::app::directory::Directory>>,
+
+    /// Compiled input schemas `tools/call` arguments are checked against,
+    /// when argument validation is on
+    #[cfg(feature = "server-validation")]
+    pub(crate) argument_validation: Option<crate::app::validation::ArgumentValidators>,
+
     /// Holds current subscriptions to resource changes
     #[cfg(feature = "legacy-spec")]
     pub(super) resource_subscriptions: DashSet<Uri>,
@@ -94,9 +108,15 @@ pub struct McpOptions {
     /// response to the transport sender, which is what makes "zero" mean
     /// "everything produced so far is queued" -- the condition the shutdown
     /// drain waits on.
-    #[cfg(not(feature = "legacy-spec"))]
     pub(crate) in_flight: Arc<AtomicUsize>,
 
+    /// Cancelled the moment shutdown is requested, a phase ahead of anything
+    /// else: from then on new requests are refused rather than started.
+    pub(crate) draining: CancellationToken,
+
+    /// Requests refused because they arrived while the server was draining.
+    pub(crate) rejected: AtomicUsize,
+
     /// An ordered list of middlewares
     pub(super) middlewares: Option<Middlewares>,
 
@@ -129,12 +149,19 @@ pub struct McpOptions {
     /// Current transport protocol that this server uses
     proto: Option<TransportProto>,
 
+    /// Records the traffic of the transport, if set
+    recorder: Option<Recorder>,
+
     /// A resource template routing data structure
     resource_routes: Route,
 
     /// Currently running requests
     requests: DashMap<RequestId, CancellationToken>,
 
+    /// The client roots known per session, for tools that are sandboxed to them
+    #[cfg(feature = "legacy-spec")]
+    pub(crate) roots: crate::app::context::RootsCache,
+
     /// Currently running tasks
     #[cfg(feature = "tasks")]
     pub(super) tasks: TaskTracker,
@@ -171,6 +198,14 @@ pub struct McpOptions {
     #[cfg(not(feature = "legacy-spec"))]
     request_state_audience: Option<Box<str>>,
 
+    /// Max size (bytes) of a streamed blob a resource handler may return;
+    /// `None` lets any size through.
+    max_blob_size: Option<u64>,
+
+    /// Decides which errors a tool returns are sent as protocol errors;
+    /// `None` goes by [`ToolErrorMode::for_kind`](crate::types::ToolErrorMode::for_kind).
+    tool_error_policy: Option<crate::types::tool::ToolErrorPolicy>,
+
     /// Max encoded `requestState` blob length (bytes) before the server
     /// rejects the round-trip with "requestState too large".
     #[cfg(not(feature = "legacy-spec"))]
@@ -224,7 +259,14 @@ impl Default for McpOptions {
             resources: Collection::new(),
             prompts: Collection::new(),
             resources_templates: Collection::new(),
+            #[cfg(feature = "manifest")]
+            manifest_contents: Default::default(),
+            #[cfg(feature = "directory")]
+            directories: Vec::new(),
+            #[cfg(feature = "server-validation")]
+            argument_validation: None,
             proto: Default::default(),
+            recorder: None,
             protocol_ver: Default::default(),
             tools_capability: Default::default(),
             resources_capability: Default::default(),
@@ -236,13 +278,16 @@ impl Default for McpOptions {
             resource_routes: Default::default(),
             requests: Default::default(),
             #[cfg(feature = "legacy-spec")]
+            roots: Default::default(),
+            #[cfg(feature = "legacy-spec")]
             resource_subscriptions: Default::default(),
             #[cfg(not(feature = "legacy-spec"))]
             subscriptions: Default::default(),
             #[cfg(not(feature = "legacy-spec"))]
             shutdown: CancellationToken::new(),
-            #[cfg(not(feature = "legacy-spec"))]
             in_flight: Default::default(),
+            draining: CancellationToken::new(),
+            rejected: Default::default(),
             middlewares: None,
             #[cfg(all(feature = "tracing", feature = "legacy-spec"))]
             log_level: Default::default(),
@@ -263,6 +308,8 @@ impl Default for McpOptions {
             request_state_ttl_secs: 300,
             #[cfg(not(feature = "legacy-spec"))]
             request_state_audience: None,
+            max_blob_size: None,
+            tool_error_policy: None,
             #[cfg(not(feature = "legacy-spec"))]
             max_state_bytes: 8 * 1024,
             #[cfg(not(feature = "legacy-spec"))]
@@ -327,6 +374,23 @@ impl McpOptions {
         self.with_http(|http| http)
     }
 
+    /// Sets the transport protocol directly.
+    #[inline]
+    pub(crate) fn with_transport(mut self, proto: TransportProto) -> Self {
+        self.proto = Some(proto);
+        self
+    }
+
+    /// Records every message the transport sends or receives.
+    ///
+    /// See [`crate::transport::record`] for the format.
+    ///
+    /// Default: not recorded
+    pub fn with_recorder(mut self, recorder: Recorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
     /// Specifies MCP server name
     pub fn with_name(mut self, name: &str) -> Self {
         self.implementation.name = name.into();
@@ -439,6 +503,32 @@ impl McpOptions {
         self
     }
 
+    /// Checks the arguments of every `tools/call` against the tool's input
+    /// schema before its handler runs.
+    ///
+    /// A call that violates the schema is refused with an `InvalidParams`
+    /// error whose `data.errors` lists each violation by JSON pointer, instead
+    /// of failing somewhere inside the handler's extractors -- or, for a
+    /// constraint no Rust type expresses (`minimum`, `pattern`, `enum`,
+    /// `oneOf`), not failing at all. Each tool's schema is compiled on its
+    /// first call and reused until the tool is replaced or removed.
+    ///
+    /// Default: off
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use neva::App;
+    ///
+    /// let app = App::new().with_options(|opt| opt.with_argument_validation());
+    /// # let _ = app;
+    /// ```
+    #[cfg(feature = "server-validation")]
+    pub fn with_argument_validation(mut self) -> Self {
+        self.argument_validation = Some(Default::default());
+        self
+    }
+
     /// Configures a `tracing_subscriber::reload::Handle` that allows changing the [`LoggingLevel`] at runtime
     #[cfg_attr(
         feature = "legacy-spec",
@@ -592,6 +682,48 @@ impl McpOptions {
             .or_insert(prompt)
     }
 
+    /// Turns on `list_changed` for prompts and resources -- and for tools, if
+    /// there are any -- for a server whose lists change without its code
+    /// changing them.
+    #[cfg(feature = "manifest")]
+    pub(crate) fn announce_list_changes(&mut self) {
+        self.prompts_capability.get_or_insert_default().list_changed = true;
+        self.resources_capability
+            .get_or_insert_default()
+            .list_changed = true;
+        if let Some(tools) = &mut self.tools_capability {
+            tools.list_changed = true;
+        }
+    }
+
+    /// Adds a directory served as resources, listing `template` for it.
+    ///
+    /// The template is listed, not routed: a file's path spans any number of
+    /// segments, so the directory answers reads under its prefix itself.
+    #[cfg(feature = "directory")]
+    pub(crate) fn add_directory(
+        &mut self,
+        directory: std::sync::Arc<crate::app::directory::Directory>,
+        template: ResourceTemplate,
+    ) {
+        self.resources_capability.get_or_insert_default();
+
+        self.resources_templates
+            .as_mut()
+            .entry(template.name.clone())
+            .or_insert(template);
+        self.directories.push(directory);
+    }
+
+    /// Turns on `list_changed` and `subscribe` for resources, for a server
+    /// whose files change under it.
+    #[cfg(feature = "directory")]
+    pub(crate) fn announce_resource_changes(&mut self) {
+        let resources = self.resources_capability.get_or_insert_default();
+        resources.list_changed = true;
+        resources.subscribe = true;
+    }
+
     /// Registers a middleware
     #[inline]
     pub(crate) fn add_middleware(&mut self, middleware: Middleware) {
@@ -620,8 +752,13 @@ impl McpOptions {
 
     /// Returns current transport protocol
     pub(crate) fn transport(&mut self) -> TransportProto {
-        let transport = self.proto.take();
-        transport.unwrap_or_default()
+        let transport = self.proto.take().unwrap_or_default();
+        match self.recorder.take() {
+            Some(recorder) => {
+                TransportProto::Recorded(Box::new(transport), Tap::new(recorder, Peer::Server))
+            }
+            None => transport,
+        }
     }
 
     /// Returns a display label for the currently configured transport
@@ -656,14 +793,67 @@ impl McpOptions {
         self.resource_routes.find(uri)
     }
 
-    /// Returns a paginated list of available resources.
-    #[inline]
+    /// Returns a paginated list of available resources: the static ones,
+    /// then those each enumerable template covers, template by template.
+    ///
+    /// The cursor keeps which of them the page starts in -- the static
+    /// resources being the first -- in its high half and the offset in the
+    /// low half, so a cursor into the static resources is the plain offset
+    /// it has always been.
     pub(crate) async fn list_resources_page(
         &self,
         cursor: Option<Cursor>,
         page_size: usize,
-    ) -> (Vec<Resource>, Option<Cursor>) {
-        self.resources.page_values(cursor, page_size).await
+    ) -> Result<(Vec<Resource>, Option<Cursor>), Error> {
+        const SHIFT: u32 = usize::BITS / 2;
+        let cursor = *cursor.unwrap_or_default();
+        let (mut source, mut offset) = (cursor >> SHIFT, cursor & ((1 << SHIFT) - 1));
+
+        let mut resources = Vec::with_capacity(page_size);
+        if source == 0 {
+            let (page, next_cursor) = self
+                .resources
+                .page_values(Some(Cursor(offset)), page_size)
+                .await;
+            if next_cursor.is_some() {
+                return Ok((page, next_cursor));
+            }
+            resources = page;
+            (source, offset) = (1, 0);
+        }
+
+        let templates: Vec<ResourceTemplate> = self
+            .resources_templates
+            .values()
+            .await
+            .into_iter()
+            .filter(ResourceTemplate::is_enumerable)
+            .collect();
+        while let Some(template) = templates.get(source - 1) {
+            let wanted = page_size - resources.len();
+            // One more than fits tells whether the template has more; on a
+            // full page, whether the next one has anything to show at all
+            let page = template.enumerate(offset, wanted + 1).await?;
+            let more = page.len() > wanted;
+            resources.extend(page.into_iter().take(wanted).flatten());
+            if more {
+                return Ok((
+                    resources,
+                    Some(Cursor((source << SHIFT) | (offset + wanted))),
+                ));
+            }
+            (source, offset) = (source + 1, 0);
+        }
+        Ok((resources, None))
+    }
+
+    /// Returns the resource template with this URI template
+    pub(crate) async fn resource_template_by_uri(&self, uri: &str) -> Option<ResourceTemplate> {
+        self.resources_templates
+            .values()
+            .await
+            .into_iter()
+            .find(|template| &*template.uri_template == uri)
     }
 
     /// Returns a paginated list of available resource templates.
@@ -752,12 +942,23 @@ impl McpOptions {
 
     /// Returns the counter of messages currently inside the middleware
     /// pipeline. See [`McpOptions::in_flight`].
-    #[cfg(not(feature = "legacy-spec"))]
     #[inline]
     pub(crate) fn in_flight(&self) -> Arc<AtomicUsize> {
         self.in_flight.clone()
     }
 
+    /// Points the request gate at the signal shutdown is requested on.
+    #[inline]
+    pub(crate) fn set_draining_token(&mut self, token: CancellationToken) {
+        self.draining = token;
+    }
+
+    /// Whether shutdown has been requested, and new requests are refused.
+    #[inline]
+    pub(crate) fn is_draining(&self) -> bool {
+        self.draining.is_cancelled()
+    }
+
     /// Returns [`ServerTasksCapability`] if configured.
     ///
     /// Otherwise, returns `None`.
@@ -924,6 +1125,27 @@ impl McpOptions {
         self.request_state_audience.as_deref()
     }
 
+    /// Sets the max size of a streamed blob in bytes.
+    pub(crate) fn set_max_blob_size(&mut self, bytes: u64) {
+        self.max_blob_size = Some(bytes);
+    }
+
+    /// Returns the max size of a streamed blob in bytes, if there is one.
+    pub(crate) fn max_blob_size(&self) -> Option<u64> {
+        self.max_blob_size
+    }
+
+    /// Sets the policy that decides how an error a tool returns is sent.
+    pub(crate) fn set_tool_error_policy(&mut self, policy: crate::types::tool::ToolErrorPolicy) {
+        self.tool_error_policy = Some(policy);
+    }
+
+    /// Returns the policy that decides how an error a tool returns is sent,
+    /// if one is set.
+    pub(crate) fn tool_error_policy(&self) -> Option<&crate::types::tool::ToolErrorPolicy> {
+        self.tool_error_policy.as_ref()
+    }
+
     /// Sets the max encoded `requestState` size in bytes.
     #[cfg(not(feature = "legacy-spec"))]
     pub(crate) fn set_max_state_bytes(&mut self, bytes: usize) {
@@ -1062,8 +1284,34 @@ mod tests {
 
         options.add_resource(Resource::new("res://res", "res"));
 
-        let (resources, next_cursor) = options.list_resources_page(None, 10).await;
+        let (resources, next_cursor) = options.list_resources_page(None, 10).await.unwrap();
+        assert_eq!(resources.len(), 1);
+        assert_eq!(next_cursor, None);
+    }
+
+    #[tokio::test]
+    async fn a_full_page_has_a_cursor_only_if_more_follows() {
+        let mut options = McpOptions::default();
+        options.add_resource(Resource::new("res://a", "a"));
+        options.add_resource(Resource::new("res://b", "b"));
+
+        let handler = |uri: Uri| async move { ResourceContents::new(uri) };
+        let mut empty = ResourceTemplate::new("empty://{id}", "empty");
+        empty.with_enumerator(Vec::<&str>::new);
+        options.add_resource_template(empty, ResourceFunc::new(handler));
+
+        let (resources, next_cursor) = options.list_resources_page(None, 2).await.unwrap();
+        assert_eq!(resources.len(), 2);
+        assert_eq!(next_cursor, None);
+
+        let mut days = ResourceTemplate::new("days://{day}", "days");
+        days.with_enumerator(|| ["monday"]);
+        options.add_resource_template(days, ResourceFunc::new(handler));
+
+        let (_, next_cursor) = options.list_resources_page(None, 2).await.unwrap();
+        let (resources, next_cursor) = options.list_resources_page(next_cursor, 2).await.unwrap();
         assert_eq!(resources.len(), 1);
+        assert_eq!(resources[0].uri.to_string(), "days://monday");
         assert_eq!(next_cursor, None);
     }
 
@@ -9,31 +9,42 @@
 //! [`ShutdownHandle`] is the second entry point. It composes with the signal
 //! handler rather than replacing it -- whichever fires first wins -- so a
 //! server that takes a handle still stops on Ctrl+C.
+//!
+//! However it is requested, shutdown is a drain rather than a cut. New requests
+//! are refused from the first moment, save those carrying on work begun before
+//! it -- an MRTR round with the `requestState` of an earlier one, a poll for a
+//! task's state or result; the ones already running get
+//! [`App::with_shutdown_drain`] to finish; tasks still running after that are
+//! failed with a reason instead of vanishing; and what was left behind is
+//! reported as a [`ShutdownSummary`].
 
-use super::App;
+use super::{App, options::RuntimeMcpOptions};
+use crate::error::{Error, ErrorCode};
 use crate::shared;
-#[cfg(not(feature = "legacy-spec"))]
-use std::sync::Arc;
+use crate::types::Request;
+use std::sync::{Arc, atomic::Ordering};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
 use tokio_util::sync::CancellationToken;
 
-/// How long shutdown waits for live `subscriptions/listen` streams to answer
-/// before the transport goes down regardless.
+/// How long shutdown waits for in-flight work to finish before the transport
+/// goes down regardless.
 ///
-/// Only ever paid by a server that has a subscription open. It is a ceiling,
-/// not a delay: the wait ends as soon as the last result is queued, which is
-/// immediate in the ordinary case. Two seconds sits well inside the ten Volga
-/// gives an in-flight connection during its own graceful shutdown, so the
-/// response body a subscription is written onto is still open when the result
-/// arrives.
-#[cfg(not(feature = "legacy-spec"))]
-pub(super) const DEFAULT_SHUTDOWN_DRAIN: std::time::Duration = std::time::Duration::from_secs(2);
+/// It is a ceiling, not a delay: the wait ends as soon as nothing is running,
+/// which is immediate for an idle server. Two seconds sits well inside the ten
+/// Volga gives an in-flight connection during its own graceful shutdown, so
+/// the response body a result is written onto is still open when it arrives.
+pub(super) const DEFAULT_SHUTDOWN_DRAIN: Duration = Duration::from_secs(2);
 
-/// How often the drain re-checks whether the subscriptions have finished.
+/// How often the drain re-checks whether the work has finished.
 ///
 /// Short enough not to add a visible tail to shutdown, long enough that the
 /// poll is not a spin.
-#[cfg(not(feature = "legacy-spec"))]
-const DRAIN_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(5);
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// What a request arriving during the drain is told, and the status message a
+/// task the drain outlived is failed with.
+const SHUTTING_DOWN: &str = "The server is shutting down";
 
 /// Stops a running [`App`] without an OS signal.
 ///
@@ -69,6 +80,63 @@ long that is allowed to take."
 #[derive(Debug, Clone, Default)]
 pub struct ShutdownHandle {
     token: CancellationToken,
+    summary: Arc<watch::Sender<Option<ShutdownSummary>>>,
+}
+
+/// What a finished shutdown left behind, as reported by
+/// [`ShutdownHandle::summary`].
+///
+/// # Example
+/// ```no_run
+/// use neva::App;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let (app, shutdown) = App::new().with_shutdown();
+/// let server = tokio::spawn(app.run());
+///
+/// shutdown.shutdown();
+/// let summary = shutdown.summary().await;
+/// if !summary.is_clean() {
+///     eprintln!("abandoned {} task(s)", summary.abandoned_tasks.len());
+/// }
+/// server.await.expect("the server task panicked");
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShutdownSummary {
+    /// From the shutdown request to the transport going down.
+    pub elapsed: Duration,
+
+    /// Requests refused because they arrived after shutdown was requested.
+    pub rejected_requests: usize,
+
+    /// Requests still running when the drain ran out. Their responses were
+    /// never sent.
+    pub abandoned_requests: usize,
+
+    /// Tasks still running when the drain ran out, by id. Each was cancelled
+    /// and left `failed`, with the shutdown as its status message.
+    pub abandoned_tasks: Vec<String>,
+
+    /// `subscriptions/listen` streams the shutdown ended. Each was sent a
+    /// final `notifications/message` saying so, then its graceful-close
+    /// result. Always zero under `legacy-spec`, which has no such streams.
+    pub closed_subscriptions: usize,
+}
+
+impl ShutdownSummary {
+    /// Whether everything that was running finished inside the drain.
+    ///
+    /// # Example
+    /// ```
+    /// use neva::app::shutdown::ShutdownSummary;
+    ///
+    /// assert!(ShutdownSummary::default().is_clean());
+    /// ```
+    pub fn is_clean(&self) -> bool {
+        self.abandoned_requests == 0 && self.abandoned_tasks.is_empty()
+    }
 }
 
 impl ShutdownHandle {
@@ -82,9 +150,7 @@ impl ShutdownHandle {
     /// assert!(!shutdown.is_shutdown_requested());
     /// ```
     pub fn new() -> Self {
-        Self {
-            token: CancellationToken::new(),
-        }
+        Self::default()
     }
 
     /// Wraps an existing [`CancellationToken`], so the server stops on a
@@ -102,7 +168,10 @@ impl ShutdownHandle {
     /// assert!(shutdown.is_shutdown_requested());
     /// ```
     pub fn from_token(token: CancellationToken) -> Self {
-        Self { token }
+        Self {
+            token,
+            summary: Default::default(),
+        }
     }
 
     /// Requests shutdown of the server this handle was taken from.
@@ -154,6 +223,40 @@ impl ShutdownHandle {
     pub fn token(&self) -> CancellationToken {
         self.token.clone()
     }
+
+    /// Waits for the server to finish shutting down and returns what it left
+    /// behind.
+    ///
+    /// Resolves once the drain is over, just before the transport goes down.
+    /// Waits indefinitely if shutdown is never requested.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use neva::App;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let (app, shutdown) = App::new().with_shutdown();
+    /// tokio::spawn(app.run());
+    ///
+    /// shutdown.shutdown();
+    /// let summary = shutdown.summary().await;
+    /// println!("refused {} request(s)", summary.rejected_requests);
+    /// # }
+    /// ```
+    pub async fn summary(&self) -> ShutdownSummary {
+        let mut rx = self.summary.subscribe();
+        // The sender lives as long as `self`, so the wait cannot fail.
+        match rx.wait_for(Option::is_some).await {
+            Ok(summary) => summary.clone().unwrap_or_default(),
+            Err(_) => ShutdownSummary::default(),
+        }
+    }
+
+    /// Publishes the summary of a finished shutdown to every clone.
+    fn report(&self, summary: ShutdownSummary) {
+        self.summary.send_replace(Some(summary));
+    }
 }
 
 impl From<CancellationToken> for ShutdownHandle {
@@ -164,75 +267,101 @@ impl From<CancellationToken> for ShutdownHandle {
 }
 
 impl App {
-    /// Turns one shutdown request into the ordered teardown the spec asks for:
-    /// end the subscriptions, let their results out, then stop the transport.
+    /// Turns one shutdown request into the ordered teardown: stop taking new
+    /// requests, let what is running finish, fail what could not, then stop
+    /// the transport.
     ///
-    /// The spec says a server ending a subscription on its own initiative
-    /// **SHOULD** answer the `subscriptions/listen` request with its empty
-    /// result before closing the stream. That result is produced by a handler
-    /// and travels the same channel as everything else, so it only lands if
-    /// the writers are still reading when it is written -- which is exactly
-    /// what one shared token made impossible.
+    /// New requests are refused as soon as the signal fires -- that half lives
+    /// on the dispatch path, which checks the same token. Under MCP 2026-07-28
+    /// live subscriptions are ended at the same moment: the spec says a server
+    /// ending a subscription on its own initiative **SHOULD** answer the
+    /// `subscriptions/listen` request with its empty result before closing the
+    /// stream, and that result travels the same channel as everything else, so
+    /// it only lands if the writers are still reading when it is written.
     ///
-    /// The wait is skipped when nothing was subscribed, so a server that never
-    /// opens a subscription shuts down as immediately as it always did.
-    #[cfg(not(feature = "legacy-spec"))]
+    /// The wait ends as soon as nothing is running, so an idle server shuts
+    /// down as immediately as it always did.
     pub(super) fn relay_shutdown(
-        shutdown: CancellationToken,
-        subscriptions_token: CancellationToken,
+        handle: ShutdownHandle,
         transport_token: CancellationToken,
-        subscriptions: crate::app::subscriptions::SubscriptionRegistry,
-        in_flight: Arc<std::sync::atomic::AtomicUsize>,
-        drain: std::time::Duration,
+        #[cfg(not(feature = "legacy-spec"))] subscriptions_token: CancellationToken,
+        options: RuntimeMcpOptions,
+        drain: Duration,
     ) {
+        let shutdown = handle.token();
         tokio::spawn(async move {
             tokio::select! {
                 // The transport going down on its own (a bind failure, a dead
                 // engine) ends this task too -- otherwise it would sit on a
                 // signal that is never coming.
-                _ = transport_token.cancelled() => return,
+                _ = transport_token.cancelled() => {
+                    handle.report(ShutdownSummary::default());
+                    return;
+                },
                 _ = shutdown.cancelled() => {}
             }
+            let started = Instant::now();
 
-            // Phase 1: end the subscriptions. Each listen handler wakes,
-            // deregisters, drains what its stream still owes and answers.
-            let owed = !subscriptions.is_empty();
+            // Phase 1: end the subscriptions. Each listen handler wakes, sends
+            // its final notice, deregisters, drains what its stream still owes
+            // and answers.
+            #[cfg(not(feature = "legacy-spec"))]
+            let closed_subscriptions = options.subscriptions().len();
+            #[cfg(feature = "legacy-spec")]
+            let closed_subscriptions = 0;
+            #[cfg(not(feature = "legacy-spec"))]
             subscriptions_token.cancel();
 
-            // Phase 2: wait for those answers to reach the outbound channel.
-            // `is_empty` says every handler deregistered; the in-flight count
-            // says every one of them also got its response as far as the
-            // sender, since the terminal middleware awaits that send before it
-            // returns. Both, because either alone is reached too early.
-            if owed {
-                let _ = tokio::time::timeout(drain, async {
-                    while !subscriptions.is_empty()
-                        || in_flight.load(std::sync::atomic::Ordering::Acquire) > 0
-                    {
-                        tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
-                    }
-                })
-                .await;
+            // Phase 2: wait for the work under way. The in-flight count drops
+            // only once a response has been handed to the sender, since the
+            // terminal middleware awaits that send before it returns -- so
+            // "nothing in flight" means every answer is queued, the listen
+            // results from phase 1 included.
+            let _ = tokio::time::timeout(drain, async {
+                while Self::is_busy(&options) {
+                    tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+                }
+            })
+            .await;
+
+            // Phase 3: give up on whatever is left, and say so.
+            let summary = ShutdownSummary {
+                elapsed: started.elapsed(),
+                rejected_requests: options.rejected.load(Ordering::Relaxed),
+                abandoned_requests: options.in_flight().load(Ordering::Acquire),
+                #[cfg(feature = "tasks")]
+                abandoned_tasks: options.tasks.abandon(SHUTTING_DOWN),
+                #[cfg(not(feature = "tasks"))]
+                abandoned_tasks: Vec::new(),
+                closed_subscriptions,
+            };
+            #[cfg(feature = "tracing")]
+            if summary.is_clean() {
+                tracing::info!(logger = "neva", ?summary, "shutdown drained");
+            } else {
+                tracing::warn!(
+                    logger = "neva",
+                    ?summary,
+                    "shutdown abandoned work the drain could not wait for"
+                );
             }
+            handle.report(summary);
 
-            // Phase 3: stop the transport. Its writers drain what is queued
+            // Phase 4: stop the transport. Its writers drain what is queued
             // before they exit, which is what carries the results written in
             // phase 2 onto the wire.
             transport_token.cancel();
         });
     }
 
-    /// The legacy profile has no `subscriptions/listen` and so nothing to
-    /// drain: shutdown reaches the transport directly.
-    #[cfg(feature = "legacy-spec")]
-    pub(super) fn relay_shutdown(shutdown: CancellationToken, transport_token: CancellationToken) {
-        tokio::spawn(async move {
-            tokio::select! {
-                _ = transport_token.cancelled() => return,
-                _ = shutdown.cancelled() => {}
-            }
-            transport_token.cancel();
-        });
+    /// Whether anything the drain waits for is still running.
+    fn is_busy(options: &RuntimeMcpOptions) -> bool {
+        let busy = options.in_flight().load(Ordering::Acquire) > 0;
+        #[cfg(not(feature = "legacy-spec"))]
+        let busy = busy || !options.subscriptions().is_empty();
+        #[cfg(feature = "tasks")]
+        let busy = busy || options.tasks.running() > 0;
+        busy
     }
 
     #[inline]
@@ -241,6 +370,45 @@ impl App {
     }
 }
 
+/// Whether a request arriving during the drain carries on work begun before
+/// it, rather than starting any of its own, and may still be served.
+pub(super) fn continues_earlier_work(req: &Request) -> bool {
+    #[cfg(not(feature = "legacy-spec"))]
+    if shared::is_mrtr_method(&req.method) && req.state().is_some() {
+        return true;
+    }
+    #[cfg(all(feature = "tasks", feature = "legacy-spec"))]
+    if req.method == crate::types::task::commands::RESULT {
+        return true;
+    }
+    #[cfg(feature = "tasks")]
+    if req.method == crate::types::task::commands::GET {
+        return true;
+    }
+    let _ = req;
+    false
+}
+
+/// The error a request arriving during the drain is answered with.
+pub(super) fn shutting_down() -> Error {
+    Error::new(ErrorCode::InternalError, SHUTTING_DOWN)
+}
+
+/// The last message a `subscriptions/listen` stream carries before the
+/// shutdown ends it: a warning-level log, untagged, so a client hears why its
+/// subscription is closing whatever categories it asked for.
+#[cfg(not(feature = "legacy-spec"))]
+pub(crate) fn shutdown_notice() -> crate::types::Message {
+    use crate::types::notification::{LogMessage, LoggingLevel};
+
+    let notice = LogMessage::new(
+        LoggingLevel::Warning,
+        Some("neva".into()),
+        Some(serde_json::json!({ "message": SHUTTING_DOWN, "reason": "shutdown" })),
+    );
+    crate::types::Message::Notification(notice.into())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -270,6 +438,26 @@ mod tests {
         assert!(shutdown.is_shutdown_requested());
     }
 
+    #[tokio::test]
+    async fn every_clone_sees_the_summary() {
+        let shutdown = ShutdownHandle::new();
+        let waiter = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { shutdown.summary().await }
+        });
+
+        let summary = ShutdownSummary {
+            rejected_requests: 2,
+            abandoned_tasks: vec!["t1".into()],
+            ..Default::default()
+        };
+        shutdown.clone().report(summary.clone());
+
+        assert_eq!(waiter.await.unwrap(), summary);
+        assert_eq!(shutdown.summary().await, summary);
+        assert!(!summary.is_clean());
+    }
+
     #[test]
     fn a_wrapped_token_is_the_same_signal_in_both_directions() {
         let token = CancellationToken::new();
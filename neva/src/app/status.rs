@@ -0,0 +1,55 @@
+//! What the HTTP transport's admin endpoint reports about the application.
+//!
+//! The transport knows its own sessions; everything else -- the tools, the live
+//! subscriptions, the running tasks, the build -- lives on the runtime options,
+//! which the transport never sees. This is the bridge: a reporter the
+//! transport calls on each admin request, reading the options as they are at
+//! that moment.
+
+use super::{App, options::RuntimeMcpOptions};
+use crate::transport::http::core::probes::StatusReporter;
+
+impl App {
+    /// Builds the reporter handed to the transport once the runtime exists.
+    pub(super) fn status_reporter(options: RuntimeMcpOptions) -> StatusReporter {
+        Box::new(move || {
+            let options = options.clone();
+            Box::pin(async move { Self::status_report(&options).await })
+        })
+    }
+
+    async fn status_report(options: &RuntimeMcpOptions) -> serde_json::Value {
+        let tools = options
+            .tools
+            .values()
+            .await
+            .into_iter()
+            .map(|tool| tool.name)
+            .collect::<Vec<_>>();
+
+        #[cfg_attr(
+            all(feature = "legacy-spec", not(feature = "tasks")),
+            allow(unused_mut)
+        )]
+        let mut report = serde_json::json!({
+            "build": {
+                "name": options.implementation.name,
+                "version": options.implementation.version,
+                "neva": env!("CARGO_PKG_VERSION"),
+                "protocolVersions": crate::PROTOCOL_VERSIONS,
+            },
+            "tools": tools,
+        });
+
+        #[cfg(not(feature = "legacy-spec"))]
+        {
+            report["subscriptions"] = options.subscriptions().snapshot().into();
+        }
+        #[cfg(feature = "tasks")]
+        {
+            report["tasks"] = serde_json::to_value(options.tasks.tasks()).unwrap_or_default();
+        }
+
+        report
+    }
+}
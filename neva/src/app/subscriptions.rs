@@ -216,6 +216,11 @@ impl SubscriptionRegistry {
         self.entries.is_empty() && self.arriving.load(Ordering::Acquire) == 0
     }
 
+    /// Returns how many subscriptions are live.
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
     /// Counts a dispatched listen request as arriving until the returned guard
     /// drops, closing the window between dispatch and [`Self::register`].
     pub(crate) fn arriving(&self) -> ArrivingGuard {
@@ -223,6 +228,21 @@ impl SubscriptionRegistry {
         ArrivingGuard(self.arriving.clone())
     }
 
+    /// Describes every live subscription, for the HTTP admin endpoint.
+    #[cfg(feature = "http-server")]
+    pub(crate) fn snapshot(&self) -> Vec<serde_json::Value> {
+        self.entries
+            .iter()
+            .map(|entry| {
+                serde_json::json!({
+                    "id": entry.id,
+                    "sessionId": entry.session_id,
+                    "accepted": entry.accepted,
+                })
+            })
+            .collect()
+    }
+
     /// Returns whether any live subscription is watching `uri`.
     pub(crate) fn is_resource_subscribed(&self, uri: &Uri) -> bool {
         self.entries
@@ -0,0 +1,184 @@
+//! Validation of `tools/call` arguments against the tool's input schema.
+//!
+//! Without it, arguments go straight to the handler's extractors: a value of
+//! the wrong type surfaces as whatever the deserializer had to say about it,
+//! and a constraint the schema states but no Rust type can (`minimum`,
+//! `pattern`, `enum`, `oneOf`) is never enforced at all. With it, a call is
+//! checked before dispatch and refused with an `InvalidParams` error whose
+//! `data.errors` lists every violation by JSON pointer.
+//!
+//! Compiling a schema costs far more than checking against one, so each tool's
+//! validator is compiled on its first call and reused until the tool is
+//! replaced or removed.
+
+use crate::error::{Error, ErrorCode};
+use crate::shared::json_schema;
+use crate::types::Tool;
+use dashmap::DashMap;
+use jsonschema::Validator;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+/// Compiled input-schema validators, by tool name.
+#[derive(Default)]
+pub(crate) struct ArgumentValidators {
+    compiled: DashMap<String, Arc<Validator>>,
+}
+
+impl Debug for ArgumentValidators {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArgumentValidators")
+            .field("compiled", &self.compiled.len())
+            .finish()
+    }
+}
+
+impl ArgumentValidators {
+    /// Checks `args` against the input schema of `tool`.
+    pub(crate) fn validate(
+        &self,
+        tool: &Tool,
+        args: Option<&HashMap<String, Value>>,
+    ) -> Result<(), Error> {
+        let validator = self.validator(tool)?;
+
+        let instance = json_schema::arguments(args);
+        let errors = json_schema::violations(&validator, &instance);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::new(
+                ErrorCode::InvalidParams,
+                format!("invalid arguments for tool `{}`", tool.name),
+            )
+            .with_data(serde_json::json!({ "tool": tool.name, "errors": errors })))
+        }
+    }
+
+    /// Drops the validator compiled for the tool `name`, so a tool registered
+    /// under it again is checked against its own schema.
+    #[inline]
+    pub(crate) fn forget(&self, name: &str) {
+        self.compiled.remove(name);
+    }
+
+    fn validator(&self, tool: &Tool) -> Result<Arc<Validator>, Error> {
+        if let Some(validator) = self.compiled.get(&tool.name) {
+            return Ok(validator.clone());
+        }
+
+        let schema = json_schema::schema_value(&tool.input_schema)?;
+        let validator = jsonschema::validator_for(&schema).map_err(|err| {
+            Error::new(
+                ErrorCode::InternalError,
+                format!("tool `{}` has an invalid input schema: {err}", tool.name),
+            )
+        })?;
+        let validator = Arc::new(validator);
+        self.compiled.insert(tool.name.clone(), validator.clone());
+        Ok(validator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const BOOKING: &str = r#"{
+        "type": "object",
+        "properties": {
+            "nights": { "type": "integer", "minimum": 1 },
+            "room": { "type": "string", "enum": ["single", "double"] }
+        },
+        "required": ["nights", "room"]
+    }"#;
+
+    fn tool(schema: &str) -> Tool {
+        let mut tool = Tool::new("book", |nights: i64, room: String| async move {
+            format!("{nights} nights in a {room} room")
+        });
+        tool.with_input_schema(|_| {
+            #[cfg(feature = "legacy-spec")]
+            {
+                crate::types::ToolSchema::from_json_str(schema)
+            }
+            #[cfg(not(feature = "legacy-spec"))]
+            {
+                crate::types::schema_2020::InputSchema::from_json_str(schema).unwrap()
+            }
+        });
+        tool
+    }
+
+    fn args(value: Value) -> HashMap<String, Value> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn it_accepts_valid_arguments() {
+        let validators = ArgumentValidators::default();
+        let args = args(json!({ "nights": 2, "room": "double" }));
+
+        assert!(validators.validate(&tool(BOOKING), Some(&args)).is_ok());
+    }
+
+    #[test]
+    fn it_reports_every_violation_by_pointer() {
+        let validators = ArgumentValidators::default();
+        let args = args(json!({ "nights": 0, "room": "suite" }));
+
+        let err = validators
+            .validate(&tool(BOOKING), Some(&args))
+            .unwrap_err();
+
+        assert_eq!(err.code, ErrorCode::InvalidParams);
+        let data = err.data().unwrap();
+        assert_eq!(data["tool"], "book");
+        let mut pointers: Vec<_> = data["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|err| err["pointer"].as_str().unwrap())
+            .collect();
+        pointers.sort();
+        assert_eq!(pointers, ["/nights", "/room"]);
+    }
+
+    #[test]
+    fn it_treats_absent_arguments_as_an_empty_object() {
+        let validators = ArgumentValidators::default();
+
+        let err = validators.validate(&tool(BOOKING), None).unwrap_err();
+
+        let errors = err.data().unwrap()["errors"].as_array().unwrap().clone();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|err| err["pointer"] == ""));
+    }
+
+    #[test]
+    fn it_compiles_a_schema_once() {
+        let validators = ArgumentValidators::default();
+        let tool = tool(BOOKING);
+        let args = args(json!({ "nights": 2, "room": "single" }));
+
+        validators.validate(&tool, Some(&args)).unwrap();
+        validators.validate(&tool, Some(&args)).unwrap();
+        assert_eq!(validators.compiled.len(), 1);
+
+        validators.forget("book");
+        assert!(validators.compiled.is_empty());
+    }
+
+    #[test]
+    fn it_refuses_a_call_to_a_tool_with_an_invalid_schema() {
+        let validators = ArgumentValidators::default();
+        let tool = tool(r#"{ "type": "object", "minimum": "one" }"#);
+
+        let err = validators.validate(&tool, None).unwrap_err();
+
+        assert_eq!(err.code, ErrorCode::InternalError);
+    }
+}
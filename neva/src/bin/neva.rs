@@ -0,0 +1,710 @@
+//! `neva`: a command-line inspector for MCP servers.
+//!
+//! Connects to a server over Streamable HTTP (`--url`) or by running it over
+//! stdio (the command after `--`), then lists and calls what it serves:
+//!
+//! ```no_rust
+//! neva --url http://127.0.0.1:3000/mcp tools
+//! neva call get_weather city=Paris -- cargo run -p my-server
+//! neva --url https://mcp.example.com/mcp --oauth listen
+//! ```
+//!
+//! Elicitations the server sends along the way are asked at the terminal.
+
+use neva::client::Terminal;
+use neva::prelude::*;
+use neva::types::notification::Notification;
+use serde_json::{Map, Value};
+use std::process::ExitCode;
+use std::time::Duration;
+
+const USAGE: &str = "\
+Usage: neva [OPTIONS] <COMMAND> [ARGS...] [-- <SERVER COMMAND>...]
+
+Inspects an MCP server: over Streamable HTTP with --url, or over stdio by
+running the command after `--`.
+
+Commands:
+  discover                     Print the server's info and capabilities
+  tools                        List the tools
+  call <TOOL> [ARGUMENTS]      Call a tool
+  resources                    List the resources and resource templates
+  read <URI>                   Read a resource
+  prompts                      List the prompts
+  prompt <PROMPT> [ARGUMENTS]  Render a prompt
+  listen [URI...]              Print list-change notifications, and updates
+                               of the given resources, until Ctrl-C
+
+ARGUMENTS are one JSON object, or `key=value` pairs. A tool argument's value
+is read as JSON where it parses and as a string otherwise (`n=3`, `city=Paris`);
+a prompt argument's value is always a string.
+
+Options:
+  --url <URL>          Connect to a Streamable HTTP endpoint
+  --bearer <TOKEN>     Send a static bearer token [env: NEVA_BEARER_TOKEN]
+  --oauth              Authorize with OAuth 2.1 in the browser when challenged
+  --client-id <ID>     Use a pre-registered OAuth client ID
+  --scope <SCOPE>      Request an OAuth scope; repeatable
+  --timeout <SECONDS>  Fail a request that takes longer than this
+  --json               Print lists as JSON
+  -v, --verbose        Log the exchange to stderr
+  -h, --help           Print this help
+  -V, --version        Print the version
+";
+
+/// What the command line asked for.
+#[derive(Debug, PartialEq)]
+enum Invocation {
+    Run(Cli),
+    Help,
+    Version,
+}
+
+/// A command to run against a server.
+#[derive(Debug, PartialEq)]
+struct Cli {
+    target: Target,
+    auth: Auth,
+    timeout: Option<Duration>,
+    json: bool,
+    verbose: bool,
+    command: Command,
+}
+
+/// Where the server is.
+#[derive(Debug, PartialEq)]
+enum Target {
+    Http(String),
+    Stdio(Vec<String>),
+}
+
+/// How to authorize against an HTTP server.
+#[derive(Debug, Default, PartialEq)]
+struct Auth {
+    bearer: Option<String>,
+    oauth: bool,
+    client_id: Option<String>,
+    scopes: Vec<String>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Discover,
+    Tools,
+    Call { tool: String, args: Option<Value> },
+    Resources,
+    Read { uri: String },
+    Prompts,
+    Prompt { prompt: String, args: Option<Value> },
+    Listen { uris: Vec<String> },
+}
+
+/// Parses the command line, without the program name.
+fn parse(args: impl IntoIterator<Item = String>) -> Result<Invocation, Error> {
+    let mut args = args.into_iter();
+    let mut url = None;
+    let mut server = Vec::new();
+    let mut auth = Auth::default();
+    let mut timeout = None;
+    let mut json = false;
+    let mut verbose = false;
+    let mut positional = Vec::new();
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| usage_error(format!("{name} needs a value")))
+        };
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Invocation::Help),
+            "-V" | "--version" => return Ok(Invocation::Version),
+            "--url" => url = Some(value("--url")?),
+            "--bearer" => auth.bearer = Some(value("--bearer")?),
+            "--oauth" => auth.oauth = true,
+            "--client-id" => auth.client_id = Some(value("--client-id")?),
+            "--scope" => auth.scopes.push(value("--scope")?),
+            "--timeout" => {
+                let secs = value("--timeout")?;
+                let secs = secs
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|secs| secs.is_finite() && *secs > 0.0)
+                    .ok_or_else(|| usage_error(format!("invalid --timeout: {secs}")))?;
+                timeout = Some(Duration::from_secs_f64(secs));
+            }
+            "--json" => json = true,
+            "-v" | "--verbose" => verbose = true,
+            "--" => {
+                server.extend(args.by_ref());
+                break;
+            }
+            flag if flag.starts_with('-') && flag.len() > 1 => {
+                return Err(usage_error(format!("unknown option: {flag}")));
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let target = match (url, server.is_empty()) {
+        (Some(url), true) => Target::Http(url),
+        (None, false) => Target::Stdio(server),
+        (Some(_), false) => {
+            return Err(usage_error(
+                "give either --url or a server command, not both",
+            ));
+        }
+        (None, true) => {
+            return Err(usage_error(
+                "no server: give --url or a server command after `--`",
+            ));
+        }
+    };
+    if matches!(target, Target::Stdio(_)) && (auth.bearer.is_some() || auth.oauth) {
+        return Err(usage_error("--bearer and --oauth apply to --url only"));
+    }
+
+    Ok(Invocation::Run(Cli {
+        target,
+        auth,
+        timeout,
+        json,
+        verbose,
+        command: command(positional)?,
+    }))
+}
+
+/// Parses the command and its own arguments.
+fn command(positional: Vec<String>) -> Result<Command, Error> {
+    let mut words = positional.into_iter();
+    let Some(name) = words.next() else {
+        return Err(usage_error("no command given"));
+    };
+    let rest: Vec<String> = words.collect();
+    let no_more = |command: Command| {
+        if rest.is_empty() {
+            Ok(command)
+        } else {
+            Err(usage_error(format!("`{name}` takes no arguments")))
+        }
+    };
+
+    match name.as_str() {
+        "discover" => no_more(Command::Discover),
+        "tools" => no_more(Command::Tools),
+        "resources" => no_more(Command::Resources),
+        "prompts" => no_more(Command::Prompts),
+        "call" => {
+            let (tool, args) = rest
+                .split_first()
+                .ok_or_else(|| usage_error("`call` needs a tool name"))?;
+            Ok(Command::Call {
+                tool: tool.clone(),
+                args: arguments(args, true)?,
+            })
+        }
+        "prompt" => {
+            let (prompt, args) = rest
+                .split_first()
+                .ok_or_else(|| usage_error("`prompt` needs a prompt name"))?;
+            Ok(Command::Prompt {
+                prompt: prompt.clone(),
+                args: arguments(args, false)?,
+            })
+        }
+        "read" => match <[String; 1]>::try_from(rest) {
+            Ok([uri]) => Ok(Command::Read { uri }),
+            Err(_) => Err(usage_error("`read` takes one resource URI")),
+        },
+        "listen" => Ok(Command::Listen { uris: rest }),
+        other => Err(usage_error(format!("unknown command: {other}"))),
+    }
+}
+
+/// Reads tool or prompt arguments: one JSON object, or `key=value` pairs.
+///
+/// A `typed` value is read as JSON where it parses, so that `n=3` is a number
+/// and `city=Paris` a string; otherwise every value is a string, as prompt
+/// arguments are.
+fn arguments(raw: &[String], typed: bool) -> Result<Option<Value>, Error> {
+    if let [object] = raw
+        && object.trim_start().starts_with('{')
+    {
+        return match serde_json::from_str(object) {
+            Ok(Value::Object(map)) => Ok(Some(Value::Object(map))),
+            _ => Err(usage_error(format!("not a JSON object: {object}"))),
+        };
+    }
+    if raw.is_empty() {
+        return Ok(None);
+    }
+
+    raw.iter()
+        .map(|pair| {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| usage_error(format!("expected key=value, got `{pair}`")))?;
+            let value = typed
+                .then(|| serde_json::from_str(value).ok())
+                .flatten()
+                .unwrap_or_else(|| Value::String(value.to_owned()));
+            Ok((key.to_owned(), value))
+        })
+        .collect::<Result<Map<_, _>, _>>()
+        .map(|map| Some(Value::Object(map)))
+}
+
+fn usage_error(message: impl Into<String>) -> Error {
+    Error::new(ErrorCode::InvalidParams, message.into())
+}
+
+/// Splits an `http(s)://host:port/path` URL into the `host:port` and path
+/// halves the client builder takes separately.
+fn split_url(url: &str) -> Result<(bool, String, String), Error> {
+    let (tls, rest) = match url.split_once("://") {
+        Some(("http", rest)) => (false, rest),
+        Some(("https", rest)) => (true, rest),
+        _ => return Err(usage_error(format!("--url must be http(s): {url}"))),
+    };
+    Ok(match rest.split_once('/') {
+        Some((addr, path)) => (tls, addr.to_owned(), format!("/{path}")),
+        None => (tls, rest.to_owned(), "/mcp".to_owned()),
+    })
+}
+
+/// Whether `addr` (`host:port`) names this machine.
+fn is_loopback(addr: &str) -> bool {
+    let host = addr
+        .rsplit_once(':')
+        .map_or(addr, |(host, _)| host)
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    host == "localhost"
+        || host
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Builds the client for `cli`, with the terminal answering elicitations.
+fn client(cli: &Cli) -> Result<Client, Error> {
+    let mut client = match &cli.target {
+        Target::Http(url) => {
+            let (tls, addr, endpoint) = split_url(url)?;
+            let bearer = cli
+                .auth
+                .bearer
+                .clone()
+                .or_else(|| std::env::var("NEVA_BEARER_TOKEN").ok());
+            let auth = &cli.auth;
+            Client::new().with_options(|opt| {
+                opt.with_http(|mut http| {
+                    http = http.bind(&addr).with_endpoint(&endpoint);
+                    if tls {
+                        http = http.with_tls(|tls| tls);
+                    }
+                    if let Some(token) = bearer {
+                        http = http.with_auth(token);
+                    }
+                    if auth.oauth {
+                        http = http.with_oauth(|mut oauth| {
+                            if let Some(id) = &auth.client_id {
+                                oauth = oauth.with_client_id(id.clone());
+                            }
+                            if !auth.scopes.is_empty() {
+                                oauth = oauth.with_scopes(auth.scopes.iter().cloned());
+                            }
+                            // A development server on this machine usually has
+                            // its issuer here too, and on plain http.
+                            oauth.require_https(tls || !is_loopback(&addr))
+                        });
+                    }
+                    http
+                })
+            })
+        }
+        Target::Stdio(command) => {
+            // The stdio transport keeps the command for the life of the
+            // process, which is this one connection's.
+            let mut words = command
+                .iter()
+                .map(|word| &*Box::leak(word.clone().into_boxed_str()));
+            let program = words.next().unwrap_or_default();
+            Client::new().with_options(|opt| opt.with_stdio(program, words))
+        }
+    };
+
+    client = client.with_options(|opt| {
+        let opt = opt
+            .with_name("neva")
+            .with_version(env!("CARGO_PKG_VERSION"));
+        match cli.timeout {
+            Some(timeout) => opt.with_timeout(timeout),
+            None => opt,
+        }
+    });
+    client.map_elicitation(Terminal::stdio().with_browser().elicitation_handler());
+    Ok(client)
+}
+
+/// Runs `command` on a connected client. Returns whether it succeeded, which
+/// is `false` for a tool call that reported an error.
+async fn run(client: &mut Client, command: Command, json: bool) -> Result<bool, Error> {
+    match command {
+        Command::Discover => print_json(&serde_json::json!({
+            "serverInfo": client.server_info(),
+            "capabilities": client.server_capabilities(),
+        }))?,
+        Command::Tools => {
+            let mut tools = Vec::new();
+            let mut cursor = None;
+            loop {
+                let page = client.list_tools(cursor).await?;
+                tools.extend(page.tools);
+                cursor = page.next_cursor;
+                if cursor.is_none() {
+                    break;
+                }
+            }
+            if json {
+                print_json(&tools)?;
+            } else {
+                for tool in tools {
+                    print_entry(&tool.name, tool.descr.as_deref());
+                }
+            }
+        }
+        Command::Call { tool, args } => {
+            let result = client.call_tool(tool, args).await?;
+            print_json(&result)?;
+            return Ok(!result.is_error);
+        }
+        Command::Resources => {
+            let mut resources = Vec::new();
+            let mut cursor = None;
+            loop {
+                let page = client.list_resources(cursor).await?;
+                resources.extend(page.resources);
+                cursor = page.next_cursor;
+                if cursor.is_none() {
+                    break;
+                }
+            }
+            let mut templates = Vec::new();
+            let mut cursor = None;
+            loop {
+                let page = client.list_resource_templates(cursor).await?;
+                templates.extend(page.templates);
+                cursor = page.next_cursor;
+                if cursor.is_none() {
+                    break;
+                }
+            }
+            if json {
+                print_json(&serde_json::json!({
+                    "resources": resources,
+                    "resourceTemplates": templates,
+                }))?;
+            } else {
+                for resource in resources {
+                    print_entry(&resource.uri.to_string(), Some(&resource.name));
+                }
+                for template in templates {
+                    print_entry(&template.uri_template.to_string(), Some(&template.name));
+                }
+            }
+        }
+        Command::Read { uri } => print_json(&client.read_resource(uri).await?)?,
+        Command::Prompts => {
+            let mut prompts = Vec::new();
+            let mut cursor = None;
+            loop {
+                let page = client.list_prompts(cursor).await?;
+                prompts.extend(page.prompts);
+                cursor = page.next_cursor;
+                if cursor.is_none() {
+                    break;
+                }
+            }
+            if json {
+                return print_json(&prompts).map(|()| true);
+            }
+            for prompt in prompts {
+                let args = prompt
+                    .args
+                    .iter()
+                    .flatten()
+                    .map(|arg| match arg.required {
+                        Some(true) => arg.name.clone(),
+                        _ => format!("{}?", arg.name),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                print_entry(&format!("{}({args})", prompt.name), prompt.descr.as_deref());
+            }
+        }
+        Command::Prompt { prompt, args } => print_json(&client.get_prompt(prompt, args).await?)?,
+        Command::Listen { uris } => listen(client, uris).await?,
+    }
+    Ok(true)
+}
+
+/// Prints every list-change notification, and every update of `uris`, as a
+/// line of JSON until Ctrl-C or the server ends the stream.
+async fn listen(client: &mut Client, uris: Vec<String>) -> Result<(), Error> {
+    use neva::types::{prompt, resource, tool};
+
+    for method in [
+        tool::commands::LIST_CHANGED,
+        prompt::commands::LIST_CHANGED,
+        resource::commands::LIST_CHANGED,
+        resource::commands::UPDATED,
+    ] {
+        client.subscribe(method, |notification: Notification| async move {
+            if let Ok(line) = serde_json::to_string(&notification) {
+                println!("{line}");
+            }
+        });
+    }
+
+    #[cfg(not(feature = "legacy-spec"))]
+    {
+        use neva::client::{ReconnectPolicy, SubscriptionEvent};
+        use neva::types::SubscriptionFilter;
+
+        let filter = SubscriptionFilter::new()
+            .with_tools_changed()
+            .with_prompts_changed()
+            .with_resources_changed()
+            .with_resources(uris);
+        let filter = match client.server_capabilities() {
+            Some(capabilities) => filter.supported_by(capabilities),
+            None => filter,
+        };
+
+        let mut subscription = client
+            .listen_with_reconnect(filter, ReconnectPolicy::new())
+            .await?;
+        eprintln!(
+            "listening for {}; Ctrl-C to stop",
+            serde_json::to_string(&subscription.acknowledged())?
+        );
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => break,
+                event = subscription.next_event() => match event {
+                    Some(SubscriptionEvent::Reconnecting { attempt, error, .. }) => {
+                        eprintln!("stream dropped, reconnecting (attempt {attempt}): {error:?}");
+                    }
+                    Some(SubscriptionEvent::ResyncNeeded { .. }) => {
+                        eprintln!("reconnected; notifications may have been missed");
+                    }
+                    None => break,
+                },
+            }
+        }
+        eprintln!("subscription ended: {:?}", subscription.cancel().await);
+    }
+
+    #[cfg(feature = "legacy-spec")]
+    {
+        for uri in uris {
+            client.subscribe_to_resource(uri).await?;
+        }
+        eprintln!("listening; Ctrl-C to stop");
+        tokio::signal::ctrl_c().await?;
+    }
+
+    Ok(())
+}
+
+fn print_json(value: &impl serde::Serialize) -> Result<(), Error> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+fn print_entry(name: &str, descr: Option<&str>) {
+    match descr.and_then(|descr| descr.lines().next()) {
+        Some(descr) => println!("{name}\t{descr}"),
+        None => println!("{name}"),
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = match parse(std::env::args().skip(1)) {
+        Ok(Invocation::Run(cli)) => cli,
+        Ok(Invocation::Help) => {
+            print!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Ok(Invocation::Version) => {
+            println!("neva {}", env!("CARGO_PKG_VERSION"));
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("error: {err}\n\nRun `neva --help` for usage.");
+            return ExitCode::from(2);
+        }
+    };
+
+    if cli.verbose {
+        tracing_subscriber::fmt()
+            .with_writer(std::io::stderr)
+            .with_max_level(tracing::Level::DEBUG)
+            .init();
+    }
+
+    match connect_and_run(cli).await {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn connect_and_run(cli: Cli) -> Result<bool, Error> {
+    let mut client = client(&cli)?;
+    client.connect().await?;
+    let result = run(&mut client, cli.command, cli.json).await;
+    client.disconnect().await?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_args(args: &[&str]) -> Result<Invocation, Error> {
+        parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    fn command_of(args: &[&str]) -> Command {
+        match parse_args(args).unwrap() {
+            Invocation::Run(cli) => cli.command,
+            other => panic!("expected a command, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn it_parses_an_http_invocation() {
+        let Invocation::Run(cli) = parse_args(&[
+            "--url",
+            "https://example.com/mcp",
+            "--oauth",
+            "--scope",
+            "mcp:tools",
+            "--timeout",
+            "5",
+            "tools",
+        ])
+        .unwrap() else {
+            panic!("expected a command");
+        };
+
+        assert_eq!(cli.target, Target::Http("https://example.com/mcp".into()));
+        assert!(cli.auth.oauth);
+        assert_eq!(cli.auth.scopes, ["mcp:tools"]);
+        assert_eq!(cli.timeout, Some(Duration::from_secs(5)));
+        assert_eq!(cli.command, Command::Tools);
+    }
+
+    #[test]
+    fn everything_after_the_separator_is_the_server_command() {
+        let Invocation::Run(cli) =
+            parse_args(&["discover", "--", "my-server", "--port", "3000"]).unwrap()
+        else {
+            panic!("expected a command");
+        };
+
+        assert_eq!(
+            cli.target,
+            Target::Stdio(vec!["my-server".into(), "--port".into(), "3000".into()])
+        );
+        assert_eq!(cli.command, Command::Discover);
+    }
+
+    #[test]
+    fn it_reads_typed_key_value_tool_arguments() {
+        let command = command_of(&["call", "add", "a=1", "b=x", "c=true", "--", "srv"]);
+
+        assert_eq!(
+            command,
+            Command::Call {
+                tool: "add".into(),
+                args: Some(serde_json::json!({ "a": 1, "b": "x", "c": true })),
+            }
+        );
+    }
+
+    #[test]
+    fn it_reads_prompt_arguments_as_strings() {
+        let command = command_of(&["prompt", "greet", "n=3", "--", "srv"]);
+
+        assert_eq!(
+            command,
+            Command::Prompt {
+                prompt: "greet".into(),
+                args: Some(serde_json::json!({ "n": "3" })),
+            }
+        );
+    }
+
+    #[test]
+    fn it_reads_a_json_object_of_arguments() {
+        let command = command_of(&["call", "add", r#"{"a": [1, 2]}"#, "--", "srv"]);
+
+        assert_eq!(
+            command,
+            Command::Call {
+                tool: "add".into(),
+                args: Some(serde_json::json!({ "a": [1, 2] })),
+            }
+        );
+        assert_eq!(
+            command_of(&["call", "ping", "--", "srv"]),
+            Command::Call {
+                tool: "ping".into(),
+                args: None,
+            }
+        );
+    }
+
+    #[test]
+    fn it_rejects_malformed_invocations() {
+        for args in [
+            &["tools"][..],
+            &["--url", "http://h/mcp", "tools", "--", "srv"],
+            &["--bearer", "t", "tools", "--", "srv"],
+            &["--frobnicate", "tools", "--", "srv"],
+            &["call", "--", "srv"],
+            &["call", "add", "a", "--", "srv"],
+            &["read", "a://1", "b://2", "--", "srv"],
+            &["tools", "extra", "--", "srv"],
+            &["--timeout", "soon", "tools", "--", "srv"],
+        ] {
+            assert!(parse_args(args).is_err(), "{args:?} must be rejected");
+        }
+    }
+
+    #[test]
+    fn it_splits_urls() {
+        assert_eq!(
+            split_url("https://example.com:8443/v1/mcp").unwrap(),
+            (true, "example.com:8443".into(), "/v1/mcp".into())
+        );
+        assert_eq!(
+            split_url("http://127.0.0.1:3000").unwrap(),
+            (false, "127.0.0.1:3000".into(), "/mcp".into())
+        );
+        assert!(split_url("ws://127.0.0.1:3000").is_err());
+    }
+
+    #[test]
+    fn it_recognizes_loopback_hosts() {
+        assert!(is_loopback("localhost:3000"));
+        assert!(is_loopback("127.0.0.1:3000"));
+        assert!(is_loopback("[::1]:3000"));
+        assert!(!is_loopback("example.com:3000"));
+    }
+}
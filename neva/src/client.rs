@@ -35,36 +35,51 @@ use crate::types::{
     GetTaskPayloadRequestParams, ListTasksRequestParams, ListTasksResult, Task, TaskPayload,
 };
 
-/// How many `tools/list` pages the `HeaderMismatch` recovery will walk looking
-/// for the tool it was sent back for.
+/// How many `tools/list` pages a call will walk to list the tools again: the
+/// `HeaderMismatch` recovery looking for the tool it was sent back for, and a
+/// call checked against a stale schema.
 ///
 /// The traversal ends on its own at a page without a `nextCursor`; this is the
 /// bound for a server that never stops handing them out, which would otherwise
-/// keep a single failed call walking forever with nothing above it able to see.
-#[cfg(all(feature = "http-client", not(feature = "legacy-spec")))]
+/// keep a single call walking forever with nothing above it able to see.
 const MAX_REFRESH_PAGES: usize = 64;
 
 pub mod batch;
 mod calls;
 mod capabilities;
 mod handler;
+pub mod health;
 mod listen;
 #[cfg(not(feature = "legacy-spec"))]
 mod mrtr;
 mod notification_handler;
+#[cfg(feature = "sampling-openai")]
+pub mod openai;
 pub mod options;
+#[cfg(not(feature = "legacy-spec"))]
+pub mod reconnect;
+mod schemas;
 mod setup;
+mod streaming;
 pub mod subscribe;
 #[cfg(not(feature = "legacy-spec"))]
 pub mod subscription;
 #[cfg(feature = "tasks")]
 pub mod task;
+pub mod terminal;
 
 pub use batch::BatchBuilder;
+pub use health::{CircuitBreakerOptions, CircuitState, ServerHealth};
+#[cfg(feature = "sampling-openai")]
+pub use openai::OpenAiSampler;
+#[cfg(not(feature = "legacy-spec"))]
+pub use reconnect::{ReconnectPolicy, ResilientSubscription, SubscriptionEvent};
+pub use streaming::{CallEvent, CallStream};
 #[cfg(not(feature = "legacy-spec"))]
 pub use subscription::{Subscription, SubscriptionEnd};
 #[cfg(feature = "tasks")]
 pub use task::TaskBuilder;
+pub use terminal::Terminal;
 
 /// Represents an MCP client app
 pub struct Client {
@@ -153,6 +168,43 @@ impl Client {
         Ok(resp)
     }
 
+    /// Returns how the connected server has been doing, as this client has seen
+    /// it, or `None` if no circuit breaker is configured.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use neva::client::{Client, health::CircuitState};
+    ///
+    /// let client = Client::new().with_options(|opt| opt.with_circuit_breaker(|cb| cb));
+    /// if let Some(health) = client.health() {
+    ///     assert_eq!(health.state, CircuitState::Closed);
+    /// }
+    /// ```
+    pub fn health(&self) -> Option<ServerHealth> {
+        self.options
+            .circuit_breaker
+            .as_ref()
+            .map(|breaker| breaker.health())
+    }
+
+    /// Returns the capabilities the connected server declared, or `None`
+    /// before [`Self::connect`].
+    #[inline]
+    pub fn server_capabilities(&self) -> Option<&ServerCapabilities> {
+        self.server_capabilities.as_ref()
+    }
+
+    /// Returns the name and version the connected server reported, or `None`
+    /// until it has reported them.
+    ///
+    /// A legacy peer reports them in the `initialize` result; a 2026-07-28
+    /// server in the `_meta` of its results, so this is filled in by the first
+    /// one that carries it.
+    #[inline]
+    pub fn server_info(&self) -> Option<&Implementation> {
+        self.server_info.as_ref()
+    }
+
     /// Creates a [`BatchBuilder`] for sending multiple requests in a single batch.
     ///
     /// # Example
@@ -261,9 +313,10 @@ impl Client {
         let request_timeout = handler.timeout();
         let pending = handler.pending().clone();
         let token = handler.cancellation();
+        let breaker = handler.circuit_breaker();
         let receivers = handler.send_batch(items).await?;
 
-        collect_batch_responses(receivers, &pending, request_timeout, token)
+        collect_batch_responses(receivers, &pending, request_timeout, token, breaker)
             .await
             .into_iter()
             .collect()
@@ -360,6 +413,9 @@ impl Client {
 /// Uses `join_all` (not `try_join_all`) so every future runs to completion: the
 /// timeout-cleanup branch (`pending.pop`) executes for each timed-out request
 /// even when another request in the same batch has already failed.
+///
+/// With a circuit breaker, each outcome is recorded against it as a single
+/// request's would be.
 async fn collect_batch_responses(
     receivers: Vec<(
         RequestId,
@@ -368,14 +424,17 @@ async fn collect_batch_responses(
     pending: &crate::shared::RequestQueue,
     request_timeout: std::time::Duration,
     token: tokio_util::sync::CancellationToken,
+    breaker: Option<Arc<health::CircuitBreaker>>,
 ) -> Vec<Result<Response, Error>> {
     use futures_util::future::join_all;
 
+    let started = std::time::Instant::now();
     let futures = receivers.into_iter().map(|(id, rx)| {
         let pending = pending.clone();
         let token = token.clone();
+        let breaker = breaker.clone();
         async move {
-            tokio::select! {
+            let result = tokio::select! {
                 biased;
                 // The transport died (or a shutdown signal cancelled it)
                 // -- no response is coming for any receiver.
@@ -397,7 +456,13 @@ async fn collect_batch_responses(
                         Err(Error::new(ErrorCode::Timeout, "Batch request timed out"))
                     }
                 }
+            };
+            match (&breaker, &result) {
+                (None, _) => (),
+                (_, Err(err)) if !health::is_failure(err) => (),
+                (Some(breaker), _) => breaker.record(result.is_err(), started.elapsed()),
             }
+            result
         }
     });
 
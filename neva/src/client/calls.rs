@@ -88,7 +88,6 @@ impl Client {
     ) -> Result<ListToolsResult, Error> {
         // A cursor-less call starts the listing over, so it replaces what the
         // previous traversal registered rather than merging into it.
-        #[cfg(all(feature = "http-client", not(feature = "legacy-spec")))]
         let fresh = cursor.is_none();
         let params = ListToolsRequestParams { cursor };
 
@@ -100,6 +99,7 @@ impl Client {
 
         #[cfg(all(feature = "http-client", not(feature = "legacy-spec")))]
         self.register_param_headers(&mut result, fresh, grace);
+        self.options.tool_schemas.register(&result, fresh);
 
         Ok(result)
     }
@@ -119,6 +119,7 @@ impl Client {
         };
 
         self.register_param_headers(&mut result, true, None);
+        self.options.tool_schemas.register(&result, true);
 
         if let Ok(value) = serde_json::to_value(&result) {
             ok.result = value;
@@ -337,6 +338,13 @@ impl Client {
 
     /// Calls a tool that MCP server supports
     ///
+    /// With [`McpOptions::with_input_validation`](crate::client::options::McpOptions::with_input_validation)
+    /// or [`McpOptions::with_output_validation`](crate::client::options::McpOptions::with_output_validation)
+    /// the arguments, or the structured result, are checked against the schemas
+    /// the last [`Self::list_tools`] compiled for the tool. When a schema from
+    /// a listing whose `ttlMs` has run out refuses the call, the tools are
+    /// listed again and the call is checked against the new schema.
+    ///
     /// # Example
     /// ```no_run
     /// use neva::client::Client;
@@ -408,8 +416,104 @@ impl Client {
             #[cfg(feature = "tasks")]
             task: None,
         };
+        self.call_tool_params(params).await
+    }
 
-        self.call_tool_raw(params).await?.into_result()
+    /// Calls a tool like [`Self::call_tool`], with a result that has `isError`
+    /// set returned as an [`Error`] too.
+    ///
+    /// Its [`kind`](Error::kind) tells a tool that failed at its task,
+    /// [`ErrorKind::Tool`](crate::error::ErrorKind::Tool), from a call the
+    /// server refused. See [`CallToolResponse::into_result`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// use neva::client::Client;
+    /// use neva::error::{Error, ErrorKind};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Error> {
+    ///     let mut client = Client::new();
+    ///
+    ///     client.connect().await?;
+    ///
+    ///     match client.call_tool_checked("divide", [("a", 1), ("b", 0)]).await {
+    ///         Ok(result) => println!("{:?}", result.content),
+    ///         Err(err) if matches!(err.kind(), ErrorKind::Tool(_)) => println!("{err}"),
+    ///         Err(err) => return Err(err),
+    ///     }
+    ///
+    ///     client.disconnect().await
+    /// }
+    /// ```
+    pub async fn call_tool_checked<N, Args>(
+        &mut self,
+        name: N,
+        args: Args,
+    ) -> Result<CallToolResponse, Error>
+    where
+        N: Into<String>,
+        Args: shared::IntoArgs,
+    {
+        self.call_tool(name, args).await?.into_result()
+    }
+
+    /// [`Self::call_tool`] with its params already assembled.
+    pub(super) async fn call_tool_params(
+        &mut self,
+        params: CallToolRequestParams,
+    ) -> Result<CallToolResponse, Error> {
+        let name = params.name.clone();
+        let args = params.args.as_ref();
+        let checked = self.options.tool_schemas.check_arguments(&name, args);
+        if checked.is_err() && self.refresh_tool_schemas(&name).await {
+            self.options.tool_schemas.check_arguments(&name, args)?;
+        } else {
+            checked?;
+        }
+
+        let resp: CallToolResponse = self.call_tool_raw(params).await?.into_result()?;
+        let checked = self.options.tool_schemas.check_result(&name, &resp);
+        if checked.is_err() && self.refresh_tool_schemas(&name).await {
+            self.options.tool_schemas.check_result(&name, &resp)?;
+        } else {
+            checked?;
+        }
+        Ok(resp)
+    }
+
+    /// Lists the tools again when a schema from a stale listing refused a
+    /// call to `name`, returning whether it did.
+    ///
+    /// Only a refusal costs a listing: a call that passes the last compiled
+    /// schema goes out as it is, however old that schema is, the same way
+    /// `Mcp-Param-*` headers are re-listed for only when the server refuses
+    /// them. The server may have changed the schema since, so the refusal is
+    /// checked again against what it lists now.
+    ///
+    /// The whole listing: a cursor-less page starts the traversal over and
+    /// replaces what the last one registered. A listing this client cannot
+    /// obtain leaves the stale schemas to be checked against, the last word
+    /// the server gave on them.
+    async fn refresh_tool_schemas(&mut self, name: &str) -> bool {
+        if !self.options.tool_schemas.is_stale(name) {
+            return false;
+        }
+        let mut cursor = None;
+        for _ in 0..MAX_REFRESH_PAGES {
+            let page = self
+                .list_tools_inner(
+                    cursor,
+                    #[cfg(all(feature = "http-client", not(feature = "legacy-spec")))]
+                    None,
+                )
+                .await;
+            match page.map(|page| page.next_cursor) {
+                Ok(Some(next)) => cursor = Some(next),
+                _ => break,
+            }
+        }
+        true
     }
 
     /// Calls a task-augmented tool that MCP server supports
@@ -491,6 +595,9 @@ impl Client {
     }
 
     /// Calls a tool
+    ///
+    /// The `_meta` of `params` is sent as given; without one, the call carries
+    /// a progress token derived from its request id.
     #[inline]
     pub async fn call_tool_raw(
         &mut self,
@@ -507,7 +614,7 @@ impl Client {
         let request = Request::new(
             Some(id.clone()),
             crate::types::tool::commands::CALL,
-            Some(params.with_meta(RequestParamsMeta::new(&id))),
+            Some(with_call_meta(params, &id)),
         );
 
         #[cfg(all(feature = "http-client", not(feature = "legacy-spec")))]
@@ -597,7 +704,7 @@ impl Client {
         let retry = Request::new(
             Some(id.clone()),
             crate::types::tool::commands::CALL,
-            Some(params.with_meta(RequestParamsMeta::new(&id))),
+            Some(with_call_meta(params, &id)),
         );
 
         self.send_request(retry).await
@@ -625,14 +732,24 @@ impl Client {
     pub async fn read_resource(
         &mut self,
         uri: impl Into<Uri>,
+    ) -> Result<ReadResourceResult, Error> {
+        self.read_resource_with_meta(uri.into(), None).await
+    }
+
+    /// [`Self::read_resource`] with `meta`, or a progress token derived from
+    /// the request id without it.
+    pub(super) async fn read_resource_with_meta(
+        &mut self,
+        uri: Uri,
+        meta: Option<RequestParamsMeta>,
     ) -> Result<ReadResourceResult, Error> {
         let id = self.generate_id()?;
         let request = Request::new(
             Some(id.clone()),
             crate::types::resource::commands::READ,
             Some(ReadResourceRequestParams {
-                uri: uri.into(),
-                meta: Some(RequestParamsMeta::new(&id)),
+                uri,
+                meta: Some(meta.unwrap_or_else(|| RequestParamsMeta::new(&id))),
                 #[cfg(feature = "server")]
                 args: None,
             }),
@@ -673,14 +790,26 @@ impl Client {
         N: Into<String>,
         Args: shared::IntoArgs,
     {
+        self.get_prompt_with_meta(name.into(), args.into_args(), None)
+            .await
+    }
+
+    /// [`Self::get_prompt`] with `meta`, or a progress token derived from the
+    /// request id without it.
+    pub(super) async fn get_prompt_with_meta(
+        &mut self,
+        name: String,
+        args: Option<std::collections::HashMap<String, serde_json::Value>>,
+        meta: Option<RequestParamsMeta>,
+    ) -> Result<GetPromptResult, Error> {
         let id = self.generate_id()?;
         let request = Request::new(
             Some(id.clone()),
             crate::types::prompt::commands::GET,
             Some(GetPromptRequestParams {
-                name: name.into(),
-                meta: Some(RequestParamsMeta::new(&id)),
-                args: args.into_args(),
+                name,
+                meta: Some(meta.unwrap_or_else(|| RequestParamsMeta::new(&id))),
+                args,
             }),
         );
 
@@ -688,6 +817,16 @@ impl Client {
     }
 }
 
+/// `params` with their own `_meta`, or one carrying a progress token derived
+/// from `id`.
+#[inline]
+fn with_call_meta(mut params: CallToolRequestParams, id: &RequestId) -> CallToolRequestParams {
+    if params.meta.is_none() {
+        params.meta = Some(RequestParamsMeta::new(id));
+    }
+    params
+}
+
 /// What the client will mirror into `Mcp-Param-*` headers is decided by the
 /// current listing and nothing else: a tool the server no longer designates --
 /// or no longer lists at all -- must stop sending its argument in a header.
@@ -1,6 +1,8 @@
 //! Request handling utilities
 
+use crate::client::health::{self, Admission, CircuitBreaker, ProbeGuard};
 use crate::client::notification_handler::NotificationsHandler;
+use crate::client::streaming::CallStreams;
 use crate::types::sampling::SamplingHandler;
 use crate::types::{Root, root::ListRootsResult};
 use crate::{
@@ -18,7 +20,7 @@ use crate::{
 use std::sync::Arc;
 use std::{
     sync::atomic::{AtomicI64, Ordering},
-    time::Duration,
+    time::{Duration, Instant},
 };
 use tokio::sync::RwLock;
 use tokio::time::timeout;
@@ -49,8 +51,9 @@ struct Roots {
 }
 
 pub(super) struct RequestHandler {
-    /// Request counter
-    counter: AtomicI64,
+    /// Request counter, shared with every [`ListenLink`] so a subscription
+    /// re-established in the background never reuses an id
+    counter: Arc<AtomicI64>,
 
     /// Request timeout
     timeout: Duration,
@@ -95,6 +98,13 @@ pub(super) struct RequestHandler {
     /// What each live subscription is allowed to deliver.
     #[cfg(not(feature = "legacy-spec"))]
     subscription_filters: crate::client::subscription::SubscriptionStates,
+
+    /// Circuit breaker guarding every request, shared with
+    /// [`McpOptions`] so its state outlives a reconnect.
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
+
+    /// Calls whose progress and logs go to a stream of their own.
+    call_streams: CallStreams,
 }
 
 impl Roots {
@@ -167,7 +177,7 @@ impl RequestHandler {
 
         let handler = Self {
             roots: Roots::new(options, &tx),
-            counter: AtomicI64::new(1),
+            counter: Arc::new(AtomicI64::new(1)),
             pending: RequestQueue::new(options.timeout),
             sender: tx,
             timeout: options.timeout,
@@ -183,6 +193,8 @@ impl RequestHandler {
             ack_waiters: Default::default(),
             #[cfg(not(feature = "legacy-spec"))]
             subscription_filters: Default::default(),
+            circuit_breaker: options.circuit_breaker.clone(),
+            call_streams: CallStreams::default(),
         };
 
         handler.start(rx)
@@ -213,9 +225,87 @@ impl RequestHandler {
         &self.pending
     }
 
-    /// Sends a request to MCP server
+    /// Returns the circuit breaker, if one is configured
+    #[inline]
+    pub(super) fn circuit_breaker(&self) -> Option<Arc<CircuitBreaker>> {
+        self.circuit_breaker.clone()
+    }
+
+    /// Returns the registry of calls with a stream of their own
     #[inline]
+    pub(super) fn call_streams(&self) -> CallStreams {
+        self.call_streams.clone()
+    }
+
+    /// Sends a request to MCP server
+    ///
+    /// With a circuit breaker configured, the request is admitted by it first
+    /// and its outcome recorded against it.
     pub(super) async fn send_request(&mut self, request: Request) -> Result<Response, Error> {
+        let id = request.id();
+        let streamed = self.call_streams.track(&request);
+        let result = self.guarded_exchange(request).await;
+        if streamed {
+            self.call_streams.untrack(&id);
+        }
+        result
+    }
+
+    /// Sends a request through the circuit breaker, if there is one.
+    async fn guarded_exchange(&mut self, request: Request) -> Result<Response, Error> {
+        let Some(breaker) = self.admit().await? else {
+            return self.exchange(request).await;
+        };
+
+        let started = Instant::now();
+        let result = self.exchange(request).await;
+        match &result {
+            Err(err) if !health::is_failure(err) => (),
+            _ => breaker.record(result.is_err(), started.elapsed()),
+        }
+        result
+    }
+
+    /// Lets the circuit breaker, if any, decide whether a request may go out,
+    /// probing the server first when the cool-down is over.
+    async fn admit(&mut self) -> Result<Option<Arc<CircuitBreaker>>, Error> {
+        let Some(breaker) = self.circuit_breaker.clone() else {
+            return Ok(None);
+        };
+
+        match breaker.admit() {
+            Admission::Allow => Ok(Some(breaker)),
+            Admission::Reject(retry_after) => Err(health::circuit_open(retry_after)),
+            Admission::Probe => {
+                // Should this future be dropped mid-probe, the guard frees
+                // the slot; otherwise every later request would be rejected.
+                let guard = ProbeGuard::new(&breaker);
+                let answered = self.probe().await;
+                guard.finish(answered);
+                if answered {
+                    Ok(Some(breaker))
+                } else {
+                    Err(health::circuit_open(breaker.open_for()))
+                }
+            }
+        }
+    }
+
+    /// Asks the server for something cheap to see whether it answers at all.
+    /// An error response is still an answer.
+    async fn probe(&mut self) -> bool {
+        #[cfg(not(feature = "legacy-spec"))]
+        let method = crate::commands::DISCOVER;
+        #[cfg(feature = "legacy-spec")]
+        let method = crate::commands::PING;
+
+        let request = Request::new(Some(self.next_id()), method, None::<()>);
+        self.exchange(request).await.is_ok()
+    }
+
+    /// Sends a request and waits for its response, bypassing the circuit
+    /// breaker.
+    async fn exchange(&mut self, request: Request) -> Result<Response, Error> {
         let id = request.id();
         let receiver = self.pending.push(&id);
         if let Err(err) = self.sender.send(request.into()).await {
@@ -250,75 +340,22 @@ impl RequestHandler {
         }
     }
 
-    /// Sends a `subscriptions/listen` request and returns the slot its final
-    /// response will arrive in.
-    ///
-    /// Unlike [`Self::send_request`] this does not await the reply and -- by
-    /// skipping [`RequestQueue::activate`] -- never starts the request TTL: a
-    /// subscription is answered only when it ends, which may be hours later.
+    /// Everything establishing a `subscriptions/listen` stream needs, detached
+    /// from the handler so it can be done again without borrowing the client --
+    /// which is what re-establishing a dropped subscription in the background
+    /// has to do.
     #[cfg(not(feature = "legacy-spec"))]
-    pub(super) async fn send_listen(
-        &mut self,
-        request: Request,
-    ) -> Result<tokio::sync::oneshot::Receiver<PendingResponse>, Error> {
-        let id = request.id();
-        let receiver = self.pending.push(&id);
-        if let Err(err) = self.sender.send(request.into()).await {
-            let _ = self.pending.pop(&id);
-            return Err(err);
+    pub(super) fn listen_link(&self) -> ListenLink {
+        ListenLink {
+            counter: self.counter.clone(),
+            timeout: self.timeout,
+            token: self.token.clone(),
+            pending: self.pending.clone(),
+            sender: self.sender.clone(),
+            ack_waiters: self.ack_waiters.clone(),
+            subscription_filters: self.subscription_filters.clone(),
+            notification_handler: self.notification_handler.clone(),
         }
-        Ok(receiver)
-    }
-
-    /// Registers interest in the acknowledgment of the subscription `id`.
-    ///
-    /// Must be called *before* the `subscriptions/listen` request goes out --
-    /// the acknowledgment is the first message the server sends back, and the
-    /// receive loop drops one it has no waiter for.
-    #[cfg(not(feature = "legacy-spec"))]
-    pub(super) fn watch_ack(
-        &self,
-        id: &RequestId,
-        requested: &crate::types::SubscriptionFilter,
-    ) -> tokio::sync::oneshot::Receiver<crate::types::SubscriptionFilter> {
-        let (tx, rx) = tokio::sync::oneshot::channel();
-        self.ack_waiters.insert(id.clone(), tx);
-        // Recorded before the request goes out, and recorded as *pending*: the
-        // acknowledgment is required to be the first message on the stream, so
-        // anything tagged with this id that arrives ahead of it is delivered by
-        // a subscription that does not exist yet -- and may never, if the peer
-        // rejects the listen or simply never answers. The requested filter
-        // rides along because the acknowledgment narrows it rather than
-        // replacing it.
-        self.subscription_filters.insert(
-            id.clone(),
-            crate::client::subscription::SubscriptionState::Pending(requested.clone()),
-        );
-
-        rx
-    }
-
-    /// Everything a [`Subscription`] needs to release its own bookkeeping once
-    /// its stream is over.
-    ///
-    /// [`Subscription`]: crate::client::Subscription
-    #[cfg(not(feature = "legacy-spec"))]
-    pub(super) fn subscription_release(&self) -> crate::client::subscription::SubscriptionRelease {
-        crate::client::subscription::SubscriptionRelease::new(
-            self.pending.clone(),
-            self.ack_waiters.clone(),
-            self.subscription_filters.clone(),
-        )
-    }
-
-    /// Returns a handle on the transport sender, so a [`Subscription`] can
-    /// cancel itself without borrowing the client.
-    ///
-    /// [`Subscription`]: crate::client::Subscription
-    #[cfg(not(feature = "legacy-spec"))]
-    #[inline]
-    pub(super) fn sender(&self) -> TransportProtoSender {
-        self.sender.clone()
     }
 
     /// Sends a batch of messages to the MCP server.
@@ -328,6 +365,10 @@ impl RequestHandler {
     /// per request (in input order). [`MessageEnvelope::Notification`] items
     /// are included in the wire payload but produce no receiver slot.
     ///
+    /// The batch is admitted by the circuit breaker like a single request; a
+    /// failed write is recorded against it here, and each response as
+    /// `collect_batch_responses` receives it.
+    ///
     /// > **Note:** under MCP 2026-07-28, per-request client metadata
     /// > (`clientInfo` / `clientCapabilities`, plus `_meta.traceparent` /
     /// > `tracestate` when a trace-context provider is installed) is injected
@@ -347,6 +388,7 @@ impl RequestHandler {
         validate_batch_ids(&items)?;
         #[cfg(not(feature = "legacy-spec"))]
         validate_no_listen(&items)?;
+        let breaker = self.admit().await?;
 
         let mut receivers = Vec::new();
         let mut envelopes = Vec::new();
@@ -361,10 +403,14 @@ impl RequestHandler {
         }
 
         let batch = MessageBatch::new(envelopes)?;
+        let started = Instant::now();
         if let Err(e) = self.sender.send(Message::Batch(batch)).await {
             for (id, _rx) in &receivers {
                 let _ = self.pending.pop(id);
             }
+            if let Some(breaker) = breaker {
+                breaker.record(true, started.elapsed());
+            }
             return Err(e);
         }
         for (id, _rx) in &receivers {
@@ -413,6 +459,7 @@ impl RequestHandler {
         let ack_waiters = self.ack_waiters.clone();
         #[cfg(not(feature = "legacy-spec"))]
         let subscription_filters = self.subscription_filters.clone();
+        let call_streams = self.call_streams.clone();
 
         tokio::task::spawn(async move {
             loop {
@@ -447,6 +494,9 @@ impl RequestHandler {
                         send_response_impl(&mut sender, resp).await;
                     }
                     Message::Notification(notification) => {
+                        let Some(notification) = call_streams.deliver(notification) else {
+                            continue;
+                        };
                         #[cfg(not(feature = "legacy-spec"))]
                         {
                             complete_ack(&notification, &ack_waiters, &subscription_filters);
@@ -468,6 +518,17 @@ impl RequestHandler {
                         // calls to time out even though their responses arrived.
                         let mut deferred = Vec::new();
                         for envelope in batch {
+                            let envelope = match envelope {
+                                MessageEnvelope::Notification(notification) => {
+                                    match call_streams.deliver(notification) {
+                                        Some(notification) => {
+                                            MessageEnvelope::Notification(notification)
+                                        }
+                                        None => continue,
+                                    }
+                                }
+                                other => other,
+                            };
                             match envelope {
                                 MessageEnvelope::Response(resp) => pending.complete(resp),
                                 // A batched notification is still a
@@ -532,6 +593,123 @@ impl RequestHandler {
     }
 }
 
+/// A detached handle on the parts of a [`RequestHandler`] that open
+/// `subscriptions/listen` streams.
+///
+/// Cheap to clone: every field is shared with the handler it came from, so a
+/// request slot taken or an acknowledgment awaited through here is exactly the
+/// one the receive loop completes.
+#[cfg(not(feature = "legacy-spec"))]
+#[derive(Clone)]
+pub(super) struct ListenLink {
+    counter: Arc<AtomicI64>,
+    timeout: Duration,
+    token: CancellationToken,
+    pending: RequestQueue,
+    sender: TransportProtoSender,
+    ack_waiters: crate::client::subscription::AckWaiters,
+    subscription_filters: crate::client::subscription::SubscriptionStates,
+    notification_handler: Option<Arc<NotificationsHandler>>,
+}
+
+#[cfg(not(feature = "legacy-spec"))]
+impl ListenLink {
+    /// Returns the next [`RequestId`] from the client's own counter.
+    #[inline]
+    pub(super) fn next_id(&self) -> RequestId {
+        let id = self.counter.fetch_add(1, Ordering::Relaxed);
+        RequestId::Number(id)
+    }
+
+    /// Returns the request timeout duration, which also bounds the wait for an
+    /// acknowledgment.
+    #[inline]
+    pub(super) fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Whether the transport is gone, and with it any chance of listening again.
+    #[inline]
+    pub(super) fn is_closed(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Returns a handle on the transport sender, so a [`Subscription`] can
+    /// cancel itself without borrowing the client.
+    ///
+    /// [`Subscription`]: crate::client::Subscription
+    #[inline]
+    pub(super) fn sender(&self) -> TransportProtoSender {
+        self.sender.clone()
+    }
+
+    /// Sends a `subscriptions/listen` request and returns the slot its final
+    /// response will arrive in.
+    ///
+    /// Unlike [`RequestHandler::send_request`] this does not await the reply
+    /// and -- by skipping [`RequestQueue::activate`] -- never starts the request
+    /// TTL: a subscription is answered only when it ends, which may be hours
+    /// later.
+    pub(super) async fn send_listen(
+        &mut self,
+        request: Request,
+    ) -> Result<tokio::sync::oneshot::Receiver<PendingResponse>, Error> {
+        let id = request.id();
+        let receiver = self.pending.push(&id);
+        if let Err(err) = self.sender.send(request.into()).await {
+            let _ = self.pending.pop(&id);
+            return Err(err);
+        }
+        Ok(receiver)
+    }
+
+    /// Registers interest in the acknowledgment of the subscription `id`.
+    ///
+    /// Must be called *before* the `subscriptions/listen` request goes out --
+    /// the acknowledgment is the first message the server sends back, and the
+    /// receive loop drops one it has no waiter for.
+    pub(super) fn watch_ack(
+        &self,
+        id: &RequestId,
+        requested: &crate::types::SubscriptionFilter,
+    ) -> tokio::sync::oneshot::Receiver<crate::types::SubscriptionFilter> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.ack_waiters.insert(id.clone(), tx);
+        // Recorded before the request goes out, and recorded as *pending*: the
+        // acknowledgment is required to be the first message on the stream, so
+        // anything tagged with this id that arrives ahead of it is delivered by
+        // a subscription that does not exist yet -- and may never, if the peer
+        // rejects the listen or simply never answers. The requested filter
+        // rides along because the acknowledgment narrows it rather than
+        // replacing it.
+        self.subscription_filters.insert(
+            id.clone(),
+            crate::client::subscription::SubscriptionState::Pending(requested.clone()),
+        );
+
+        rx
+    }
+
+    /// Everything a [`Subscription`] needs to release its own bookkeeping once
+    /// its stream is over.
+    ///
+    /// [`Subscription`]: crate::client::Subscription
+    pub(super) fn subscription_release(&self) -> crate::client::subscription::SubscriptionRelease {
+        crate::client::subscription::SubscriptionRelease::new(
+            self.pending.clone(),
+            self.ack_waiters.clone(),
+            self.subscription_filters.clone(),
+        )
+    }
+
+    /// Hands a notification the client produced itself to the registered
+    /// handlers, bypassing the subscription gate the receive loop applies to
+    /// the wire.
+    pub(super) async fn notify(&self, notification: Notification) {
+        dispatch_notification(notification, &self.notification_handler).await;
+    }
+}
+
 #[inline]
 async fn dispatch_batch_deferred(
     deferred: Vec<MessageEnvelope>,
@@ -1080,6 +1258,95 @@ mod tests {
         assert!(result.is_err(), "the aborted request must surface an error");
     }
 
+    #[tokio::test]
+    #[cfg(feature = "http-client")]
+    async fn open_circuit_fails_requests_fast() {
+        use tokio::time::Duration;
+
+        let options = McpOptions::default()
+            .with_timeout(Duration::from_millis(20))
+            .with_circuit_breaker(|cb| {
+                cb.with_min_requests(2)
+                    .with_open_for(Duration::from_secs(60))
+            });
+        let mut handler = RequestHandler::new(
+            TransportProto::HttpClient(Box::default()),
+            &options,
+            CancellationToken::new(),
+        );
+
+        // The transport was never started -- every request times out.
+        for id in 1..=2 {
+            let req = Request::new(Some(RequestId::Number(id)), "ping", None::<()>);
+            let err = handler.send_request(req).await.unwrap_err();
+            assert_eq!(err.code, ErrorCode::Timeout);
+        }
+
+        let started = Instant::now();
+        let req = Request::new(Some(RequestId::Number(3)), "ping", None::<()>);
+        let err = handler.send_request(req).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::CircuitOpen);
+        assert!(started.elapsed() < Duration::from_millis(20));
+
+        let health = options.circuit_breaker.as_ref().unwrap().health();
+        assert_eq!(health.state, crate::client::health::CircuitState::Open);
+        assert_eq!(
+            (health.requests, health.failures, health.rejected),
+            (2, 2, 1)
+        );
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "http-client")]
+    async fn batch_outcomes_are_recorded_against_the_circuit() {
+        use tokio::time::Duration;
+
+        let options = McpOptions::default()
+            .with_timeout(Duration::from_millis(20))
+            .with_circuit_breaker(|cb| {
+                cb.with_min_requests(2)
+                    .with_open_for(Duration::from_secs(60))
+            });
+        let mut handler = RequestHandler::new(
+            TransportProto::HttpClient(Box::default()),
+            &options,
+            CancellationToken::new(),
+        );
+
+        // The transport was never started -- both requests time out.
+        let items = (1..=2)
+            .map(|id| {
+                MessageEnvelope::Request(Request::new(
+                    Some(RequestId::Number(id)),
+                    "ping",
+                    None::<()>,
+                ))
+            })
+            .collect();
+        let receivers = handler.send_batch(items).await.unwrap();
+        let responses = crate::client::collect_batch_responses(
+            receivers,
+            handler.pending(),
+            handler.timeout(),
+            handler.cancellation(),
+            handler.circuit_breaker(),
+        )
+        .await;
+        assert!(responses.iter().all(Result::is_err));
+
+        let health = options.circuit_breaker.as_ref().unwrap().health();
+        assert_eq!(health.state, crate::client::health::CircuitState::Open);
+        assert_eq!((health.requests, health.failures), (2, 2));
+
+        let items = vec![MessageEnvelope::Request(Request::new(
+            Some(RequestId::Number(3)),
+            "ping",
+            None::<()>,
+        ))];
+        let err = handler.send_batch(items).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::CircuitOpen);
+    }
+
     #[tokio::test]
     async fn batch_responses_are_distributed_individually() {
         use crate::types::MessageBatch;
@@ -0,0 +1,550 @@
+//! Health tracking and a circuit breaker for the connected server.
+//!
+//! Off by default. Once configured with
+//! [`McpOptions::with_circuit_breaker`](crate::client::options::McpOptions::with_circuit_breaker),
+//! every request the client sends is timed and its outcome recorded over a
+//! rolling window. When the failure rate crosses the threshold the circuit
+//! *opens*: requests fail fast with [`ErrorCode::CircuitOpen`] instead of
+//! queueing up behind a server that is not answering. After the cool-down the
+//! next request first probes the server -- with `server/discover`, or `ping`
+//! on the legacy profile -- and an answer of any kind puts the circuit
+//! *half-open*, letting traffic through on trial until enough of it succeeds
+//! to close it again.
+//!
+//! A failure here is a request the server never answered: a timeout, a dropped
+//! connection, a transport error. A JSON-RPC error *response* is the server
+//! working as intended and counts as a success.
+
+use crate::error::{Error, ErrorCode};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const DEFAULT_WINDOW: usize = 20;
+const DEFAULT_MIN_REQUESTS: usize = 5;
+const DEFAULT_FAILURE_RATE: f64 = 0.5;
+const DEFAULT_OPEN_FOR: Duration = Duration::from_secs(30);
+const DEFAULT_HALF_OPEN_SUCCESSES: u32 = 3;
+
+/// Where the circuit stands.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests flow normally and their outcomes are tracked.
+    #[default]
+    Closed,
+
+    /// Requests fail fast with [`ErrorCode::CircuitOpen`] until the cool-down
+    /// elapses and a probe gets an answer.
+    Open,
+
+    /// A probe was answered; requests go through on trial. A single failure
+    /// re-opens the circuit, enough successes close it.
+    HalfOpen,
+}
+
+/// Thresholds for the client's circuit breaker.
+///
+/// # Examples
+/// ```no_run
+/// use std::time::Duration;
+/// use neva::Client;
+///
+/// let client = Client::new().with_options(|opt| opt
+///     .with_circuit_breaker(|cb| cb
+///         .with_window(50)
+///         .with_failure_rate(0.25)
+///         .with_slow_call(Duration::from_secs(2))
+///         .with_open_for(Duration::from_secs(10))));
+/// # let _ = client;
+/// ```
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerOptions {
+    window: usize,
+    min_requests: usize,
+    failure_rate: f64,
+    slow_call: Option<Duration>,
+    open_for: Duration,
+    half_open_successes: u32,
+}
+
+impl Default for CircuitBreakerOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            window: DEFAULT_WINDOW,
+            min_requests: DEFAULT_MIN_REQUESTS,
+            failure_rate: DEFAULT_FAILURE_RATE,
+            slow_call: None,
+            open_for: DEFAULT_OPEN_FOR,
+            half_open_successes: DEFAULT_HALF_OPEN_SUCCESSES,
+        }
+    }
+}
+
+impl CircuitBreakerOptions {
+    /// Sets how many of the most recent outcomes the failure rate is taken
+    /// over.
+    ///
+    /// Default: 20
+    pub fn with_window(mut self, requests: usize) -> Self {
+        self.window = requests.max(1);
+        self
+    }
+
+    /// Sets how many outcomes the window must hold before the failure rate is
+    /// acted on, so a single early timeout does not open the circuit.
+    ///
+    /// Default: 5
+    pub fn with_min_requests(mut self, requests: usize) -> Self {
+        self.min_requests = requests;
+        self
+    }
+
+    /// Sets the failure rate, between `0.0` and `1.0`, at or above which the
+    /// circuit opens.
+    ///
+    /// Default: 0.5
+    pub fn with_failure_rate(mut self, rate: f64) -> Self {
+        self.failure_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Counts a request answered slower than `latency` as a failure, so a
+    /// server that degrades without timing out still opens the circuit.
+    ///
+    /// Default: off
+    pub fn with_slow_call(mut self, latency: Duration) -> Self {
+        self.slow_call = Some(latency);
+        self
+    }
+
+    /// Sets how long the circuit stays open before it is probed.
+    ///
+    /// Default: 30 seconds
+    pub fn with_open_for(mut self, cool_down: Duration) -> Self {
+        self.open_for = cool_down;
+        self
+    }
+
+    /// Sets how many trial requests must succeed in a row to close a
+    /// half-open circuit.
+    ///
+    /// Default: 3
+    pub fn with_half_open_successes(mut self, successes: u32) -> Self {
+        self.half_open_successes = successes.max(1);
+        self
+    }
+}
+
+/// A point-in-time view of the connected server's health, as this client has
+/// seen it.
+///
+/// Returned by [`Client::health`](crate::Client::health).
+#[derive(Debug, Clone)]
+pub struct ServerHealth {
+    /// Where the circuit stands.
+    pub state: CircuitState,
+
+    /// The share of failures in the current window, from `0.0` to `1.0`.
+    pub failure_rate: f64,
+
+    /// Requests recorded since the client was created.
+    pub requests: u64,
+
+    /// Failed requests recorded since the client was created.
+    pub failures: u64,
+
+    /// Requests rejected without being sent while the circuit was open.
+    pub rejected: u64,
+
+    /// Failures in a row, reset by the next success.
+    pub consecutive_failures: u32,
+
+    /// Mean latency over the current window, if it holds anything.
+    pub average_latency: Option<Duration>,
+
+    /// Latency of the most recently recorded request.
+    pub last_latency: Option<Duration>,
+
+    /// How long ago the circuit last opened, if it ever has.
+    pub opened_ago: Option<Duration>,
+}
+
+/// What the breaker allows the next request to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Admission {
+    /// Send it.
+    Allow,
+    /// Probe the server first; the request goes out only if the probe is
+    /// answered.
+    Probe,
+    /// Fail it fast; the circuit stays open for at least this long.
+    Reject(Duration),
+}
+
+/// The probe a request was admitted to send. Dropped before
+/// [`finish`](ProbeGuard::finish) -- the request cancelled mid-probe -- it
+/// counts as unanswered, so the slot is freed for the next request.
+#[derive(Debug)]
+pub(crate) struct ProbeGuard<'a> {
+    breaker: &'a CircuitBreaker,
+    done: bool,
+}
+
+impl<'a> ProbeGuard<'a> {
+    /// Holds the probe [`CircuitBreaker::admit`] handed out.
+    #[inline]
+    pub(crate) fn new(breaker: &'a CircuitBreaker) -> Self {
+        Self {
+            breaker,
+            done: false,
+        }
+    }
+
+    /// Records whether the probe was answered.
+    #[inline]
+    pub(crate) fn finish(mut self, answered: bool) {
+        self.done = true;
+        self.breaker.probed(answered);
+    }
+}
+
+impl Drop for ProbeGuard<'_> {
+    fn drop(&mut self) {
+        if !self.done {
+            self.breaker.probed(false);
+        }
+    }
+}
+
+/// The breaker itself, shared by the client and every connection it opens.
+#[derive(Debug)]
+pub(crate) struct CircuitBreaker {
+    options: CircuitBreakerOptions,
+    inner: Mutex<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    state: CircuitState,
+    /// `(failed, latency)` of the most recent requests, oldest first.
+    window: VecDeque<(bool, Duration)>,
+    opened_at: Option<Instant>,
+    /// A probe is out, so nobody else gets to send one.
+    probing: bool,
+    trial_successes: u32,
+    consecutive_failures: u32,
+    requests: u64,
+    failures: u64,
+    rejected: u64,
+    last_latency: Option<Duration>,
+}
+
+impl Inner {
+    fn failure_rate(&self) -> f64 {
+        if self.window.is_empty() {
+            return 0.0;
+        }
+        let failed = self.window.iter().filter(|(failed, _)| *failed).count();
+        failed as f64 / self.window.len() as f64
+    }
+
+    fn open(&mut self) {
+        self.state = CircuitState::Open;
+        self.opened_at = Some(Instant::now());
+        self.trial_successes = 0;
+    }
+
+    fn close(&mut self) {
+        self.state = CircuitState::Closed;
+        self.window.clear();
+        self.trial_successes = 0;
+    }
+}
+
+impl CircuitBreaker {
+    /// Creates a closed breaker with the given thresholds.
+    pub(crate) fn new(options: CircuitBreakerOptions) -> Self {
+        Self {
+            options,
+            inner: Default::default(),
+        }
+    }
+
+    /// Decides what the next request may do.
+    pub(crate) fn admit(&self) -> Admission {
+        let mut inner = self.lock();
+        if inner.state != CircuitState::Open {
+            return Admission::Allow;
+        }
+
+        let elapsed = inner.opened_at.map_or(Duration::MAX, |at| at.elapsed());
+        if !inner.probing && elapsed >= self.options.open_for {
+            inner.probing = true;
+            return Admission::Probe;
+        }
+
+        inner.rejected += 1;
+        Admission::Reject(self.options.open_for.saturating_sub(elapsed))
+    }
+
+    /// Returns how long the circuit stays open before it is probed.
+    #[inline]
+    pub(crate) fn open_for(&self) -> Duration {
+        self.options.open_for
+    }
+
+    /// Records whether a probe was answered. Answered half-opens the circuit;
+    /// unanswered restarts the cool-down.
+    pub(crate) fn probed(&self, answered: bool) {
+        let mut inner = self.lock();
+        inner.probing = false;
+        if answered {
+            inner.state = CircuitState::HalfOpen;
+            inner.trial_successes = 0;
+        } else {
+            inner.open();
+        }
+    }
+
+    /// Records the outcome of a request that was sent.
+    pub(crate) fn record(&self, failed: bool, latency: Duration) {
+        let failed = failed || self.options.slow_call.is_some_and(|slow| latency > slow);
+
+        let mut inner = self.lock();
+        inner.requests += 1;
+        inner.last_latency = Some(latency);
+        if failed {
+            inner.failures += 1;
+            inner.consecutive_failures += 1;
+        } else {
+            inner.consecutive_failures = 0;
+        }
+
+        if inner.window.len() == self.options.window {
+            inner.window.pop_front();
+        }
+        inner.window.push_back((failed, latency));
+
+        match inner.state {
+            CircuitState::Closed => {
+                if inner.window.len() >= self.options.min_requests
+                    && inner.failure_rate() >= self.options.failure_rate
+                {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(
+                        logger = "neva",
+                        failure_rate = inner.failure_rate(),
+                        "opening the circuit to the server"
+                    );
+                    inner.open();
+                }
+            }
+            CircuitState::HalfOpen if failed => inner.open(),
+            CircuitState::HalfOpen => {
+                inner.trial_successes += 1;
+                if inner.trial_successes >= self.options.half_open_successes {
+                    inner.close();
+                }
+            }
+            // An answer to something sent before the circuit opened says
+            // nothing about whether it should close.
+            CircuitState::Open => {}
+        }
+    }
+
+    /// Takes a [`ServerHealth`] snapshot.
+    pub(crate) fn health(&self) -> ServerHealth {
+        let inner = self.lock();
+        let average_latency = (!inner.window.is_empty()).then(|| {
+            let total: Duration = inner.window.iter().map(|(_, latency)| *latency).sum();
+            total / inner.window.len() as u32
+        });
+
+        ServerHealth {
+            state: inner.state,
+            failure_rate: inner.failure_rate(),
+            requests: inner.requests,
+            failures: inner.failures,
+            rejected: inner.rejected,
+            consecutive_failures: inner.consecutive_failures,
+            average_latency,
+            last_latency: inner.last_latency,
+            opened_ago: inner.opened_at.map(|at| at.elapsed()),
+        }
+    }
+
+    #[inline]
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        // Every critical section is a handful of field updates that cannot
+        // panic half-way, so a poisoned lock still guards consistent state.
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Whether an error from sending a request means the server never answered.
+///
+/// Everything the transport reports is that -- a timeout, a closed connection,
+/// a failed write -- except a cancellation, which is this client's own doing.
+#[inline]
+pub(crate) fn is_failure(err: &Error) -> bool {
+    err.code != ErrorCode::RequestCancelled
+}
+
+/// The error a request fails fast with while the circuit is open.
+pub(crate) fn circuit_open(retry_after: Duration) -> Error {
+    Error::new(
+        ErrorCode::CircuitOpen,
+        "The circuit to the server is open; the request was not sent",
+    )
+    .with_data(serde_json::json!({ "retryAfterMs": retry_after.as_millis() as u64 }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker::new(
+            CircuitBreakerOptions::default()
+                .with_window(4)
+                .with_min_requests(4)
+                .with_failure_rate(0.5)
+                .with_open_for(Duration::ZERO)
+                .with_half_open_successes(2),
+        )
+    }
+
+    const FAST: Duration = Duration::from_millis(1);
+
+    #[test]
+    fn it_stays_closed_below_the_minimum_sample() {
+        let breaker = breaker();
+        for _ in 0..3 {
+            breaker.record(true, FAST);
+        }
+
+        assert_eq!(breaker.health().state, CircuitState::Closed);
+        assert_eq!(breaker.admit(), Admission::Allow);
+    }
+
+    #[test]
+    fn it_opens_at_the_failure_rate() {
+        let breaker = breaker();
+        breaker.record(false, FAST);
+        breaker.record(false, FAST);
+        breaker.record(true, FAST);
+        breaker.record(true, FAST);
+
+        let health = breaker.health();
+        assert_eq!(health.state, CircuitState::Open);
+        assert_eq!(health.failure_rate, 0.5);
+        assert_eq!(health.consecutive_failures, 2);
+    }
+
+    #[test]
+    fn it_rejects_while_cooling_down() {
+        let breaker = CircuitBreaker::new(
+            CircuitBreakerOptions::default()
+                .with_min_requests(1)
+                .with_open_for(Duration::from_secs(60)),
+        );
+        breaker.record(true, FAST);
+
+        assert!(matches!(breaker.admit(), Admission::Reject(d) if d > Duration::ZERO));
+        assert_eq!(breaker.health().rejected, 1);
+    }
+
+    #[test]
+    fn only_one_probe_goes_out_at_a_time() {
+        let breaker = breaker();
+        for _ in 0..4 {
+            breaker.record(true, FAST);
+        }
+
+        assert_eq!(breaker.admit(), Admission::Probe);
+        assert!(matches!(breaker.admit(), Admission::Reject(_)));
+    }
+
+    #[test]
+    fn an_answered_probe_half_opens_and_trials_close() {
+        let breaker = breaker();
+        for _ in 0..4 {
+            breaker.record(true, FAST);
+        }
+        assert_eq!(breaker.admit(), Admission::Probe);
+
+        breaker.probed(true);
+        assert_eq!(breaker.health().state, CircuitState::HalfOpen);
+
+        breaker.record(false, FAST);
+        assert_eq!(breaker.health().state, CircuitState::HalfOpen);
+        breaker.record(false, FAST);
+        assert_eq!(breaker.health().state, CircuitState::Closed);
+    }
+
+    #[test]
+    fn a_failed_trial_reopens() {
+        let breaker = breaker();
+        for _ in 0..4 {
+            breaker.record(true, FAST);
+        }
+        breaker.admit();
+        breaker.probed(true);
+
+        breaker.record(true, FAST);
+        assert_eq!(breaker.health().state, CircuitState::Open);
+    }
+
+    #[test]
+    fn an_unanswered_probe_keeps_it_open() {
+        let breaker = breaker();
+        for _ in 0..4 {
+            breaker.record(true, FAST);
+        }
+        breaker.admit();
+        breaker.probed(false);
+
+        assert_eq!(breaker.health().state, CircuitState::Open);
+        // The cool-down restarted, and with it the right to probe.
+        assert_eq!(breaker.admit(), Admission::Probe);
+    }
+
+    #[test]
+    fn a_dropped_probe_frees_the_slot() {
+        let breaker = breaker();
+        for _ in 0..4 {
+            breaker.record(true, FAST);
+        }
+        assert_eq!(breaker.admit(), Admission::Probe);
+
+        drop(ProbeGuard::new(&breaker));
+
+        assert_eq!(breaker.health().state, CircuitState::Open);
+        assert_eq!(breaker.admit(), Admission::Probe);
+    }
+
+    #[test]
+    fn slow_calls_count_as_failures() {
+        let breaker = CircuitBreaker::new(
+            CircuitBreakerOptions::default()
+                .with_min_requests(1)
+                .with_slow_call(Duration::from_millis(10)),
+        );
+        breaker.record(false, Duration::from_millis(50));
+
+        assert_eq!(breaker.health().state, CircuitState::Open);
+        assert_eq!(
+            breaker.health().average_latency,
+            Some(Duration::from_millis(50))
+        );
+    }
+
+    #[test]
+    fn cancellations_are_not_failures() {
+        assert!(!is_failure(&Error::from(ErrorCode::RequestCancelled)));
+        assert!(is_failure(&Error::from(ErrorCode::Timeout)));
+    }
+}
@@ -56,118 +56,20 @@ impl Client {
 
         let id = self.generate_id()?;
         let mut request = Request::new(
-            Some(id.clone()),
+            Some(id),
             crate::types::subscription::commands::LISTEN,
             Some(SubscriptionsListenRequestParams::new(notifications.clone())),
         );
 
         self.apply_client_meta(&mut request, None, None);
 
-        let handler = self
+        let mut link = self
             .handler
-            .as_mut()
-            .ok_or_else(|| Error::new(ErrorCode::InternalError, "Connection closed"))?;
-
-        // Watch for the acknowledgment before sending: it is the first thing
-        // the server puts on the stream, and the receive loop drops one nobody
-        // is waiting for.
-        let ack = handler.watch_ack(&id, &notifications);
-        let sender = handler.sender();
-        let release = handler.subscription_release();
-
-        // Armed before the send, not after it. `watch_ack` has already
-        // registered the waiter and the pending state, `send_listen` takes the
-        // untimed request slot before it awaits the transport, and that await
-        // is a suspension point like any other: a caller who drops this future
-        // (an outer `tokio::time::timeout`, a lost `select!` branch) runs none
-        // of the branches below, and everything registered so far would be left
-        // behind. Nothing between here and `watch_ack` awaits, so there is no
-        // gap left to fall into.
-        let guard =
-            subscription::EstablishmentGuard::new(id.clone(), release.clone(), sender.clone());
-
-        let mut response = match handler.send_listen(request).await {
-            Ok(response) => response,
-            // Never reached the wire, so there is no stream to cancel -- only
-            // this client's own bookkeeping to drop.
-            Err(err) => {
-                guard.forget();
-                return Err(err);
-            }
-        };
-
-        // Race the acknowledgment against the request's own reply: a peer that
-        // rejects the subscription outright -- `MethodNotFound`, an
-        // authorization failure, invalid params -- answers instead of
-        // acknowledging, and waiting only on the acknowledgment would sit out
-        // the whole timeout and report that instead of the server's reason.
-        let timeout = self.options.timeout;
-        let established = tokio::select! {
-            biased;
-            acknowledged = ack => Ok(acknowledged),
-            answered = &mut response => Err(match answered {
-                Ok(shared::PendingResponse::Response(resp)) => match resp {
-                    // An error reply is the server's own explanation; surface it.
-                    Response::Err(err) => err.error.into(),
-                    // A success reply this early is the graceful-close result
-                    // for a subscription that never carried anything.
-                    Response::Ok(_) => Error::new(
-                        ErrorCode::InternalError,
-                        "Subscription ended before it was acknowledged",
-                    ),
-                },
-                Ok(shared::PendingResponse::Timeout) => {
-                    Error::new(ErrorCode::Timeout, "Subscription was not acknowledged")
-                }
-                // The slot's sender was dropped: the receive loop released it
-                // on its way out, so the transport is gone. That is a lost
-                // connection, not a peer that would not acknowledge, and
-                // callers act on the two differently.
-                Err(_) => Error::new(ErrorCode::InternalError, "Connection closed"),
-            }),
-            _ = tokio::time::sleep(timeout) => Err(Error::new(
-                ErrorCode::Timeout,
-                "Subscription was not acknowledged",
-            )),
-        };
-
-        let acknowledged = match established {
-            Ok(Ok(filter)) => filter,
-            // The waiter's sender was dropped: the receive loop is gone.
-            Ok(Err(_)) => {
-                guard.abandon().await;
-                return Err(Error::new(ErrorCode::InternalError, "Connection closed"));
-            }
-            Err(err) => {
-                guard.abandon().await;
-                return Err(err);
-            }
-        };
-
-        // The server may narrow the filter -- that is the whole point of the
-        // acknowledgment -- but it must not widen it. Notifications are
-        // dispatched to the client's own handlers with no per-subscription
-        // filtering, so an acknowledgment claiming a category or URI this call
-        // never asked for would deliver events outside the requested scope.
-        if !acknowledged.is_subset_of(&notifications) {
-            guard.abandon().await;
-            return Err(Error::new(
-                ErrorCode::InvalidRequest,
-                "Server acknowledged a subscription broader than the one requested",
-            ));
-        }
+            .as_ref()
+            .ok_or_else(|| Error::new(ErrorCode::InternalError, "Connection closed"))?
+            .listen_link();
 
-        // The handle takes over from here.
-        guard.disarm();
-
-        Ok(Subscription::new(
-            id,
-            notifications,
-            acknowledged,
-            response,
-            sender,
-            release,
-        ))
+        establish(&mut link, request, notifications).await
     }
 
     /// Subscribes to a resource on the server to receive notifications when it changes.
@@ -236,6 +138,121 @@ impl Client {
     }
 }
 
+/// Sends a prepared `subscriptions/listen` request and waits for the server to
+/// acknowledge it.
+///
+/// Shared by [`Client::listen`] and the background re-establishment behind
+/// [`Client::listen_with_reconnect`], which has no client to borrow -- only the
+/// request it sent the first time, re-issued under a fresh id.
+#[cfg(not(feature = "legacy-spec"))]
+pub(super) async fn establish(
+    link: &mut handler::ListenLink,
+    request: Request,
+    notifications: SubscriptionFilter,
+) -> Result<Subscription, Error> {
+    let id = request.id();
+
+    // Watch for the acknowledgment before sending: it is the first thing
+    // the server puts on the stream, and the receive loop drops one nobody
+    // is waiting for.
+    let ack = link.watch_ack(&id, &notifications);
+    let sender = link.sender();
+    let release = link.subscription_release();
+
+    // Armed before the send, not after it. `watch_ack` has already
+    // registered the waiter and the pending state, `send_listen` takes the
+    // untimed request slot before it awaits the transport, and that await
+    // is a suspension point like any other: a caller who drops this future
+    // (an outer `tokio::time::timeout`, a lost `select!` branch) runs none
+    // of the branches below, and everything registered so far would be left
+    // behind. Nothing between here and `watch_ack` awaits, so there is no
+    // gap left to fall into.
+    let guard = subscription::EstablishmentGuard::new(id.clone(), release.clone(), sender.clone());
+
+    let mut response = match link.send_listen(request).await {
+        Ok(response) => response,
+        // Never reached the wire, so there is no stream to cancel -- only
+        // this client's own bookkeeping to drop.
+        Err(err) => {
+            guard.forget();
+            return Err(err);
+        }
+    };
+
+    // Race the acknowledgment against the request's own reply: a peer that
+    // rejects the subscription outright -- `MethodNotFound`, an
+    // authorization failure, invalid params -- answers instead of
+    // acknowledging, and waiting only on the acknowledgment would sit out
+    // the whole timeout and report that instead of the server's reason.
+    let timeout = link.timeout();
+    let established = tokio::select! {
+        biased;
+        acknowledged = ack => Ok(acknowledged),
+        answered = &mut response => Err(match answered {
+            Ok(shared::PendingResponse::Response(resp)) => match resp {
+                // An error reply is the server's own explanation; surface it.
+                Response::Err(err) => err.error.into(),
+                // A success reply this early is the graceful-close result
+                // for a subscription that never carried anything.
+                Response::Ok(_) => Error::new(
+                    ErrorCode::InternalError,
+                    "Subscription ended before it was acknowledged",
+                ),
+            },
+            Ok(shared::PendingResponse::Timeout) => {
+                Error::new(ErrorCode::Timeout, "Subscription was not acknowledged")
+            }
+            // The slot's sender was dropped: the receive loop released it
+            // on its way out, so the transport is gone. That is a lost
+            // connection, not a peer that would not acknowledge, and
+            // callers act on the two differently.
+            Err(_) => Error::new(ErrorCode::InternalError, "Connection closed"),
+        }),
+        _ = tokio::time::sleep(timeout) => Err(Error::new(
+            ErrorCode::Timeout,
+            "Subscription was not acknowledged",
+        )),
+    };
+
+    let acknowledged = match established {
+        Ok(Ok(filter)) => filter,
+        // The waiter's sender was dropped: the receive loop is gone.
+        Ok(Err(_)) => {
+            guard.abandon().await;
+            return Err(Error::new(ErrorCode::InternalError, "Connection closed"));
+        }
+        Err(err) => {
+            guard.abandon().await;
+            return Err(err);
+        }
+    };
+
+    // The server may narrow the filter -- that is the whole point of the
+    // acknowledgment -- but it must not widen it. Notifications are
+    // dispatched to the client's own handlers with no per-subscription
+    // filtering, so an acknowledgment claiming a category or URI this call
+    // never asked for would deliver events outside the requested scope.
+    if !acknowledged.is_subset_of(&notifications) {
+        guard.abandon().await;
+        return Err(Error::new(
+            ErrorCode::InvalidRequest,
+            "Server acknowledged a subscription broader than the one requested",
+        ));
+    }
+
+    // The handle takes over from here.
+    guard.disarm();
+
+    Ok(Subscription::new(
+        id,
+        notifications,
+        acknowledged,
+        response,
+        sender,
+        release,
+    ))
+}
+
 /// Establishing a subscription against a peer that misbehaves: answering
 /// instead of acknowledging, or acknowledging more than was asked for. Driven
 /// by a raw-HTTP mock, since a real neva server produces neither.
@@ -405,7 +422,12 @@ mod listen_rejection_tests {
         use crate::transport::Sender as _;
 
         let id = RequestId::Number(99);
-        let mut sender = client.handler.as_ref().expect("connected").sender();
+        let mut sender = client
+            .handler
+            .as_ref()
+            .expect("connected")
+            .listen_link()
+            .sender();
 
         let listen = Request::new(
             Some(id.clone()),
@@ -145,8 +145,9 @@ impl Client {
             meta.baggage = tc.baggage;
         }
 
-        // Request-scoped logging level (replaces the removed `logging/setLevel`).
-        if self.options.log_level.is_some() {
+        // Request-scoped logging level (replaces the removed `logging/setLevel`),
+        // unless the request asks for one of its own.
+        if meta.log_level.is_none() {
             meta.log_level = self.options.log_level;
         }
 
@@ -289,8 +290,9 @@ impl Client {
             let request_timeout = handler.timeout();
             let pending = handler.pending().clone();
             let token = handler.cancellation();
+            let breaker = handler.circuit_breaker();
             let receivers = handler.send_batch(extras).await?;
-            return collect_batch_responses(receivers, &pending, request_timeout, token)
+            return collect_batch_responses(receivers, &pending, request_timeout, token, breaker)
                 .await
                 .into_iter()
                 .collect();
@@ -329,9 +331,10 @@ impl Client {
             let request_timeout = handler.timeout();
             let pending = handler.pending().clone();
             let token = handler.cancellation();
+            let breaker = handler.circuit_breaker();
             let receivers = handler.send_batch(envelopes).await?;
             let responses =
-                collect_batch_responses(receivers, &pending, request_timeout, token).await;
+                collect_batch_responses(receivers, &pending, request_timeout, token, breaker).await;
 
             // `responses` aligns with `round_slots`: `send_batch` preserves
             // request order and extras produce no receiver. Final responses fill
@@ -0,0 +1,733 @@
+//! Sampling through an OpenAI-compatible chat-completions endpoint.
+//!
+//! Most local and hosted model servers -- vLLM, the llama.cpp server, Ollama --
+//! speak the OpenAI `POST /chat/completions` API. [`OpenAiSampler`] answers
+//! `sampling/createMessage` requests, and MRTR sampling input requests, by
+//! translating them into a chat completion and the completion back into a
+//! [`CreateMessageResult`].
+//!
+//! # Example
+//! ```no_run
+//! use neva::client::{Client, OpenAiSampler};
+//!
+//! # #[allow(deprecated)]
+//! # fn configure(client: &mut Client) {
+//! let sampler = OpenAiSampler::new("http://localhost:11434/v1")
+//!     .with_model("llama3.2")
+//!     .with_models(["llama3.2", "qwen2.5-coder", "mistral-small"]);
+//!
+//! client.map_sampling(sampler.handler());
+//! # }
+//! ```
+
+use crate::error::{Error, ErrorCode};
+use crate::shared::BoxFuture;
+use crate::types::sampling::{
+    CreateMessageRequestParams, CreateMessageResult, ModelPreferences, SamplingMessage, StopReason,
+    ToolChoiceMode,
+};
+use crate::types::{Content, ResourceContents, Role, ToolResult, ToolUse};
+use base64::{Engine, engine::general_purpose};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::time::Duration;
+
+/// The stop reason of a sampling request the endpoint could not complete.
+///
+/// A sampling handler has no way to fail the request, so
+/// [`OpenAiSampler::handler`] reports a failed completion as a result that
+/// carries no model output and says why it stopped.
+pub const FAILED: &str = "error";
+
+/// A sampler that forwards sampling requests to an OpenAI-compatible
+/// chat-completions endpoint.
+///
+/// # Mapping
+/// * The system prompt becomes a leading `system` message, and each sampling
+///   message a `user` or `assistant` message. Text, images, audio, resource
+///   links and text resources are sent as content parts; tool uses become
+///   `tool_calls` and tool results `tool` messages.
+/// * `maxTokens`, `temperature` and `stopSequences` map onto `max_tokens`,
+///   `temperature` and `stop`; tools onto `function` tools and the tool choice
+///   onto `tool_choice`.
+/// * The model is picked by the request's [`ModelPreferences`]: the first hint
+///   that names one of the [known models](Self::with_models) -- hints match
+///   as case-insensitive substrings, as the specification asks -- or else the
+///   [default model](Self::with_model). Priorities are not weighed.
+/// * The response's text and tool calls become the result's content, and its
+///   `finish_reason` the stop reason: `stop` is `endTurn`, `length` is
+///   `maxTokens` and `tool_calls` is `toolUse`; anything else is passed on as
+///   it is.
+#[derive(Clone)]
+pub struct OpenAiSampler {
+    http: reqwest::Client,
+    endpoint: String,
+    api_key: Option<String>,
+    model: Option<String>,
+    models: Vec<String>,
+    timeout: Option<Duration>,
+}
+
+impl Debug for OpenAiSampler {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OpenAiSampler")
+            .field("endpoint", &self.endpoint)
+            .field("api_key", &self.api_key.is_some())
+            .field("model", &self.model)
+            .field("models", &self.models)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+#[derive(Deserialize)]
+struct Completion {
+    #[serde(default)]
+    model: String,
+    choices: Vec<Choice>,
+}
+
+#[derive(Deserialize)]
+struct Choice {
+    message: ChoiceMessage,
+    finish_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ChoiceMessage {
+    content: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<ToolCall>,
+}
+
+#[derive(Deserialize)]
+struct ToolCall {
+    id: String,
+    function: FunctionCall,
+}
+
+#[derive(Deserialize)]
+struct FunctionCall {
+    name: String,
+    #[serde(default)]
+    arguments: String,
+}
+
+impl OpenAiSampler {
+    /// Creates a sampler for the API at `base_url`, e.g.
+    /// `http://localhost:8000/v1`; requests go to its `/chat/completions`.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        let base_url = base_url.into();
+        Self {
+            http: reqwest::Client::new(),
+            endpoint: format!("{}/chat/completions", base_url.trim_end_matches('/')),
+            api_key: None,
+            model: None,
+            models: Vec::new(),
+            timeout: None,
+        }
+    }
+
+    /// Sends `key` as a bearer token with every request.
+    ///
+    /// Default: no `Authorization` header, which is what local servers expect.
+    pub fn with_api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_key = Some(key.into());
+        self
+    }
+
+    /// Sets the model a request is sent to when none of its hints names a
+    /// [known model](Self::with_models).
+    ///
+    /// Default: the first known model or, with none, the request's first hint.
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// Sets the models the endpoint serves, which the hints of a request's
+    /// [`ModelPreferences`] are matched against.
+    ///
+    /// Default: none, so hints are not matched.
+    pub fn with_models<T, I>(mut self, models: I) -> Self
+    where
+        T: Into<String>,
+        I: IntoIterator<Item = T>,
+    {
+        self.models = models.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets how long a completion may take before it fails.
+    ///
+    /// Default: no limit.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sends a sampling request as a chat completion and returns the model's
+    /// response.
+    pub async fn create_message(
+        &self,
+        params: CreateMessageRequestParams,
+    ) -> Result<CreateMessageResult, Error> {
+        let body = self.request_body(&params)?;
+
+        let mut request = self.http.post(&self.endpoint).json(&body);
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+        if let Some(timeout) = self.timeout {
+            request = request.timeout(timeout);
+        }
+
+        let transport = |err: reqwest::Error| Error::new(ErrorCode::InternalError, err.to_string());
+        let resp = request.send().await.map_err(transport)?;
+        let status = resp.status();
+        let text = resp.text().await.map_err(transport)?;
+        if !status.is_success() {
+            return Err(Error::new(
+                ErrorCode::InternalError,
+                format!(
+                    "chat completion failed with {status}: {}",
+                    error_message(&text)
+                ),
+            ));
+        }
+
+        let completion = serde_json::from_str(&text).map_err(|err| {
+            Error::new(
+                ErrorCode::ParseError,
+                format!("invalid chat completion: {err}"),
+            )
+        })?;
+        into_result(completion)
+    }
+
+    /// A sampling handler for [`Client::map_sampling`](crate::client::Client::map_sampling)
+    /// that calls [`create_message`](Self::create_message).
+    ///
+    /// A completion that fails comes back as a result with the [`FAILED`]
+    /// stop reason and no content.
+    pub fn handler(
+        &self,
+    ) -> impl Fn(CreateMessageRequestParams) -> BoxFuture<'static, CreateMessageResult>
+    + Clone
+    + Send
+    + Sync
+    + 'static {
+        let sampler = self.clone();
+        move |params| {
+            let sampler = sampler.clone();
+            Box::pin(async move {
+                sampler.create_message(params).await.unwrap_or_else(|_err| {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(
+                        logger = "neva",
+                        "Sampling through chat completions failed: {_err}"
+                    );
+                    CreateMessageResult::assistant().with_stop_reason(FAILED)
+                })
+            })
+        }
+    }
+
+    /// Picks the model a request is sent to.
+    fn pick_model(&self, pref: Option<&ModelPreferences>) -> Result<String, Error> {
+        let hints: Vec<&str> = pref
+            .and_then(|pref| pref.hints.as_deref())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|hint| hint.name.as_deref())
+            .collect();
+
+        let matched = hints.iter().find_map(|hint| {
+            let hint = hint.to_lowercase();
+            self.models
+                .iter()
+                .find(|model| model.to_lowercase().contains(&hint))
+        });
+        matched
+            .or(self.model.as_ref())
+            .or(self.models.first())
+            .cloned()
+            .or_else(|| hints.first().map(|hint| hint.to_string()))
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorCode::InvalidParams,
+                    "no model to sample with: configure one with `OpenAiSampler::with_model`",
+                )
+            })
+    }
+
+    /// Builds the chat-completions request body for a sampling request.
+    fn request_body(&self, params: &CreateMessageRequestParams) -> Result<Value, Error> {
+        let mut messages = Vec::new();
+        if let Some(sys_prompt) = &params.sys_prompt {
+            messages.push(json!({ "role": "system", "content": sys_prompt }));
+        }
+        for message in &params.messages {
+            push_message(&mut messages, message);
+        }
+
+        let mut body = json!({
+            "model": self.pick_model(params.model_pref.as_ref())?,
+            "messages": messages,
+            "max_tokens": params.max_tokens,
+            "stream": false,
+        });
+        if let Some(temp) = params.temp {
+            body["temperature"] = json!(temp);
+        }
+        if let Some(stop) = &params.stop_sequences {
+            body["stop"] = json!(stop);
+        }
+        if let Some(tools) = params.tools.as_deref().filter(|tools| !tools.is_empty()) {
+            let tools = tools
+                .iter()
+                .map(|tool| {
+                    let mut function = json!({
+                        "name": tool.name,
+                        "parameters": serde_json::to_value(&tool.input_schema)?,
+                    });
+                    if let Some(descr) = &tool.descr {
+                        function["description"] = json!(descr);
+                    }
+                    Ok(json!({ "type": "function", "function": function }))
+                })
+                .collect::<Result<Vec<_>, serde_json::Error>>()?;
+            body["tools"] = Value::Array(tools);
+        }
+        if let Some(choice) = &params.tool_choice {
+            body["tool_choice"] = json!(match choice.mode {
+                ToolChoiceMode::Auto => "auto",
+                ToolChoiceMode::Required => "required",
+                ToolChoiceMode::None => "none",
+            });
+        }
+        Ok(body)
+    }
+}
+
+/// Appends the chat messages a sampling message maps onto.
+///
+/// Tool results cannot share a message with anything else in the chat
+/// format, so each becomes a `tool` message ahead of the rest of the turn.
+fn push_message(messages: &mut Vec<Value>, message: &SamplingMessage) {
+    let mut parts = Vec::new();
+    let mut tool_calls = Vec::new();
+    for content in message.content.iter() {
+        match content {
+            Content::ToolUse(tool_use) => tool_calls.push(tool_call(tool_use)),
+            Content::ToolResult(result) => messages.push(json!({
+                "role": "tool",
+                "tool_call_id": result.tool_use_id,
+                "content": tool_result_text(result),
+            })),
+            content => parts.extend(content_part(content)),
+        }
+    }
+
+    match message.role {
+        Role::Assistant => {
+            if parts.is_empty() && tool_calls.is_empty() {
+                return;
+            }
+            // Assistant content is plain text in the chat format.
+            let text: Vec<&str> = parts
+                .iter()
+                .filter_map(|part| part["text"].as_str())
+                .collect();
+            let mut chat = json!({
+                "role": "assistant",
+                "content": if text.is_empty() { Value::Null } else { json!(text.join("\n\n")) },
+            });
+            if !tool_calls.is_empty() {
+                chat["tool_calls"] = Value::Array(tool_calls);
+            }
+            messages.push(chat);
+        }
+        Role::User => {
+            let content = match parts.as_slice() {
+                [] => return,
+                [part] if part["type"] == "text" => part["text"].clone(),
+                _ => Value::Array(parts),
+            };
+            messages.push(json!({ "role": "user", "content": content }));
+        }
+    }
+}
+
+/// A content item as a chat content part; `None` for what has no part.
+fn content_part(content: &Content) -> Option<Value> {
+    match content {
+        Content::Text(text) => Some(text_part(&text.text)),
+        Content::Image(image) => Some(image_part(&image.mime, &image.data)),
+        Content::Audio(audio) => {
+            let format = match audio.mime.as_str() {
+                "audio/mpeg" | "audio/mp3" => "mp3",
+                "audio/wav" | "audio/x-wav" | "audio/wave" => "wav",
+                mime => mime.rsplit('/').next().unwrap_or(mime),
+            };
+            Some(json!({
+                "type": "input_audio",
+                "input_audio": {
+                    "data": general_purpose::STANDARD.encode(&audio.data),
+                    "format": format,
+                },
+            }))
+        }
+        Content::ResourceLink(link) => Some(text_part(&format!("{} <{}>", link.name, link.uri))),
+        Content::Resource(embedded) => match &embedded.resource {
+            ResourceContents::Blob(blob) if blob.mime.as_deref().is_some_and(is_image) => {
+                let data = general_purpose::STANDARD.decode(&blob.blob).ok()?;
+                Some(image_part(blob.mime.as_deref().unwrap_or_default(), &data))
+            }
+            ResourceContents::Text(text) => Some(text_part(&text.text)),
+            ResourceContents::Json(json) => Some(text_part(&json.value.to_string())),
+            ResourceContents::Blob(_)
+            | ResourceContents::Empty(_)
+            | ResourceContents::Stream(_) => None,
+        },
+        Content::ToolUse(_) | Content::ToolResult(_) | Content::Empty(_) => None,
+    }
+}
+
+fn text_part(text: &str) -> Value {
+    json!({ "type": "text", "text": text })
+}
+
+fn image_part(mime: &str, data: &[u8]) -> Value {
+    let data = general_purpose::STANDARD.encode(data);
+    json!({ "type": "image_url", "image_url": { "url": format!("data:{mime};base64,{data}") } })
+}
+
+fn is_image(mime: &str) -> bool {
+    mime.starts_with("image/")
+}
+
+fn tool_call(tool_use: &ToolUse) -> Value {
+    let arguments = tool_use
+        .input
+        .as_ref()
+        .map_or_else(|| "{}".to_owned(), |input| json!(input).to_string());
+    json!({
+        "id": tool_use.id,
+        "type": "function",
+        "function": { "name": tool_use.name, "arguments": arguments },
+    })
+}
+
+/// A tool result as the text of a `tool` message: its text content, or its
+/// structured content when it has no text.
+fn tool_result_text(result: &ToolResult) -> String {
+    let text: Vec<&str> = result
+        .content
+        .iter()
+        .filter_map(|content| content.as_text().map(|text| text.text.as_str()))
+        .collect();
+    match (&result.struct_content, text.is_empty()) {
+        (Some(structured), true) => structured.to_string(),
+        _ => text.join("\n\n"),
+    }
+}
+
+/// Maps a chat completion onto a sampling result.
+fn into_result(completion: Completion) -> Result<CreateMessageResult, Error> {
+    let Some(choice) = completion.choices.into_iter().next() else {
+        return Err(Error::new(
+            ErrorCode::ParseError,
+            "invalid chat completion: no choices",
+        ));
+    };
+
+    let mut result = CreateMessageResult::assistant().with_model(completion.model);
+    if let Some(text) = choice.message.content.filter(|text| !text.is_empty()) {
+        result = result.with_content(text);
+    }
+    for call in choice.message.tool_calls {
+        let input = match call.function.arguments.trim() {
+            "" => None,
+            arguments => Some(
+                serde_json::from_str::<HashMap<String, Value>>(arguments).map_err(|err| {
+                    Error::new(
+                        ErrorCode::ParseError,
+                        format!("invalid arguments for `{}`: {err}", call.function.name),
+                    )
+                })?,
+            ),
+        };
+        result = result.with_content(ToolUse {
+            id: call.id,
+            name: call.function.name,
+            input,
+            meta: None,
+        });
+    }
+
+    if let Some(reason) = choice.finish_reason {
+        result = result.with_stop_reason(match reason.as_str() {
+            "stop" => StopReason::EndTurn,
+            "length" => StopReason::MaxTokens,
+            "tool_calls" | "function_call" => StopReason::ToolUse,
+            _ => StopReason::Other(reason),
+        });
+    }
+    Ok(result)
+}
+
+/// The message of an OpenAI-style error body, or the body itself.
+fn error_message(body: &str) -> String {
+    serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|body| body["error"]["message"].as_str().map(str::to_owned))
+        .unwrap_or_else(|| body.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::sampling::ModelHint;
+    use crate::types::{CallToolResponse, ImageContent, Tool};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn sampler() -> OpenAiSampler {
+        OpenAiSampler::new("http://localhost:8000/v1/")
+            .with_model("llama3.2")
+            .with_models(["llama3.2", "qwen2.5-coder:7b", "mistral-small"])
+    }
+
+    #[test]
+    fn its_debug_output_hides_the_api_key() {
+        let shown = format!("{:?}", sampler().with_api_key("sk-secret"));
+
+        assert!(!shown.contains("sk-secret"), "got: {shown}");
+        assert!(shown.contains("api_key: true"), "got: {shown}");
+    }
+
+    #[test]
+    fn it_maps_messages_and_options() {
+        let params = CreateMessageRequestParams::new()
+            .with_sys_prompt("Be brief.")
+            .with_message("What is in this picture?")
+            .with_message(SamplingMessage::user().with("Look:").with(ImageContent {
+                data: vec![1, 2, 3].into(),
+                mime: "image/png".into(),
+                annotations: None,
+                meta: None,
+            }))
+            .with_max_tokens(256)
+            .with_temp(0.5)
+            .with_stop_seq(vec!["END".into()]);
+
+        let body = sampler().request_body(&params).unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "model": "llama3.2",
+                "messages": [
+                    { "role": "system", "content": "Be brief." },
+                    { "role": "user", "content": "What is in this picture?" },
+                    { "role": "user", "content": [
+                        { "type": "text", "text": "Look:" },
+                        { "type": "image_url", "image_url": { "url": "data:image/png;base64,AQID" } },
+                    ] },
+                ],
+                "max_tokens": 256,
+                "temperature": 0.5,
+                "stop": ["END"],
+                "stream": false,
+            })
+        );
+    }
+
+    #[test]
+    fn it_picks_the_model_by_hint() {
+        let sampler = sampler();
+        let pref = ModelPreferences::new().with_hints(["claude", "QWEN", "mistral"]);
+        assert_eq!(sampler.pick_model(Some(&pref)).unwrap(), "qwen2.5-coder:7b");
+
+        let pref = ModelPreferences::new().with_hint(ModelHint::new("gpt-4o"));
+        assert_eq!(sampler.pick_model(Some(&pref)).unwrap(), "llama3.2");
+
+        let bare = OpenAiSampler::new("http://localhost:8000/v1");
+        assert_eq!(bare.pick_model(Some(&pref)).unwrap(), "gpt-4o");
+        assert_eq!(
+            bare.pick_model(None).unwrap_err().code,
+            ErrorCode::InvalidParams
+        );
+    }
+
+    #[test]
+    fn it_maps_tools_and_a_tool_round_trip() {
+        let tool_use = ToolUse::new("get_weather", [("city", "Paris")]);
+        let id = tool_use.id.clone();
+        let params = CreateMessageRequestParams::new()
+            .with_message("Weather in Paris?")
+            .with_message(SamplingMessage::assistant().with(tool_use))
+            .with_message(SamplingMessage::user().with(ToolResult::new(
+                id.clone(),
+                CallToolResponse::new("Sunny, 21C"),
+            )))
+            .with_tools([Tool::new("get_weather", async |city: String| city)])
+            .with_tool_choice(ToolChoiceMode::Required);
+
+        let body = sampler().request_body(&params).unwrap();
+        let messages = body["messages"].as_array().unwrap();
+        assert_eq!(
+            messages[1],
+            json!({
+                "role": "assistant",
+                "content": null,
+                "tool_calls": [{
+                    "id": id,
+                    "type": "function",
+                    "function": { "name": "get_weather", "arguments": "{\"city\":\"Paris\"}" },
+                }],
+            })
+        );
+        assert_eq!(
+            messages[2],
+            json!({ "role": "tool", "tool_call_id": id, "content": "Sunny, 21C" })
+        );
+        assert_eq!(messages.len(), 3);
+
+        assert_eq!(body["tools"][0]["type"], "function");
+        assert_eq!(body["tools"][0]["function"]["name"], "get_weather");
+        assert_eq!(body["tools"][0]["function"]["parameters"]["type"], "object");
+        assert_eq!(body["tool_choice"], "required");
+    }
+
+    #[test]
+    fn it_maps_a_completion_with_tool_calls() {
+        let completion = serde_json::from_value(json!({
+            "model": "qwen2.5-coder:7b",
+            "choices": [{
+                "message": {
+                    "role": "assistant",
+                    "content": "Let me check.",
+                    "tool_calls": [{
+                        "id": "call_1",
+                        "type": "function",
+                        "function": { "name": "get_weather", "arguments": "{\"city\":\"Paris\"}" },
+                    }],
+                },
+                "finish_reason": "tool_calls",
+            }],
+        }))
+        .unwrap();
+
+        let result = into_result(completion).unwrap();
+        assert_eq!(result.model, "qwen2.5-coder:7b");
+        assert_eq!(result.stop_reason, Some(StopReason::ToolUse));
+        assert_eq!(result.text().next().unwrap().text, "Let me check.");
+
+        let tool_use = result.tools().next().unwrap();
+        assert_eq!(tool_use.id, "call_1");
+        assert_eq!(tool_use.input.as_ref().unwrap()["city"], "Paris");
+    }
+
+    #[test]
+    fn it_maps_finish_reasons() {
+        let reason = |finish_reason: &str| {
+            let completion = serde_json::from_value(json!({
+                "choices": [{ "message": { "content": "..." }, "finish_reason": finish_reason }],
+            }))
+            .unwrap();
+            into_result(completion).unwrap().stop_reason.unwrap()
+        };
+
+        assert_eq!(reason("stop"), StopReason::EndTurn);
+        assert_eq!(reason("length"), StopReason::MaxTokens);
+        assert_eq!(
+            reason("content_filter"),
+            StopReason::Other("content_filter".into())
+        );
+    }
+
+    /// A chat-completions endpoint answering every request with `status` and
+    /// `body`, and recording the requests it got.
+    async fn spawn_endpoint(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, Arc<std::sync::Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorder = seen.clone();
+
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = [0u8; 16384];
+                let read = stream.read(&mut buf).await.unwrap_or(0);
+                recorder
+                    .lock()
+                    .unwrap()
+                    .push(String::from_utf8_lossy(&buf[..read]).to_string());
+
+                let resp = format!(
+                    "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = stream.write_all(resp.as_bytes()).await;
+            }
+        });
+        (format!("http://{addr}/v1"), seen)
+    }
+
+    #[tokio::test]
+    async fn it_sends_a_completion_request() {
+        let (base_url, seen) = spawn_endpoint(
+            "200 OK",
+            r#"{"model":"llama3.2","choices":[{"message":{"role":"assistant","content":"Paris."},"finish_reason":"stop"}]}"#,
+        )
+        .await;
+
+        let sampler = OpenAiSampler::new(base_url)
+            .with_model("llama3.2")
+            .with_api_key("sk-test");
+        let result = sampler
+            .create_message(CreateMessageRequestParams::new().with_message("Capital of France?"))
+            .await
+            .unwrap();
+        assert_eq!(result.text().next().unwrap().text, "Paris.");
+        assert_eq!(result.stop_reason, Some(StopReason::EndTurn));
+
+        let request = seen.lock().unwrap()[0].to_lowercase();
+        assert!(
+            request.starts_with("post /v1/chat/completions "),
+            "got: {request}"
+        );
+        assert!(
+            request.contains("authorization: bearer sk-test"),
+            "got: {request}"
+        );
+    }
+
+    #[tokio::test]
+    async fn a_failed_completion_is_an_error_or_a_failed_result() {
+        let (base_url, _) = spawn_endpoint(
+            "404 Not Found",
+            r#"{"error":{"message":"model 'llama9' not found"}}"#,
+        )
+        .await;
+        let sampler = OpenAiSampler::new(base_url).with_model("llama9");
+        let params = CreateMessageRequestParams::new().with_message("Hi");
+
+        let err = sampler.create_message(params.clone()).await.unwrap_err();
+        assert!(
+            err.to_string().contains("model 'llama9' not found"),
+            "got: {err}"
+        );
+
+        let result = sampler.handler()(params).await;
+        assert_eq!(result.stop_reason, Some(StopReason::Other(FAILED.into())));
+        assert!(result.content.is_empty());
+    }
+}
@@ -1,7 +1,10 @@
 //! MCP client options
 
 use crate::PROTOCOL_VERSIONS;
+use crate::client::health::{CircuitBreaker, CircuitBreakerOptions};
 use crate::client::notification_handler::NotificationsHandler;
+use crate::transport::record::{Peer, Recorder, Tap};
+use crate::transport::replay::Replay;
 use crate::transport::{StdIoClient, TransportProto, stdio::options::StdIoOptions};
 use crate::types::SamplingCapability;
 use crate::types::elicitation::ElicitationHandler;
@@ -61,6 +64,10 @@ pub struct McpOptions {
     #[cfg(all(feature = "http-client", not(feature = "legacy-spec")))]
     pub(crate) rejected_tools: std::collections::HashSet<String>,
 
+    /// Tool schemas compiled from `tools/list`, and whether calls are checked
+    /// against them
+    pub(crate) tool_schemas: super::schemas::ToolSchemas,
+
     /// Request timeout
     pub(super) timeout: Duration,
 
@@ -92,6 +99,9 @@ pub struct McpOptions {
     /// Current transport protocol that the server uses
     proto: Option<TransportProto>,
 
+    /// Records the traffic of the transport, if set
+    recorder: Option<Recorder>,
+
     /// Represents a list of roots that the client supports
     roots: HashMap<Uri, Root>,
 
@@ -116,6 +126,9 @@ pub struct McpOptions {
     /// the removed global `logging/setLevel`.
     #[cfg(not(feature = "legacy-spec"))]
     pub(crate) log_level: Option<crate::types::notification::LoggingLevel>,
+
+    /// Circuit breaker guarding requests to the server, if one is configured.
+    pub(super) circuit_breaker: Option<Arc<CircuitBreaker>>,
 }
 
 impl Debug for McpOptions {
@@ -132,7 +145,9 @@ impl Debug for McpOptions {
 
         let dbg = dbg.field("protocol_ver", &self.protocol_ver);
 
-        let dbg = dbg.field("roots", &self.roots);
+        let dbg = dbg
+            .field("roots", &self.roots)
+            .field("circuit_breaker", &self.circuit_breaker);
 
         #[cfg(feature = "tasks")]
         dbg.field("tasks_capability", &self.tasks_capability);
@@ -151,6 +166,7 @@ impl Default for McpOptions {
             param_headers: Default::default(),
             #[cfg(all(feature = "http-client", not(feature = "legacy-spec")))]
             rejected_tools: Default::default(),
+            tool_schemas: Default::default(),
             roots: Default::default(),
             roots_capability: None,
             sampling_capability: None,
@@ -158,6 +174,7 @@ impl Default for McpOptions {
             #[cfg(feature = "tasks")]
             tasks_capability: None,
             proto: None,
+            recorder: None,
             protocol_ver: None,
             sampling_handler: None,
             elicitation_handler: None,
@@ -170,6 +187,7 @@ impl Default for McpOptions {
             peer_mode: Default::default(),
             #[cfg(not(feature = "legacy-spec"))]
             log_level: None,
+            circuit_breaker: None,
         }
     }
 }
@@ -206,6 +224,33 @@ impl McpOptions {
         self.with_http(|http| http)
     }
 
+    /// Sets the transport protocol directly.
+    #[inline]
+    #[cfg(feature = "testing")]
+    pub(crate) fn with_transport(mut self, proto: TransportProto) -> Self {
+        self.proto = Some(proto);
+        self
+    }
+
+    /// Plays `replay` in place of a server: the client talks to a mock that
+    /// answers from the recording and checks the client's requests against it.
+    ///
+    /// Call [`Replay::finish`] once done to learn whether the client diverged.
+    pub fn with_replay(mut self, replay: &Replay) -> Self {
+        self.proto = Some(TransportProto::Replay(replay.serve()));
+        self
+    }
+
+    /// Records every message the transport sends or receives.
+    ///
+    /// See [`crate::transport::record`] for the format.
+    ///
+    /// Default: not recorded
+    pub fn with_recorder(mut self, recorder: Recorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
     /// Specifies MCP client name
     pub fn with_name(mut self, name: &str) -> Self {
         self.implementation.name = name.into();
@@ -298,6 +343,75 @@ impl McpOptions {
         self
     }
 
+    /// Enables the circuit breaker and health tracking for the connected server.
+    ///
+    /// Requests sent while the circuit is open fail fast with
+    /// [`ErrorCode::CircuitOpen`](crate::error::ErrorCode::CircuitOpen). The
+    /// current state is available from [`Client::health`](crate::Client::health).
+    /// See [`health`](crate::client::health) for how it trips and recovers.
+    ///
+    /// Default: disabled
+    pub fn with_circuit_breaker<T>(mut self, config: T) -> Self
+    where
+        T: FnOnce(CircuitBreakerOptions) -> CircuitBreakerOptions,
+    {
+        self.circuit_breaker = Some(Arc::new(CircuitBreaker::new(config(Default::default()))));
+        self
+    }
+
+    /// Checks the arguments of every [`Client::call_tool`](crate::Client::call_tool)
+    /// against the tool's `inputSchema` before the call is sent.
+    ///
+    /// A call that breaks the schema fails with `InvalidParams` without
+    /// reaching the server; its `data.errors` lists each violation by JSON
+    /// pointer. Schemas are compiled once per `tools/list` and checked against
+    /// until a later listing replaces them. A call refused by a schema whose
+    /// listing's `ttlMs` has run out lists the tools again and is checked
+    /// against the new one; a tool never listed is not checked.
+    ///
+    /// Default: off
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use neva::Client;
+    ///
+    /// let client = Client::new().with_options(|opt| opt.with_input_validation());
+    /// # let _ = client;
+    /// ```
+    pub fn with_input_validation(mut self) -> Self {
+        self.tool_schemas.input = true;
+        self
+    }
+
+    /// Checks the `structuredContent` of every
+    /// [`Client::call_tool`](crate::Client::call_tool) result against the tool's
+    /// `outputSchema`.
+    ///
+    /// A result that breaks the schema, or a tool that declares one and
+    /// returns no structured content, fails the call with `ParseError`; its
+    /// `data.errors` lists each violation by JSON pointer. Results flagged
+    /// `isError` are not checked. Schemas are compiled once per `tools/list`
+    /// and checked against until a later listing replaces them. A result
+    /// refused by a schema whose listing's `ttlMs` has run out lists the tools
+    /// again and is checked against the new one; a tool never listed is not
+    /// checked.
+    ///
+    /// Default: off
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use neva::Client;
+    ///
+    /// let client = Client::new().with_options(|opt| opt.with_output_validation());
+    /// # let _ = client;
+    /// ```
+    pub fn with_output_validation(mut self) -> Self {
+        self.tool_schemas.output = true;
+        self
+    }
+
     /// Specifies request timeout
     ///
     /// Default: 10 seconds
@@ -400,7 +514,12 @@ impl McpOptions {
             )),
             other => other,
         };
-        transport
+        match self.recorder.take() {
+            Some(recorder) => {
+                TransportProto::Recorded(Box::new(transport), Tap::new(recorder, Peer::Client))
+            }
+            None => transport,
+        }
     }
 
     /// The newest legacy protocol version -- what the dual-mode fallback
@@ -0,0 +1,678 @@
+//! Keeping a `subscriptions/listen` stream open across dropped connections.
+//!
+//! A [`Subscription`] ends for good when its stream goes away: MCP 2026-07-28
+//! removed `Last-Event-ID`, so there is no position to resume from and the only
+//! way back is a fresh `subscriptions/listen`. [`Client::listen_with_reconnect`]
+//! does that on the caller's behalf -- it re-issues the same filter with
+//! backoff whenever the stream ends abruptly -- and, because whatever the server
+//! sent while nothing was listening is lost, says so: a
+//! [`SubscriptionEvent::ResyncNeeded`] on the handle, and a replayed
+//! `list_changed` for every category the new stream acknowledged, so handlers
+//! that re-fetch a listing on change re-fetch the one they may have missed.
+
+use super::handler::ListenLink;
+use super::listen::establish;
+use super::{Client, Subscription, SubscriptionEnd};
+use crate::error::{Error, ErrorCode};
+use crate::types::{
+    Request, RequestId, SUBSCRIPTION_ID_KEY, SubscriptionFilter, SubscriptionsListenRequestParams,
+    notification::Notification,
+};
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// How [`Client::listen_with_reconnect`] re-establishes a dropped stream.
+///
+/// The delay before each attempt doubles from
+/// [`initial_backoff`](Self::with_initial_backoff) up to
+/// [`max_backoff`](Self::with_max_backoff). Attempts are unlimited unless
+/// capped with [`Self::with_max_attempts`]; the count starts over once a stream
+/// is acknowledged again.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+/// use neva::client::reconnect::ReconnectPolicy;
+///
+/// let policy = ReconnectPolicy::new()
+///     .with_initial_backoff(Duration::from_millis(100))
+///     .with_max_backoff(Duration::from_secs(5))
+///     .with_max_attempts(10);
+///
+/// assert_eq!(policy.backoff(1), Duration::from_millis(100));
+/// assert_eq!(policy.backoff(2), Duration::from_millis(200));
+/// assert_eq!(policy.backoff(20), Duration::from_secs(5));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    max_attempts: Option<u32>,
+    replay_list_changed: bool,
+}
+
+impl Default for ReconnectPolicy {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            max_attempts: None,
+            replay_list_changed: true,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Creates a policy with the defaults: 250 ms doubling up to 30 s, retried
+    /// for as long as the connection is up.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the delay before the first attempt.
+    ///
+    /// Default: 250 ms
+    pub fn with_initial_backoff(mut self, delay: Duration) -> Self {
+        self.initial_backoff = delay;
+        self
+    }
+
+    /// Sets the ceiling the doubling delay stops at.
+    ///
+    /// Default: 30 seconds
+    pub fn with_max_backoff(mut self, delay: Duration) -> Self {
+        self.max_backoff = delay;
+        self
+    }
+
+    /// Gives up after `attempts` consecutive failures to re-establish the
+    /// stream, ending the subscription as [`SubscriptionEnd::Abrupt`].
+    ///
+    /// Default: unlimited
+    pub fn with_max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = Some(attempts);
+        self
+    }
+
+    /// Whether a re-established stream replays a `list_changed` to the
+    /// registered handlers for every list category it acknowledged, and a
+    /// `resources/updated` for every resource URI.
+    ///
+    /// Default: `true`. Turn it off when the handlers are not idempotent and
+    /// [`SubscriptionEvent::ResyncNeeded`] is handled on its own instead.
+    pub fn with_replay_list_changed(mut self, replay: bool) -> Self {
+        self.replay_list_changed = replay;
+        self
+    }
+
+    /// Returns the delay before the `attempt`-th (1-based) re-establishment.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    /// Whether `attempt` is past the configured cap.
+    #[inline]
+    fn exhausted(&self, attempt: u32) -> bool {
+        self.max_attempts.is_some_and(|max| attempt > max)
+    }
+}
+
+/// What happened to a [`ResilientSubscription`] between its streams.
+#[derive(Debug)]
+pub enum SubscriptionEvent {
+    /// The stream ended abruptly and a new one is about to be requested.
+    Reconnecting {
+        /// 1-based count of consecutive attempts.
+        attempt: u32,
+        /// How long the supervisor waits before this attempt.
+        delay: Duration,
+        /// Why the previous attempt failed, if this is a retry of one.
+        error: Option<Error>,
+    },
+
+    /// A new stream is acknowledged, and whatever the server sent while none
+    /// was open is lost: any list or resource it covers may be stale.
+    ResyncNeeded {
+        /// The subscription id of the stream that dropped.
+        previous: RequestId,
+        /// The subscription id of the stream that replaced it.
+        subscription: RequestId,
+        /// What the new stream agreed to carry. A server that changed its
+        /// capabilities in the meantime may acknowledge less than before.
+        acknowledged: SubscriptionFilter,
+    },
+}
+
+/// A `subscriptions/listen` stream that re-establishes itself when it drops.
+///
+/// Opened with [`Client::listen_with_reconnect`]. Notifications still go to the
+/// handlers registered with [`Client::subscribe`] and its helpers, exactly as
+/// for a [`Subscription`]; this handle reports the gaps between streams with
+/// [`Self::next_event`] and ends the whole thing on [`Self::cancel`].
+///
+/// A graceful close from the server is respected rather than reconnected
+/// over: the server ended the subscription on purpose. Nor is a lost transport
+/// -- once the client disconnects there is nothing left to listen on, and the
+/// subscription ends [`SubscriptionEnd::Abrupt`].
+///
+/// Dropping the handle cancels the current stream, as dropping a
+/// [`Subscription`] does.
+pub struct ResilientSubscription {
+    current: watch::Receiver<(RequestId, SubscriptionFilter)>,
+    events: mpsc::UnboundedReceiver<SubscriptionEvent>,
+    stop: CancellationToken,
+    supervisor: Option<JoinHandle<SubscriptionEnd>>,
+}
+
+impl std::fmt::Debug for ResilientSubscription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let current = self.current.borrow();
+        f.debug_struct("ResilientSubscription")
+            .field("id", &current.0)
+            .field("acknowledged", &current.1)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ResilientSubscription {
+    /// Returns the subscription id of the stream currently open -- it changes
+    /// with every re-establishment.
+    #[inline]
+    pub fn id(&self) -> RequestId {
+        self.current.borrow().0.clone()
+    }
+
+    /// Returns the filter the current stream was acknowledged with.
+    #[inline]
+    pub fn acknowledged(&self) -> SubscriptionFilter {
+        self.current.borrow().1.clone()
+    }
+
+    /// Waits for the next [`SubscriptionEvent`].
+    ///
+    /// Returns `None` once the subscription has ended and every event has
+    /// been read.
+    pub async fn next_event(&mut self) -> Option<SubscriptionEvent> {
+        self.events.recv().await
+    }
+
+    /// Cancels the current stream and stops re-establishing it.
+    pub async fn cancel(mut self) -> SubscriptionEnd {
+        self.stop.cancel();
+        self.join().await
+    }
+
+    /// Waits for the subscription to end for good and reports how.
+    pub async fn closed(mut self) -> SubscriptionEnd {
+        self.join().await
+    }
+
+    async fn join(&mut self) -> SubscriptionEnd {
+        match self.supervisor.take() {
+            Some(task) => task.await.unwrap_or(SubscriptionEnd::Abrupt),
+            None => SubscriptionEnd::Abrupt,
+        }
+    }
+}
+
+/// Stops the supervisor, which drops -- and so cancels -- the current stream.
+impl Drop for ResilientSubscription {
+    fn drop(&mut self) {
+        self.stop.cancel();
+    }
+}
+
+impl Client {
+    /// Opens a `subscriptions/listen` stream that re-establishes itself when
+    /// it drops (MCP 2026-07-28).
+    ///
+    /// Behaves like [`Self::listen`] -- it returns once the first stream is
+    /// acknowledged, and fails exactly when that would -- and then keeps the
+    /// filter listening in the background: whenever the stream ends abruptly,
+    /// the same filter is re-issued under a fresh id with backoff per `policy`.
+    ///
+    /// Each re-established stream is reported as
+    /// [`SubscriptionEvent::ResyncNeeded`], since the server's notifications in
+    /// between are gone. Unless the policy turns it off, the registered
+    /// handlers also receive a `list_changed` for every list category the new
+    /// stream acknowledged and a `resources/updated` for every resource URI, so
+    /// a handler that re-fetches on change catches up on its own.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use neva::{Client, error::Error, types::SubscriptionFilter};
+    /// use neva::client::reconnect::{ReconnectPolicy, SubscriptionEvent};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Error> {
+    ///     let mut client = Client::new();
+    ///     client.connect().await?;
+    ///     client.on_tools_changed(|_| async { println!("re-fetch tools"); });
+    ///
+    ///     let mut subscription = client
+    ///         .listen_with_reconnect(
+    ///             SubscriptionFilter::new().with_tools_changed(),
+    ///             ReconnectPolicy::new().with_max_attempts(5),
+    ///         )
+    ///         .await?;
+    ///
+    ///     while let Some(event) = subscription.next_event().await {
+    ///         if let SubscriptionEvent::ResyncNeeded { .. } = event {
+    ///             println!("missed notifications while reconnecting");
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn listen_with_reconnect(
+        &mut self,
+        notifications: SubscriptionFilter,
+        policy: ReconnectPolicy,
+    ) -> Result<ResilientSubscription, Error> {
+        if self.is_legacy_peer() {
+            return Err(Error::new(
+                ErrorCode::MethodNotFound,
+                "Peer speaks the legacy protocol; use subscribe_to_resource instead",
+            ));
+        }
+
+        let id = self.generate_id()?;
+        let mut template = Request::new(
+            Some(id),
+            crate::types::subscription::commands::LISTEN,
+            Some(SubscriptionsListenRequestParams::new(notifications.clone())),
+        );
+
+        // Assembled once: the supervisor has no client to assemble it with
+        // again, and nothing in it -- client info, capabilities, the protocol
+        // version -- changes over the life of a connection.
+        self.apply_client_meta(&mut template, None, None);
+
+        let mut link = self
+            .handler
+            .as_ref()
+            .ok_or_else(|| Error::new(ErrorCode::InternalError, "Connection closed"))?
+            .listen_link();
+
+        let subscription = establish(&mut link, template.clone(), notifications.clone()).await?;
+
+        let (current_tx, current) = watch::channel((
+            subscription.id().clone(),
+            subscription.acknowledged().clone(),
+        ));
+        let (events_tx, events) = mpsc::unbounded_channel();
+        let stop = CancellationToken::new();
+
+        let supervisor = Supervisor {
+            link,
+            template,
+            notifications,
+            policy,
+            current: current_tx,
+            events: events_tx,
+            stop: stop.clone(),
+        };
+
+        Ok(ResilientSubscription {
+            current,
+            events,
+            stop,
+            supervisor: Some(tokio::spawn(supervisor.run(subscription))),
+        })
+    }
+}
+
+/// The background half of a [`ResilientSubscription`].
+struct Supervisor {
+    link: ListenLink,
+    template: Request,
+    notifications: SubscriptionFilter,
+    policy: ReconnectPolicy,
+    current: watch::Sender<(RequestId, SubscriptionFilter)>,
+    events: mpsc::UnboundedSender<SubscriptionEvent>,
+    stop: CancellationToken,
+}
+
+impl Supervisor {
+    async fn run(mut self, mut subscription: Subscription) -> SubscriptionEnd {
+        loop {
+            let end = tokio::select! {
+                biased;
+                _ = self.stop.cancelled() => None,
+                end = subscription.ended() => Some(end),
+            };
+
+            let end = match end {
+                Some(end) => end,
+                None => {
+                    let _ = subscription.cancel().await;
+                    return SubscriptionEnd::Cancelled;
+                }
+            };
+
+            // Only an abrupt end is a gap to bridge. A graceful close is the
+            // server's decision, and a lost transport leaves nothing to
+            // re-establish on.
+            if !matches!(end, SubscriptionEnd::Abrupt) || self.link.is_closed() {
+                return end;
+            }
+
+            let previous = subscription.id().clone();
+            subscription = match self.reestablish().await {
+                Some(subscription) => subscription,
+                None if self.stop.is_cancelled() => return SubscriptionEnd::Cancelled,
+                None => return SubscriptionEnd::Abrupt,
+            };
+
+            self.resync(previous, &subscription).await;
+        }
+    }
+
+    /// Re-issues the filter until a stream is acknowledged, the policy gives
+    /// up, the handle is dropped, or the transport goes away.
+    async fn reestablish(&mut self) -> Option<Subscription> {
+        let mut error = None;
+        let mut attempt = 1;
+
+        while !self.policy.exhausted(attempt) && !self.link.is_closed() {
+            let delay = self.policy.backoff(attempt);
+            let _ = self.events.send(SubscriptionEvent::Reconnecting {
+                attempt,
+                delay,
+                error: error.take(),
+            });
+
+            tokio::select! {
+                biased;
+                _ = self.stop.cancelled() => return None,
+                _ = tokio::time::sleep(delay) => {}
+            }
+
+            let mut request = self.template.clone();
+            request.id = self.link.next_id();
+
+            let established = tokio::select! {
+                biased;
+                _ = self.stop.cancelled() => return None,
+                established = establish(&mut self.link, request, self.notifications.clone()) => established,
+            };
+
+            match established {
+                Ok(subscription) => return Some(subscription),
+                Err(err) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(
+                        logger = "neva",
+                        attempt,
+                        "failed to re-establish a subscription: {err:?}"
+                    );
+                    error = Some(err);
+                    attempt += 1;
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Reports the gap a re-established stream leaves behind.
+    async fn resync(&self, previous: RequestId, subscription: &Subscription) {
+        let id = subscription.id().clone();
+        let acknowledged = subscription.acknowledged().clone();
+
+        let _ = self.current.send((id.clone(), acknowledged.clone()));
+
+        // Replayed ahead of the event, so a caller acting on the event finds
+        // the handlers already caught up.
+        if self.policy.replay_list_changed {
+            for notification in missed(&id, &acknowledged) {
+                self.link.notify(notification).await;
+            }
+        }
+
+        let _ = self.events.send(SubscriptionEvent::ResyncNeeded {
+            previous,
+            subscription: id,
+            acknowledged,
+        });
+    }
+}
+
+/// One stand-in notification per category `filter` covers, tagged with the
+/// subscription `id` the way the server would have tagged the real one.
+fn missed(id: &RequestId, filter: &SubscriptionFilter) -> Vec<Notification> {
+    use crate::types::{prompt, resource, tool};
+
+    let meta = serde_json::json!({ SUBSCRIPTION_ID_KEY: id });
+    let list_changed = [
+        (filter.tools_list_changed, tool::commands::LIST_CHANGED),
+        (filter.prompts_list_changed, prompt::commands::LIST_CHANGED),
+        (
+            filter.resources_list_changed,
+            resource::commands::LIST_CHANGED,
+        ),
+    ];
+
+    list_changed
+        .into_iter()
+        .filter(|(selected, _)| *selected)
+        .map(|(_, method)| Notification::new(method, Some(serde_json::json!({ "_meta": meta }))))
+        .chain(filter.resource_subscriptions.iter().map(|uri| {
+            Notification::new(
+                resource::commands::UPDATED,
+                Some(serde_json::json!({ "uri": uri, "_meta": meta })),
+            )
+        }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_the_ceiling() {
+        let policy = ReconnectPolicy::new()
+            .with_initial_backoff(Duration::from_millis(100))
+            .with_max_backoff(Duration::from_millis(700));
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(3), Duration::from_millis(400));
+        assert_eq!(policy.backoff(4), Duration::from_millis(700));
+        // Far past the point where the factor itself would overflow.
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_millis(700));
+    }
+
+    #[test]
+    fn attempts_are_unlimited_unless_capped() {
+        assert!(!ReconnectPolicy::new().exhausted(u32::MAX));
+
+        let capped = ReconnectPolicy::new().with_max_attempts(2);
+        assert!(!capped.exhausted(2));
+        assert!(capped.exhausted(3));
+    }
+
+    #[test]
+    fn it_replays_every_acknowledged_category() {
+        let filter = SubscriptionFilter::new()
+            .with_tools_changed()
+            .with_resource("file:///a.txt");
+
+        let replayed = missed(&RequestId::Number(4), &filter);
+        let methods: Vec<_> = replayed.iter().map(|n| n.method.as_str()).collect();
+
+        assert_eq!(
+            methods,
+            [
+                crate::types::tool::commands::LIST_CHANGED,
+                crate::types::resource::commands::UPDATED,
+            ]
+        );
+        let params = replayed[1].params.as_ref().unwrap();
+        assert_eq!(params["uri"], "file:///a.txt");
+        assert_eq!(params["_meta"][SUBSCRIPTION_ID_KEY], 4);
+    }
+}
+
+/// A stream that drops mid-subscription, against a raw-HTTP mock: the first
+/// `subscriptions/listen` is acknowledged and then closed without a result,
+/// the second is acknowledged and held.
+#[cfg(all(test, feature = "http-client"))]
+mod reconnect_tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    async fn read_body(stream: &mut TcpStream) -> Option<String> {
+        let mut buf = Vec::new();
+        let mut tmp = [0u8; 2048];
+        let header_end = loop {
+            let n = stream.read(&mut tmp).await.ok()?;
+            if n == 0 {
+                return None;
+            }
+            buf.extend_from_slice(&tmp[..n]);
+            if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                break pos + 4;
+            }
+        };
+        let head = String::from_utf8_lossy(&buf[..header_end]).to_ascii_lowercase();
+        let length = head
+            .lines()
+            .find_map(|l| l.strip_prefix("content-length:")?.trim().parse().ok())
+            .unwrap_or(0usize);
+        while buf.len() < header_end + length {
+            let n = stream.read(&mut tmp).await.ok()?;
+            if n == 0 {
+                return None;
+            }
+            buf.extend_from_slice(&tmp[..n]);
+        }
+        Some(String::from_utf8_lossy(&buf[header_end..header_end + length]).to_string())
+    }
+
+    async fn serve_dropping(listener: TcpListener, listens: Arc<AtomicUsize>) {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let listens = listens.clone();
+            tokio::spawn(async move {
+                while let Some(body) = read_body(&mut stream).await {
+                    let msg: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+                    let id = msg.get("id").cloned().unwrap_or_default();
+
+                    if msg["method"] == crate::commands::DISCOVER {
+                        let reply = serde_json::json!({
+                            "jsonrpc": "2.0", "id": id,
+                            "result": {
+                                "supportedVersions": [crate::LATEST_PROTOCOL_VERSION],
+                                "capabilities": { "tools": { "listChanged": true } }
+                            }
+                        })
+                        .to_string();
+                        let resp = format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{reply}",
+                            reply.len()
+                        );
+                        let _ = stream.write_all(resp.as_bytes()).await;
+                        continue;
+                    }
+
+                    let n = listens.fetch_add(1, Ordering::SeqCst);
+                    let ack = serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "method": "notifications/subscriptions/acknowledged",
+                        "params": {
+                            "notifications": { "toolsListChanged": true },
+                            "_meta": { SUBSCRIPTION_ID_KEY: id }
+                        }
+                    });
+                    let head = "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n";
+                    let _ = stream.write_all(head.as_bytes()).await;
+                    let _ = stream
+                        .write_all(format!("data: {ack}\n\n").as_bytes())
+                        .await;
+
+                    if n > 0 {
+                        tokio::time::sleep(Duration::from_secs(30)).await;
+                    }
+                    // The first stream closes here, with no closing result.
+                    return;
+                }
+            });
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn a_dropped_stream_is_reestablished_and_reported() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let listens = Arc::new(AtomicUsize::new(0));
+        tokio::spawn(serve_dropping(listener, listens.clone()));
+
+        let mut client = Client::new().with_options(|opt| {
+            opt.with_http(|http| http.bind(addr.to_string()))
+                .with_timeout(Duration::from_secs(5))
+        });
+        client.connect().await.expect("connect");
+
+        let replayed = Arc::new(AtomicUsize::new(0));
+        let seen = replayed.clone();
+        client.on_tools_changed(move |_| {
+            let seen = seen.clone();
+            async move {
+                seen.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        let mut subscription = client
+            .listen_with_reconnect(
+                SubscriptionFilter::new().with_tools_changed(),
+                ReconnectPolicy::new().with_initial_backoff(Duration::from_millis(10)),
+            )
+            .await
+            .expect("listen");
+        let first = subscription.id();
+
+        let resync = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                match subscription.next_event().await {
+                    Some(SubscriptionEvent::ResyncNeeded {
+                        previous,
+                        subscription,
+                        ..
+                    }) => break (previous, subscription),
+                    Some(_) => continue,
+                    None => panic!("the subscription ended instead of reconnecting"),
+                }
+            }
+        })
+        .await
+        .expect("a resync event");
+
+        assert_eq!(resync.0, first);
+        assert_ne!(resync.1, first, "a new stream is a new subscription id");
+        assert_eq!(subscription.id(), resync.1);
+        assert_eq!(listens.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            replayed.load(Ordering::SeqCst),
+            1,
+            "the missed tools/list_changed is replayed to the handler"
+        );
+
+        assert!(matches!(
+            subscription.cancel().await,
+            SubscriptionEnd::Cancelled
+        ));
+    }
+}
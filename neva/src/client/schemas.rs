@@ -0,0 +1,353 @@
+//! Tool schemas compiled from `tools/list`, for checking calls client-side.
+//!
+//! Compiling a JSON Schema costs far more than checking a value against one,
+//! and an agent tends to call the same few tools over and over. So a listing
+//! compiles each tool's schemas once, and `call_tool` reuses them until a later
+//! listing replaces them -- recompiling only a schema that actually changed.
+//!
+//! A registration is current exactly as long as the `x-mcp-header` one beside
+//! it: the listing's `ttlMs` says for how long, and `0` -- which is also what
+//! an absent `ttlMs` means -- makes it stale on arrival. A call is checked
+//! against the last schema compiled for its tool, however old. Only when a
+//! stale one refuses the call are the tools listed again, since the server may
+//! have changed the schema since, and the call checked against the new one --
+//! or the stale one again if the listing failed. Legacy listings carry no TTL
+//! and never go stale.
+
+use crate::error::{Error, ErrorCode};
+use crate::shared::json_schema;
+use crate::types::{CallToolResponse, ListToolsResult, Tool, ToolInputSchema};
+use jsonschema::Validator;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Compiled tool schemas, by tool name, and which of them calls are checked
+/// against.
+#[derive(Default)]
+pub(crate) struct ToolSchemas {
+    /// Check arguments against `inputSchema` before a call is sent.
+    pub(super) input: bool,
+    /// Check `structuredContent` against `outputSchema` when a call returns.
+    pub(super) output: bool,
+    tools: HashMap<String, Registration>,
+}
+
+/// What one tool's listing said, and how long that remains true.
+struct Registration {
+    input: Option<Compiled>,
+    output: Option<Compiled>,
+    /// `None` when the listing never goes stale.
+    expires_at: Option<Instant>,
+}
+
+/// A schema and its validator; the source is kept to tell whether a later
+/// listing changed it.
+#[derive(Clone)]
+struct Compiled {
+    source: Value,
+    validator: Arc<Validator>,
+}
+
+impl Debug for ToolSchemas {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ToolSchemas")
+            .field("input", &self.input)
+            .field("output", &self.output)
+            .field("tools", &self.tools.len())
+            .finish()
+    }
+}
+
+impl ToolSchemas {
+    /// Compiles the schemas of every tool in `result`.
+    ///
+    /// `fresh` marks the first page of a traversal, which replaces what the
+    /// previous one registered; later pages accumulate onto it. Either way a
+    /// schema identical to the one already compiled for the tool is reused
+    /// rather than compiled again.
+    pub(crate) fn register(&mut self, result: &ListToolsResult, fresh: bool) {
+        if !self.input && !self.output {
+            return;
+        }
+
+        let mut previous = if fresh {
+            std::mem::take(&mut self.tools)
+        } else {
+            HashMap::new()
+        };
+
+        #[cfg(not(feature = "legacy-spec"))]
+        let expires_at =
+            Instant::now().checked_add(std::time::Duration::from_millis(result.ttl_ms));
+        #[cfg(feature = "legacy-spec")]
+        let expires_at = None;
+
+        for tool in &result.tools {
+            let prior = previous
+                .remove(&tool.name)
+                .or_else(|| self.tools.remove(&tool.name));
+            let (prior_input, prior_output) = match prior {
+                Some(prior) => (prior.input, prior.output),
+                None => (None, None),
+            };
+
+            let input = self
+                .input
+                .then(|| compile(tool, Some(&tool.input_schema), prior_input))
+                .flatten();
+            let output = self
+                .output
+                .then(|| compile(tool, tool.output_schema.as_ref(), prior_output))
+                .flatten();
+
+            self.tools.insert(
+                tool.name.clone(),
+                Registration {
+                    input,
+                    output,
+                    expires_at,
+                },
+            );
+        }
+    }
+
+    /// Checks the arguments of a call to the tool `name` before it is sent.
+    pub(crate) fn check_arguments(
+        &self,
+        name: &str,
+        args: Option<&HashMap<String, Value>>,
+    ) -> Result<(), Error> {
+        let Some(compiled) = self.current(name).and_then(|reg| reg.input.as_ref()) else {
+            return Ok(());
+        };
+
+        let errors = json_schema::violations(&compiled.validator, &json_schema::arguments(args));
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::new(
+                ErrorCode::InvalidParams,
+                format!("invalid arguments for tool `{name}`"),
+            )
+            .with_data(serde_json::json!({ "tool": name, "errors": errors })))
+        }
+    }
+
+    /// Checks the structured content of what the tool `name` returned.
+    ///
+    /// A result flagged `isError` is exempt: the spec has the output schema
+    /// describe a tool's output, not the error it reports instead.
+    pub(crate) fn check_result(&self, name: &str, resp: &CallToolResponse) -> Result<(), Error> {
+        if resp.is_error {
+            return Ok(());
+        }
+        let Some(compiled) = self.current(name).and_then(|reg| reg.output.as_ref()) else {
+            return Ok(());
+        };
+
+        let errors = json_schema::violations(&compiled.validator, resp.struct_content()?);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::new(
+                ErrorCode::ParseError,
+                format!("result of tool `{name}` does not match its output schema"),
+            )
+            .with_data(serde_json::json!({ "tool": name, "errors": errors })))
+        }
+    }
+
+    /// Whether calls to `name` are checked against a schema from a stale
+    /// listing, one to list the tools again for when it refuses a call.
+    pub(crate) fn is_stale(&self, name: &str) -> bool {
+        (self.input || self.output)
+            && self
+                .tools
+                .get(name)
+                .and_then(|reg| reg.expires_at)
+                .is_some_and(|at| Instant::now() >= at)
+    }
+
+    /// The registration for `name`, however old.
+    fn current(&self, name: &str) -> Option<&Registration> {
+        self.tools.get(name)
+    }
+}
+
+/// Compiles `schema`, or reuses `prior` when it was compiled from the same one.
+///
+/// A schema that does not compile leaves the tool unchecked rather than
+/// uncallable: the check exists to catch a bad argument or result, and a bad
+/// schema says nothing about either.
+fn compile(
+    tool: &Tool,
+    schema: Option<&ToolInputSchema>,
+    prior: Option<Compiled>,
+) -> Option<Compiled> {
+    let source = json_schema::schema_value(schema?).ok()?;
+    if let Some(prior) = prior.filter(|prior| prior.source == *source) {
+        return Some(prior);
+    }
+
+    match jsonschema::validator_for(&source) {
+        Ok(validator) => Some(Compiled {
+            source: source.into_owned(),
+            validator: Arc::new(validator),
+        }),
+        Err(_err) => {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                logger = "neva",
+                "Not checking calls to tool `{}`: its schema does not compile: {_err}",
+                tool.name
+            );
+            #[cfg(not(feature = "tracing"))]
+            let _ = tool;
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn listing(tools: Value, ttl_ms: u64) -> ListToolsResult {
+        serde_json::from_value(json!({ "tools": tools, "ttlMs": ttl_ms })).expect("valid listing")
+    }
+
+    fn forecast(temperature: &str) -> Value {
+        json!([{
+            "name": "forecast",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "city": { "type": "string" } },
+                "required": ["city"]
+            },
+            "outputSchema": {
+                "type": "object",
+                "properties": { "temperature": { "type": temperature } },
+                "required": ["temperature"]
+            }
+        }])
+    }
+
+    fn result(structured: Value, is_error: bool) -> CallToolResponse {
+        serde_json::from_value(json!({
+            "content": [],
+            "structuredContent": structured,
+            "isError": is_error
+        }))
+        .expect("valid result")
+    }
+
+    fn schemas() -> ToolSchemas {
+        ToolSchemas {
+            input: true,
+            output: true,
+            ..Default::default()
+        }
+    }
+
+    fn output_validator(schemas: &ToolSchemas) -> Arc<Validator> {
+        schemas.tools["forecast"]
+            .output
+            .as_ref()
+            .expect("a compiled output schema")
+            .validator
+            .clone()
+    }
+
+    #[test]
+    fn it_registers_nothing_when_checks_are_off() {
+        let mut schemas = ToolSchemas::default();
+        schemas.register(&listing(forecast("number"), 60_000), true);
+
+        assert!(schemas.tools.is_empty());
+    }
+
+    #[test]
+    fn it_reports_every_output_violation_by_pointer() {
+        let mut schemas = schemas();
+        schemas.register(&listing(forecast("number"), 60_000), true);
+
+        assert!(
+            schemas
+                .check_result("forecast", &result(json!({ "temperature": 21.5 }), false))
+                .is_ok()
+        );
+
+        let err = schemas
+            .check_result("forecast", &result(json!({ "temperature": "warm" }), false))
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::ParseError);
+        assert_eq!(err.data().unwrap()["errors"][0]["pointer"], "/temperature");
+    }
+
+    #[test]
+    fn it_does_not_check_a_result_flagged_as_an_error() {
+        let mut schemas = schemas();
+        schemas.register(&listing(forecast("number"), 60_000), true);
+
+        let resp = result(json!({ "reason": "no such city" }), true);
+        assert!(schemas.check_result("forecast", &resp).is_ok());
+    }
+
+    #[test]
+    fn it_checks_arguments_before_a_call() {
+        let mut schemas = schemas();
+        schemas.register(&listing(forecast("number"), 60_000), true);
+
+        let err = schemas.check_arguments("forecast", None).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidParams);
+
+        let args = HashMap::from([("city".to_owned(), json!("London"))]);
+        assert!(schemas.check_arguments("forecast", Some(&args)).is_ok());
+        assert!(schemas.check_arguments("unlisted", None).is_ok());
+    }
+
+    #[test]
+    fn it_reuses_a_schema_a_later_listing_did_not_change() {
+        let mut schemas = schemas();
+        schemas.register(&listing(forecast("number"), 60_000), true);
+        let first = output_validator(&schemas);
+
+        schemas.register(&listing(forecast("number"), 60_000), true);
+        assert!(Arc::ptr_eq(&first, &output_validator(&schemas)));
+
+        schemas.register(&listing(forecast("string"), 60_000), true);
+        assert!(!Arc::ptr_eq(&first, &output_validator(&schemas)));
+    }
+
+    #[test]
+    fn a_fresh_listing_forgets_a_tool_it_no_longer_lists() {
+        let mut schemas = schemas();
+        schemas.register(&listing(forecast("number"), 60_000), true);
+        schemas.register(&listing(json!([]), 60_000), true);
+
+        assert!(schemas.tools.is_empty());
+    }
+
+    /// `ttlMs: 0` -- also what an absent one means, and what a neva server
+    /// sends -- is stale on arrival: it asks for a new listing, but its schema
+    /// is still checked against until one replaces it.
+    #[test]
+    #[cfg(not(feature = "legacy-spec"))]
+    fn a_stale_listing_is_still_checked_against() {
+        let mut schemas = schemas();
+        schemas.register(&listing(forecast("number"), 0), true);
+        assert!(schemas.is_stale("forecast"));
+        assert!(!schemas.is_stale("unlisted"));
+
+        let resp = result(json!({ "temperature": "warm" }), false);
+        assert!(schemas.check_result("forecast", &resp).is_err());
+        assert!(schemas.check_arguments("forecast", None).is_err());
+
+        schemas.register(&listing(forecast("number"), 60_000), true);
+        assert!(!schemas.is_stale("forecast"));
+    }
+}
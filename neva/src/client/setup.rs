@@ -353,7 +353,11 @@ impl Client {
                     "Server supports {:?} but the client speaks {expected}",
                     result.supported_versions
                 ),
-            ));
+            )
+            .with_data(serde_json::json!({
+                "supported": result.supported_versions,
+                "requested": expected,
+            })));
         }
         self.server_capabilities = Some(result.capabilities);
         // `serverInfo` left `DiscoverResult` in the final spec: servers now
@@ -0,0 +1,466 @@
+//! Per-call streams of the progress and log notifications a request produces.
+//!
+//! The `*_streaming` variants of [`Client::call_tool`], [`Client::read_resource`]
+//! and [`Client::get_prompt`] mint a progress token for the call, ask for its
+//! logs, and return a [`CallStream`]: the notifications the server reports
+//! while handling that call, then its result. The call is sent once the stream
+//! is first polled, so its log level can still be set on the stream. What a call's stream takes is no
+//! longer delivered to the client-wide notification handlers.
+//!
+//! Progress is matched by its token on any transport. Log messages carry no
+//! token, so they can only be matched when the transport receives them on the
+//! call's own response stream -- Streamable HTTP under MCP 2026-07-28 does; a
+//! stdio peer and the legacy session stream do not, and there a call's logs
+//! go to the notification handlers as before.
+
+use super::*;
+use crate::types::ProgressToken;
+use crate::types::notification::{LogMessage, ProgressNotification, commands};
+use dashmap::DashMap;
+use futures_util::Stream;
+use futures_util::future::BoxFuture;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+/// What the server reported while handling a streamed call.
+#[derive(Debug, Clone)]
+pub enum CallEvent {
+    /// A `notifications/progress` for the call.
+    Progress(ProgressNotification),
+
+    /// A `notifications/message` the server logged while handling the call.
+    Log(LogMessage),
+}
+
+/// A call in flight, together with what the server reports about it.
+///
+/// As a [`Stream`] it yields the call's [`CallEvent`]s while driving the call,
+/// and ends once the call has returned and every event before the result has
+/// been yielded. [`Self::result`] then hands over the result; called earlier,
+/// it waits for the call and drops the events not yet read.
+///
+/// Nothing is sent until either is first awaited.
+///
+/// # Example
+/// ```no_run
+/// use futures_util::StreamExt;
+/// use neva::prelude::*;
+/// use neva::client::CallEvent;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Error> {
+///     let mut client = Client::new();
+///     client.connect().await?;
+///
+///     let mut call = client.call_tool_streaming("import", ("path", "data.csv"))?;
+///     while let Some(event) = call.next().await {
+///         match event {
+///             CallEvent::Progress(p) => println!("{}/{:?}", p.progress, p.total),
+///             CallEvent::Log(log) => println!("{:?}", log.data),
+///         }
+///     }
+///     let result = call.result().await?;
+///     # drop(result);
+///     client.disconnect().await
+/// }
+/// ```
+pub struct CallStream<'a, T> {
+    token: ProgressToken,
+    unsent: Option<Unsent<'a, T>>,
+    call: Option<BoxFuture<'a, Result<T, Error>>>,
+    result: Option<Result<T, Error>>,
+    events: UnboundedReceiver<CallEvent>,
+}
+
+/// A [`CallStream`]'s call before it is sent.
+struct Unsent<'a, T> {
+    /// The `_meta` the call will carry.
+    meta: RequestParamsMeta,
+    send: Box<dyn FnOnce(RequestParamsMeta) -> BoxFuture<'a, Result<T, Error>> + Send + 'a>,
+}
+
+// Nothing is projected through the pin: the call is boxed, and the result is
+// only ever moved out of an `Option`.
+impl<T> Unpin for CallStream<'_, T> {}
+
+impl<T> Debug for CallStream<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CallStream")
+            .field("token", &self.token)
+            .field("finished", &(self.unsent.is_none() && self.call.is_none()))
+            .finish()
+    }
+}
+
+impl<'a, T: Send + 'a> CallStream<'a, T> {
+    /// Registers `token` with `streams` and wraps `send`, which sends the call
+    /// with the `_meta` it is given -- `meta`, unless changed on the stream.
+    pub(super) fn new<S, F>(
+        streams: &CallStreams,
+        token: ProgressToken,
+        meta: RequestParamsMeta,
+        send: S,
+    ) -> Self
+    where
+        S: FnOnce(RequestParamsMeta) -> F + Send + 'a,
+        F: Future<Output = Result<T, Error>> + Send + 'a,
+    {
+        let (events, guard) = streams.open(token.clone());
+        Self {
+            token,
+            unsent: Some(Unsent {
+                meta,
+                send: Box::new(move |meta| {
+                    Box::pin(async move {
+                        // Owned by the call, so that its entry goes away with
+                        // it however the call ends -- including the stream
+                        // being dropped mid-flight, or before it was sent.
+                        let _guard = guard;
+                        send(meta).await
+                    })
+                }),
+            }),
+            call: None,
+            result: None,
+            events,
+        }
+    }
+
+    /// Asks for this call's log messages at `level` rather than at the level
+    /// the client was configured with.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use neva::prelude::*;
+    /// use neva::types::notification::LoggingLevel;
+    ///
+    /// # async fn call(client: &mut Client) -> Result<(), Error> {
+    /// # #[allow(deprecated)]
+    /// let result = client
+    ///     .call_tool_streaming("import", ("path", "data.csv"))?
+    ///     .with_log_level(LoggingLevel::Debug)
+    ///     .result()
+    ///     .await?;
+    /// # drop(result);
+    /// # Ok(()) }
+    /// ```
+    #[cfg(not(feature = "legacy-spec"))]
+    #[deprecated(
+        note = "Request-scoped logging is deprecated in MCP 2026-07-28 and may be removed in a future revision."
+    )]
+    pub fn with_log_level(mut self, level: crate::types::notification::LoggingLevel) -> Self {
+        if let Some(unsent) = self.unsent.as_mut() {
+            unsent.meta.log_level = Some(level);
+        }
+        self
+    }
+
+    /// The progress token minted for this call.
+    #[inline]
+    pub fn token(&self) -> &ProgressToken {
+        &self.token
+    }
+
+    /// Waits for the call to return and hands over its result.
+    pub async fn result(mut self) -> Result<T, Error> {
+        self.send();
+        match (self.result.take(), self.call.take()) {
+            (Some(result), _) => result,
+            (None, Some(call)) => call.await,
+            (None, None) => Err(Error::new(
+                ErrorCode::InternalError,
+                "the call result was already taken",
+            )),
+        }
+    }
+}
+
+impl<T> CallStream<'_, T> {
+    /// Sends the call, if it has not been sent yet.
+    fn send(&mut self) {
+        if let Some(unsent) = self.unsent.take() {
+            self.call = Some((unsent.send)(unsent.meta));
+        }
+    }
+}
+
+impl<T> Stream for CallStream<'_, T> {
+    type Item = CallEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<CallEvent>> {
+        let this = self.get_mut();
+        this.send();
+        if let Poll::Ready(Some(event)) = this.events.poll_recv(cx) {
+            return Poll::Ready(Some(event));
+        }
+        if let Some(call) = this.call.as_mut() {
+            let Poll::Ready(result) = call.as_mut().poll(cx) else {
+                return Poll::Pending;
+            };
+            // Dropping the call unregisters it, which closes the channel once
+            // what is already in it has been read.
+            this.result = Some(result);
+            this.call = None;
+        }
+        this.events.poll_recv(cx)
+    }
+}
+
+/// The calls with an open [`CallStream`], shared between the client and its
+/// receive loop.
+#[derive(Clone, Default)]
+pub(super) struct CallStreams {
+    /// Where each open call's events go, by its progress token.
+    calls: Arc<DashMap<ProgressToken, UnboundedSender<CallEvent>>>,
+
+    /// The progress token of each request in flight for an open call: one per
+    /// MRTR round, since every round is sent under a new id.
+    requests: Arc<DashMap<RequestId, ProgressToken>>,
+}
+
+/// Closes a call's entry in [`CallStreams`] when dropped.
+pub(super) struct CallGuard {
+    streams: CallStreams,
+    token: ProgressToken,
+}
+
+impl Drop for CallGuard {
+    fn drop(&mut self) {
+        self.streams.calls.remove(&self.token);
+        self.streams
+            .requests
+            .retain(|_, token| *token != self.token);
+    }
+}
+
+impl CallStreams {
+    /// Opens a stream for the call sent under `token`.
+    fn open(&self, token: ProgressToken) -> (UnboundedReceiver<CallEvent>, CallGuard) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.calls.insert(token.clone(), tx);
+        let guard = CallGuard {
+            streams: self.clone(),
+            token,
+        };
+        (rx, guard)
+    }
+
+    /// Notes that `req` is in flight for an open call, if it is one. Returns
+    /// whether it is.
+    pub(super) fn track(&self, req: &Request) -> bool {
+        if self.calls.is_empty() {
+            return false;
+        }
+        let Some(token) = req.meta().and_then(|meta| meta.progress_token) else {
+            return false;
+        };
+        if !self.calls.contains_key(&token) {
+            return false;
+        }
+        self.requests.insert(req.id(), token);
+        true
+    }
+
+    /// Forgets a request [`Self::track`] noted once it has been answered.
+    #[inline]
+    pub(super) fn untrack(&self, id: &RequestId) {
+        self.requests.remove(id);
+    }
+
+    /// Hands `notification` to the stream of the call it belongs to, or back
+    /// to the caller if it belongs to none.
+    pub(super) fn deliver(&self, notification: Notification) -> Option<Notification> {
+        if self.calls.is_empty() {
+            return Some(notification);
+        }
+        let event = match notification.method.as_str() {
+            commands::PROGRESS => notification
+                .params::<ProgressNotification>()
+                .map(|progress| {
+                    (
+                        progress.progress_token.clone(),
+                        CallEvent::Progress(progress),
+                    )
+                }),
+            commands::MESSAGE => notification
+                .request_id
+                .as_ref()
+                .and_then(|id| self.requests.get(id).map(|token| token.clone()))
+                .zip(notification.params::<LogMessage>())
+                .map(|(token, log)| (token, CallEvent::Log(log))),
+            _ => None,
+        };
+        match event.and_then(|(token, event)| Some((self.calls.get(&token)?, event))) {
+            Some((call, event)) => {
+                // A stream dropped mid-call is the caller having stopped
+                // listening; its events are dropped with it.
+                let _ = call.send(event);
+                None
+            }
+            None => Some(notification),
+        }
+    }
+}
+
+impl Client {
+    /// Calls a tool, streaming the progress and logs the server reports for
+    /// this call.
+    ///
+    /// The call carries a fresh progress token and asks for log messages at
+    /// the level set with [`CallStream::with_log_level`], else with
+    /// `McpOptions::with_log_level`, or at `info` without either. See
+    /// [`CallStream`].
+    pub fn call_tool_streaming<N, Args>(
+        &mut self,
+        name: N,
+        args: Args,
+    ) -> Result<CallStream<'_, CallToolResponse>, Error>
+    where
+        N: Into<String>,
+        Args: shared::IntoArgs,
+    {
+        let (streams, token, meta) = self.stream_meta()?;
+        let (name, args) = (name.into(), args.into_args());
+        Ok(CallStream::new(&streams, token, meta, move |meta| {
+            self.call_tool_params(CallToolRequestParams {
+                name,
+                meta: Some(meta),
+                args,
+                #[cfg(feature = "tasks")]
+                task: None,
+            })
+        }))
+    }
+
+    /// Reads a resource, streaming the progress and logs the server reports
+    /// for this read. See [`Self::call_tool_streaming`].
+    pub fn read_resource_streaming(
+        &mut self,
+        uri: impl Into<Uri>,
+    ) -> Result<CallStream<'_, ReadResourceResult>, Error> {
+        let (streams, token, meta) = self.stream_meta()?;
+        let uri = uri.into();
+        Ok(CallStream::new(&streams, token, meta, move |meta| {
+            self.read_resource_with_meta(uri, Some(meta))
+        }))
+    }
+
+    /// Gets a prompt, streaming the progress and logs the server reports for
+    /// this request. See [`Self::call_tool_streaming`].
+    pub fn get_prompt_streaming<N, Args>(
+        &mut self,
+        name: N,
+        args: Args,
+    ) -> Result<CallStream<'_, GetPromptResult>, Error>
+    where
+        N: Into<String>,
+        Args: shared::IntoArgs,
+    {
+        let (streams, token, meta) = self.stream_meta()?;
+        let (name, args) = (name.into(), args.into_args());
+        Ok(CallStream::new(&streams, token, meta, move |meta| {
+            self.get_prompt_with_meta(name, args, Some(meta))
+        }))
+    }
+
+    /// The registry of the current connection, and the progress token and
+    /// `_meta` of a new streamed call.
+    fn stream_meta(&self) -> Result<(CallStreams, ProgressToken, RequestParamsMeta), Error> {
+        let streams = self
+            .handler
+            .as_ref()
+            .ok_or_else(|| Error::new(ErrorCode::InternalError, "Connection closed"))?
+            .call_streams();
+
+        let token = ProgressToken::Uuid(uuid::Uuid::new_v4());
+        #[cfg_attr(feature = "legacy-spec", allow(unused_mut))]
+        let mut meta = RequestParamsMeta {
+            progress_token: Some(token.clone()),
+            ..Default::default()
+        };
+        #[cfg(not(feature = "legacy-spec"))]
+        {
+            meta.log_level = Some(
+                self.options
+                    .log_level
+                    .unwrap_or(crate::types::notification::LoggingLevel::Info),
+            );
+        }
+        Ok((streams, token, meta))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_token() -> ProgressToken {
+        ProgressToken::Uuid(uuid::Uuid::new_v4())
+    }
+
+    fn progress(token: &ProgressToken, value: f64) -> Notification {
+        token.notify(value, None).into()
+    }
+
+    fn log(request_id: Option<RequestId>) -> Notification {
+        let mut notification = Notification::new(
+            commands::MESSAGE,
+            Some(serde_json::json!({ "level": "info", "data": "working" })),
+        );
+        notification.request_id = request_id;
+        notification
+    }
+
+    #[tokio::test]
+    async fn it_routes_a_calls_notifications_to_its_stream() {
+        let streams = CallStreams::default();
+        let token = new_token();
+        let (mut rx, guard) = streams.open(token.clone());
+
+        let req = Request::new(
+            Some(RequestId::Number(7)),
+            "tools/call",
+            Some(serde_json::json!({ "_meta": { "progressToken": token } })),
+        );
+        assert!(streams.track(&req));
+
+        assert!(streams.deliver(progress(&token, 1.0)).is_none());
+        assert!(streams.deliver(log(Some(RequestId::Number(7)))).is_none());
+        assert!(matches!(rx.try_recv(), Ok(CallEvent::Progress(p)) if p.progress == 1.0));
+        assert!(matches!(rx.try_recv(), Ok(CallEvent::Log(_))));
+
+        // Not this call's, and not attributable to any call.
+        assert!(streams.deliver(progress(&new_token(), 1.0)).is_some());
+        assert!(streams.deliver(log(Some(RequestId::Number(8)))).is_some());
+        assert!(streams.deliver(log(None)).is_some());
+
+        drop(guard);
+        assert!(streams.deliver(progress(&token, 2.0)).is_some());
+        assert!(streams.requests.is_empty());
+        assert!(rx.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn it_yields_events_then_ends_with_the_call() {
+        use futures_util::StreamExt;
+
+        let streams = CallStreams::default();
+        let token = new_token();
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+        let sender = streams.clone();
+        let reported = token.clone();
+        let mut call = CallStream::new(&streams, token, Default::default(), |_| async move {
+            let _ = rx.await;
+            Ok::<_, Error>("done")
+        });
+
+        assert!(sender.deliver(progress(&reported, 1.0)).is_none());
+        assert!(matches!(call.next().await, Some(CallEvent::Progress(_))));
+
+        tx.send(()).unwrap();
+        assert!(call.next().await.is_none());
+        assert_eq!(call.result().await.unwrap(), "done");
+    }
+}
@@ -98,7 +98,9 @@ pub enum SubscriptionEnd {
 
     /// The stream went away without a final result -- a dropped connection, a
     /// timeout, or a server that died. Subscriptions are not resumable, so a
-    /// client that wants to keep listening re-sends `subscriptions/listen`.
+    /// client that wants to keep listening re-sends `subscriptions/listen` --
+    /// or opens it with [`Client::listen_with_reconnect`](crate::Client::listen_with_reconnect),
+    /// which does so on its own.
     Abrupt,
 
     /// This client ended it via [`Subscription::cancel`].
@@ -254,6 +256,12 @@ impl Subscription {
 
     /// Waits for the subscription to end and reports how.
     pub async fn closed(mut self) -> SubscriptionEnd {
+        self.ended().await
+    }
+
+    /// [`Self::closed`] without giving up the handle, for a caller that has to
+    /// be able to [`Self::cancel`] it while waiting.
+    pub(super) async fn ended(&mut self) -> SubscriptionEnd {
         // A cancel this client issued needs no waiting: over HTTP it closed the
         // stream, so the peer has nowhere to send a final result and awaiting
         // one would hang until the request timeout.
@@ -0,0 +1,845 @@
+//! Interactive terminal handlers for elicitation and sampling.
+//!
+//! A CLI client answers `elicitation/create` by asking its user, and ought to
+//! show a sampling request to its user before a model sees it. [`Terminal`]
+//! does both over any line-oriented reader and writer -- the process's own
+//! stdin and stdout by default -- so a client wires it in with
+//! [`Client::map_elicitation`](crate::client::Client::map_elicitation) and
+//! [`Client::map_sampling`](crate::client::Client::map_sampling) instead of
+//! writing the prompt loop again.
+//!
+//! # Example
+//! ```no_run
+//! use neva::client::{Client, Terminal};
+//! use neva::types::sampling::CreateMessageResult;
+//!
+//! # #[allow(deprecated)]
+//! # fn configure(client: &mut Client) {
+//! let terminal = Terminal::stdio().with_browser();
+//!
+//! client.map_elicitation(terminal.elicitation_handler());
+//! client.map_sampling(terminal.sampling_handler(|params| async move {
+//!     // Send `params` to the model of your choice.
+//!     CreateMessageResult::assistant()
+//!         .with_model("my-model")
+//!         .with_content("Hello!")
+//!         .end_turn()
+//! }));
+//! # }
+//! ```
+
+use crate::shared::BoxFuture;
+use crate::types::elicitation::{
+    ElicitRequestFormParams, ElicitRequestParams, ElicitRequestUrlParams, ElicitResult, Validator,
+};
+use crate::types::sampling::{CreateMessageRequestParams, CreateMessageResult};
+use crate::types::{Content, PropertyType, Role, Schema};
+use serde_json::Value;
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::sync::Arc;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::Mutex;
+
+/// What a form field answer of `!decline` does: declines the elicitation.
+const DECLINE: &str = "!decline";
+
+/// What a form field answer of `!cancel` does: cancels the elicitation.
+const CANCEL: &str = "!cancel";
+
+/// The stop reason of a sampling request the user turned down.
+///
+/// A sampling handler has no way to fail the request, so a rejection is
+/// reported as a result that carries no model output and says why it stopped.
+pub const REJECTED: &str = "rejected";
+
+/// Elicitation and sampling handlers that ask the user at a terminal.
+///
+/// Clones share the terminal, and concurrent requests take turns at it: one
+/// form is filled in before the next one is shown.
+#[derive(Clone)]
+pub struct Terminal {
+    io: Arc<Mutex<Io>>,
+    open_urls: bool,
+}
+
+/// The two ends of the terminal.
+struct Io {
+    input: Box<dyn AsyncBufRead + Send + Unpin>,
+    output: Box<dyn AsyncWrite + Send + Unpin>,
+}
+
+/// What the user answered for one form field.
+enum Answer {
+    Value(Value),
+    Skip,
+    Decline,
+    Cancel,
+}
+
+impl Debug for Terminal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Terminal")
+            .field("open_urls", &self.open_urls)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Terminal {
+    /// Creates a [`Terminal`] on the process's stdin and stdout.
+    pub fn stdio() -> Self {
+        Self::new(tokio::io::stdin(), tokio::io::stdout())
+    }
+
+    /// Creates a [`Terminal`] reading answers from `input` and writing prompts
+    /// to `output`.
+    pub fn new<R, W>(input: R, output: W) -> Self
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+        W: AsyncWrite + Send + Unpin + 'static,
+    {
+        Self {
+            io: Arc::new(Mutex::new(Io {
+                input: Box::new(BufReader::new(input)),
+                output: Box::new(output),
+            })),
+            open_urls: false,
+        }
+    }
+
+    /// Offers to open the URL of a URL-mode elicitation in the system browser,
+    /// as well as printing it. The user is asked first, and only `https` and
+    /// `http` URLs are offered.
+    ///
+    /// Default: the URL is only printed.
+    pub fn with_browser(mut self) -> Self {
+        self.open_urls = true;
+        self
+    }
+
+    /// Asks the user to answer an elicitation request.
+    ///
+    /// A form is asked field by field, required fields first. An empty answer
+    /// takes the field's default, or leaves an optional field out; each answer
+    /// is checked against its schema and asked again until it fits. Answering
+    /// `!decline` or `!cancel` to any field does that to the whole request, and
+    /// so does the end of input to the latter.
+    ///
+    /// A URL request prints the URL -- and offers to open it, with
+    /// [`with_browser`](Self::with_browser) -- and asks whether the user went
+    /// through with it.
+    pub async fn elicit(&self, params: ElicitRequestParams) -> ElicitResult {
+        let mut io = self.io.lock().await;
+        match params {
+            ElicitRequestParams::Form(form) => io.fill_in(form).await,
+            ElicitRequestParams::Url(url) => io.visit(url, self.open_urls).await,
+        }
+    }
+
+    /// Shows a sampling request for approval, forwards it to the model with
+    /// `forward`, and shows the model's response for approval in turn.
+    ///
+    /// At either step the user can send it on as it is, edit it first -- the
+    /// system prompt and last user message of the request, or the text of the
+    /// response -- or reject it, which returns a result with the
+    /// [`REJECTED`] stop reason and nothing from the model.
+    pub async fn sample<F, R>(
+        &self,
+        mut params: CreateMessageRequestParams,
+        forward: F,
+    ) -> CreateMessageResult
+    where
+        F: FnOnce(CreateMessageRequestParams) -> R,
+        R: Future,
+        R::Output: Into<CreateMessageResult>,
+    {
+        if !self.io.lock().await.review_request(&mut params).await {
+            return rejected();
+        }
+
+        // The terminal is free while the model works: another request may be
+        // shown in the meantime.
+        let mut result = forward(params).await.into();
+
+        if !self.io.lock().await.review_result(&mut result).await {
+            return rejected();
+        }
+        result
+    }
+
+    /// An elicitation handler for [`Client::map_elicitation`](crate::client::Client::map_elicitation)
+    /// that calls [`elicit`](Self::elicit).
+    pub fn elicitation_handler(
+        &self,
+    ) -> impl Fn(ElicitRequestParams) -> BoxFuture<'static, ElicitResult> + Clone + Send + Sync + 'static
+    {
+        let terminal = self.clone();
+        move |params| {
+            let terminal = terminal.clone();
+            Box::pin(async move { terminal.elicit(params).await })
+        }
+    }
+
+    /// A sampling handler for [`Client::map_sampling`](crate::client::Client::map_sampling)
+    /// that calls [`sample`](Self::sample) with `forward`.
+    pub fn sampling_handler<F, R>(
+        &self,
+        forward: F,
+    ) -> impl Fn(CreateMessageRequestParams) -> BoxFuture<'static, CreateMessageResult>
+    + Clone
+    + Send
+    + Sync
+    + 'static
+    where
+        F: Fn(CreateMessageRequestParams) -> R + Clone + Send + Sync + 'static,
+        R: Future + Send + 'static,
+        R::Output: Into<CreateMessageResult>,
+    {
+        let terminal = self.clone();
+        move |params| {
+            let terminal = terminal.clone();
+            let forward = forward.clone();
+            Box::pin(async move { terminal.sample(params, forward).await })
+        }
+    }
+}
+
+impl Io {
+    /// Writes `text` as it is.
+    ///
+    /// A terminal that cannot be written to still reads: the user may see
+    /// less, but the request is answered either way, so a write error is not
+    /// worth failing it over.
+    async fn say(&mut self, text: &str) {
+        let _ = self.output.write_all(text.as_bytes()).await;
+        let _ = self.output.flush().await;
+    }
+
+    /// Writes `prompt` and reads a trimmed line; `None` at the end of input.
+    async fn ask(&mut self, prompt: &str) -> Option<String> {
+        self.say(prompt).await;
+        let mut line = String::new();
+        match self.input.read_line(&mut line).await {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(line.trim().to_owned()),
+        }
+    }
+
+    /// Asks a yes, edit or no question; `None` at the end of input.
+    async fn choose(&mut self, prompt: &str) -> Option<char> {
+        loop {
+            let answer = self.ask(prompt).await?.to_ascii_lowercase();
+            match answer.as_str() {
+                "y" | "yes" | "" => return Some('y'),
+                "e" | "edit" => return Some('e'),
+                "n" | "no" => return Some('n'),
+                _ => self.say("  Answer y, e or n.\n").await,
+            }
+        }
+    }
+
+    async fn fill_in(&mut self, form: ElicitRequestFormParams) -> ElicitResult {
+        self.say(&format!(
+            "\n{}\n(answer {DECLINE} to decline or {CANCEL} to cancel)\n",
+            form.message
+        ))
+        .await;
+
+        let required = form.schema.required.clone().unwrap_or_default();
+        let mut fields: Vec<_> = form
+            .schema
+            .properties
+            .iter()
+            .map(|(name, schema)| (name, schema, required.contains(name)))
+            .collect();
+        fields.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.0.cmp(b.0)));
+
+        let mut content = serde_json::Map::new();
+        for (name, schema, required) in fields {
+            match self.field(name, schema, required).await {
+                Answer::Value(value) => {
+                    content.insert(name.clone(), value);
+                }
+                Answer::Skip => {}
+                Answer::Decline => return ElicitResult::decline(),
+                Answer::Cancel => return ElicitResult::cancel(),
+            }
+        }
+
+        let content = Value::Object(content);
+        match Validator::new(form).validate_value(&content) {
+            Ok(()) => ElicitResult::accept().with_content(content),
+            // Every field was checked as it was answered, so this is a schema
+            // the fields cannot satisfy; asking again would not help.
+            Err(err) => {
+                self.say(&format!("  {err}\n")).await;
+                ElicitResult::cancel()
+            }
+        }
+    }
+
+    /// Asks for one field until the answer fits its schema.
+    async fn field(&mut self, name: &str, schema: &Schema, required: bool) -> Answer {
+        let (title, descr) = describe(schema);
+        let options = options(schema);
+        let default = default(schema);
+
+        let mut prompt = title.unwrap_or(name).to_owned();
+        if required {
+            prompt.push_str(" *");
+        }
+        if let Some(descr) = descr {
+            prompt.push_str(&format!(" ({descr})"));
+        }
+        if let Some(options) = &options {
+            for (i, (_, title)) in options.iter().enumerate() {
+                prompt.push_str(&format!("\n  {}) {title}", i + 1));
+            }
+            prompt.push('\n');
+        }
+        match &default {
+            Some(value) => prompt.push_str(&format!(" [{}]: ", show(value))),
+            None if matches!(schema, Schema::Boolean(_)) => prompt.push_str(" [y/n]: "),
+            None => prompt.push_str(": "),
+        }
+
+        loop {
+            let Some(answer) = self.ask(&prompt).await else {
+                return Answer::Cancel;
+            };
+            let value = match answer.as_str() {
+                CANCEL => return Answer::Cancel,
+                DECLINE => return Answer::Decline,
+                "" => match &default {
+                    Some(value) => return Answer::Value(value.clone()),
+                    None if !required => return Answer::Skip,
+                    None => {
+                        self.say("  This field is required.\n").await;
+                        continue;
+                    }
+                },
+                answer => parse(schema, options.as_deref(), answer),
+            };
+            match value.and_then(|value| match schema.validate(&value) {
+                Ok(()) => Ok(value),
+                Err(err) => Err(err.to_string()),
+            }) {
+                Ok(value) => return Answer::Value(value),
+                Err(err) => self.say(&format!("  {err}\n")).await,
+            }
+        }
+    }
+
+    async fn visit(&mut self, params: ElicitRequestUrlParams, open: bool) -> ElicitResult {
+        let url = params.url.to_string();
+        self.say(&format!("\n{}\nOpen {url}\n", params.message))
+            .await;
+        // The URL is the server's to choose: nothing is launched without the
+        // user saying so, and nothing but a web page.
+        if open && is_web(&url) {
+            let Some(answer) = self.ask("Open it in the browser? [y/N]: ").await else {
+                return ElicitResult::cancel();
+            };
+            if matches!(answer.to_ascii_lowercase().as_str(), "y" | "yes") && !open_url(&url) {
+                self.say("  The browser could not be started.\n").await;
+            }
+        }
+
+        loop {
+            let Some(answer) = self.ask("Done? [y]es / [n]o / [c]ancel: ").await else {
+                return ElicitResult::cancel();
+            };
+            match answer.to_ascii_lowercase().as_str() {
+                "y" | "yes" => return ElicitResult::accept(),
+                "n" | "no" => return ElicitResult::decline(),
+                "c" | "cancel" => return ElicitResult::cancel(),
+                _ => self.say("  Answer y, n or c.\n").await,
+            }
+        }
+    }
+
+    /// Shows a sampling request; `false` when the user rejects it.
+    async fn review_request(&mut self, params: &mut CreateMessageRequestParams) -> bool {
+        let mut shown = String::from("\n-- Sampling request --\n");
+        if let Some(sys_prompt) = &params.sys_prompt {
+            shown.push_str(&format!("[system] {sys_prompt}\n"));
+        }
+        for message in &params.messages {
+            for content in message.content.iter() {
+                shown.push_str(&format!(
+                    "[{}] {}\n",
+                    role(&message.role),
+                    show_content(content)
+                ));
+            }
+        }
+        shown.push_str(&format!("(up to {} tokens)\n", params.max_tokens));
+        self.say(&shown).await;
+
+        match self
+            .choose("Send to the model? [y]es / [e]dit / [n]o: ")
+            .await
+        {
+            Some('y') => true,
+            Some('e') => {
+                let kept = "(Enter keeps it)";
+                if let Some(sys_prompt) = &mut params.sys_prompt
+                    && let Some(edited) = self.ask(&format!("System prompt {kept}: ")).await
+                    && !edited.is_empty()
+                {
+                    *sys_prompt = edited;
+                }
+                let last_text = params
+                    .messages
+                    .iter_mut()
+                    .rev()
+                    .filter(|m| matches!(m.role, Role::User))
+                    .find_map(|m| m.content.iter_mut().rev().find_map(as_text_mut));
+                if let Some(text) = last_text
+                    && let Some(edited) = self.ask(&format!("Message {kept}: ")).await
+                    && !edited.is_empty()
+                {
+                    *text = edited;
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Shows a model's response; `false` when the user rejects it.
+    async fn review_result(&mut self, result: &mut CreateMessageResult) -> bool {
+        let mut shown = format!("\n-- Model response ({}) --\n", result.model);
+        for content in result.content.iter() {
+            shown.push_str(&format!("{}\n", show_content(content)));
+        }
+        self.say(&shown).await;
+
+        match self
+            .choose("Return it to the server? [y]es / [e]dit / [n]o: ")
+            .await
+        {
+            Some('y') => true,
+            Some('e') => {
+                if let Some(edited) = self.ask("Response (Enter keeps it): ").await
+                    && !edited.is_empty()
+                {
+                    result.content = Content::text(edited).into();
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The result of a sampling request the user turned down.
+fn rejected() -> CreateMessageResult {
+    CreateMessageResult::assistant().with_stop_reason(REJECTED)
+}
+
+/// A schema's title and description.
+fn describe(schema: &Schema) -> (Option<&str>, Option<&str>) {
+    let (title, descr) = match schema {
+        Schema::String(s) => (&s.title, &s.descr),
+        Schema::Number(s) => (&s.title, &s.descr),
+        Schema::Boolean(s) => (&s.title, &s.descr),
+        Schema::SingleUntitledEnum(s) => (&s.title, &s.descr),
+        Schema::SingleTitledEnum(s) => (&s.title, &s.descr),
+        Schema::MultiUntitledEnum(s) => (&s.title, &s.descr),
+        Schema::MultiTitledEnum(s) => (&s.title, &s.descr),
+        Schema::LegacyEnum(s) => (&s.title, &s.descr),
+    };
+    (title.as_deref(), descr.as_deref())
+}
+
+/// An enum schema's options as value and title.
+fn options(schema: &Schema) -> Option<Vec<(String, String)>> {
+    let untitled = |values: &[String]| values.iter().map(|v| (v.clone(), v.clone())).collect();
+    Some(match schema {
+        Schema::SingleUntitledEnum(s) => untitled(&s.r#enum),
+        Schema::MultiUntitledEnum(s) => untitled(&s.items.r#enum),
+        Schema::SingleTitledEnum(s) => s
+            .one_of
+            .iter()
+            .map(|o| (o.value.clone(), o.title.clone()))
+            .collect(),
+        Schema::MultiTitledEnum(s) => s
+            .items
+            .any_of
+            .iter()
+            .map(|o| (o.value.clone(), o.title.clone()))
+            .collect(),
+        Schema::LegacyEnum(s) => match &s.enum_names {
+            Some(names) => s
+                .r#enum
+                .iter()
+                .cloned()
+                .zip(names.iter().cloned())
+                .collect(),
+            None => untitled(&s.r#enum),
+        },
+        Schema::String(_) | Schema::Number(_) | Schema::Boolean(_) => return None,
+    })
+}
+
+/// A schema's default value.
+fn default(schema: &Schema) -> Option<Value> {
+    match schema {
+        Schema::Boolean(s) => s.default.map(Value::Bool),
+        Schema::SingleUntitledEnum(s) => s.default.clone().map(Value::String),
+        Schema::SingleTitledEnum(s) => s.default.clone().map(Value::String),
+        Schema::MultiUntitledEnum(s) => s.default.clone().map(Value::from),
+        Schema::MultiTitledEnum(s) => s.default.clone().map(Value::from),
+        Schema::String(s) => s.extra.get("default").cloned(),
+        Schema::Number(s) => s.extra.get("default").cloned(),
+        Schema::LegacyEnum(s) => s.extra.get("default").cloned(),
+    }
+}
+
+/// Reads an answer as a value of the schema's type.
+fn parse(
+    schema: &Schema,
+    options: Option<&[(String, String)]>,
+    answer: &str,
+) -> Result<Value, String> {
+    match schema {
+        Schema::String(_) => Ok(Value::String(answer.to_owned())),
+        Schema::Number(s) if s.r#type == PropertyType::Integer => answer
+            .parse::<i64>()
+            .map(Value::from)
+            .map_err(|_| "Expected a whole number.".to_owned()),
+        Schema::Number(_) => answer
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number)
+            .ok_or_else(|| "Expected a number.".to_owned()),
+        Schema::Boolean(_) => match answer.to_ascii_lowercase().as_str() {
+            "y" | "yes" | "true" => Ok(Value::Bool(true)),
+            "n" | "no" | "false" => Ok(Value::Bool(false)),
+            _ => Err("Expected y or n.".to_owned()),
+        },
+        Schema::MultiUntitledEnum(_) | Schema::MultiTitledEnum(_) => answer
+            .split(',')
+            .map(|choice| pick(options.unwrap_or_default(), choice.trim()).map(Value::String))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array),
+        Schema::SingleUntitledEnum(_) | Schema::SingleTitledEnum(_) | Schema::LegacyEnum(_) => {
+            pick(options.unwrap_or_default(), answer).map(Value::String)
+        }
+    }
+}
+
+/// The option an answer picks: by its number, its value or its title.
+fn pick(options: &[(String, String)], answer: &str) -> Result<String, String> {
+    if let Ok(n) = answer.parse::<usize>()
+        && let Some((value, _)) = n.checked_sub(1).and_then(|i| options.get(i))
+    {
+        return Ok(value.clone());
+    }
+    options
+        .iter()
+        .find(|(value, title)| value == answer || title.eq_ignore_ascii_case(answer))
+        .map(|(value, _)| value.clone())
+        .ok_or_else(|| format!("Expected an option from 1 to {}.", options.len()))
+}
+
+/// A value the way the user would type it.
+fn show(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Bool(true) => "y".into(),
+        Value::Bool(false) => "n".into(),
+        Value::Array(items) => items.iter().map(show).collect::<Vec<_>>().join(", "),
+        value => value.to_string(),
+    }
+}
+
+/// A content item in one line: text as it is, anything else by its type.
+fn show_content(content: &Content) -> String {
+    match content.as_text() {
+        Some(text) => text.text.clone(),
+        None => format!("<{}>", content.get_type()),
+    }
+}
+
+fn as_text_mut(content: &mut Content) -> Option<&mut String> {
+    match content {
+        Content::Text(text) => Some(&mut text.text),
+        _ => None,
+    }
+}
+
+fn role(role: &Role) -> &'static str {
+    match role {
+        Role::User => "user",
+        Role::Assistant => "assistant",
+    }
+}
+
+/// Whether `url` is an `https` or `http` URL.
+fn is_web(url: &str) -> bool {
+    url.split_once("://").is_some_and(|(scheme, _)| {
+        scheme.eq_ignore_ascii_case("https") || scheme.eq_ignore_ascii_case("http")
+    })
+}
+
+/// Opens `url` in the system browser; `false` if that could not be started.
+fn open_url(url: &str) -> bool {
+    #[cfg(target_os = "macos")]
+    let mut command = std::process::Command::new("open");
+    // Not `cmd /C start`: cmd.exe would read `&` and `^` in the URL as its own.
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = std::process::Command::new("rundll32");
+        command.arg("url.dll,FileProtocolHandler");
+        command
+    };
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut command = std::process::Command::new("xdg-open");
+
+    command
+        .arg(url)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::elicitation::ElicitationAction;
+    use crate::types::{EnumOption, NumberSchema, TitledSingleSelectEnumSchema};
+    use serde_json::json;
+    use tokio::io::AsyncReadExt;
+
+    /// A terminal answering with `input`, and the end its prompts can be read
+    /// from once it is dropped.
+    fn piped(input: &str) -> (Terminal, tokio::io::DuplexStream) {
+        let (output, shown) = tokio::io::duplex(64 * 1024);
+        let input = std::io::Cursor::new(input.as_bytes().to_vec());
+        (Terminal::new(input, output), shown)
+    }
+
+    async fn shown(terminal: Terminal, mut shown: tokio::io::DuplexStream) -> String {
+        drop(terminal);
+        let mut text = String::new();
+        shown.read_to_string(&mut text).await.unwrap();
+        text
+    }
+
+    fn booking() -> ElicitRequestParams {
+        ElicitRequestParams::form("Book a room")
+            .with_required(
+                "nights",
+                Schema::Number(NumberSchema {
+                    r#type: PropertyType::Integer,
+                    min: Some(1.0),
+                    ..Default::default()
+                })
+                .with_title("Nights"),
+            )
+            .with_required(
+                "room",
+                Schema::SingleTitledEnum(TitledSingleSelectEnumSchema {
+                    one_of: vec![
+                        EnumOption::new("single", "Single room"),
+                        EnumOption::new("double", "Double room"),
+                    ],
+                    ..Default::default()
+                }),
+            )
+            .with_prop("notes", Schema::string())
+            .with_prop("newsletter", Schema::boolean().with_default(true))
+            .into()
+    }
+
+    #[tokio::test]
+    async fn it_fills_in_a_form_field_by_field() {
+        let (terminal, output) = piped("0\nthree\n2\n\n2\n\n\n");
+
+        let result = terminal.elicit(booking()).await;
+        assert!(result.is_accepted());
+        assert_eq!(
+            result.content,
+            Some(json!({ "nights": 2, "room": "double", "newsletter": true }))
+        );
+
+        let shown = shown(terminal, output).await;
+        assert!(shown.contains("Nights *: "), "got: {shown}");
+        assert!(shown.contains("  2) Double room"), "got: {shown}");
+        assert!(shown.contains("Number too small"), "got: {shown}");
+        assert!(shown.contains("Expected a whole number."), "got: {shown}");
+    }
+
+    #[tokio::test]
+    async fn a_required_field_is_asked_again_when_left_empty() {
+        let (terminal, output) = piped("\n3\nsingle room\nn\nquiet\n");
+
+        let result = terminal.elicit(booking()).await;
+        assert_eq!(
+            result.content,
+            Some(json!({ "nights": 3, "room": "single", "notes": "quiet", "newsletter": false }))
+        );
+        assert!(
+            shown(terminal, output)
+                .await
+                .contains("This field is required.")
+        );
+    }
+
+    #[tokio::test]
+    async fn a_form_can_be_declined_or_cancelled() {
+        let (terminal, _output) = piped("2\n!decline\n");
+        assert_eq!(
+            terminal.elicit(booking()).await.action,
+            ElicitationAction::Decline
+        );
+
+        let (terminal, _output) = piped("!cancel\n");
+        assert_eq!(
+            terminal.elicit(booking()).await.action,
+            ElicitationAction::Cancel
+        );
+
+        // Closed input cancels rather than accepting a half-filled form.
+        let (terminal, _output) = piped("2\n");
+        assert_eq!(
+            terminal.elicit(booking()).await.action,
+            ElicitationAction::Cancel
+        );
+    }
+
+    #[tokio::test]
+    async fn a_url_request_is_printed_and_confirmed() {
+        let (terminal, output) = piped("maybe\nn\n");
+        let params = ElicitRequestParams::url("https://example.com/pay", "Pay the bill").into();
+
+        let result = terminal.elicit(params).await;
+        assert!(result.is_declined());
+
+        let shown = shown(terminal, output).await;
+        assert!(
+            shown.contains("Open https://example.com/pay"),
+            "got: {shown}"
+        );
+        assert!(shown.contains("Answer y, n or c."), "got: {shown}");
+    }
+
+    #[tokio::test]
+    async fn the_browser_is_only_opened_with_consent() {
+        let (terminal, output) = piped("n\ny\n");
+        let terminal = terminal.with_browser();
+        let params = ElicitRequestParams::url("https://example.com/pay", "Pay the bill").into();
+
+        let result = terminal.elicit(params).await;
+        assert!(result.is_accepted());
+
+        let shown = shown(terminal, output).await;
+        assert!(
+            shown.contains("Open it in the browser? [y/N]"),
+            "got: {shown}"
+        );
+    }
+
+    #[tokio::test]
+    async fn only_web_urls_are_offered_to_the_browser() {
+        let (terminal, output) = piped("y\n");
+        let terminal = terminal.with_browser();
+        let params = ElicitRequestParams::url("file:///etc/passwd", "Look at this").into();
+
+        let result = terminal.elicit(params).await;
+        assert!(result.is_accepted());
+
+        let shown = shown(terminal, output).await;
+        assert!(shown.contains("Open file:///etc/passwd"), "got: {shown}");
+        assert!(!shown.contains("in the browser?"), "got: {shown}");
+
+        assert!(is_web("HTTPS://example.com"));
+        assert!(!is_web("javascript:alert(1)"));
+    }
+
+    fn question() -> CreateMessageRequestParams {
+        CreateMessageRequestParams::new()
+            .with_sys_prompt("Be brief.")
+            .with_message("What is the capital of France?")
+    }
+
+    fn answer(params: CreateMessageRequestParams) -> std::future::Ready<CreateMessageResult> {
+        let asked = params
+            .text()
+            .map(|t| t.text.clone())
+            .collect::<Vec<_>>()
+            .join(" ");
+        std::future::ready(
+            CreateMessageResult::assistant()
+                .with_model("echo")
+                .with_content(format!(
+                    "{} / {asked}",
+                    params.sys_prompt.unwrap_or_default()
+                ))
+                .end_turn(),
+        )
+    }
+
+    #[tokio::test]
+    async fn an_approved_sampling_request_is_forwarded() {
+        let (terminal, output) = piped("y\ny\n");
+
+        let result = terminal.sample(question(), answer).await;
+        assert_eq!(
+            result.text().next().unwrap().text,
+            "Be brief. / What is the capital of France?"
+        );
+
+        let shown = shown(terminal, output).await;
+        assert!(shown.contains("[system] Be brief."), "got: {shown}");
+        assert!(
+            shown.contains("[user] What is the capital of France?"),
+            "got: {shown}"
+        );
+        assert!(
+            shown.contains("-- Model response (echo) --"),
+            "got: {shown}"
+        );
+    }
+
+    #[tokio::test]
+    async fn a_sampling_request_and_its_response_can_be_edited() {
+        let (terminal, _output) = piped("e\n\nWhat is the capital of Italy?\ne\nRome.\n");
+
+        let forwarded = std::sync::Mutex::new(None);
+        let result = terminal
+            .sample(question(), |params| {
+                *forwarded.lock().unwrap() = Some(params.clone());
+                answer(params)
+            })
+            .await;
+
+        let forwarded = forwarded.into_inner().unwrap().unwrap();
+        assert_eq!(forwarded.sys_prompt.as_deref(), Some("Be brief."));
+        assert_eq!(
+            forwarded.text().next().unwrap().text,
+            "What is the capital of Italy?"
+        );
+        assert_eq!(result.text().next().unwrap().text, "Rome.");
+    }
+
+    #[tokio::test]
+    async fn a_rejected_sampling_request_never_reaches_the_model() {
+        let (terminal, _output) = piped("n\n");
+
+        let result = terminal
+            .sample(question(), |_| async {
+                unreachable!("forwarded a rejected request");
+                #[allow(unreachable_code)]
+                CreateMessageResult::assistant()
+            })
+            .await;
+        assert_eq!(result.stop_reason, Some(REJECTED.into()));
+        assert!(result.content.is_empty());
+    }
+}
@@ -6,8 +6,10 @@ use std::fmt;
 use std::io::Error as IoError;
 
 pub use error_code::ErrorCode;
+pub use kind::{ErrorKind, FieldError};
 
 pub mod error_code;
+mod kind;
 
 type BoxError = Box<dyn StdError + Send + Sync>;
 
@@ -112,6 +114,35 @@ impl Error {
         self.data.as_ref()
     }
 
+    /// Returns a reference to the cause of this error if it is of type `E`.
+    ///
+    /// # Example
+    /// ```
+    /// use neva::error::Error;
+    ///
+    /// let err = Error::from(std::io::Error::other("disk full"));
+    /// assert!(err.downcast_ref::<std::io::Error>().is_some());
+    /// ```
+    #[inline]
+    pub fn downcast_ref<E: StdError + 'static>(&self) -> Option<&E> {
+        self.inner.downcast_ref()
+    }
+
+    /// Returns `true` if the cause of this error is of type `E`.
+    #[inline]
+    pub fn is<E: StdError + 'static>(&self) -> bool {
+        self.inner.is::<E>()
+    }
+
+    /// Takes the cause of this error out if it is of type `E`, or gives the
+    /// error back otherwise.
+    pub fn downcast<E: StdError + 'static>(self) -> Result<E, Self> {
+        match self.inner.downcast::<E>() {
+            Ok(err) => Ok(*err),
+            Err(inner) => Err(Self { inner, ..self }),
+        }
+    }
+
     /// Builds the internal MRTR "input required" sentinel error.
     ///
     /// Returned by `Context::elicit` on a cache miss to unwind the handler;
@@ -64,6 +64,10 @@ pub enum ErrorCode {
     /// and converted into an `InputRequiredResult`.
     #[cfg(not(feature = "legacy-spec"))]
     InputRequired = -99997,
+
+    /// [Internal code] The client's circuit breaker is open, so the request
+    /// was failed fast without being sent
+    CircuitOpen = -99996,
 }
 
 impl From<ErrorCode> for i32 {
@@ -96,6 +100,7 @@ impl TryFrom<i32> for ErrorCode {
             -99998 => Ok(ErrorCode::Timeout),
             #[cfg(not(feature = "legacy-spec"))]
             -99997 => Ok(ErrorCode::InputRequired),
+            -99996 => Ok(ErrorCode::CircuitOpen),
             _ => Err(()),
         }
     }
@@ -148,6 +153,7 @@ impl Display for ErrorCode {
             ErrorCode::Timeout => write!(f, "Request timed out"),
             #[cfg(not(feature = "legacy-spec"))]
             ErrorCode::InputRequired => write!(f, "Input required"),
+            ErrorCode::CircuitOpen => write!(f, "Circuit breaker is open"),
         }
     }
 }
@@ -161,7 +167,7 @@ impl From<ErrorCode> for Error {
 impl ErrorCode {
     /// Returns the wire-safe equivalent of this code.
     ///
-    /// Internal codes (`RequestCancelled`, `Timeout`) fall outside the JSON-RPC 2.0
+    /// Internal codes (`RequestCancelled`, `Timeout`, `CircuitOpen`) fall outside the JSON-RPC 2.0
     /// reserved range (`-32768` to `-32000`) and must never appear in a response
     /// payload. This method maps them to [`ErrorCode::InternalError`] so callers can
     /// always serialise a spec-compliant code.
@@ -184,7 +190,7 @@ impl ErrorCode {
     #[inline]
     pub fn wire_code(self) -> Self {
         match self {
-            Self::RequestCancelled | Self::Timeout => Self::InternalError,
+            Self::RequestCancelled | Self::Timeout | Self::CircuitOpen => Self::InternalError,
             #[cfg(not(feature = "legacy-spec"))]
             Self::InputRequired => Self::InternalError,
             #[cfg(not(feature = "legacy-spec"))]
@@ -242,6 +248,7 @@ mod tests {
             (-32042, ErrorCode::UrlElicitationRequiredError),
             (-99999, ErrorCode::RequestCancelled),
             (-99998, ErrorCode::Timeout),
+            (-99996, ErrorCode::CircuitOpen),
         ];
 
         for (code, val) in codes {
@@ -266,6 +273,7 @@ mod tests {
             ("-32042", ErrorCode::UrlElicitationRequiredError),
             ("-99999", ErrorCode::RequestCancelled),
             ("-99998", ErrorCode::Timeout),
+            ("-99996", ErrorCode::CircuitOpen),
         ];
 
         for (code, val) in codes {
@@ -284,6 +292,7 @@ mod tests {
             ErrorCode::InternalError
         );
         assert_eq!(ErrorCode::Timeout.wire_code(), ErrorCode::InternalError);
+        assert_eq!(ErrorCode::CircuitOpen.wire_code(), ErrorCode::InternalError);
     }
 
     #[test]
@@ -0,0 +1,433 @@
+//! The MCP error taxonomy: what an [`Error`] is, beyond its numeric code
+
+use crate::error::{Error, ErrorCode};
+use crate::types::ToolError;
+use crate::types::elicitation::{ElicitRequestUrlParams, UrlElicitationRequiredError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+
+#[cfg(not(feature = "legacy-spec"))]
+use crate::types::mrtr::ClientMrtrCapabilities;
+
+/// What an [`Error`] is, together with the part of its `data` a caller can act
+/// on.
+///
+/// Read back from the error's code and `data` by [`Error::kind`], so an error
+/// decoded from a peer's response has the kind the peer built it with. A
+/// payload the peer left out, or shaped differently, reads as empty.
+///
+/// # Example
+/// ```
+/// use neva::error::{Error, ErrorKind, FieldError};
+///
+/// let err = Error::invalid_params(
+///     "invalid arguments",
+///     [FieldError::new("/city", "must not be empty")],
+/// );
+///
+/// match err.kind() {
+///     ErrorKind::InvalidParams(fields) => assert_eq!(fields[0].pointer, "/city"),
+///     kind => panic!("unexpected {kind:?}"),
+/// }
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The message was not valid JSON.
+    Parse,
+
+    /// The message was not a valid JSON-RPC request.
+    InvalidRequest,
+
+    /// The method does not exist or is not available.
+    MethodNotFound,
+
+    /// The parameters were invalid, with the fields at fault when the error
+    /// names them.
+    InvalidParams(Vec<FieldError>),
+
+    /// No resource is there to read, with its URI when the error names it.
+    ///
+    /// Under MCP 2026-07-28 this is an `InvalidParams` error that names a
+    /// `uri` in its `data`.
+    ResourceNotFound(Option<String>),
+
+    /// The server failed to process the request.
+    Internal,
+
+    /// The request needs the user to visit the URLs of these elicitations
+    /// first.
+    UrlElicitationRequired(Vec<ElicitRequestUrlParams>),
+
+    /// The request's HTTP headers do not match its body.
+    #[cfg(not(feature = "legacy-spec"))]
+    HeaderMismatch,
+
+    /// The request needs client capabilities the client did not declare.
+    #[cfg(not(feature = "legacy-spec"))]
+    MissingClientCapability(ClientMrtrCapabilities),
+
+    /// The protocol version the request was made under is not served.
+    #[cfg(not(feature = "legacy-spec"))]
+    UnsupportedProtocolVersion {
+        /// The versions on offer instead.
+        supported: Vec<String>,
+        /// The version the request stated, if the error names it.
+        requested: Option<String>,
+    },
+
+    /// The request was cancelled.
+    Cancelled,
+
+    /// No response came before the request timed out.
+    Timeout,
+
+    /// The client's circuit breaker is open, so the request was not sent.
+    CircuitOpen {
+        /// How long until the circuit lets a request through again.
+        retry_after: Option<Duration>,
+    },
+
+    /// [Internal] A handler asked for more input over MRTR.
+    #[cfg(not(feature = "legacy-spec"))]
+    InputRequired,
+
+    /// A tool failed at its task.
+    ///
+    /// Built from a [`ToolError`], on the server by the tool itself and on the
+    /// client by [`CallToolResponse::into_result`](crate::types::CallToolResponse::into_result)
+    /// from an `isError` result.
+    Tool(ToolError),
+}
+
+/// One field that failed validation, as an `InvalidParams` error names it in
+/// `data.errors`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldError {
+    /// A JSON pointer to the offending value, such as `/city`; empty for the
+    /// parameters as a whole.
+    #[serde(default)]
+    pub pointer: String,
+
+    /// What is wrong with it.
+    pub message: String,
+
+    /// A JSON pointer to the schema keyword that rejected it, if a schema did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_path: Option<String>,
+}
+
+impl FieldError {
+    /// Creates a new [`FieldError`] for the value at `pointer`.
+    #[inline]
+    pub fn new(pointer: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            pointer: pointer.into(),
+            message: message.into(),
+            schema_path: None,
+        }
+    }
+
+    /// Names the schema keyword that rejected the value.
+    #[inline]
+    pub fn with_schema_path(mut self, schema_path: impl Into<String>) -> Self {
+        self.schema_path = Some(schema_path.into());
+        self
+    }
+}
+
+impl Error {
+    /// The error code.
+    #[inline]
+    pub fn code(&self) -> ErrorCode {
+        self.code
+    }
+
+    /// What this error is, decoded from its code and `data`.
+    ///
+    /// See [`ErrorKind`].
+    #[allow(deprecated)]
+    pub fn kind(&self) -> ErrorKind {
+        if let Some(err) = self.downcast_ref::<ToolError>() {
+            return ErrorKind::Tool(err.clone());
+        }
+        let data = self.wire_data();
+        let field = |name: &str| data.as_ref().and_then(|data| data.get(name));
+        match self.code {
+            ErrorCode::ParseError => ErrorKind::Parse,
+            ErrorCode::InvalidRequest => ErrorKind::InvalidRequest,
+            ErrorCode::MethodNotFound => ErrorKind::MethodNotFound,
+            #[cfg(not(feature = "legacy-spec"))]
+            ErrorCode::InvalidParams if field("errors").is_none() && field("uri").is_some() => {
+                ErrorKind::ResourceNotFound(string(field("uri")))
+            }
+            ErrorCode::InvalidParams => ErrorKind::InvalidParams(decode(field("errors"))),
+            ErrorCode::ResourceNotFound => ErrorKind::ResourceNotFound(string(field("uri"))),
+            ErrorCode::InternalError => ErrorKind::Internal,
+            ErrorCode::UrlElicitationRequiredError => {
+                ErrorKind::UrlElicitationRequired(decode(field("elicitations")))
+            }
+            #[cfg(not(feature = "legacy-spec"))]
+            ErrorCode::HeaderMismatch => ErrorKind::HeaderMismatch,
+            #[cfg(not(feature = "legacy-spec"))]
+            ErrorCode::MissingRequiredClientCapability => {
+                ErrorKind::MissingClientCapability(decode(field("requiredCapabilities")))
+            }
+            #[cfg(not(feature = "legacy-spec"))]
+            ErrorCode::UnsupportedProtocolVersion => ErrorKind::UnsupportedProtocolVersion {
+                supported: decode(field("supported")),
+                requested: string(field("requested")),
+            },
+            ErrorCode::RequestCancelled => ErrorKind::Cancelled,
+            ErrorCode::Timeout => ErrorKind::Timeout,
+            ErrorCode::CircuitOpen => ErrorKind::CircuitOpen {
+                retry_after: field("retryAfterMs")
+                    .and_then(Value::as_u64)
+                    .map(Duration::from_millis),
+            },
+            #[cfg(not(feature = "legacy-spec"))]
+            ErrorCode::InputRequired => ErrorKind::InputRequired,
+        }
+    }
+
+    /// An `InvalidParams` error naming the fields at fault in `data.errors`.
+    ///
+    /// # Example
+    /// ```
+    /// use neva::error::{Error, FieldError};
+    ///
+    /// let err = Error::invalid_params("invalid city", [FieldError::new("/city", "is empty")]);
+    /// assert_eq!(err.data().unwrap()["errors"][0]["pointer"], "/city");
+    /// ```
+    pub fn invalid_params(
+        message: impl Into<String>,
+        errors: impl IntoIterator<Item = FieldError>,
+    ) -> Self {
+        let errors = errors.into_iter().collect::<Vec<_>>();
+        let err = Self::new(ErrorCode::InvalidParams, message.into());
+        match errors.is_empty() {
+            true => err,
+            false => err.with_data(serde_json::json!({ "errors": errors })),
+        }
+    }
+
+    /// A `MethodNotFound` error for `method`.
+    #[inline]
+    pub fn method_not_found(method: impl std::fmt::Display) -> Self {
+        Self::new(
+            ErrorCode::MethodNotFound,
+            format!("Method not found: {method}"),
+        )
+    }
+
+    /// A resource-not-found error naming `uri` in `data.uri`, under the code
+    /// the active protocol version gives it (see
+    /// [`ErrorCode::RESOURCE_NOT_FOUND`]).
+    #[inline]
+    pub fn resource_not_found(uri: impl std::fmt::Display) -> Self {
+        let uri = uri.to_string();
+        Self::new(
+            ErrorCode::RESOURCE_NOT_FOUND,
+            format!("Resource not found: {uri}"),
+        )
+        .with_data(serde_json::json!({ "uri": uri }))
+    }
+
+    /// A `UrlElicitationRequiredError` listing the elicitations the user has
+    /// to complete before the request is retried.
+    pub fn url_elicitation_required(
+        message: impl Into<String>,
+        elicitations: impl IntoIterator<Item = ElicitRequestUrlParams>,
+    ) -> Self {
+        UrlElicitationRequiredError::new(elicitations).to_error(message)
+    }
+
+    /// A `HeaderMismatch` error: the request's HTTP headers do not match its
+    /// body.
+    #[cfg(not(feature = "legacy-spec"))]
+    #[inline]
+    pub fn header_mismatch(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::HeaderMismatch, message.into())
+    }
+
+    /// A `MissingRequiredClientCapability` error naming what the client has
+    /// to declare in `data.requiredCapabilities`.
+    #[cfg(not(feature = "legacy-spec"))]
+    pub fn missing_client_capability(
+        message: impl Into<String>,
+        required: ClientMrtrCapabilities,
+    ) -> Self {
+        Self::new(ErrorCode::MissingRequiredClientCapability, message.into())
+            .with_data(serde_json::json!({ "requiredCapabilities": required }))
+    }
+
+    /// An `UnsupportedProtocolVersion` error for the `requested` version,
+    /// naming the `supported` ones in `data`.
+    ///
+    /// # Example
+    /// ```
+    /// use neva::error::{Error, ErrorKind};
+    ///
+    /// let err = Error::unsupported_protocol_version("2025-06-18", ["2026-07-28"]);
+    /// assert!(matches!(
+    ///     err.kind(),
+    ///     ErrorKind::UnsupportedProtocolVersion { supported, .. } if supported == ["2026-07-28"]
+    /// ));
+    /// ```
+    #[cfg(not(feature = "legacy-spec"))]
+    pub fn unsupported_protocol_version<I, S>(requested: impl Into<String>, supported: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let requested = requested.into();
+        let supported = supported.into_iter().map(Into::into).collect::<Vec<_>>();
+        Self::new(
+            ErrorCode::UnsupportedProtocolVersion,
+            format!("Unsupported MCP protocol version: {requested}"),
+        )
+        .with_data(serde_json::json!({
+            "supported": supported,
+            "requested": requested,
+        }))
+    }
+
+    /// The `data` this error goes on the wire with: what was attached with
+    /// [`Self::with_data`], or else what its cause says about itself.
+    pub(crate) fn wire_data(&self) -> Option<Value> {
+        if let Some(data) = &self.data {
+            return Some(data.clone());
+        }
+        if let Some(err) = self.downcast_ref::<UrlElicitationRequiredError>() {
+            return serde_json::to_value(err).ok();
+        }
+        #[cfg(not(feature = "legacy-spec"))]
+        if self.code == ErrorCode::UnsupportedProtocolVersion {
+            return Some(serde_json::json!({ "supported": [crate::LATEST_PROTOCOL_VERSION] }));
+        }
+        None
+    }
+}
+
+impl From<UrlElicitationRequiredError> for Error {
+    #[inline]
+    fn from(err: UrlElicitationRequiredError) -> Self {
+        Self::new(ErrorCode::UrlElicitationRequiredError, err)
+    }
+}
+
+#[inline]
+fn decode<T: serde::de::DeserializeOwned + Default>(value: Option<&Value>) -> T {
+    value
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+        .unwrap_or_default()
+}
+
+#[inline]
+fn string(value: Option<&Value>) -> Option<String> {
+    value.and_then(Value::as_str).map(str::to_owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ErrorDetails;
+
+    fn round_trip(err: Error) -> Error {
+        let wire = serde_json::to_value(ErrorDetails::from(err)).unwrap();
+        Error::from(serde_json::from_value::<ErrorDetails>(wire).unwrap())
+    }
+
+    #[test]
+    fn invalid_params_name_their_fields_across_the_wire() {
+        let err = round_trip(Error::invalid_params(
+            "invalid arguments",
+            [FieldError::new("/city", "is empty").with_schema_path("/properties/city/minLength")],
+        ));
+        let ErrorKind::InvalidParams(fields) = err.kind() else {
+            panic!("unexpected {:?}", err.kind());
+        };
+        assert_eq!(
+            fields,
+            [FieldError::new("/city", "is empty").with_schema_path("/properties/city/minLength")]
+        );
+    }
+
+    #[test]
+    fn a_resource_not_found_names_its_uri() {
+        let err = round_trip(Error::resource_not_found("file:///missing"));
+        assert!(matches!(
+            err.kind(),
+            ErrorKind::ResourceNotFound(Some(uri)) if uri == "file:///missing"
+        ));
+        assert_eq!(err.code(), ErrorCode::RESOURCE_NOT_FOUND.wire_code());
+    }
+
+    #[test]
+    fn a_typed_cause_fills_in_the_data() {
+        let err = Error::from(UrlElicitationRequiredError::new([]));
+        assert!(err.downcast_ref::<UrlElicitationRequiredError>().is_some());
+
+        let details = ErrorDetails::from(err);
+        assert_eq!(
+            details.data,
+            Some(serde_json::json!({ "elicitations": [] }))
+        );
+    }
+
+    #[test]
+    fn codes_without_a_payload_decode_to_their_kind() {
+        assert!(matches!(
+            Error::from(ErrorCode::Timeout).kind(),
+            ErrorKind::Timeout
+        ));
+        assert!(matches!(
+            Error::method_not_found("tools/nope").kind(),
+            ErrorKind::MethodNotFound
+        ));
+        assert!(matches!(
+            Error::new(ErrorCode::InvalidParams, "bad").kind(),
+            ErrorKind::InvalidParams(fields) if fields.is_empty()
+        ));
+    }
+
+    #[cfg(not(feature = "legacy-spec"))]
+    #[test]
+    fn version_and_capability_errors_carry_their_payloads() {
+        let err = round_trip(Error::unsupported_protocol_version(
+            "2025-06-18",
+            ["2026-07-28"],
+        ));
+        let ErrorKind::UnsupportedProtocolVersion {
+            supported,
+            requested,
+        } = err.kind()
+        else {
+            panic!("unexpected {:?}", err.kind());
+        };
+        assert_eq!(supported, ["2026-07-28"]);
+        assert_eq!(requested.as_deref(), Some("2025-06-18"));
+
+        let required = ClientMrtrCapabilities {
+            roots: true,
+            ..Default::default()
+        };
+        let err = round_trip(Error::missing_client_capability("no roots", required));
+        assert!(matches!(
+            err.kind(),
+            ErrorKind::MissingClientCapability(caps) if caps.roots && !caps.sampling
+        ));
+    }
+
+    #[cfg(not(feature = "legacy-spec"))]
+    #[test]
+    fn a_bare_unsupported_version_still_says_what_is_supported() {
+        let details = ErrorDetails::from(Error::from(ErrorCode::UnsupportedProtocolVersion));
+        assert_eq!(
+            details.data.unwrap()["supported"],
+            serde_json::json!([crate::LATEST_PROTOCOL_VERSION])
+        );
+    }
+}
@@ -98,18 +98,20 @@ pub mod macros;
 #[cfg(feature = "server")]
 pub mod middleware;
 pub mod shared;
+#[cfg(feature = "testing")]
+pub mod testing;
 #[cfg(any(feature = "server", feature = "client"))]
 pub mod transport;
 pub mod types;
 
 #[cfg(feature = "client-macros")]
 pub use neva_macros::elicitation;
-#[cfg(feature = "macros")]
-pub use neva_macros::json_schema;
 #[cfg(all(feature = "client-macros", feature = "legacy-spec"))]
 pub use neva_macros::sampling;
+#[cfg(feature = "macros")]
+pub use neva_macros::{ElicitForm, json_schema};
 #[cfg(feature = "server-macros")]
-pub use neva_macros::{completion, handler, prompt, resource, resources, tool};
+pub use neva_macros::{completion, handler, prompt, resource, resources, tool, toolset};
 
 pub(crate) const SDK_NAME: &str = "neva";
 #[cfg(any(feature = "server", feature = "client"))]
@@ -488,7 +490,8 @@ pub mod prelude {
     pub use crate::transport::http::{DevCertMode, TlsConfig};
     #[cfg(feature = "http-server")]
     pub use crate::transport::{
-        HttpContext, HttpEngine, HttpRequest, HttpResponse, HttpServer, StreamResponse, handlers,
+        BodyStream, HttpContext, HttpEngine, HttpRequest, HttpResponse, HttpServer, StreamResponse,
+        handlers,
     };
 
     #[cfg(all(feature = "server", not(feature = "legacy-spec")))]
@@ -496,7 +499,11 @@ pub mod prelude {
     #[cfg(all(feature = "server", not(feature = "legacy-spec"), feature = "tasks"))]
     pub use crate::app::extension::TasksExtension;
     #[cfg(feature = "server")]
-    pub use crate::app::{App, context::Context, options};
+    pub use crate::app::{
+        App,
+        context::{Context, Sandbox, SandboxedPath},
+        options,
+    };
     #[cfg(feature = "server")]
     pub use crate::middleware::{MwContext, Next};
 
@@ -507,12 +514,12 @@ pub mod prelude {
 
     #[cfg(feature = "client-macros")]
     pub use crate::elicitation;
-    #[cfg(feature = "macros")]
-    pub use crate::json_schema;
     #[cfg(all(feature = "client-macros", feature = "legacy-spec"))]
     pub use crate::sampling;
+    #[cfg(feature = "macros")]
+    pub use crate::{ElicitForm, json_schema};
     #[cfg(feature = "server-macros")]
-    pub use crate::{completion, handler, prompt, resource, resources, tool};
+    pub use crate::{completion, handler, prompt, resource, resources, tool, toolset};
 
     #[cfg(feature = "di")]
     pub use crate::di::Dc;
@@ -2,6 +2,7 @@
 
 use super::inventory;
 use crate::App;
+use std::sync::Arc;
 
 /// Registrar unit for tools, resources, templates and prompts
 #[derive(Debug)]
@@ -23,3 +24,39 @@ impl App {
         }
     }
 }
+
+/// A group of tools, resources and prompts served by one shared instance.
+///
+/// Implemented by [`#[toolset]`](crate::toolset) for the `impl` block it
+/// annotates, and registered with [`App::add_toolset`].
+pub trait Toolset: Send + Sync + 'static {
+    /// Registers every item in the group, each bound to this instance.
+    fn register(self: Arc<Self>, app: &mut App);
+}
+
+impl App {
+    /// Registers a [`Toolset`]: every tool, resource and prompt its `impl`
+    /// block declares, all calling into `toolset`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use neva::prelude::*;
+    ///
+    /// struct Counter(std::sync::atomic::AtomicUsize);
+    ///
+    /// #[toolset]
+    /// impl Counter {
+    ///     #[tool(descr = "Counts the calls")]
+    ///     async fn next(&self) -> usize {
+    ///         self.0.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+    ///     }
+    /// }
+    ///
+    /// let mut app = App::new();
+    /// app.add_toolset(Counter(Default::default()));
+    /// ```
+    pub fn add_toolset<T: Toolset>(&mut self, toolset: T) -> &mut Self {
+        Arc::new(toolset).register(self);
+        self
+    }
+}
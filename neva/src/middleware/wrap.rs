@@ -200,6 +200,28 @@ impl App {
         self
     }
 
+    /// Registers a middleware that runs only
+    /// if the MCP server received a `resources/read` request
+    /// that the resource template `name` serves
+    pub fn wrap_resource<F, R>(&mut self, name: &'static str, middleware: F) -> &mut Self
+    where
+        F: Fn(MwContext, Next) -> R + Clone + Send + Sync + 'static,
+        R: Future<Output = Response> + Send + 'static,
+    {
+        let mw = move |ctx: MwContext, next: Next| {
+            let middleware = middleware.clone();
+            async move {
+                if reads_template(&ctx, name) {
+                    middleware(ctx, next).await
+                } else {
+                    next(ctx).await
+                }
+            }
+        };
+        self.options.add_middleware(make_mw(mw));
+        self
+    }
+
     /// Registers a middleware that runs only
     /// if the MCP server received a `prompt/get` request
     pub fn wrap_prompt<F, R>(&mut self, name: &'static str, middleware: F) -> &mut Self
@@ -221,3 +243,26 @@ impl App {
         self
     }
 }
+
+/// Whether `ctx` holds a `resources/read` request routed to the resource
+/// template `name`.
+fn reads_template(ctx: &MwContext, name: &str) -> bool {
+    let Message::Request(req) = &ctx.msg else {
+        return false;
+    };
+    if req.method != crate::types::resource::commands::READ {
+        return false;
+    }
+    let Some(uri) = req
+        .params
+        .as_ref()
+        .and_then(|p| p.get("uri"))
+        .and_then(|uri| uri.as_str())
+    else {
+        return false;
+    };
+    ctx.runtime
+        .options()
+        .read_resource(&uri.into())
+        .is_some_and(|(handler, _)| handler.template == name)
+}
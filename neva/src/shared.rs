@@ -32,6 +32,10 @@ mod arc_slice;
 mod arc_str;
 mod either;
 mod into_args;
+// Tool schemas are checked on whichever end opted in: the client against what
+// `tools/list` declared, the server against the tools it registered.
+#[cfg(any(feature = "client", feature = "server-validation"))]
+pub(crate) mod json_schema;
 mod memchr;
 #[cfg(feature = "tracing")]
 mod message_registry;
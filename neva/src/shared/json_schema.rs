@@ -0,0 +1,53 @@
+//! Checking JSON values against a tool's schemas.
+//!
+//! Both ends report a failed check the same way: one [`FieldError`] per
+//! violation, naming where in the instance it is (`pointer`), which keyword of the schema
+//! it broke (`schemaPath`), and what went wrong (`message`). A caller that gets
+//! such a list back can act on each entry without parsing prose.
+
+use crate::error::{Error, FieldError};
+use crate::types::ToolInputSchema;
+use jsonschema::Validator;
+use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Every violation of `validator`'s schema in `instance`; empty when there is
+/// none.
+pub(crate) fn violations(validator: &Validator, instance: &Value) -> Vec<FieldError> {
+    validator
+        .iter_errors(instance)
+        .map(|err| {
+            FieldError::new(err.instance_path().as_str(), err.to_string())
+                .with_schema_path(err.schema_path().as_str())
+        })
+        .collect()
+}
+
+/// `tools/call` arguments as the instance an input schema is checked against.
+///
+/// An absent `arguments` is an empty object as far as the schema goes: a tool
+/// that requires nothing is called without any.
+pub(crate) fn arguments(args: Option<&HashMap<String, Value>>) -> Value {
+    Value::Object(
+        args.map(|args| args.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+            .unwrap_or_default(),
+    )
+}
+
+/// A tool schema as the JSON a validator compiles.
+///
+/// Under MCP 2026-07-28 the schema already is one and is borrowed as is; the
+/// legacy `ToolSchema` struct has to be serialized first.
+pub(crate) fn schema_value(schema: &ToolInputSchema) -> Result<Cow<'_, Value>, Error> {
+    #[cfg(feature = "legacy-spec")]
+    {
+        serde_json::to_value(schema)
+            .map(Cow::Owned)
+            .map_err(Error::from)
+    }
+    #[cfg(not(feature = "legacy-spec"))]
+    {
+        Ok(Cow::Borrowed(schema.as_value()))
+    }
+}
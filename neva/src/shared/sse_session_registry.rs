@@ -24,6 +24,18 @@ pub(crate) struct SseSessionRegistry {
     next_gen: AtomicU64,
 }
 
+/// One session as the admin endpoint reports it.
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SseSessionInfo {
+    id: Uuid,
+    /// Whether a live stream is attached right now.
+    connected: bool,
+    /// Events held for `Last-Event-ID` replay.
+    buffered: usize,
+    idle_seconds: u64,
+}
+
 struct SseSession {
     sender: Sender<(u64, Arc<Message>)>,
     buffer: Mutex<VecDeque<(u64, Arc<Message>)>>,
@@ -269,6 +281,26 @@ impl SseSessionRegistry {
         }
     }
 
+    /// Describes every session this registry holds, for the admin endpoint.
+    pub(crate) fn sessions(&self) -> Vec<SseSessionInfo> {
+        let now = Instant::now();
+        self.sessions
+            .iter()
+            .map(|entry| {
+                let last_activity = *entry
+                    .last_activity
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner());
+                SseSessionInfo {
+                    id: *entry.key(),
+                    connected: !entry.sender.is_closed(),
+                    buffered: entry.buffer.lock().map_or(0, |b| b.len()),
+                    idle_seconds: now.saturating_duration_since(last_activity).as_secs(),
+                }
+            })
+            .collect()
+    }
+
     /// Removes disconnected sessions whose last activity is older than `ttl`.
     pub(crate) fn evict_stale(&self, ttl: Duration) {
         let now = Instant::now();
@@ -129,7 +129,7 @@ impl TaskTracker {
     }
 
     /// Returns a list of currently running tasks.
-    #[cfg(feature = "legacy-spec")]
+    #[cfg(any(feature = "legacy-spec", feature = "http-server"))]
     pub(crate) fn tasks(&self) -> Vec<Task> {
         self.cleanup_expired();
 
@@ -238,6 +238,50 @@ impl TaskTracker {
         }
     }
 
+    /// Returns how many tasks have not reached a terminal status yet.
+    #[cfg(feature = "server")]
+    pub(crate) fn running(&self) -> usize {
+        self.tasks
+            .iter()
+            .filter(|entry| !Self::is_terminal(&entry.task))
+            .count()
+    }
+
+    /// Fails every task that has not reached a terminal status, recording
+    /// `reason` as its status message, and returns their ids.
+    ///
+    /// Used when the server shuts down under a task: the tool is stopped
+    /// through its cancellation token, and the task reports `failed` with the
+    /// reason rather than staying `working` until its entry expires. A task
+    /// parked on `ctx.task().elicit` is released with its pending input, so it
+    /// does not outlive the answer it can no longer receive.
+    #[cfg(feature = "server")]
+    pub(crate) fn abandon(&self, reason: &str) -> Vec<String> {
+        self.cleanup_expired();
+
+        let mut abandoned = Vec::new();
+        for mut entry in self.tasks.iter_mut() {
+            if Self::is_terminal(&entry.task) {
+                continue;
+            }
+            entry.token.cancel();
+            #[cfg(not(feature = "legacy-spec"))]
+            if let Ok(mut state) = entry.state.lock() {
+                state.inputs.clear();
+                state.error = serde_json::to_value(crate::types::ErrorDetails::from(Error::new(
+                    ErrorCode::InternalError,
+                    reason,
+                )))
+                .ok();
+            }
+            entry.task.set_message(reason);
+            entry.task.fail();
+            self.schedule_expiry(&entry.task);
+            abandoned.push(entry.task.id.clone());
+        }
+        abandoned
+    }
+
     /// Sets the task into `input_required` status
     #[cfg(feature = "server")]
     #[cfg_attr(not(feature = "legacy-spec"), allow(dead_code))]
@@ -718,6 +762,37 @@ mod tests {
         // Should not panic
     }
 
+    #[cfg(feature = "server")]
+    #[tokio::test]
+    async fn it_abandons_only_unfinished_tasks() {
+        let tracker = TaskTracker::new();
+        let working = Task::new();
+        let done = Task::new();
+        let (working_id, done_id) = (working.id.clone(), done.id.clone());
+
+        let handle = tracker.track(working);
+        let _done = tracker.track(done);
+        tracker.complete(&done_id);
+        assert_eq!(tracker.running(), 1);
+
+        let abandoned = tracker.abandon("server shutting down");
+        assert_eq!(abandoned, vec![working_id.clone()]);
+        assert_eq!(tracker.running(), 0);
+
+        // The tool is told to stop, and the task says why it failed.
+        handle.cancelled().await;
+        let task = tracker.get_status(&working_id).unwrap();
+        assert_eq!(task.status, TaskStatus::Failed);
+        assert_eq!(task.status_msg.as_deref(), Some("server shutting down"));
+        assert_eq!(
+            tracker.get_status(&done_id).unwrap().status,
+            TaskStatus::Completed
+        );
+
+        #[cfg(not(feature = "legacy-spec"))]
+        assert!(tracker.get_state(&working_id).unwrap().error.is_some());
+    }
+
     #[cfg(feature = "server")]
     #[test]
     fn it_can_require_input() {
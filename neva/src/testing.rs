@@ -0,0 +1,556 @@
+//! Testing an MCP server in-process.
+//!
+//! A [`TestClient`] runs an [`App`] and connects a [`Client`] to it over an
+//! in-memory transport: no ports, no child processes, no waiting for a
+//! listener to come up. Requests go through the same dispatch, middleware and
+//! MRTR round-trips as they would over stdio, so what passes here passes on the
+//! wire.
+//!
+//! On top of the client it adds what a server's test suite keeps rewriting:
+//! canned answers for the elicitation and sampling a handler asks for, the
+//! roots the client reports, [`Claims`] for the tools that check them, and a
+//! capture of every notification the server sends -- progress and log messages
+//! included. [`CallToolResponseExt`] asserts on what a tool returned.
+//!
+//! Like any [`Client`], it needs the multi-threaded runtime:
+//! `#[tokio::test(flavor = "multi_thread")]`.
+//!
+//! # Example
+//! ```no_run
+//! use neva::App;
+//! use neva::testing::{CallToolResponseExt, TestClient};
+//!
+//! # async fn test() -> Result<(), neva::error::Error> {
+//! let mut app = App::new();
+//! app.map_tool("greet", |name: String| async move {
+//!     format!("Hello, {name}!")
+//! })
+//! .with_arg_names(["name"]);
+//!
+//! let mut client = TestClient::new(app);
+//! client.connect().await?;
+//!
+//! client
+//!     .call_tool("greet", [("name", "Ada")])
+//!     .await?
+//!     .assert_text("Hello, Ada!");
+//! # Ok(()) }
+//! ```
+
+use crate::App;
+use crate::auth::Claims;
+use crate::client::Client;
+use crate::client::options::McpOptions;
+use crate::error::{Error, ErrorCode};
+use crate::shared::IntoArgs;
+use crate::transport::TransportProto;
+use crate::transport::memory::{MemoryTransport, Remote};
+use crate::types::notification::{LogMessage, Notification, ProgressNotification, commands};
+use crate::types::prompt::GetPromptResult;
+use crate::types::resource::ReadResourceResult;
+use crate::types::sampling::CreateMessageResult;
+use crate::types::{
+    CallToolResponse, ElicitResult, ListPromptsResult, ListResourcesResult, ListToolsResult,
+    Message, RequestId, Root, Uri,
+};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex, RwLock};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+/// The claims stamped on every request the server receives.
+type ClaimsSlot = Arc<RwLock<Option<Arc<dyn Claims>>>>;
+
+/// A [`Client`] connected to an in-process [`App`].
+///
+/// Configure it before [`connect`](Self::connect); dropping it stops the app.
+pub struct TestClient {
+    client: Client,
+    app: Option<App>,
+    claims: ClaimsSlot,
+    notifications: Arc<Mutex<Vec<Notification>>>,
+    token: CancellationToken,
+    server: Option<JoinHandle<()>>,
+    #[cfg(feature = "tracing")]
+    tracing_capture: bool,
+}
+
+impl Debug for TestClient {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TestClient")
+            .field("client", &self.client)
+            .field("connected", &self.server.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for TestClient {
+    fn drop(&mut self) {
+        self.token.cancel();
+    }
+}
+
+impl TestClient {
+    /// Creates a test client for `app`. Whatever transport `app` was
+    /// configured with is not used.
+    pub fn new(app: App) -> Self {
+        Self {
+            client: Client::new(),
+            app: Some(app),
+            claims: Default::default(),
+            notifications: Default::default(),
+            token: CancellationToken::new(),
+            server: None,
+            #[cfg(feature = "tracing")]
+            tracing_capture: false,
+        }
+    }
+
+    /// Installs [`notification::fmt::layer`](crate::types::notification::fmt::layer)
+    /// as the process-wide tracing subscriber on [`connect`](Self::connect),
+    /// so the progress and log messages a handler reports through `tracing`
+    /// reach [`notifications`](Self::notifications).
+    ///
+    /// A test that set up a subscriber of its own is left with it; one that
+    /// wants these messages adds the layer to it instead.
+    ///
+    /// Default: no subscriber is installed
+    #[cfg(feature = "tracing")]
+    pub fn with_tracing_capture(mut self) -> Self {
+        self.tracing_capture = true;
+        self
+    }
+
+    /// Configures the underlying [`Client`].
+    pub fn with_options<F>(mut self, config: F) -> Self
+    where
+        F: FnOnce(McpOptions) -> McpOptions,
+    {
+        self.client = std::mem::take(&mut self.client).with_options(config);
+        self
+    }
+
+    /// Answers every elicitation the server asks for with `result`.
+    ///
+    /// For an answer that depends on the request, register a handler with
+    /// [`Client::map_elicitation`] through [`client_mut`](Self::client_mut).
+    pub fn with_elicitation_answer(mut self, result: ElicitResult) -> Self {
+        self.client.map_elicitation(move |_| {
+            let result = result.clone();
+            async move { result }
+        });
+        self
+    }
+
+    /// Answers every sampling request the server makes with `result`.
+    ///
+    /// For an answer that depends on the request, register a handler with
+    /// [`Client::map_sampling`] through [`client_mut`](Self::client_mut).
+    pub fn with_sampling_answer(mut self, result: CreateMessageResult) -> Self {
+        #[allow(deprecated)]
+        self.client.map_sampling(move |_| {
+            let result = result.clone();
+            async move { result }
+        });
+        self
+    }
+
+    /// Reports `roots` when the server lists the client's roots.
+    pub fn with_roots<T, I>(self, roots: I) -> Self
+    where
+        T: Into<Root>,
+        I: IntoIterator<Item = T>,
+    {
+        self.with_options(|mut opt| {
+            opt.add_roots(roots);
+            opt
+        })
+    }
+
+    /// Attaches `claims` to every request, as an authenticating HTTP engine
+    /// would.
+    ///
+    /// Default: no claims
+    pub fn with_claims(self, claims: impl Claims) -> Self {
+        self.set_claims(claims);
+        self
+    }
+
+    /// Attaches `claims` to every request from now on.
+    pub fn set_claims(&self, claims: impl Claims) {
+        if let Ok(mut slot) = self.claims.write() {
+            *slot = Some(Arc::new(claims));
+        }
+    }
+
+    /// Sends the requests from now on without claims.
+    pub fn clear_claims(&self) {
+        if let Ok(mut slot) = self.claims.write() {
+            *slot = None;
+        }
+    }
+
+    /// Starts the app and connects the client to it.
+    pub async fn connect(&mut self) -> Result<(), Error> {
+        let app = self
+            .app
+            .take()
+            .ok_or_else(|| Error::new(ErrorCode::InternalError, "already connected"))?;
+
+        #[cfg(feature = "tracing")]
+        if self.tracing_capture {
+            use tracing_subscriber::prelude::*;
+            _ = tracing_subscriber::registry()
+                .with(crate::types::notification::fmt::layer())
+                .try_init();
+        }
+
+        let (server, server_end) = MemoryTransport::new(self.token.clone());
+        let (client, client_end) = MemoryTransport::new(self.token.clone());
+        tokio::spawn(relay(
+            client_end,
+            server_end,
+            self.claims.clone(),
+            self.notifications.clone(),
+            self.token.clone(),
+        ));
+
+        let app = app.with_options(|opt| opt.with_transport(TransportProto::Memory(server)));
+        self.server = Some(tokio::spawn(app.run()));
+
+        let client = std::mem::take(&mut self.client)
+            .with_options(|opt| opt.with_transport(TransportProto::Memory(client)));
+        self.client = client;
+        self.client.connect().await
+    }
+
+    /// Stops the app and waits for it to finish.
+    pub async fn shutdown(mut self) {
+        self.token.cancel();
+        if let Some(server) = self.server.take() {
+            _ = server.await;
+        }
+    }
+
+    /// The underlying [`Client`].
+    #[inline]
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// The underlying [`Client`], for the calls this type does not wrap.
+    #[inline]
+    pub fn client_mut(&mut self) -> &mut Client {
+        &mut self.client
+    }
+
+    /// Lists the app's tools.
+    pub async fn list_tools(&mut self) -> Result<ListToolsResult, Error> {
+        self.client.list_tools(None).await
+    }
+
+    /// Calls a tool.
+    pub async fn call_tool<N, Args>(
+        &mut self,
+        name: N,
+        args: Args,
+    ) -> Result<CallToolResponse, Error>
+    where
+        N: Into<String>,
+        Args: IntoArgs,
+    {
+        self.client.call_tool(name, args).await
+    }
+
+    /// Calls a tool and deserializes what it returned: its structured content
+    /// if it has any, or else its first text content as JSON.
+    ///
+    /// A result with `isError` set fails as an
+    /// [`ErrorKind::Tool`](crate::error::ErrorKind::Tool) error.
+    pub async fn call_tool_as<T, N, Args>(&mut self, name: N, args: Args) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+        N: Into<String>,
+        Args: IntoArgs,
+    {
+        let resp = self.client.call_tool_checked(name, args).await?;
+        match resp.struct_content {
+            Some(value) => serde_json::from_value(value).map_err(Into::into),
+            None => serde_json::from_str(&resp.text()).map_err(Into::into),
+        }
+    }
+
+    /// Lists the app's prompts.
+    pub async fn list_prompts(&mut self) -> Result<ListPromptsResult, Error> {
+        self.client.list_prompts(None).await
+    }
+
+    /// Gets a prompt.
+    pub async fn get_prompt<N, Args>(
+        &mut self,
+        name: N,
+        args: Args,
+    ) -> Result<GetPromptResult, Error>
+    where
+        N: Into<String>,
+        Args: IntoArgs,
+    {
+        self.client.get_prompt(name, args).await
+    }
+
+    /// Lists the app's resources.
+    pub async fn list_resources(&mut self) -> Result<ListResourcesResult, Error> {
+        self.client.list_resources(None).await
+    }
+
+    /// Reads a resource.
+    pub async fn read_resource(
+        &mut self,
+        uri: impl Into<Uri>,
+    ) -> Result<ReadResourceResult, Error> {
+        self.client.read_resource(uri).await
+    }
+
+    /// Every notification the server has sent so far, in order.
+    ///
+    /// A notification sent while handling a request has arrived by the time
+    /// the request returns. Progress and log messages reported through
+    /// `tracing` are sent by
+    /// [`notification::fmt::layer`](crate::types::notification::fmt::layer),
+    /// which [`with_tracing_capture`](Self::with_tracing_capture) installs.
+    pub fn notifications(&self) -> Vec<Notification> {
+        self.notifications
+            .lock()
+            .map(|notifications| notifications.clone())
+            .unwrap_or_default()
+    }
+
+    /// The progress the server has reported so far, in order.
+    pub fn progress(&self) -> Vec<ProgressNotification> {
+        self.notifications_of(commands::PROGRESS)
+    }
+
+    /// The log messages the server has sent so far, in order.
+    pub fn logs(&self) -> Vec<LogMessage> {
+        self.notifications_of(commands::MESSAGE)
+    }
+
+    /// Forgets the notifications captured so far.
+    pub fn clear_notifications(&self) {
+        if let Ok(mut notifications) = self.notifications.lock() {
+            notifications.clear();
+        }
+    }
+
+    fn notifications_of<T: DeserializeOwned>(&self, method: &str) -> Vec<T> {
+        self.notifications()
+            .iter()
+            .filter(|notification| notification.method == method)
+            .filter_map(Notification::params)
+            .collect()
+    }
+}
+
+/// How many notifications one request can have in flight to the client.
+#[cfg(not(feature = "legacy-spec"))]
+const NOTIFICATION_CAPACITY: usize = 1024;
+
+/// Carries messages between the client and the app the way the Streamable
+/// HTTP transport would: each one through JSON, each request with the claims
+/// and as a `POST` of its own -- a fresh session id, with a notification sink
+/// drained before its response. The notifications on the way to the client
+/// are captured.
+async fn relay(
+    mut client: Remote,
+    mut server: Remote,
+    claims: ClaimsSlot,
+    notifications: Arc<Mutex<Vec<Notification>>>,
+    token: CancellationToken,
+) {
+    #[cfg(not(feature = "legacy-spec"))]
+    let mut sinks = tokio_stream::StreamMap::new();
+    // The request each sink belongs to, for the client to tell whose
+    // notifications they are, as it does over HTTP.
+    #[cfg(not(feature = "legacy-spec"))]
+    let mut origins = std::collections::HashMap::new();
+
+    let deliver = |message: Message, origin: Option<&RequestId>, client: &Remote| {
+        let Some(mut message) = through_json(&message) else {
+            return true;
+        };
+        if let Message::Notification(notification) = &mut message {
+            notification.request_id = origin.cloned();
+            if let Ok(mut captured) = notifications.lock() {
+                captured.push(notification.clone());
+            }
+        }
+        client.tx.send(message).is_ok()
+    };
+
+    loop {
+        #[cfg(not(feature = "legacy-spec"))]
+        let sink = async {
+            use tokio_stream::StreamExt;
+            match sinks.is_empty() {
+                true => std::future::pending().await,
+                false => sinks.next().await,
+            }
+        };
+        #[cfg(feature = "legacy-spec")]
+        let sink = std::future::pending::<Option<((), Message)>>();
+
+        tokio::select! {
+            biased;
+            _ = token.cancelled() => break,
+            Some(message) = client.rx.recv() => {
+                let Some(mut message) = through_json(&message) else {
+                    continue;
+                };
+                let claims = claims.read().ok().and_then(|claims| claims.clone());
+                match &mut message {
+                    Message::Request(req) => req.claims = claims,
+                    Message::Batch(batch) => batch.claims = claims,
+                    _ => {}
+                }
+                #[cfg(not(feature = "legacy-spec"))]
+                if matches!(message, Message::Request(_) | Message::Batch(_)) {
+                    let id = uuid::Uuid::new_v4();
+                    let listen = matches!(
+                        &message,
+                        Message::Request(req) if req.method == crate::types::subscription::commands::LISTEN
+                    );
+                    let rx = crate::types::notification::sink::register(
+                        id,
+                        NOTIFICATION_CAPACITY,
+                        listen,
+                    )
+                    .await;
+                    sinks.insert(id, tokio_stream::wrappers::ReceiverStream::new(rx));
+                    if let Message::Request(req) = &message {
+                        origins.insert(id, req.id());
+                    }
+                    message = message.set_session_id(id);
+                }
+                if server.tx.send(message).is_err() {
+                    break;
+                }
+            }
+            Some(message) = server.rx.recv() => {
+                // What the request reported before it answered goes first.
+                #[cfg(not(feature = "legacy-spec"))]
+                if let Message::Response(_) | Message::Batch(_) = &message
+                    && let Some(id) = message.session_id()
+                {
+                    crate::types::notification::sink::unregister(id);
+                    let origin = origins.remove(id);
+                    if let Some(rx) = sinks.remove(id) {
+                        let mut rx = rx.into_inner();
+                        while let Ok(notification) = rx.try_recv() {
+                            deliver(notification, origin.as_ref(), &client);
+                        }
+                    }
+                }
+                if !deliver(message, None, &client) {
+                    break;
+                }
+            }
+            Some((id, notification)) = sink => {
+                #[cfg(not(feature = "legacy-spec"))]
+                let origin = origins.get(&id);
+                #[cfg(feature = "legacy-spec")]
+                let origin = {
+                    let () = id;
+                    None
+                };
+                if !deliver(notification, origin, &client) {
+                    break;
+                }
+            }
+            else => break,
+        }
+    }
+}
+
+/// `message` as the other side would read it off the wire.
+fn through_json(message: &Message) -> Option<Message> {
+    serde_json::to_value(message)
+        .and_then(serde_json::from_value)
+        .ok()
+}
+
+/// Assertions on a [`CallToolResponse`], for tests.
+///
+/// Each one panics with the whole response when it does not hold, and returns
+/// the response otherwise, so that they chain.
+pub trait CallToolResponseExt {
+    /// The text contents of the response, joined with newlines.
+    fn text(&self) -> String;
+
+    /// Asserts that the tool succeeded and its text is `expected`.
+    fn assert_text(&self, expected: &str) -> &Self;
+
+    /// Asserts that the text of the response contains `expected`.
+    fn assert_text_contains(&self, expected: &str) -> &Self;
+
+    /// Asserts that the tool succeeded: `isError` is not set.
+    fn assert_success(&self) -> &Self;
+
+    /// Asserts that the tool failed: `isError` is set.
+    fn assert_error(&self) -> &Self;
+
+    /// Asserts that the structured content of the response equals `expected`
+    /// serialized.
+    fn assert_structured<T: Serialize>(&self, expected: T) -> &Self;
+}
+
+impl CallToolResponseExt for CallToolResponse {
+    fn text(&self) -> String {
+        self.content
+            .iter()
+            .filter_map(|content| content.as_text())
+            .map(|text| text.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[track_caller]
+    fn assert_text(&self, expected: &str) -> &Self {
+        self.assert_success();
+        assert_eq!(self.text(), expected, "unexpected tool text in {self:?}");
+        self
+    }
+
+    #[track_caller]
+    fn assert_text_contains(&self, expected: &str) -> &Self {
+        assert!(
+            self.text().contains(expected),
+            "expected the tool text to contain {expected:?}, got {self:?}"
+        );
+        self
+    }
+
+    #[track_caller]
+    fn assert_success(&self) -> &Self {
+        assert!(!self.is_error, "expected the tool to succeed, got {self:?}");
+        self
+    }
+
+    #[track_caller]
+    fn assert_error(&self) -> &Self {
+        assert!(self.is_error, "expected the tool to fail, got {self:?}");
+        self
+    }
+
+    #[track_caller]
+    fn assert_structured<T: Serialize>(&self, expected: T) -> &Self {
+        let expected = serde_json::to_value(expected).expect("serializable expected content");
+        assert_eq!(
+            self.struct_content.as_ref(),
+            Some(&expected),
+            "unexpected structured content in {self:?}"
+        );
+        self
+    }
+}
@@ -7,7 +7,8 @@ use tokio_util::sync::CancellationToken;
 
 #[cfg(feature = "http-server")]
 pub use http::{
-    HttpContext, HttpEngine, HttpRequest, HttpResponse, HttpServer, StreamResponse, handlers,
+    BodyStream, HttpContext, HttpEngine, HttpRequest, HttpResponse, HttpServer, StreamResponse,
+    handlers,
 };
 
 #[cfg(feature = "http-server")]
@@ -22,8 +23,13 @@ pub(crate) use http::HttpClient;
 #[cfg(feature = "client")]
 pub(crate) use stdio::StdIoClient;
 
+#[cfg(feature = "server")]
+pub(crate) mod blob;
 #[cfg(any(feature = "http-server", feature = "http-client"))]
 pub mod http;
+pub(crate) mod memory;
+pub mod record;
+pub mod replay;
 pub(crate) mod stdio;
 
 /// Describes a sender that can send messages to a client
@@ -61,6 +67,13 @@ pub(crate) enum TransportProto {
     HttpServer(Box<dyn http::core::engine::HttpTransport>),
     #[cfg(feature = "http-client")]
     HttpClient(Box<HttpClient>),
+    /// Another transport, with a [`record::Recorder`] attached.
+    Recorded(Box<TransportProto>, record::Tap),
+    /// A recorded session played in place of a peer.
+    Replay(replay::ReplayTransport),
+    /// An in-process connection.
+    #[cfg(feature = "testing")]
+    Memory(memory::MemoryTransport),
     //Ws(Websocket),
     // add more options here...
 }
@@ -89,6 +102,8 @@ pub(crate) enum TransportProtoSender {
         /// synchronous mutex is the right tool here.
         responses: std::sync::Arc<std::sync::Mutex<Vec<crate::types::MessageEnvelope>>>,
     },
+    Recorded(Box<TransportProtoSender>, record::Tap),
+    Memory(memory::MemorySender),
 }
 
 pub(crate) enum TransportProtoReceiver {
@@ -96,6 +111,8 @@ pub(crate) enum TransportProtoReceiver {
     Stdio(stdio::StdIoReceiver),
     #[cfg(any(feature = "http-server", feature = "http-client"))]
     Http(http::HttpReceiver),
+    Recorded(Box<TransportProtoReceiver>, record::Tap),
+    Memory(memory::MemoryReceiver),
 }
 
 impl Default for TransportProto {
@@ -105,6 +122,20 @@ impl Default for TransportProto {
     }
 }
 
+impl TransportProto {
+    /// The lifecycle and inventory the HTTP server's health and admin
+    /// endpoints report, or `None` for a transport that serves neither.
+    #[inline]
+    #[cfg(feature = "http-server")]
+    pub(crate) fn server_status(&self) -> Option<std::sync::Arc<http::core::probes::ServerStatus>> {
+        match self {
+            TransportProto::HttpServer(http) => Some(http.status()),
+            TransportProto::Recorded(inner, _) => inner.server_status(),
+            _ => None,
+        }
+    }
+}
+
 impl Sender for TransportProtoSender {
     #[inline]
     async fn send(&mut self, resp: Message) -> Result<(), Error> {
@@ -120,7 +151,7 @@ impl Sender for TransportProtoSender {
             TransportProtoSender::BatchCollect {
                 real_sender,
                 responses,
-            } => match resp {
+            } => match blob::materialize(resp).await {
                 Message::Response(response) => {
                     if let Ok(mut guard) = responses.lock() {
                         guard.push(crate::types::MessageEnvelope::Response(response));
@@ -132,6 +163,17 @@ impl Sender for TransportProtoSender {
                     Box::pin(guard.send(other)).await
                 }
             },
+            // These send the message whole: a streamed blob is read into it.
+            TransportProtoSender::Recorded(inner, tap) => {
+                #[cfg(feature = "server")]
+                let resp = blob::materialize(resp).await;
+                record::send(inner, tap, resp).await
+            }
+            TransportProtoSender::Memory(memory) => {
+                #[cfg(feature = "server")]
+                let resp = blob::materialize(resp).await;
+                memory.send(resp).await
+            }
         }
     }
 }
@@ -147,6 +189,8 @@ impl Receiver for TransportProtoReceiver {
                 ErrorCode::InternalError,
                 "Transport protocol must be specified",
             )),
+            TransportProtoReceiver::Recorded(inner, tap) => record::recv(inner, tap).await,
+            TransportProtoReceiver::Memory(memory) => memory.recv().await,
         }
     }
 }
@@ -166,6 +210,10 @@ impl Transport for TransportProto {
             TransportProto::HttpServer(http) => http.start(),
             #[cfg(feature = "http-client")]
             TransportProto::HttpClient(http) => http.start(),
+            TransportProto::Recorded(inner, _) => inner.start(),
+            TransportProto::Replay(replay) => replay.start(),
+            #[cfg(feature = "testing")]
+            TransportProto::Memory(memory) => memory.start(),
             TransportProto::None => CancellationToken::new(),
         }
     }
@@ -204,6 +252,28 @@ impl Transport for TransportProto {
                     TransportProtoReceiver::Http(rx),
                 )
             }
+            TransportProto::Recorded(inner, tap) => {
+                let (tx, rx) = inner.split();
+                (
+                    TransportProtoSender::Recorded(Box::new(tx), tap.clone()),
+                    TransportProtoReceiver::Recorded(Box::new(rx), tap),
+                )
+            }
+            TransportProto::Replay(replay) => {
+                let (tx, rx) = replay.split();
+                (
+                    TransportProtoSender::Memory(tx),
+                    TransportProtoReceiver::Memory(rx),
+                )
+            }
+            #[cfg(feature = "testing")]
+            TransportProto::Memory(memory) => {
+                let (tx, rx) = memory.split();
+                (
+                    TransportProtoSender::Memory(tx),
+                    TransportProtoReceiver::Memory(rx),
+                )
+            }
             TransportProto::None => (TransportProtoSender::None, TransportProtoReceiver::None),
         }
     }
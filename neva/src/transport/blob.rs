@@ -0,0 +1,272 @@
+//! Writing responses that carry streamed blobs.
+//!
+//! A [`BlobStream`](crate::types::BlobStream) in a `resources/read` result is
+//! sent as blob resource contents without bytes, and the blob itself rides
+//! along on the response with the index of the contents it fills in. A
+//! transport that can write a body piecemeal -- stdio, and HTTP for a plain
+//! JSON reply -- writes the JSON around the blobs as it is and base64-encodes
+//! each blob into its place as it reads it ([`into_body`]). Every other path
+//! sends messages whole, and reads the blobs into the result first
+//! ([`materialize`]).
+
+use crate::error::{Error, ErrorCode};
+use crate::types::{BlobStream, Message, RequestId, Response, response::OkResponse};
+use bytes::{BufMut, Bytes, BytesMut};
+use futures_util::{Stream, StreamExt, stream};
+use serde_json::Value;
+use std::{io, pin::Pin};
+
+/// The body of a response with streamed blobs, chunk by chunk.
+pub(crate) type JsonBody = Pin<Box<dyn Stream<Item = io::Result<Bytes>> + Send>>;
+
+/// A piece of the body of a response with streamed blobs.
+enum Part {
+    /// Serialized JSON, written as it is.
+    Json(Bytes),
+
+    /// A blob, written as its base64 as it is read.
+    Blob(BlobStream),
+}
+
+/// Turns a response carrying streamed blobs into its JSON body, each blob's
+/// base64 read into its place as the body is polled. The body ends at the
+/// first error.
+///
+/// Returns the message back as it is when it carries no streamed blob.
+#[allow(clippy::result_large_err)]
+pub(crate) fn into_body(msg: Message) -> Result<(RequestId, JsonBody), Message> {
+    let resp = match msg {
+        Message::Response(Response::Ok(resp)) if !resp.blobs.is_empty() => resp,
+        msg => return Err(msg),
+    };
+    let id = resp.id.clone();
+    let parts = match parts(resp) {
+        Ok(parts) => parts,
+        Err(err) => {
+            let err = stream::once(async move { Err(io::Error::other(err)) });
+            return Ok((id, Box::pin(err)));
+        }
+    };
+
+    let body = stream::iter(parts)
+        .flat_map(|part| match part {
+            Part::Json(json) => stream::iter([Ok(json)]).left_stream(),
+            Part::Blob(blob) => blob.encode().right_stream(),
+        })
+        .scan(false, |failed, chunk| {
+            let next = (!*failed).then(|| {
+                *failed = chunk.is_err();
+                chunk
+            });
+            std::future::ready(next)
+        });
+    Ok((id, Box::pin(body)))
+}
+
+/// Splits a response into the JSON around its streamed blobs and the blobs.
+///
+/// The response goes out with the result's `contents` left out, and the
+/// `contents` are written after the rest of the result, each streamed blob's
+/// `blob` last in its object, so each blob's place is known without looking
+/// for it in the JSON.
+fn parts(mut resp: OkResponse) -> serde_json::Result<Vec<Part>> {
+    let mut blobs = std::mem::take(&mut resp.blobs).into_iter().peekable();
+    let contents = match resp.result.as_object_mut().map(|r| r.remove("contents")) {
+        Some(Some(Value::Array(contents))) => contents,
+        _ => Vec::new(),
+    };
+
+    // `result` is the last field of the response, so the envelope ends in the
+    // result's closing brace and its own.
+    let envelope = serde_json::to_vec(&resp)?;
+    let (envelope, _) = envelope.split_at(envelope.len() - 2);
+    let mut json = BytesMut::from(envelope);
+    if !json.ends_with(b"{") {
+        json.put_u8(b',');
+    }
+    json.put_slice(br#""contents":["#);
+
+    let mut parts = Vec::with_capacity(blobs.len() * 2 + 1);
+    for (index, mut content) in contents.into_iter().enumerate() {
+        if index > 0 {
+            json.put_u8(b',');
+        }
+        let Some((_, blob)) = blobs.next_if(|(at, _)| *at == index) else {
+            serde_json::to_writer((&mut json).writer(), &content)?;
+            continue;
+        };
+        if let Some(content) = content.as_object_mut() {
+            content.remove("blob");
+        }
+        let object = serde_json::to_vec(&content)?;
+        json.put_slice(&object[..object.len() - 1]);
+        if object.len() > 2 {
+            json.put_u8(b',');
+        }
+        json.put_slice(br#""blob":""#);
+        parts.push(Part::Json(json.split().freeze()));
+        parts.push(Part::Blob(blob));
+        json.put_slice(br#""}"#);
+    }
+    json.put_slice(b"]}}");
+    parts.push(Part::Json(json.freeze()));
+    Ok(parts)
+}
+
+/// Reads the streamed blobs a response carries into its result, for a path
+/// that sends each message whole.
+///
+/// A blob that cannot be read turns the response into an error response.
+pub(crate) async fn materialize(msg: Message) -> Message {
+    let mut resp = match msg {
+        Message::Response(Response::Ok(resp)) if !resp.blobs.is_empty() => resp,
+        msg => return msg,
+    };
+    for (index, blob) in std::mem::take(&mut resp.blobs) {
+        let mut encoded = Vec::new();
+        let mut chunks = std::pin::pin!(blob.encode());
+        while let Some(chunk) = chunks.next().await {
+            match chunk {
+                Ok(chunk) => encoded.extend_from_slice(&chunk),
+                Err(err) => return failed(Response::Ok(resp), err),
+            }
+        }
+        // Base64 is ASCII throughout.
+        let encoded = String::from_utf8(encoded).unwrap_or_default();
+        if let Some(content) = resp.result["contents"].get_mut(index) {
+            content["blob"] = encoded.into();
+        }
+    }
+    Message::Response(Response::Ok(resp))
+}
+
+/// The error a response turns into when one of its streamed blobs fails.
+pub(crate) fn read_error(id: RequestId, err: io::Error) -> Response {
+    Response::error(
+        id,
+        Error::new(
+            ErrorCode::InternalError,
+            format!("Failed to read the resource: {err}"),
+        ),
+    )
+}
+
+/// Turns `resp` into the error response for `err`, keeping where it goes.
+fn failed(resp: Response, err: io::Error) -> Message {
+    let mut error = read_error(resp.id().clone(), err);
+    if let Some(session_id) = resp.session_id() {
+        error = error.set_session_id(*session_id);
+    }
+    #[cfg(feature = "http-server")]
+    if let Response::Ok(ok) = resp {
+        error = error.set_headers(ok.headers);
+    }
+    Message::Response(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{BlobStream, IntoResponse, ReadResourceResult};
+
+    fn response(data: &'static [u8]) -> Message {
+        let chunks = data.chunks(4).map(|c| Ok(Bytes::from_static(c)));
+        let blob = BlobStream::from_stream("file://data", stream::iter(chunks))
+            .with_mime("application/octet-stream");
+        let result = ReadResourceResult::new()
+            .with_content(("file://note", "a note"))
+            .with_content(blob);
+        Message::Response(result.into_response(RequestId::Number(1)))
+    }
+
+    async fn collect(mut body: JsonBody) -> io::Result<Vec<u8>> {
+        let mut out = Vec::new();
+        while let Some(chunk) = body.next().await {
+            out.extend_from_slice(&chunk?);
+        }
+        Ok(out)
+    }
+
+    #[tokio::test]
+    async fn it_writes_the_blob_into_the_json_body() {
+        let (id, body) = into_body(response(b"a blob written as it is read")).unwrap();
+        assert_eq!(id, RequestId::Number(1));
+
+        let json: serde_json::Value =
+            serde_json::from_slice(&collect(body).await.unwrap()).unwrap();
+        let resp: crate::types::Response = serde_json::from_value(json).unwrap();
+        let result: ReadResourceResult = resp.into_result().unwrap();
+        assert_eq!(result.contents[0].text(), Some("a note"));
+        let crate::types::ResourceContents::Blob(blob) = &result.contents[1] else {
+            panic!("expected a blob");
+        };
+        assert_eq!(&blob.blob[..], b"a blob written as it is read");
+        assert_eq!(blob.mime.as_deref(), Some("application/octet-stream"));
+    }
+
+    #[tokio::test]
+    async fn it_places_each_blob_in_its_own_contents() {
+        let blob = |data: &'static [u8]| {
+            BlobStream::from_stream("file://data", stream::iter([Ok(Bytes::from_static(data))]))
+        };
+        let mut resp = ReadResourceResult::new()
+            .with_content(blob(b"first"))
+            .with_content(("file://note", "a note"))
+            .with_content(blob(b""))
+            .into_response(RequestId::Number(2));
+        if let Response::Ok(ok) = &mut resp {
+            ok.result["_meta"] = serde_json::json!({ "contents": [], "blob": "" });
+        }
+
+        let (_, body) = into_body(Message::Response(resp)).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_slice(&collect(body).await.unwrap()).unwrap();
+        assert_eq!(json["id"], 2);
+        assert_eq!(json["result"]["_meta"]["contents"], serde_json::json!([]));
+        let contents = &json["result"]["contents"];
+        assert_eq!(contents[0]["blob"], "Zmlyc3Q=");
+        assert_eq!(contents[0]["uri"], "file://data");
+        assert_eq!(contents[1]["text"], "a note");
+        assert_eq!(contents[2]["blob"], "");
+    }
+
+    #[tokio::test]
+    async fn it_materializes_the_blob_into_the_result() {
+        let msg = materialize(response(b"a blob read whole")).await;
+        let Message::Response(resp) = msg else {
+            panic!("expected a response");
+        };
+        let result: ReadResourceResult = resp.into_result().unwrap();
+        let crate::types::ResourceContents::Blob(blob) = &result.contents[1] else {
+            panic!("expected a blob");
+        };
+        assert_eq!(&blob.blob[..], b"a blob read whole");
+    }
+
+    #[tokio::test]
+    async fn a_failed_read_ends_the_body_and_fails_the_materialized_response() {
+        let failing = || {
+            let chunks = [
+                Ok(Bytes::from_static(b"abc")),
+                Err(io::Error::other("disk gone")),
+            ];
+            let blob = BlobStream::from_stream("file://data", stream::iter(chunks));
+            Message::Response(ReadResourceResult::from(blob).into_response(RequestId::Number(1)))
+        };
+
+        let (_, body) = into_body(failing()).unwrap();
+        assert!(collect(body).await.is_err());
+
+        let Message::Response(resp) = materialize(failing()).await else {
+            panic!("expected a response");
+        };
+        let err = resp.into_result::<ReadResourceResult>().unwrap_err();
+        assert!(err.to_string().contains("disk gone"));
+    }
+
+    #[test]
+    fn it_leaves_other_messages_alone() {
+        let msg = Message::Response(Response::empty(RequestId::Number(1)));
+        assert!(into_body(msg).is_err());
+    }
+}
@@ -34,7 +34,8 @@ pub use core::{
     context::HttpContext,
     engine::HttpEngine,
     handlers,
-    types::{HttpRequest, HttpResponse, StreamResponse},
+    probes::{AdminOptions, HealthProbeOptions},
+    types::{BodyStream, HttpRequest, HttpResponse, StreamResponse},
 };
 
 #[cfg(feature = "http-server")]
@@ -219,6 +220,9 @@ where
     /// `None` means "derive from the bind address" -- see
     /// [`Self::with_allowed_origins`].
     origin_policy: Option<core::origin::OriginPolicy>,
+    health_probes: Option<HealthProbeOptions>,
+    admin: Option<AdminOptions>,
+    status: std::sync::Arc<core::probes::ServerStatus>,
     #[cfg(feature = "server-oauth")]
     oauth: Option<core::oauth::OAuthResourceOptions>,
     sender: HttpSender,
@@ -292,6 +296,8 @@ where
             .field("sse_log_queue_capacity", &self.sse_log_queue_capacity)
             .field("sse_cleanup_interval", &self.sse_cleanup_interval)
             .field("sse_session_ttl", &self.sse_session_ttl)
+            .field("health_probes", &self.health_probes)
+            .field("admin", &self.admin)
             .finish()
     }
 }
@@ -309,6 +315,9 @@ impl Default for HttpServer<server::DefaultClaims, server::VolgaEngine> {
             sse_cleanup_interval: DEFAULT_SSE_CLEANUP_INTERVAL,
             sse_session_ttl: DEFAULT_SSE_SESSION_TTL,
             origin_policy: None,
+            health_probes: None,
+            admin: None,
+            status: Default::default(),
             #[cfg(feature = "server-oauth")]
             oauth: None,
             receiver: HttpReceiver::new(),
@@ -460,6 +469,9 @@ where
             sse_cleanup_interval: DEFAULT_SSE_CLEANUP_INTERVAL,
             sse_session_ttl: DEFAULT_SSE_SESSION_TTL,
             origin_policy: None,
+            health_probes: None,
+            admin: None,
+            status: Default::default(),
             #[cfg(feature = "server-oauth")]
             oauth: None,
             receiver: HttpReceiver::new(),
@@ -605,6 +617,9 @@ where
             // Carried across the swap: the DNS-rebinding gate is a property of
             // the deployment, not of which engine serves it.
             origin_policy: self.origin_policy,
+            health_probes: self.health_probes,
+            admin: self.admin,
+            status: self.status,
             #[cfg(feature = "server-oauth")]
             oauth: self.oauth,
             sender: self.sender,
@@ -705,6 +720,50 @@ where
         self
     }
 
+    /// Enables the liveness and readiness probes, on `/healthz` and `/readyz`
+    /// unless `config` moves them.
+    ///
+    /// Off by default. They are served outside the MCP endpoint and without
+    /// authentication. Liveness answers `200 OK` for as long as the server is
+    /// up; readiness answers `503 Service Unavailable` until the server is
+    /// dispatching requests and again as soon as shutdown is requested, so an
+    /// orchestrator stops routing here while the server drains.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// HttpServer::new("0.0.0.0:3000")
+    ///     .with_health_probes(|probes| probes.with_liveness_path("/livez"))
+    /// ```
+    pub fn with_health_probes<F>(mut self, config: F) -> Self
+    where
+        F: FnOnce(HealthProbeOptions) -> HealthProbeOptions,
+    {
+        self.health_probes = Some(config(self.health_probes.unwrap_or_default()));
+        self
+    }
+
+    /// Enables the admin introspection endpoint: a JSON report of registered
+    /// tools, SSE sessions, live subscriptions, running tasks and build
+    /// information.
+    ///
+    /// Off by default. Requests must carry the configured token as
+    /// `Authorization: Bearer <token>`; the endpoint is outside the MCP
+    /// endpoint and does not share its authorization. A server configured
+    /// without a token fails to start.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// HttpServer::new("0.0.0.0:3000")
+    ///     .with_admin(|admin| admin.with_token("change-me"))
+    /// ```
+    pub fn with_admin<F>(mut self, config: F) -> Self
+    where
+        F: FnOnce(AdminOptions) -> AdminOptions,
+    {
+        self.admin = Some(config(AdminOptions::default()));
+        self
+    }
+
     /// Configures the OAuth Protected Resource Metadata document
     /// (RFC 9728) advertised by this server. Engine-neutral: the resolved
     /// document reaches the engine through
@@ -745,6 +804,7 @@ where
             .clone()
             .map(|o| o.resolve(&self.url.to_string()))
             .transpose()?;
+        let admin = self.admin.clone().map(AdminOptions::resolve).transpose()?;
         let Some(sender_rx) = self.sender.rx.take() else {
             return Err(Error::new(
                 ErrorCode::InternalError,
@@ -767,6 +827,9 @@ where
                 .origin_policy
                 .clone()
                 .unwrap_or_else(|| core::origin::OriginPolicy::for_addr(&self.url.addr)),
+            health_probes: self.health_probes.clone(),
+            admin,
+            status: self.status.clone(),
             #[cfg(feature = "server-oauth")]
             oauth,
         };
@@ -799,6 +862,9 @@ impl HttpServer<server::DefaultClaims, VolgaEngine> {
             sse_cleanup_interval: DEFAULT_SSE_CLEANUP_INTERVAL,
             sse_session_ttl: DEFAULT_SSE_SESSION_TTL,
             origin_policy: None,
+            health_probes: None,
+            admin: None,
+            status: Default::default(),
             #[cfg(feature = "server-oauth")]
             oauth: None,
             receiver: HttpReceiver::new(),
@@ -1098,6 +1164,10 @@ where
     fn url_label(&self) -> String {
         self.url.to_string()
     }
+
+    fn status(&self) -> std::sync::Arc<core::probes::ServerStatus> {
+        self.status.clone()
+    }
 }
 
 #[cfg(feature = "http-client")]
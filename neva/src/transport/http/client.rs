@@ -1784,6 +1784,30 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn forward_sse_message_tags_notifications_with_the_request_they_belong_to() {
+        let (tx, mut rx) = mpsc::channel(2);
+        let frame = r#"{"jsonrpc":"2.0","method":"notifications/message","params":{"level":"info","data":"x"}}"#;
+
+        let mut owed = vec![crate::types::RequestId::Number(1)];
+        forward_sse_message(sse_stream::Sse::default().data(frame), &tx, &mut owed).await;
+        let Ok(Ok(Message::Notification(n))) = rx.try_recv() else {
+            panic!("expected a notification");
+        };
+        assert_eq!(n.request_id, Some(crate::types::RequestId::Number(1)));
+
+        // A batched `POST` has no single request to attribute it to.
+        let mut owed = vec![
+            crate::types::RequestId::Number(1),
+            crate::types::RequestId::Number(2),
+        ];
+        forward_sse_message(sse_stream::Sse::default().data(frame), &tx, &mut owed).await;
+        let Ok(Ok(Message::Notification(n))) = rx.try_recv() else {
+            panic!("expected a notification");
+        };
+        assert_eq!(n.request_id, None);
+    }
+
     /// Media types are case-insensitive and may carry parameters: mistaking such
     /// a reply for JSON would fail the request on an SSE-framed body.
     #[test]
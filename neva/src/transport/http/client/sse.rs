@@ -547,7 +547,7 @@ pub(super) async fn forward_sse_message(
         return;
     };
 
-    let msg = match serde_json::from_str::<Message>(&data) {
+    let mut msg = match serde_json::from_str::<Message>(&data) {
         Ok(msg) => msg,
         Err(_err) => {
             #[cfg(feature = "tracing")]
@@ -556,6 +556,13 @@ pub(super) async fn forward_sse_message(
         }
     };
 
+    // A stream carrying a single request is that request's own: what it
+    // reports in between belongs to it, which is how the client tells one
+    // call's logs from another's.
+    if let (Message::Notification(notification), [id]) = (&mut msg, owed.as_slice()) {
+        notification.request_id = Some(id.clone());
+    }
+
     let answered: Vec<_> = match &msg {
         Message::Response(resp) => vec![resp.full_id()],
         Message::Batch(batch) => batch
@@ -9,6 +9,7 @@ pub mod context;
 pub mod engine;
 #[cfg(feature = "server-oauth")]
 pub mod oauth;
+pub mod probes;
 pub mod types;
 
 pub(crate) mod auth;
@@ -34,6 +34,12 @@ pub struct HttpContext {
     /// Which `Origin` / `Host` this server answers to -- the DNS-rebinding
     /// gate every route runs before it does anything else.
     pub(crate) origin_policy: super::origin::OriginPolicy,
+    /// Where the liveness and readiness probes are served, if at all.
+    pub(crate) health_probes: Option<super::probes::HealthProbeOptions>,
+    /// The admin introspection endpoint, when configured.
+    pub(crate) admin: Option<super::probes::AdminEndpoint>,
+    /// What the probes and the admin endpoint report.
+    pub(crate) status: Arc<super::probes::ServerStatus>,
     #[cfg(feature = "server-oauth")]
     pub(crate) oauth: Option<super::oauth::OAuthResource>,
 }
@@ -49,6 +55,37 @@ impl HttpContext {
         &self.endpoint
     }
 
+    /// The path the liveness probe is served on (e.g. `"/healthz"`), when the
+    /// probes are enabled via
+    /// [`HttpServer::with_health_probes`](crate::transport::http::HttpServer::with_health_probes).
+    ///
+    /// An engine mounts a GET route here and serves it with
+    /// [`handlers::handle_liveness`](super::handlers::handle_liveness).
+    pub fn liveness_path(&self) -> Option<&str> {
+        self.health_probes.as_ref()?.liveness_path.as_deref()
+    }
+
+    /// The path the readiness probe is served on (e.g. `"/readyz"`), when the
+    /// probes are enabled via
+    /// [`HttpServer::with_health_probes`](crate::transport::http::HttpServer::with_health_probes).
+    ///
+    /// An engine mounts a GET route here and serves it with
+    /// [`handlers::handle_readiness`](super::handlers::handle_readiness).
+    pub fn readiness_path(&self) -> Option<&str> {
+        self.health_probes.as_ref()?.readiness_path.as_deref()
+    }
+
+    /// The path the admin endpoint is served on, when it is configured via
+    /// [`HttpServer::with_admin`](crate::transport::http::HttpServer::with_admin).
+    ///
+    /// An engine mounts a GET route here, outside whatever authorization
+    /// guards the MCP endpoint, and serves it with
+    /// [`handlers::handle_admin`](super::handlers::handle_admin) -- which
+    /// checks the admin token itself.
+    pub fn admin_path(&self) -> Option<&str> {
+        self.admin.as_ref().map(|admin| &*admin.path)
+    }
+
     /// The path of the RFC 9728 Protected Resource Metadata document
     /// (e.g. `"/.well-known/oauth-protected-resource/mcp"`), when OAuth
     /// is configured via
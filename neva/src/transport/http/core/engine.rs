@@ -59,6 +59,16 @@ use super::{
 /// Both helpers return neva's neutral [`HttpResponse`] -- pass it through
 /// `adapt_response` like any other reply.
 ///
+/// # Health and admin contract
+///
+/// Mount GET routes on `HttpContext::liveness_path`,
+/// `HttpContext::readiness_path` and `HttpContext::admin_path` -- each is
+/// `None` unless enabled -- and serve them with `handlers::handle_liveness`,
+/// `handlers::handle_readiness` and `handlers::handle_admin`. Keep them outside
+/// whatever authorization guards the MCP endpoint: an orchestrator probing the
+/// server has no MCP credentials, and the admin handler checks a token of its
+/// own.
+///
 /// [`dispatch_post`]: super::handlers::dispatch_post
 ///
 /// # Example
@@ -162,4 +172,7 @@ pub(crate) trait HttpTransport: Send + Sync + 'static {
     );
     /// Human-readable URL label for the greeting banner.
     fn url_label(&self) -> String;
+    /// The lifecycle and inventory the health and admin endpoints report,
+    /// shared with the App so it can fill them in.
+    fn status(&self) -> std::sync::Arc<super::probes::ServerStatus>;
 }
@@ -81,8 +81,7 @@ pub async fn dispatch_post<E: HttpEngine>(
     // never produced.
     #[cfg(feature = "legacy-spec")]
     {
-        let resp = handle_post(neutral, ctx).await;
-        Ok(StreamResponse::<stream::Empty<E::SseEvent>>::Complete(resp))
+        Ok(handle_post_body::<stream::Empty<E::SseEvent>>(neutral, ctx).await)
     }
 }
 
@@ -143,13 +142,44 @@ pub async fn handle_post(req: HttpRequest, ctx: &HttpContext) -> HttpResponse {
                 return status_response(http::StatusCode::INTERNAL_SERVER_ERROR, id);
             }
             match resp_rx.await {
-                Ok(resp) => build_json_response(dispatched_status(&resp), id, &resp),
+                Ok(resp) => {
+                    // A single `HttpResponse` is whole: streamed blobs are read
+                    // into it.
+                    let resp = crate::transport::blob::materialize(resp).await;
+                    build_json_response(dispatched_status(&resp), id, &resp)
+                }
                 Err(_) => status_response(http::StatusCode::INTERNAL_SERVER_ERROR, id),
             }
         }
     }
 }
 
+/// The legacy arm of [`dispatch_post`]: [`handle_post`], but a reply carrying
+/// streamed blobs is written as it is read rather than read into memory.
+#[cfg(feature = "legacy-spec")]
+async fn handle_post_body<S>(req: HttpRequest, ctx: &HttpContext) -> StreamResponse<S> {
+    match prepare_post(req, ctx).await {
+        PostPrep::Reply(resp) => StreamResponse::Complete(resp),
+        PostPrep::Dispatch { id, msg } => {
+            let (resp_tx, resp_rx) = tokio::sync::oneshot::channel::<Message>();
+            ctx.pending.insert(msg.full_id(), resp_tx);
+            if ctx.inbound_tx.send(Ok(msg)).await.is_err() {
+                return StreamResponse::Complete(status_response(
+                    http::StatusCode::INTERNAL_SERVER_ERROR,
+                    id,
+                ));
+            }
+            match resp_rx.await {
+                Ok(resp) => json_reply(id, resp),
+                Err(_) => StreamResponse::Complete(status_response(
+                    http::StatusCode::INTERNAL_SERVER_ERROR,
+                    id,
+                )),
+            }
+        }
+    }
+}
+
 /// Outcome of the shared POST preamble: either an early reply (protocol error,
 /// parse error, or a `202` for a notification/notification-only batch, all with
 /// side effects already applied), or a request ready to dispatch.
@@ -205,19 +235,11 @@ async fn prepare_post(req: HttpRequest, ctx: &HttpContext) -> PostPrep {
         // version problem (-32022), and the client is told what is on offer so
         // it can retry. Both answer `400 Bad Request` per the spec.
         version_err = match header {
-            None => Some(Error::new(
-                ErrorCode::HeaderMismatch,
+            None => Some(Error::header_mismatch(
                 "Missing or malformed MCP-Protocol-Version header",
             )),
             Some(v) if v != crate::LATEST_PROTOCOL_VERSION => Some(
-                Error::new(
-                    ErrorCode::UnsupportedProtocolVersion,
-                    format!("Unsupported MCP protocol version: {v}"),
-                )
-                .with_data(serde_json::json!({
-                    "supported": [crate::LATEST_PROTOCOL_VERSION],
-                    "requested": v,
-                })),
+                Error::unsupported_protocol_version(v, [crate::LATEST_PROTOCOL_VERSION]),
             ),
             Some(_) => None,
         };
@@ -332,8 +354,7 @@ async fn prepare_post(req: HttpRequest, ctx: &HttpContext) -> PostPrep {
             .then(|| {
                 reject_post(
                     &msg,
-                    Error::new(
-                        ErrorCode::HeaderMismatch,
+                    Error::header_mismatch(
                         "Mcp-Method / Mcp-Name cannot describe a batch and must be omitted",
                     ),
                 )
@@ -351,14 +372,11 @@ async fn prepare_post(req: HttpRequest, ctx: &HttpContext) -> PostPrep {
                 .map(|stated| {
                     Message::Response(Response::error(
                         RequestId::Null,
-                        Error::new(
-                            ErrorCode::HeaderMismatch,
-                            format!(
-                                "Header mismatch: Mcp-Method header value {stated:?} \
+                        Error::header_mismatch(format!(
+                            "Header mismatch: Mcp-Method header value {stated:?} \
                                  does not match body value {:?}",
-                                n.method
-                            ),
-                        ),
+                            n.method
+                        )),
                     ))
                 }),
             _ => None,
@@ -502,11 +520,7 @@ async fn handle_post_streaming<E: HttpEngine>(
                     ));
                 }
                 return match resp_rx.await {
-                    Ok(resp) => StreamResponse::Complete(build_json_response(
-                        dispatched_status(&resp),
-                        id,
-                        &resp,
-                    )),
+                    Ok(resp) => json_reply(id, resp),
                     Err(_) => StreamResponse::Complete(status_response(
                         http::StatusCode::INTERNAL_SERVER_ERROR,
                         id,
@@ -805,7 +819,9 @@ fn post_notification_stream(
         if let Some(rx) = state.resp_rx.take() {
             state.response = rx.await.ok();
         }
-        state.response.take().map(|resp| (resp, state))
+        // An SSE event is written whole: streamed blobs are read into it.
+        let resp = crate::transport::blob::materialize(state.response.take()?).await;
+        Some((resp, state))
     })
 }
 
@@ -960,9 +976,7 @@ fn header_version_mismatch(
     let stated = req.stated_protocol_version()?;
     let header = header_version?;
     (stated != header).then(|| {
-        Error::new(
-            ErrorCode::HeaderMismatch,
-            format!(
+        Error::header_mismatch(format!(
                 "Header mismatch: MCP-Protocol-Version header value {header:?} does not match body value {stated:?}"
             ),
         )
@@ -1008,18 +1022,14 @@ fn name_source(req: &crate::types::Request) -> Option<(&str, bool)> {
 #[cfg(not(feature = "legacy-spec"))]
 fn routing_header_error(req: &crate::types::Request, headers: &HeaderMap) -> Option<Error> {
     let mismatch = |header: &str, stated: &str, body: &str| {
-        Some(Error::new(
-            ErrorCode::HeaderMismatch,
-            format!(
-                "Header mismatch: {header} header value {stated:?} does not match body value {body:?}"
-            ),
-        ))
+        Some(Error::header_mismatch(format!(
+            "Header mismatch: {header} header value {stated:?} does not match body value {body:?}"
+        )))
     };
     let missing = |header: &str| {
-        Some(Error::new(
-            ErrorCode::HeaderMismatch,
-            format!("Missing or malformed {header} header"),
-        ))
+        Some(Error::header_mismatch(format!(
+            "Missing or malformed {header} header"
+        )))
     };
 
     let method = crate::transport::http::MCP_METHOD;
@@ -1086,6 +1096,28 @@ fn dispatched_status(_msg: &Message) -> http::StatusCode {
     http::StatusCode::OK
 }
 
+/// Frames a dispatched reply as a single JSON body: written as it is read
+/// when it carries streamed blobs, built whole otherwise.
+fn json_reply<S>(session: uuid::Uuid, resp: Message) -> StreamResponse<S> {
+    let status = dispatched_status(&resp);
+    match crate::transport::blob::into_body(resp) {
+        Ok((_, body)) => {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                http::header::CONTENT_TYPE,
+                HeaderValue::from_static("application/json"),
+            );
+            // Stateless 2026-07-28 transport never puts the session id on the wire.
+            #[cfg(feature = "legacy-spec")]
+            if let Ok(v) = HeaderValue::from_str(&session.to_string()) {
+                headers.insert(MCP_SESSION_ID, v);
+            }
+            StreamResponse::Body { headers, body }
+        }
+        Err(resp) => StreamResponse::Complete(build_json_response(status, session, &resp)),
+    }
+}
+
 fn build_json_response(
     status: http::StatusCode,
     #[cfg_attr(not(feature = "legacy-spec"), allow(unused_variables))] session: uuid::Uuid,
@@ -1170,6 +1202,99 @@ fn parse_session_id(headers: &HeaderMap) -> Option<uuid::Uuid> {
         .and_then(|s| uuid::Uuid::parse_str(s).ok())
 }
 
+/// Handle a GET on the liveness path: `200 OK` for as long as the server
+/// can answer at all.
+///
+/// The engine mounts this on [`HttpContext::liveness_path`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let resp = E::adapt_response(handlers::handle_liveness());
+/// ```
+pub fn handle_liveness() -> HttpResponse {
+    probe_response(http::StatusCode::OK, "ok")
+}
+
+/// Handle a GET on the readiness path: `200 OK` while the server is
+/// dispatching requests, `503 Service Unavailable` before it starts to and
+/// from the moment shutdown is requested.
+///
+/// The engine mounts this on [`HttpContext::readiness_path`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let resp = E::adapt_response(handlers::handle_readiness(&ctx));
+/// ```
+pub fn handle_readiness(ctx: &HttpContext) -> HttpResponse {
+    if ctx.status.is_ready() {
+        probe_response(http::StatusCode::OK, "ready")
+    } else if ctx.status.is_draining() {
+        probe_response(http::StatusCode::SERVICE_UNAVAILABLE, "draining")
+    } else {
+        probe_response(http::StatusCode::SERVICE_UNAVAILABLE, "starting")
+    }
+}
+
+/// Handle a GET on the admin path: the JSON introspection report, for a
+/// request carrying the admin token as `Authorization: Bearer <token>`.
+///
+/// Answers `401 Unauthorized` without the token and `404 Not Found` when the
+/// admin endpoint is not configured. The engine mounts this on
+/// [`HttpContext::admin_path`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let neutral = E::adapt_request(req).await?;
+/// let resp = E::adapt_response(handlers::handle_admin(neutral, &ctx).await);
+/// ```
+pub async fn handle_admin(req: HttpRequest, ctx: &HttpContext) -> HttpResponse {
+    let Some(admin) = &ctx.admin else {
+        return http::Response::builder()
+            .status(http::StatusCode::NOT_FOUND)
+            .body(Bytes::new())
+            .unwrap_or_default();
+    };
+    let authorization = req
+        .headers()
+        .get(http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+    if !admin.authorizes(authorization) {
+        return http::Response::builder()
+            .status(http::StatusCode::UNAUTHORIZED)
+            .header(http::header::WWW_AUTHENTICATE, "Bearer")
+            .body(Bytes::new())
+            .unwrap_or_default();
+    }
+
+    let mut report = ctx.status.report().await;
+    if let Some(object) = report.as_object_mut() {
+        object.insert(
+            "sseSessions".into(),
+            serde_json::to_value(ctx.sse_registry.sessions()).unwrap_or_default(),
+        );
+    }
+    let body = serde_json::to_vec(&report).unwrap_or_default();
+    http::Response::builder()
+        .status(http::StatusCode::OK)
+        .header(http::header::CONTENT_TYPE, "application/json")
+        .header(http::header::CACHE_CONTROL, "no-store")
+        .body(Bytes::from(body))
+        .unwrap_or_default()
+}
+
+#[inline]
+fn probe_response(status: http::StatusCode, body: &'static str) -> HttpResponse {
+    http::Response::builder()
+        .status(status)
+        .header(http::header::CONTENT_TYPE, "text/plain")
+        .header(http::header::CACHE_CONTROL, "no-store")
+        .body(Bytes::from_static(body.as_bytes()))
+        .unwrap_or_default()
+}
+
 /// Handle a GET on the well-known path -- serves the RFC 9728 Protected
 /// Resource Metadata document pre-built at server start.
 ///
@@ -1387,6 +1512,9 @@ mod tests {
             // them; `Any` states that the gate is not what they are about.
             // `origin_gate_rejects_a_rebound_name` sets its own.
             origin_policy: crate::transport::http::core::origin::OriginPolicy::Any,
+            health_probes: Default::default(),
+            admin: None,
+            status: Default::default(),
             #[cfg(feature = "server-oauth")]
             oauth: None,
         };
@@ -2118,6 +2246,7 @@ mod tests {
         match handle_get_sse::<TestEngine>(get, &ctx).await {
             StreamResponse::Complete(r) => assert_eq!(r.status(), http::StatusCode::NOT_FOUND),
             StreamResponse::Stream { .. } => panic!("a terminated session opened a stream"),
+            StreamResponse::Body { .. } => panic!("a terminated session got a body"),
         }
     }
 
@@ -2286,6 +2415,7 @@ mod tests {
         match resp {
             StreamResponse::Complete(r) => assert_eq!(r.status(), http::StatusCode::BAD_REQUEST),
             StreamResponse::Stream { .. } => panic!("expected Status, got Stream"),
+            StreamResponse::Body { .. } => panic!("expected Status, got Body"),
         }
     }
 
@@ -2309,9 +2439,70 @@ mod tests {
                 );
             }
             StreamResponse::Complete(_) => panic!("expected Stream, got Status"),
+            StreamResponse::Body { .. } => panic!("expected Stream, got Body"),
         }
     }
 
+    #[test]
+    fn readiness_reports_starting_ready_and_draining() {
+        let (ctx, _rx) = make_ctx();
+        let shutdown = tokio_util::sync::CancellationToken::new();
+        ctx.status.watch_shutdown(shutdown.clone());
+
+        let resp = handle_readiness(&ctx);
+        assert_eq!(resp.status(), http::StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(resp.body().as_ref(), b"starting");
+
+        ctx.status
+            .accept(Box::new(|| Box::pin(async { serde_json::json!({}) })));
+        assert_eq!(handle_readiness(&ctx).status(), http::StatusCode::OK);
+        assert_eq!(handle_liveness().status(), http::StatusCode::OK);
+
+        shutdown.cancel();
+        let resp = handle_readiness(&ctx);
+        assert_eq!(resp.status(), http::StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(resp.body().as_ref(), b"draining");
+    }
+
+    #[tokio::test]
+    async fn admin_is_not_found_unless_configured() {
+        let (ctx, _rx) = make_ctx();
+        let req = http::Request::get("/admin").body(Bytes::new()).unwrap();
+
+        let resp = handle_admin(req, &ctx).await;
+        assert_eq!(resp.status(), http::StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn admin_requires_its_token_and_reports_sessions() {
+        use crate::transport::http::core::probes::AdminOptions;
+
+        let (mut ctx, _rx) = make_ctx();
+        ctx.admin = Some(
+            AdminOptions::default()
+                .with_token("t0ken")
+                .resolve()
+                .unwrap(),
+        );
+        ctx.sse_registry.pre_register(uuid::Uuid::new_v4());
+
+        let req = http::Request::get("/admin").body(Bytes::new()).unwrap();
+        let resp = handle_admin(req, &ctx).await;
+        assert_eq!(resp.status(), http::StatusCode::UNAUTHORIZED);
+
+        let req = http::Request::get("/admin")
+            .header(http::header::AUTHORIZATION, "Bearer t0ken")
+            .body(Bytes::new())
+            .unwrap();
+        let resp = handle_admin(req, &ctx).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+
+        let report: serde_json::Value = serde_json::from_slice(resp.body()).unwrap();
+        assert_eq!(report["sseSessions"].as_array().unwrap().len(), 1);
+        assert_eq!(report["sseSessions"][0]["connected"], false);
+        assert_eq!(report["ready"], false);
+    }
+
     #[cfg(feature = "server-oauth")]
     fn make_oauth_ctx() -> HttpContext {
         use crate::transport::http::core::oauth::OAuthResourceOptions;
@@ -0,0 +1,325 @@
+//! Liveness, readiness and admin introspection for the HTTP transport.
+//!
+//! An orchestrator needs two answers the MCP endpoint cannot give it: whether
+//! the process is up at all (*liveness*) and whether it should be sent traffic
+//! right now (*readiness*). Once enabled, both are served on paths of their
+//! own, outside the MCP endpoint and without authentication -- they carry no
+//! data beyond a status line. Readiness turns false as soon as shutdown is requested, so a
+//! load balancer stops routing new work here while the server drains what it
+//! already has.
+//!
+//! The admin endpoint is the opposite: off unless configured, guarded by a
+//! bearer token of its own, and reporting what the server is holding -- its
+//! tools, SSE sessions, live subscriptions, running tasks and build.
+
+use crate::error::{Error, ErrorCode};
+use crate::shared::BoxFuture;
+use std::sync::{
+    Arc, OnceLock,
+    atomic::{AtomicBool, Ordering},
+};
+use std::time::Instant;
+use tokio_util::sync::CancellationToken;
+
+const DEFAULT_LIVENESS_PATH: &str = "/healthz";
+const DEFAULT_READINESS_PATH: &str = "/readyz";
+const DEFAULT_ADMIN_PATH: &str = "/admin";
+
+/// Where the liveness and readiness probes are served.
+///
+/// Enabled and configured with
+/// [`HttpServer::with_health_probes`](crate::transport::http::HttpServer::with_health_probes).
+///
+/// # Example
+/// ```no_run
+/// use neva::App;
+///
+/// let app = App::new()
+///     .with_options(|opt| opt
+///         .with_http(|http| http
+///             .with_health_probes(|probes| probes
+///                 .with_liveness_path("/livez")
+///                 .with_readiness_path("/ready"))
+///         )
+///     );
+/// ```
+#[derive(Debug, Clone)]
+pub struct HealthProbeOptions {
+    pub(crate) liveness_path: Option<Arc<str>>,
+    pub(crate) readiness_path: Option<Arc<str>>,
+}
+
+impl Default for HealthProbeOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            liveness_path: Some(DEFAULT_LIVENESS_PATH.into()),
+            readiness_path: Some(DEFAULT_READINESS_PATH.into()),
+        }
+    }
+}
+
+impl HealthProbeOptions {
+    /// Serves the liveness probe on `path`.
+    ///
+    /// Default: `/healthz`
+    pub fn with_liveness_path(mut self, path: impl AsRef<str>) -> Self {
+        self.liveness_path = Some(path.as_ref().into());
+        self
+    }
+
+    /// Serves the readiness probe on `path`.
+    ///
+    /// Default: `/readyz`
+    pub fn with_readiness_path(mut self, path: impl AsRef<str>) -> Self {
+        self.readiness_path = Some(path.as_ref().into());
+        self
+    }
+
+    /// Does not serve the liveness probe.
+    pub fn without_liveness(mut self) -> Self {
+        self.liveness_path = None;
+        self
+    }
+
+    /// Does not serve the readiness probe.
+    pub fn without_readiness(mut self) -> Self {
+        self.readiness_path = None;
+        self
+    }
+}
+
+/// The admin introspection endpoint.
+///
+/// Configured with
+/// [`HttpServer::with_admin`](crate::transport::http::HttpServer::with_admin).
+/// A token is required: a server configured without one fails to start rather
+/// than serving its internals to anyone who asks.
+///
+/// # Example
+/// ```no_run
+/// use neva::App;
+///
+/// let app = App::new()
+///     .with_options(|opt| opt
+///         .with_http(|http| http
+///             .with_admin(|admin| admin
+///                 .with_token(std::env::var("ADMIN_TOKEN").unwrap_or_default())
+///                 .with_path("/_admin"))
+///         )
+///     );
+/// ```
+#[derive(Clone, Default)]
+pub struct AdminOptions {
+    path: Option<Arc<str>>,
+    token: Option<Arc<str>>,
+}
+
+impl std::fmt::Debug for AdminOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AdminOptions")
+            .field("path", &self.path)
+            .field("token", &self.token.as_ref().map(|_| "***"))
+            .finish()
+    }
+}
+
+impl AdminOptions {
+    /// Serves the admin endpoint on `path`.
+    ///
+    /// Default: `/admin`
+    pub fn with_path(mut self, path: impl AsRef<str>) -> Self {
+        self.path = Some(path.as_ref().into());
+        self
+    }
+
+    /// Sets the bearer token a request must carry in `Authorization` to be
+    /// answered.
+    pub fn with_token(mut self, token: impl AsRef<str>) -> Self {
+        self.token = Some(token.as_ref().into());
+        self
+    }
+
+    /// Checks the configuration once, at server start.
+    pub(crate) fn resolve(self) -> Result<AdminEndpoint, Error> {
+        match self.token {
+            Some(token) if !token.is_empty() => Ok(AdminEndpoint {
+                path: self.path.unwrap_or_else(|| DEFAULT_ADMIN_PATH.into()),
+                token,
+            }),
+            _ => Err(Error::new(
+                ErrorCode::InternalError,
+                "The admin endpoint requires a non-empty token",
+            )),
+        }
+    }
+}
+
+/// A resolved [`AdminOptions`], as the engine serves it.
+#[derive(Clone)]
+pub(crate) struct AdminEndpoint {
+    pub(crate) path: Arc<str>,
+    token: Arc<str>,
+}
+
+impl std::fmt::Debug for AdminEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AdminEndpoint")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AdminEndpoint {
+    /// Whether an `Authorization` header value carries the admin token.
+    ///
+    /// Compared in constant time, so the time a wrong guess takes says nothing
+    /// about how much of it was right.
+    pub(crate) fn authorizes(&self, header: Option<&str>) -> bool {
+        let Some(presented) = header.and_then(|h| h.strip_prefix("Bearer ")) else {
+            return false;
+        };
+        let (presented, expected) = (presented.trim().as_bytes(), self.token.as_bytes());
+        presented.len() == expected.len()
+            && presented
+                .iter()
+                .zip(expected)
+                .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+                == 0
+    }
+}
+
+/// What the application layer contributes to the admin report: everything the
+/// transport cannot see for itself.
+pub(crate) type StatusReporter =
+    Box<dyn Fn() -> BoxFuture<'static, serde_json::Value> + Send + Sync>;
+
+/// The server's lifecycle and inventory as the probes report it, shared by the
+/// transport that serves them and the [`App`](crate::App) that knows the
+/// answers.
+#[derive(Default)]
+pub(crate) struct ServerStatus {
+    /// Set once the application is dispatching requests.
+    accepting: AtomicBool,
+    /// The shutdown signal. Readiness ends the moment it fires, not when the
+    /// transport finally goes down.
+    shutdown: OnceLock<CancellationToken>,
+    started_at: OnceLock<Instant>,
+    reporter: OnceLock<StatusReporter>,
+}
+
+impl std::fmt::Debug for ServerStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServerStatus")
+            .field("ready", &self.is_ready())
+            .finish_non_exhaustive()
+    }
+}
+
+impl ServerStatus {
+    /// Ties readiness to the shutdown signal.
+    pub(crate) fn watch_shutdown(&self, token: CancellationToken) {
+        let _ = self.shutdown.set(token);
+    }
+
+    /// Marks the server as dispatching requests and installs what fills in
+    /// the admin report.
+    pub(crate) fn accept(&self, reporter: StatusReporter) {
+        let _ = self.reporter.set(reporter);
+        let _ = self.started_at.set(Instant::now());
+        self.accepting.store(true, Ordering::Release);
+    }
+
+    /// Whether the server should be sent new traffic.
+    pub(crate) fn is_ready(&self) -> bool {
+        self.accepting.load(Ordering::Acquire) && !self.is_draining()
+    }
+
+    /// Whether shutdown has been requested.
+    pub(crate) fn is_draining(&self) -> bool {
+        self.shutdown
+            .get()
+            .is_some_and(CancellationToken::is_cancelled)
+    }
+
+    /// Builds the admin report's application half, plus the lifecycle this
+    /// type tracks itself.
+    pub(crate) async fn report(&self) -> serde_json::Value {
+        let mut report = match self.reporter.get() {
+            Some(reporter) => reporter().await,
+            None => serde_json::Value::Object(Default::default()),
+        };
+        if let Some(object) = report.as_object_mut() {
+            object.insert("ready".into(), self.is_ready().into());
+            object.insert("draining".into(), self.is_draining().into());
+            object.insert(
+                "uptimeSeconds".into(),
+                self.started_at
+                    .get()
+                    .map(|at| at.elapsed().as_secs())
+                    .into(),
+            );
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readiness_follows_accepting_and_shutdown() {
+        let status = ServerStatus::default();
+        let shutdown = CancellationToken::new();
+        status.watch_shutdown(shutdown.clone());
+        assert!(!status.is_ready());
+
+        status.accept(Box::new(|| Box::pin(async { serde_json::json!({}) })));
+        assert!(status.is_ready());
+
+        shutdown.cancel();
+        assert!(!status.is_ready());
+        assert!(status.is_draining());
+    }
+
+    #[test]
+    fn admin_requires_a_token() {
+        assert!(AdminOptions::default().resolve().is_err());
+        assert!(AdminOptions::default().with_token("").resolve().is_err());
+
+        let admin = AdminOptions::default()
+            .with_token("s3cret")
+            .resolve()
+            .unwrap();
+        assert_eq!(&*admin.path, DEFAULT_ADMIN_PATH);
+    }
+
+    #[test]
+    fn admin_checks_the_bearer_token() {
+        let admin = AdminOptions::default()
+            .with_token("s3cret")
+            .resolve()
+            .unwrap();
+
+        assert!(admin.authorizes(Some("Bearer s3cret")));
+        assert!(!admin.authorizes(Some("Bearer s3cre")));
+        assert!(!admin.authorizes(Some("Bearer s3cret!")));
+        assert!(!admin.authorizes(Some("Basic s3cret")));
+        assert!(!admin.authorizes(None));
+    }
+
+    #[tokio::test]
+    async fn report_adds_the_lifecycle() {
+        let status = ServerStatus::default();
+        status.accept(Box::new(|| {
+            Box::pin(async { serde_json::json!({ "tools": ["echo"] }) })
+        }));
+
+        let report = status.report().await;
+        assert_eq!(report["tools"][0], "echo");
+        assert_eq!(report["ready"], true);
+        assert_eq!(report["draining"], false);
+        assert_eq!(report["uptimeSeconds"], 0);
+    }
+}
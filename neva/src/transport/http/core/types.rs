@@ -48,8 +48,8 @@ pub type HttpResponse = http::Response<Bytes>;
 ///
 /// `Stream` is the streaming path -- 200 OK + the event stream.
 /// `Complete` is a finished single-body reply: a JSON object or batch array,
-/// a `202 Accepted`, or an error status.
-#[derive(Debug)]
+/// a `202 Accepted`, or an error status. `Body` is a single JSON object too,
+/// but one too large to hold in memory, written as it is produced.
 pub enum StreamResponse<S> {
     /// 200 OK with an SSE event stream.
     Stream {
@@ -60,8 +60,38 @@ pub enum StreamResponse<S> {
     },
     /// A complete non-streaming reply (JSON body or bare status).
     Complete(HttpResponse),
+    /// 200 OK with a single JSON body written as it is produced: a
+    /// `resources/read` result whose blobs are read while it is sent.
+    Body {
+        /// Response headers, `Content-Type` included.
+        headers: HeaderMap,
+        /// The body, chunk by chunk. An error means the body cannot be
+        /// completed, and the engine should abort the response.
+        body: BodyStream,
+    },
+}
+
+impl<S: std::fmt::Debug> std::fmt::Debug for StreamResponse<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Stream { headers, stream } => f
+                .debug_struct("Stream")
+                .field("headers", headers)
+                .field("stream", stream)
+                .finish(),
+            Self::Complete(resp) => f.debug_tuple("Complete").field(resp).finish(),
+            Self::Body { headers, .. } => f
+                .debug_struct("Body")
+                .field("headers", headers)
+                .finish_non_exhaustive(),
+        }
+    }
 }
 
+/// The body of a [`StreamResponse::Body`] reply.
+pub type BodyStream =
+    std::pin::Pin<Box<dyn futures_util::Stream<Item = Result<Bytes, std::io::Error>> + Send>>;
+
 /// Former name of [`StreamResponse`], kept for one release.
 ///
 /// Note the `Status` variant is now [`StreamResponse::Complete`].
@@ -119,6 +119,9 @@ impl HttpEngine for VolgaEngine {
     async fn run(self, ctx: HttpContext, token: CancellationToken) -> Result<(), Error> {
         let addr = ctx.addr().to_owned();
         let endpoint = ctx.endpoint().to_owned();
+        let liveness_path = ctx.liveness_path().map(str::to_owned);
+        let readiness_path = ctx.readiness_path().map(str::to_owned);
+        let admin_path = ctx.admin_path().map(str::to_owned);
         #[cfg(feature = "server-oauth")]
         let oauth_metadata_path = ctx.oauth_metadata_path().map(str::to_owned);
         #[cfg(feature = "server-oauth")]
@@ -197,6 +200,19 @@ impl HttpEngine for VolgaEngine {
             server.map_get(path, routes::oauth_metadata);
         }
 
+        // Probes and the admin report sit outside the MCP group too: an
+        // orchestrator has no MCP credentials, and the admin endpoint checks a
+        // token of its own.
+        if let Some(path) = &liveness_path {
+            server.map_get(path, routes::liveness);
+        }
+        if let Some(path) = &readiness_path {
+            server.map_get(path, routes::readiness);
+        }
+        if let Some(path) = &admin_path {
+            server.map_get(path, routes::admin);
+        }
+
         if let Err(e) = server.run().await {
             token.cancel();
             return Err(Error::new(ErrorCode::InternalError, e.to_string()));
@@ -8,11 +8,14 @@
 //! routes call those methods so the seam matches every other engine.
 
 use crate::transport::http::core::{
-    context::HttpContext, engine::HttpEngine, handlers, types::StreamResponse,
+    context::HttpContext,
+    engine::HttpEngine,
+    handlers,
+    types::{BodyStream, StreamResponse},
 };
 use ::volga::{
-    HttpRequest, HttpResult, di::Dc, error::Error as VolgaError, http::sse::Message as SseMessage,
-    sse,
+    HttpBody, HttpRequest, HttpResult, di::Dc, error::Error as VolgaError,
+    http::sse::Message as SseMessage, sse,
 };
 
 use super::engine::VolgaEngine;
@@ -41,6 +44,7 @@ pub(crate) async fn post(manager: Dc<HttpContext>, req: HttpRequest) -> HttpResu
             sse!(stream; [crate::transport::http::CONTENT_TYPE_OPTIONS])
         }
         StreamResponse::Complete(resp) => VolgaEngine::adapt_response(resp),
+        StreamResponse::Body { headers, body } => body_response(headers, body),
     }
 }
 
@@ -86,6 +90,7 @@ pub(crate) async fn get(manager: Dc<HttpContext>, req: HttpRequest) -> HttpResul
             }
         }
         StreamResponse::Complete(resp) => VolgaEngine::adapt_response(resp),
+        StreamResponse::Body { headers, body } => body_response(headers, body),
     }
 }
 
@@ -101,6 +106,37 @@ pub(crate) async fn oauth_metadata(manager: Dc<HttpContext>) -> HttpResult {
     VolgaEngine::adapt_response(handlers::handle_oauth_metadata(&manager))
 }
 
+/// `GET <liveness path>` -- the liveness probe, outside the MCP endpoint group
+/// and its authorization.
+pub(crate) async fn liveness() -> HttpResult {
+    VolgaEngine::adapt_response(handlers::handle_liveness())
+}
+
+/// `GET <readiness path>` -- the readiness probe, outside the MCP endpoint
+/// group and its authorization.
+pub(crate) async fn readiness(manager: Dc<HttpContext>) -> HttpResult {
+    VolgaEngine::adapt_response(handlers::handle_readiness(&manager))
+}
+
+/// `GET <admin path>` -- the admin report. Outside the MCP endpoint group: the
+/// admin token is checked by [`handlers::handle_admin`], not by the bearer
+/// pipeline guarding MCP.
+pub(crate) async fn admin(manager: Dc<HttpContext>, req: HttpRequest) -> HttpResult {
+    let neutral = VolgaEngine::adapt_request(req)
+        .await
+        .map_err(to_volga_err)?;
+    VolgaEngine::adapt_response(handlers::handle_admin(neutral, &manager).await)
+}
+
+/// A `200 OK` whose body is written as `body` yields it.
+fn body_response(headers: http::HeaderMap, body: BodyStream) -> HttpResult {
+    let mut builder = ::volga::builder!(200);
+    for (name, value) in headers.iter() {
+        builder = builder.header_raw(name.as_str(), value.as_bytes());
+    }
+    builder.body(HttpBody::stream(body))
+}
+
 /// Map a neva `Error` raised by engine-agnostic helpers onto a Volga
 /// server-error so the route can short-circuit with `?` into `HttpResult`.
 fn to_volga_err(err: crate::error::Error) -> VolgaError {
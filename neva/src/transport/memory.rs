@@ -0,0 +1,85 @@
+//! An in-process transport over unbounded channels, for [`crate::testing`]
+//! and [`replay`](super::replay).
+
+use crate::error::{Error, ErrorCode};
+use crate::transport::{Receiver, Sender, Transport};
+use crate::types::Message;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio_util::sync::CancellationToken;
+
+/// One end of an in-process connection: what it sends comes out of its
+/// [`Remote`], and what is put into the [`Remote`] it receives.
+pub(crate) struct MemoryTransport {
+    token: CancellationToken,
+    sender: MemorySender,
+    receiver: MemoryReceiver,
+}
+
+/// The far side of a [`MemoryTransport`].
+pub(crate) struct Remote {
+    /// Delivers a message to the transport.
+    pub(crate) tx: UnboundedSender<Message>,
+    /// Yields what the transport sent.
+    pub(crate) rx: UnboundedReceiver<Message>,
+}
+
+/// Sends over a [`MemoryTransport`].
+#[derive(Clone)]
+pub(crate) struct MemorySender {
+    tx: UnboundedSender<Message>,
+}
+
+/// Receives over a [`MemoryTransport`].
+pub(crate) struct MemoryReceiver {
+    rx: UnboundedReceiver<Message>,
+}
+
+impl MemoryTransport {
+    /// Creates a transport stopped by `token`, and its far side.
+    pub(crate) fn new(token: CancellationToken) -> (Self, Remote) {
+        let (out_tx, out_rx) = mpsc::unbounded_channel();
+        let (in_tx, in_rx) = mpsc::unbounded_channel();
+        let transport = Self {
+            token,
+            sender: MemorySender { tx: out_tx },
+            receiver: MemoryReceiver { rx: in_rx },
+        };
+        let remote = Remote {
+            tx: in_tx,
+            rx: out_rx,
+        };
+        (transport, remote)
+    }
+}
+
+impl Transport for MemoryTransport {
+    type Sender = MemorySender;
+    type Receiver = MemoryReceiver;
+
+    #[inline]
+    fn start(&mut self) -> CancellationToken {
+        self.token.clone()
+    }
+
+    #[inline]
+    fn split(self) -> (Self::Sender, Self::Receiver) {
+        (self.sender, self.receiver)
+    }
+}
+
+impl Sender for MemorySender {
+    async fn send(&mut self, message: Message) -> Result<(), Error> {
+        self.tx
+            .send(message)
+            .map_err(|_| Error::new(ErrorCode::InternalError, "Connection closed"))
+    }
+}
+
+impl Receiver for MemoryReceiver {
+    async fn recv(&mut self) -> Result<Message, Error> {
+        self.rx
+            .recv()
+            .await
+            .ok_or_else(|| Error::new(ErrorCode::InvalidRequest, "Unexpected end of stream"))
+    }
+}
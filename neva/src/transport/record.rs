@@ -0,0 +1,409 @@
+//! Recording the JSON-RPC traffic of a transport.
+//!
+//! A [`Recorder`] attached with `with_recorder` -- on the server's or the
+//! client's options -- writes every [`Message`] the transport sends or
+//! receives as one line of JSON, with the time, the side that sent it and the
+//! session it belongs to:
+//!
+//! ```text
+//! {"ts":"2026-10-18T09:12:03.114Z","from":"client","message":{"jsonrpc":"2.0","id":1,"method":"tools/list"}}
+//! {"ts":"2026-10-18T09:12:03.119Z","from":"server","message":{"jsonrpc":"2.0","id":1,"result":{"tools":[]}}}
+//! ```
+//!
+//! A [`Recording`] reads such a file back, for a [`Replay`](super::replay::Replay)
+//! to play.
+//!
+//! Only what goes over the wire is recorded: HTTP headers and the claims of an
+//! authenticated request are not.
+
+use crate::error::{Error, ErrorCode};
+use crate::transport::{Receiver, Sender, TransportProtoReceiver, TransportProtoSender};
+use crate::types::Message;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Formatter};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex, mpsc};
+
+/// A side of an MCP session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Peer {
+    /// The MCP client.
+    Client,
+
+    /// The MCP server.
+    Server,
+}
+
+/// One message of a [`Recording`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedMessage {
+    /// When the recording side sent or received the message.
+    #[serde(rename = "ts")]
+    pub timestamp: DateTime<Utc>,
+
+    /// Which side sent the message.
+    pub from: Peer,
+
+    /// The MCP session the message belongs to, if the transport has sessions.
+    #[serde(rename = "session", default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<uuid::Uuid>,
+
+    /// The message itself.
+    pub message: Message,
+}
+
+/// A recorded session: the messages both sides sent, in the order the
+/// recording side saw them.
+#[derive(Debug, Clone, Default)]
+pub struct Recording {
+    messages: Vec<RecordedMessage>,
+}
+
+/// Writes the traffic of a transport as JSON lines.
+///
+/// Clones share the sink, so one recorder can record a server and a client
+/// into the same file. The lines are written on a thread of the recorder's
+/// own, so a slow disk never holds up a transport; [`Self::flush`] waits for
+/// them. A line that cannot be written is dropped rather than failing the
+/// message it records.
+///
+/// # Example
+/// ```no_run
+/// # #[cfg(feature = "server")] {
+/// use neva::App;
+/// use neva::transport::record::Recorder;
+///
+/// # fn run() -> Result<(), neva::error::Error> {
+/// let recorder = Recorder::create("session.jsonl")?;
+/// let app = App::new().with_options(|opt| opt
+///     .with_stdio()
+///     .with_recorder(recorder));
+/// # Ok(()) }
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Recorder {
+    sink: Arc<Sink>,
+}
+
+/// Where a [`Recorder`] writes.
+enum Sink {
+    /// The writer thread's queue.
+    Writer(mpsc::Sender<Command>),
+    Memory(Mutex<Vec<RecordedMessage>>),
+}
+
+/// What the writer thread is asked to do.
+enum Command {
+    Write(Box<RecordedMessage>),
+    Flush(tokio::sync::oneshot::Sender<()>),
+}
+
+impl Debug for Recorder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Recorder").finish_non_exhaustive()
+    }
+}
+
+impl Recorder {
+    /// Creates a recorder that writes to the file at `path`, truncating it.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let file = std::fs::File::create(path)?;
+        Ok(Self::new(std::io::BufWriter::new(file)))
+    }
+
+    /// Creates a recorder that writes to `writer` on a thread of its own,
+    /// flushing it whenever it has written all it was given.
+    ///
+    /// The thread ends once every clone of the recorder is dropped.
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        let (tx, rx) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("neva-recorder".into())
+            .spawn(move || write_lines(writer, rx))
+            .expect("failed to spawn the recorder thread");
+        Self {
+            sink: Arc::new(Sink::Writer(tx)),
+        }
+    }
+
+    /// Creates a recorder that keeps what it records in memory, for
+    /// [`Self::recording`] to return.
+    pub fn in_memory() -> Self {
+        Self {
+            sink: Arc::new(Sink::Memory(Mutex::new(Vec::new()))),
+        }
+    }
+
+    /// Returns what an [`in_memory`](Self::in_memory) recorder has recorded
+    /// so far, or `None` for one that writes elsewhere.
+    pub fn recording(&self) -> Option<Recording> {
+        match &*self.sink {
+            Sink::Memory(messages) => Some(Recording {
+                messages: messages.lock().ok()?.clone(),
+            }),
+            Sink::Writer(_) => None,
+        }
+    }
+
+    /// Waits until every message recorded so far is written and the writer
+    /// flushed. Returns at once for an [`in_memory`](Self::in_memory) recorder.
+    pub async fn flush(&self) {
+        if let Sink::Writer(tx) = &*self.sink {
+            let (done, flushed) = tokio::sync::oneshot::channel();
+            if tx.send(Command::Flush(done)).is_ok() {
+                let _ = flushed.await;
+            }
+        }
+    }
+
+    /// Records `message`, sent by `from`.
+    pub(crate) fn record(&self, from: Peer, message: &Message) {
+        // Kept as it reads on the wire, so that an in-memory recording plays
+        // the same as one read back from a file.
+        let Ok(wire) = serde_json::to_value(message).and_then(serde_json::from_value) else {
+            return;
+        };
+        let entry = RecordedMessage {
+            timestamp: Utc::now(),
+            from,
+            session_id: message.session_id().copied(),
+            message: wire,
+        };
+        match &*self.sink {
+            Sink::Memory(messages) => {
+                if let Ok(mut messages) = messages.lock() {
+                    messages.push(entry);
+                }
+            }
+            Sink::Writer(tx) => {
+                let _ = tx.send(Command::Write(Box::new(entry)));
+            }
+        }
+    }
+}
+
+/// Writes what `commands` brings to `writer` until every [`Recorder`] sharing
+/// it is gone, flushing whenever the queue runs dry.
+fn write_lines(mut writer: impl Write, commands: mpsc::Receiver<Command>) {
+    let mut unflushed = false;
+    loop {
+        let command = match commands.try_recv() {
+            Ok(command) => command,
+            Err(mpsc::TryRecvError::Empty) => {
+                if unflushed {
+                    flush(&mut writer);
+                }
+                match commands.recv() {
+                    Ok(command) => command,
+                    Err(_) => return,
+                }
+            }
+            Err(mpsc::TryRecvError::Disconnected) => break,
+        };
+        match command {
+            Command::Write(entry) => {
+                let written =
+                    serde_json::to_vec(&entry)
+                        .map_err(Error::from)
+                        .and_then(|mut line| {
+                            line.push(b'\n');
+                            writer.write_all(&line).map_err(Error::from)
+                        });
+                if let Err(_err) = written {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(logger = "neva", "Failed to record a message: {_err}");
+                }
+                unflushed = true;
+            }
+            Command::Flush(done) => {
+                flush(&mut writer);
+                unflushed = false;
+                let _ = done.send(());
+            }
+        }
+    }
+    if unflushed {
+        flush(&mut writer);
+    }
+}
+
+#[inline]
+fn flush(writer: &mut impl Write) {
+    if let Err(_err) = writer.flush() {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(logger = "neva", "Failed to flush the recording: {_err}");
+    }
+}
+
+impl Recording {
+    /// Reads the recording at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Parses a recording from its JSON lines. Blank lines are skipped.
+    pub fn parse(lines: &str) -> Result<Self, Error> {
+        let messages = lines
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(n, line)| {
+                serde_json::from_str(line).map_err(|err| {
+                    Error::new(ErrorCode::ParseError, format!("line {}: {err}", n + 1))
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { messages })
+    }
+
+    /// The recorded messages, in order.
+    #[inline]
+    pub fn messages(&self) -> &[RecordedMessage] {
+        &self.messages
+    }
+
+    /// Writes the recording as JSON lines.
+    pub fn write_to(&self, mut writer: impl Write) -> Result<(), Error> {
+        for message in &self.messages {
+            serde_json::to_writer(&mut writer, message)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush().map_err(Error::from)
+    }
+}
+
+impl From<Vec<RecordedMessage>> for Recording {
+    #[inline]
+    fn from(messages: Vec<RecordedMessage>) -> Self {
+        Self { messages }
+    }
+}
+
+/// A [`Recorder`] attached to one side of a transport.
+#[derive(Debug, Clone)]
+pub(crate) struct Tap {
+    recorder: Recorder,
+    local: Peer,
+}
+
+impl Tap {
+    /// Attaches `recorder` to the transport of `local`.
+    pub(crate) fn new(recorder: Recorder, local: Peer) -> Self {
+        Self { recorder, local }
+    }
+
+    /// The side at the other end of the transport.
+    #[inline]
+    fn remote(&self) -> Peer {
+        match self.local {
+            Peer::Client => Peer::Server,
+            Peer::Server => Peer::Client,
+        }
+    }
+}
+
+/// Records what a sender sends before sending it.
+pub(crate) async fn send(
+    inner: &mut TransportProtoSender,
+    tap: &Tap,
+    message: Message,
+) -> Result<(), Error> {
+    tap.recorder.record(tap.local, &message);
+    Box::pin(inner.send(message)).await
+}
+
+/// Records what a receiver receives.
+pub(crate) async fn recv(inner: &mut TransportProtoReceiver, tap: &Tap) -> Result<Message, Error> {
+    let message = Box::pin(inner.recv()).await?;
+    tap.recorder.record(tap.remote(), &message);
+    Ok(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Request, RequestId, Response};
+
+    #[test]
+    fn it_records_in_memory() {
+        let recorder = Recorder::in_memory();
+        let request = Request::new(Some(RequestId::Number(1)), "tools/list", None::<()>);
+        recorder.record(Peer::Client, &request.into());
+        recorder.record(
+            Peer::Server,
+            &Response::success(RequestId::Number(1), serde_json::json!({})).into(),
+        );
+
+        let recording = recorder.recording().unwrap();
+        let from: Vec<_> = recording.messages().iter().map(|m| m.from).collect();
+        assert_eq!(from, [Peer::Client, Peer::Server]);
+        assert!(Recorder::new(std::io::sink()).recording().is_none());
+    }
+
+    #[test]
+    fn it_round_trips_json_lines() {
+        let recorder = Recorder::in_memory();
+        let request = Request::new(Some(RequestId::Number(7)), "ping", None::<()>);
+        recorder.record(Peer::Client, &request.into());
+
+        let mut lines = Vec::new();
+        recorder.recording().unwrap().write_to(&mut lines).unwrap();
+        let lines = String::from_utf8(lines).unwrap();
+        assert!(lines.contains(r#""from":"client""#), "{lines}");
+
+        let parsed = Recording::parse(&format!("\n{lines}\n")).unwrap();
+        assert_eq!(parsed.messages().len(), 1);
+        assert_eq!(parsed.messages()[0].message.id(), RequestId::Number(7));
+    }
+
+    /// A writer the test can read back.
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn it_writes_lines_off_the_caller() {
+        let out = Shared::default();
+        let recorder = Recorder::new(out.clone());
+        for id in 1..=3 {
+            let request = Request::new(Some(RequestId::Number(id)), "ping", None::<()>);
+            recorder.record(Peer::Client, &request.into());
+        }
+        recorder.flush().await;
+
+        let lines = String::from_utf8(out.0.lock().unwrap().clone()).unwrap();
+        let recording = Recording::parse(&lines).unwrap();
+        let ids: Vec<_> = recording
+            .messages()
+            .iter()
+            .map(|m| m.message.id())
+            .collect();
+        assert_eq!(
+            ids,
+            [
+                RequestId::Number(1),
+                RequestId::Number(2),
+                RequestId::Number(3)
+            ]
+        );
+    }
+
+    #[test]
+    fn a_malformed_line_names_its_number() {
+        let err = Recording::parse("\n{not json}").unwrap_err();
+        assert!(err.to_string().starts_with("line 2:"), "{err}");
+    }
+}
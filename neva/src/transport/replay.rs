@@ -0,0 +1,504 @@
+//! Replaying a [`Recording`] against an [`App`](crate::App), or to a
+//! [`Client`](crate::Client) as a mock server.
+//!
+//! A [`Replay`] plays one side of the recorded session and expects the other
+//! side -- the code under test -- to send what it sent then. Playing the
+//! client drives an app ([`Replay::run`]); playing the server answers a
+//! client's requests with the recorded responses (`McpOptions::with_replay`).
+//!
+//! Messages are played in recorded order, each one only once everything the
+//! other side sent before it has arrived, so a request that depended on an
+//! earlier answer is not sent ahead of it. Timing is not reproduced.
+//!
+//! # Matching
+//! A message matches a recorded one when both serialize to the same JSON, with
+//! these exceptions:
+//! * the `id` of a request is not compared, since the side under test mints
+//!   its own; a recorded response to it is played back under the new id;
+//! * a result's `requestState` is not compared, since it is sealed anew each
+//!   time; a recorded request that echoes it back is played with the new one;
+//! * the JSON pointers passed to [`Replay::with_ignored`] are not compared.
+//!
+//! The side under test may send messages the recording does not have -- a log
+//! notification, say; those are let through.
+
+use crate::error::{Error, ErrorCode};
+use crate::transport::Transport;
+use crate::transport::memory::{MemoryReceiver, MemorySender, MemoryTransport};
+use crate::transport::record::{Peer, RecordedMessage, Recording};
+use crate::types::{Message, RequestId};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+/// How long a replay waits for an expected message unless set otherwise.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The pointer of the sealed MRTR state in a result.
+const RESULT_STATE: &str = "/result/requestState";
+
+/// The pointer of the sealed MRTR state a retried request echoes back, in its
+/// params.
+const PARAMS_STATE: &str = "/requestState";
+
+/// Where a mock server's driver is left for [`Replay::finish`].
+type DriverSlot = Arc<Mutex<Option<JoinHandle<Result<(), Error>>>>>;
+
+/// A recorded session, ready to be played against the code under test.
+///
+/// # Example
+/// ```no_run
+/// # #[cfg(feature = "server")] {
+/// use neva::App;
+/// use neva::transport::{record::Recording, replay::Replay};
+///
+/// # async fn replay() -> Result<(), neva::error::Error> {
+/// let mut app = App::new();
+/// app.map_tool("add", |a: i32, b: i32| async move { a + b });
+///
+/// Replay::new(Recording::open("tests/sessions/add.jsonl")?)
+///     .with_ignored("/result/_meta")
+///     .run(app)
+///     .await
+/// # }
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Replay {
+    messages: Arc<[RecordedMessage]>,
+    ignored: Vec<String>,
+    timeout: Duration,
+    /// The driver a mock server runs, once a client has started it.
+    driver: DriverSlot,
+}
+
+impl Debug for Replay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Replay")
+            .field("messages", &self.messages.len())
+            .field("ignored", &self.ignored)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+impl Replay {
+    /// Creates a replay of `recording`.
+    pub fn new(recording: Recording) -> Self {
+        Self {
+            messages: recording.messages().into(),
+            ignored: Vec::new(),
+            timeout: DEFAULT_TIMEOUT,
+            driver: Arc::default(),
+        }
+    }
+
+    /// Leaves the value at `pointer` (RFC 6901, e.g. `/result/createdAt`)
+    /// out of every comparison, for a field that differs from run to run.
+    ///
+    /// Default: only request ids and `requestState` are left out.
+    pub fn with_ignored(mut self, pointer: impl Into<String>) -> Self {
+        self.ignored.push(pointer.into());
+        self
+    }
+
+    /// Sets how long to wait for each expected message before failing.
+    ///
+    /// Default: 5 seconds
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Plays the client's side of the recording against `app`, and returns
+    /// once every message of it has been exchanged.
+    ///
+    /// Fails with the first server message that does not arrive as recorded.
+    /// Whatever transport `app` was configured with is not used.
+    #[cfg(feature = "server")]
+    pub async fn run(&self, app: crate::App) -> Result<(), Error> {
+        let (transport, driver) = self.transport(Peer::Client);
+        let token = driver.token.clone();
+        let app =
+            app.with_options(|opt| opt.with_transport(super::TransportProto::Replay(transport)));
+        let server = tokio::spawn(app.run());
+
+        let result = driver.run().await;
+        token.cancel();
+        server
+            .await
+            .map_err(|err| Error::new(ErrorCode::InternalError, err.to_string()))?;
+        result
+    }
+
+    /// Waits for a client given this replay to exchange every message of the
+    /// recording, and returns how that went.
+    ///
+    /// Fails with the first client message that does not arrive as recorded,
+    /// or if no client was connected with it.
+    pub async fn finish(&self) -> Result<(), Error> {
+        let driver = self.driver.lock().ok().and_then(|mut driver| driver.take());
+        let Some(driver) = driver else {
+            return Err(Error::new(
+                ErrorCode::InternalError,
+                "the replay was not started: no client connected with it",
+            ));
+        };
+        driver
+            .await
+            .map_err(|err| Error::new(ErrorCode::InternalError, err.to_string()))?
+    }
+
+    /// A transport that plays the server's side of the recording to a client.
+    #[cfg(feature = "client")]
+    pub(crate) fn serve(&self) -> ReplayTransport {
+        let (mut transport, driver) = self.transport(Peer::Server);
+        transport.driver = Some((driver, self.driver.clone()));
+        transport
+    }
+
+    /// A transport for the side under test, and the driver that plays the
+    /// side `plays` over it.
+    fn transport(&self, plays: Peer) -> (ReplayTransport, Driver) {
+        let token = CancellationToken::new();
+        let (inner, remote) = MemoryTransport::new(token.clone());
+        let driver = Driver {
+            messages: self.messages.clone(),
+            plays,
+            ignored: self.ignored.clone(),
+            timeout: self.timeout,
+            played: remote.tx,
+            observed: remote.rx,
+            token,
+        };
+        let transport = ReplayTransport {
+            inner,
+            driver: None,
+        };
+        (transport, driver)
+    }
+}
+
+/// The transport the side under test is given in place of its own.
+pub(crate) struct ReplayTransport {
+    /// What the side under test sends reaches the driver through the far
+    /// side, and what the driver plays comes back through it.
+    inner: MemoryTransport,
+    /// The driver to start with the transport, and where to leave it.
+    driver: Option<(Driver, DriverSlot)>,
+}
+
+impl Transport for ReplayTransport {
+    type Sender = MemorySender;
+    type Receiver = MemoryReceiver;
+
+    fn start(&mut self) -> CancellationToken {
+        if let Some((driver, slot)) = self.driver.take() {
+            let handle = tokio::spawn(driver.run());
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some(handle);
+            }
+        }
+        self.inner.start()
+    }
+
+    #[inline]
+    fn split(self) -> (Self::Sender, Self::Receiver) {
+        self.inner.split()
+    }
+}
+
+/// Plays one side of a recording and checks what the other side sends.
+struct Driver {
+    messages: Arc<[RecordedMessage]>,
+    plays: Peer,
+    ignored: Vec<String>,
+    timeout: Duration,
+    played: UnboundedSender<Message>,
+    observed: UnboundedReceiver<Message>,
+    token: CancellationToken,
+}
+
+/// What a driver learned from the messages it matched so far.
+#[derive(Default)]
+struct Matched {
+    /// Recorded request ids, by the ids the side under test used instead.
+    ids: HashMap<RequestId, RequestId>,
+    /// Recorded `requestState`s, by the ones the side under test sealed.
+    states: HashMap<String, String>,
+    /// Messages that arrived but have not matched a recorded one yet.
+    unmatched: Vec<Message>,
+}
+
+impl Driver {
+    async fn run(mut self) -> Result<(), Error> {
+        let mut matched = Matched::default();
+        let messages = self.messages.clone();
+        for (n, recorded) in messages.iter().enumerate() {
+            if recorded.from == self.plays {
+                let mut message = matched.substitute(recorded.message.clone());
+                // A server keys its state by session; a client keys its
+                // pending requests by the bare id, as a transport would hand
+                // it a response.
+                if let (Peer::Client, Some(session_id)) = (self.plays, recorded.session_id) {
+                    message = message.set_session_id(session_id);
+                }
+                if self.played.send(message).is_err() {
+                    return Err(diverged(n, "the side under test closed the transport"));
+                }
+            } else {
+                self.expect(n, &recorded.message, &mut matched).await?;
+            }
+        }
+
+        // The side under test may still be talking -- a client disconnecting,
+        // say -- so its end stays open until the transport is stopped.
+        let (played, token) = (self.played, self.token);
+        tokio::spawn(async move {
+            token.cancelled().await;
+            drop(played);
+        });
+        Ok(())
+    }
+
+    /// Waits for the message recorded as the `n`th, from the side under test.
+    async fn expect(
+        &mut self,
+        n: usize,
+        expected: &Message,
+        matched: &mut Matched,
+    ) -> Result<(), Error> {
+        let want = self.normalize(expected);
+        if let Some(i) = matched
+            .unmatched
+            .iter()
+            .position(|actual| self.normalize(actual) == want)
+        {
+            let actual = matched.unmatched.remove(i);
+            matched.learn(expected, &actual);
+            return Ok(());
+        }
+
+        let deadline = tokio::time::Instant::now() + self.timeout;
+        let reason = loop {
+            match tokio::time::timeout_at(deadline, self.observed.recv()).await {
+                Ok(Some(actual)) if self.normalize(&actual) == want => {
+                    matched.learn(expected, &actual);
+                    return Ok(());
+                }
+                Ok(Some(actual)) => matched.unmatched.push(actual),
+                Ok(None) => break "the side under test closed the transport",
+                Err(_) => break "it did not arrive in time",
+            }
+        };
+
+        let sent = matched
+            .unmatched
+            .iter()
+            .map(|message| serde_json::to_string(message).unwrap_or_default())
+            .collect::<Vec<_>>();
+        Err(diverged(
+            n,
+            format!(
+                "expected {want}, but {reason}; unmatched messages it sent: [{}]",
+                sent.join(", ")
+            ),
+        ))
+    }
+
+    /// The JSON of `message` that takes part in the comparison.
+    fn normalize(&self, message: &Message) -> Value {
+        let mut value = serde_json::to_value(message).unwrap_or_default();
+        if let Message::Request(_) = message
+            && let Value::Object(map) = &mut value
+        {
+            map.remove("id");
+        }
+        remove_pointer(&mut value, RESULT_STATE);
+        for pointer in &self.ignored {
+            remove_pointer(&mut value, pointer);
+        }
+        value
+    }
+}
+
+impl Matched {
+    /// Notes how `actual`, matched to the recorded `expected`, differs from it
+    /// in what the comparison leaves out.
+    fn learn(&mut self, expected: &Message, actual: &Message) {
+        if let (Message::Request(expected), Message::Request(actual)) = (expected, actual) {
+            self.ids.insert(expected.id(), actual.id());
+        }
+        let state = |message: &Message| {
+            serde_json::to_value(message)
+                .ok()?
+                .pointer(RESULT_STATE)?
+                .as_str()
+                .map(str::to_owned)
+        };
+        if let (Some(expected), Some(actual)) = (state(expected), state(actual)) {
+            self.states.insert(expected, actual);
+        }
+    }
+
+    /// Rewrites a recorded message to play for what was learned: a response
+    /// goes to the id its request was sent under, and a retry carries the
+    /// state sealed this time.
+    fn substitute(&self, message: Message) -> Message {
+        match message {
+            Message::Response(resp) => match self.ids.get(resp.id()) {
+                Some(id) => Message::Response(resp.set_id(id.clone())),
+                None => Message::Response(resp),
+            },
+            Message::Request(mut req) => {
+                if let Some(params) = req.params.as_mut()
+                    && let Some(state) = params.pointer_mut(PARAMS_STATE)
+                    && let Some(sealed) = state.as_str().and_then(|s| self.states.get(s))
+                {
+                    *state = Value::String(sealed.clone());
+                }
+                Message::Request(req)
+            }
+            other => other,
+        }
+    }
+}
+
+/// Removes the value at the JSON `pointer`, if there is one.
+fn remove_pointer(value: &mut Value, pointer: &str) {
+    let Some((parent, key)) = pointer.rsplit_once('/') else {
+        return;
+    };
+    let key = key.replace("~1", "/").replace("~0", "~");
+    match value.pointer_mut(parent) {
+        Some(Value::Object(map)) => {
+            map.remove(&key);
+        }
+        Some(Value::Array(items)) => {
+            if let Ok(i) = key.parse::<usize>()
+                && i < items.len()
+            {
+                items.remove(i);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The error a replay fails with at the `n`th recorded message.
+fn diverged(n: usize, reason: impl std::fmt::Display) -> Error {
+    Error::new(
+        ErrorCode::InternalError,
+        format!("replay diverged at recorded message {}: {reason}", n + 1),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::Sender;
+    use crate::types::{Request, Response};
+    use serde_json::json;
+
+    fn replay() -> Replay {
+        Replay::new(Recording::default()).with_ignored("/result/_meta/at")
+    }
+
+    fn driver(replay: &Replay) -> Driver {
+        replay.transport(Peer::Client).1
+    }
+
+    #[test]
+    fn requests_match_whatever_their_id() {
+        let driver = driver(&replay());
+        let recorded = Request::new(Some(RequestId::Number(1)), "ping", None::<()>).into();
+        let actual = Request::new(Some(RequestId::Number(9)), "ping", None::<()>).into();
+
+        assert_eq!(driver.normalize(&recorded), driver.normalize(&actual));
+    }
+
+    #[test]
+    fn ignored_fields_and_request_state_are_not_compared() {
+        let driver = driver(&replay());
+        let recorded = Response::success(
+            RequestId::Number(1),
+            json!({ "requestState": "a", "_meta": { "at": 1, "by": "x" } }),
+        )
+        .into();
+        let actual = Response::success(
+            RequestId::Number(1),
+            json!({ "requestState": "b", "_meta": { "at": 2, "by": "x" } }),
+        )
+        .into();
+        let other = Response::success(
+            RequestId::Number(1),
+            json!({ "requestState": "a", "_meta": { "at": 1, "by": "y" } }),
+        )
+        .into();
+
+        assert_eq!(driver.normalize(&recorded), driver.normalize(&actual));
+        assert_ne!(driver.normalize(&recorded), driver.normalize(&other));
+    }
+
+    #[test]
+    fn it_plays_recorded_messages_under_what_it_learned() {
+        let mut matched = Matched::default();
+        matched.learn(
+            &Request::new(
+                Some(RequestId::Number(1)),
+                "sampling/createMessage",
+                None::<()>,
+            )
+            .into(),
+            &Request::new(
+                Some(RequestId::Number(42)),
+                "sampling/createMessage",
+                None::<()>,
+            )
+            .into(),
+        );
+        matched.learn(
+            &Response::success(RequestId::Number(3), json!({ "requestState": "old" })).into(),
+            &Response::success(RequestId::Number(3), json!({ "requestState": "new" })).into(),
+        );
+
+        let response = matched.substitute(Response::empty(RequestId::Number(1)).into());
+        assert_eq!(response.id(), RequestId::Number(42));
+
+        let retry = Request::new(
+            Some(RequestId::Number(3)),
+            "tools/call",
+            Some(json!({ "name": "t", "requestState": "old" })),
+        );
+        let Message::Request(retry) = matched.substitute(retry.into()) else {
+            unreachable!();
+        };
+        assert_eq!(retry.params.unwrap()["requestState"], "new");
+    }
+
+    #[tokio::test]
+    async fn it_fails_on_a_message_that_does_not_arrive() {
+        let recording = Recording::from(vec![RecordedMessage {
+            timestamp: chrono::Utc::now(),
+            from: Peer::Server,
+            session_id: None,
+            message: Response::empty(RequestId::Number(1)).into(),
+        }]);
+        let replay = Replay::new(recording).with_timeout(Duration::from_millis(10));
+        let (transport, driver) = replay.transport(Peer::Client);
+        let (mut sender, _receiver) = transport.split();
+        sender
+            .send(Response::empty(RequestId::Number(2)).into())
+            .await
+            .unwrap();
+
+        let err = driver.run().await.unwrap_err();
+        assert!(err.to_string().contains("recorded message 1"), "{err}");
+        assert!(err.to_string().contains(r#""id":2"#), "{err}");
+    }
+}
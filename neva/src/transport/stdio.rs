@@ -76,6 +76,13 @@ impl StdIoSender {
         Self { tx, rx: Some(rx) }
     }
 
+    /// Creates a sender that writes into the returned receiver instead of stdout.
+    #[cfg(test)]
+    pub(crate) fn channel() -> (Self, Receiver<Message>) {
+        let (tx, rx) = mpsc::channel(100);
+        (Self { tx, rx: None }, rx)
+    }
+
     /// Starts a new thread that writes to stdout asynchronously
     pub(crate) fn start<T: AsyncWrite + Unpin + Send + 'static>(
         &mut self,
@@ -122,6 +129,16 @@ impl StdIoSender {
 /// next one too.
 #[inline]
 async fn write_message<T: AsyncWrite + Unpin + Send>(writer: &mut BufWriter<T>, resp: Message) {
+    #[cfg(feature = "server")]
+    let resp = match crate::transport::blob::into_body(resp) {
+        Ok((id, body)) => return write_body(writer, id, body).await,
+        Err(resp) => resp,
+    };
+    write_json(writer, resp).await
+}
+
+/// Writes a message serialized whole, as [`write_message`] describes.
+async fn write_json<T: AsyncWrite + Unpin + Send>(writer: &mut BufWriter<T>, resp: Message) {
     match serde_json::to_vec(&resp) {
         Ok(mut json_bytes) => {
             json_bytes.push(b'\n');
@@ -138,6 +155,46 @@ async fn write_message<T: AsyncWrite + Unpin + Send>(writer: &mut BufWriter<T>,
     }
 }
 
+/// Writes a response carrying streamed blobs as one line of JSON, flushing it.
+///
+/// The blobs are encoded onto stdout as they are read, so the line is never
+/// held whole. A read that fails midway ends the broken line where it stands
+/// and follows it with an error response for the same request: the peer
+/// cannot parse the broken line, and the error is what completes its request.
+#[cfg(feature = "server")]
+async fn write_body<T: AsyncWrite + Unpin + Send>(
+    writer: &mut BufWriter<T>,
+    id: crate::types::RequestId,
+    mut body: crate::transport::blob::JsonBody,
+) {
+    use futures_util::StreamExt;
+
+    while let Some(chunk) = body.next().await {
+        let written = match chunk {
+            Ok(chunk) => writer.write_all(&chunk).await,
+            Err(err) => {
+                #[cfg(feature = "tracing")]
+                tracing::error!(logger = "neva", "streamed blob read error: {:?}", err);
+                if writer.write_all(b"\n").await.is_ok() {
+                    let error = Message::Response(crate::transport::blob::read_error(id, err));
+                    write_json(writer, error).await;
+                }
+                return;
+            }
+        };
+        if let Err(_err) = written {
+            #[cfg(feature = "tracing")]
+            tracing::error!(logger = "neva", "stdout write error: {:?}", _err);
+            return;
+        }
+    }
+    if let Err(_err) = writer.write_all(b"\n").await {
+        #[cfg(feature = "tracing")]
+        tracing::error!(logger = "neva", "stdout write error: {:?}", _err);
+    }
+    let _ = writer.flush().await;
+}
+
 /// The direction an unreadable line has to travel -- a parse failure is
 /// answered or completed depending on what the line *was*, and routing it
 /// the wrong way loses it silently.
@@ -836,6 +893,58 @@ mod tests {
         });
     }
 
+    /// A streamed blob goes out as one line of JSON; one that fails midway
+    /// ends its broken line and is followed by an error for the same request.
+    #[tokio::test]
+    #[cfg(feature = "server")]
+    async fn it_writes_streamed_blobs_line_by_line() {
+        use super::write_message;
+        use crate::types::{
+            BlobStream, IntoResponse, Message, ReadResourceResult, RequestId, Response,
+        };
+        use bytes::Bytes;
+        use tokio::io::BufWriter;
+
+        let streamed = |chunks: Vec<std::io::Result<Bytes>>| {
+            let blob = BlobStream::from_stream("file://data", futures_util::stream::iter(chunks));
+            Message::Response(ReadResourceResult::from(blob).into_response(RequestId::Number(7)))
+        };
+
+        let sink = SharedSink::default();
+        let mut writer = BufWriter::new(sink.clone());
+        write_message(
+            &mut writer,
+            streamed(vec![
+                Ok(Bytes::from_static(b"ab")),
+                Ok(Bytes::from_static(b"c")),
+            ]),
+        )
+        .await;
+        write_message(
+            &mut writer,
+            streamed(vec![
+                Ok(Bytes::from_static(b"ab")),
+                Err(std::io::Error::other("disk gone")),
+            ]),
+        )
+        .await;
+
+        let out = sink.0.lock().unwrap().clone();
+        let lines: Vec<&[u8]> = out.split(|b| *b == b'\n').collect();
+        assert_eq!(lines.len(), 4, "three lines and the trailing newline");
+
+        let ok: Response = serde_json::from_slice(lines[0]).unwrap();
+        let result: ReadResourceResult = ok.into_result().unwrap();
+        assert_eq!(result.contents[0].blob(), Some(&b"abc"[..]));
+
+        assert!(lines[1].ends_with(b"\"blob\":\""));
+        assert!(serde_json::from_slice::<serde_json::Value>(lines[1]).is_err());
+
+        let failed: Response = serde_json::from_slice(lines[2]).unwrap();
+        assert_eq!(failed.id(), &RequestId::Number(7));
+        assert!(failed.into_result::<ReadResourceResult>().is_err());
+    }
+
     #[tokio::test]
     #[cfg(all(feature = "client", target_os = "windows"))]
     async fn it_tests_handshake() {
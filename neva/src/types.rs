@@ -34,8 +34,8 @@ pub use capabilities::{
 pub use capabilities::{SamplingCapability, SamplingContextCapability, SamplingToolsCapability};
 pub use completion::{Argument, CompleteRequestParams, CompleteResult, Completion};
 pub use content::{
-    AudioContent, Content, EmbeddedResource, ImageContent, ResourceLink, TextContent, ToolResult,
-    ToolUse,
+    AudioContent, Content, EmbeddedResource, ImageContent, ImageLimits, ResourceLink, Table,
+    TableFormat, TextContent, ToolResult, ToolUse,
 };
 pub use cursor::{Cursor, Page, Pagination};
 #[cfg(feature = "server")]
@@ -67,14 +67,14 @@ pub use capabilities::{SamplingCreateMessageTaskCapability, SamplingTaskCapabili
 
 pub use tool::{
     CallToolRequestParams, CallToolResponse, ListToolsRequestParams, ListToolsResult, Tool,
-    ToolAnnotations,
+    ToolAnnotations, ToolError,
 };
 
 #[cfg(feature = "legacy-spec")]
 pub use tool::ToolSchema;
 
 #[cfg(feature = "server")]
-pub use tool::ToolHandler;
+pub use tool::{IntoToolResponse, ToolErrorMode, ToolHandler};
 
 /// The MCP schema type for tool input and output schemas.
 ///
@@ -121,20 +121,22 @@ pub type ToolInputSchema = schema_2020::InputSchema;
 #[cfg(feature = "legacy-spec")]
 pub use elicitation::ElicitationCompleteParams;
 pub use elicitation::{
-    ElicitRequestFormParams, ElicitRequestParams, ElicitRequestUrlParams, ElicitResult,
+    ElicitForm, ElicitRequestFormParams, ElicitRequestParams, ElicitRequestUrlParams, ElicitResult,
     ElicitationAction, ElicitationMode, UrlElicitationRequiredError,
 };
 pub use prompt::{
     GetPromptRequestParams, GetPromptResult, ListPromptsRequestParams, ListPromptsResult, Prompt,
     PromptArgument, PromptMessage,
 };
+#[cfg(feature = "server")]
+pub use resource::TemplateArgs;
 #[cfg(any(feature = "legacy-spec", feature = "client"))]
 pub use resource::UnsubscribeRequestParams;
 pub use resource::{
-    BlobResourceContents, ListResourceTemplatesRequestParams, ListResourceTemplatesResult,
-    ListResourcesRequestParams, ListResourcesResult, ReadResourceRequestParams, ReadResourceResult,
-    Resource, ResourceContents, ResourceTemplate, SubscribeRequestParams, TextResourceContents,
-    Uri,
+    BlobResourceContents, BlobStream, ListResourceTemplatesRequestParams,
+    ListResourceTemplatesResult, ListResourcesRequestParams, ListResourcesResult,
+    ReadResourceRequestParams, ReadResourceResult, Resource, ResourceContents, ResourceTemplate,
+    SubscribeRequestParams, TextResourceContents, Uri,
 };
 #[cfg(any(feature = "legacy-spec", feature = "client"))]
 pub use sampling::{
@@ -142,9 +144,9 @@ pub use sampling::{
     ToolChoiceMode,
 };
 pub use schema::{
-    BooleanSchema, LegacyTitledEnumSchema, NumberSchema, Schema, StringFormat, StringSchema,
-    TitledMultiSelectEnumSchema, TitledSingleSelectEnumSchema, UntitledMultiSelectEnumSchema,
-    UntitledSingleSelectEnumSchema,
+    BooleanSchema, EnumItems, EnumOption, EnumOptions, LegacyTitledEnumSchema, NumberSchema,
+    Schema, StringFormat, StringSchema, TitledMultiSelectEnumSchema, TitledSingleSelectEnumSchema,
+    UntitledMultiSelectEnumSchema, UntitledSingleSelectEnumSchema,
 };
 #[cfg(not(feature = "legacy-spec"))]
 pub use subscription::{
@@ -192,7 +194,7 @@ pub mod prompt;
 mod reference;
 mod request;
 pub mod resource;
-mod response;
+pub(crate) mod response;
 // Under MCP 2026-07-28 these are no longer capability-driven server->client requests,
 // but the types did not go away: they are the params/results of the deprecated
 // `roots/list` and `sampling/createMessage` MRTR input-request kinds (#85), so
@@ -10,9 +10,16 @@ use std::collections::HashMap;
 
 use crate::types::helpers::{deserialize_base64_as_bytes, serialize_bytes_as_base64};
 use crate::types::{
-    Annotations, CallToolRequestParams, CallToolResponse, Icon, Resource, ResourceContents, Uri,
+    Annotations, BlobResourceContents, CallToolRequestParams, CallToolResponse, Icon, Resource,
+    ResourceContents, TextResourceContents, Uri,
 };
 
+pub use media::ImageLimits;
+pub use table::{Table, TableFormat};
+
+mod media;
+mod table;
+
 const CHUNK_SIZE: usize = 8192;
 
 /// Represents the content of the response.
@@ -478,17 +485,110 @@ impl Content {
     }
 
     /// Creates an image [`Content`]
+    ///
+    /// The MIME type is read off the image's first bytes, see [`ImageContent::new`].
     #[inline]
     pub fn image(data: impl Into<Bytes>) -> Self {
         Self::Image(ImageContent::new(data))
     }
 
     /// Creates an audio [`Content`]
+    ///
+    /// The MIME type is read off the clip's first bytes, see [`AudioContent::new`].
     #[inline]
     pub fn audio(data: impl Into<Bytes>) -> Self {
         Self::Audio(AudioContent::new(data))
     }
 
+    /// Creates a text [`Content`] that renders `table` in its
+    /// [`format`](Table::with_format)
+    #[inline]
+    pub fn table(table: &Table) -> Self {
+        Self::text(table.render())
+    }
+
+    /// Reads the file at `path` into a [`Content`]: an image or an audio clip
+    /// if its first bytes say it is one, or else an embedded resource with a
+    /// `file://` URI -- text if it is UTF-8, a blob otherwise.
+    ///
+    /// `path` is read as given. A tool that takes its path from the model
+    /// should take a `SandboxedPath`, so that one like `../../etc/passwd` fails
+    /// the call instead of being read.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # #[cfg(feature = "server")] {
+    /// use neva::prelude::*;
+    ///
+    /// let mut app = App::new();
+    /// app.map_tool("chart", |path: SandboxedPath| async move {
+    ///     Content::from_path(path).await
+    /// })
+    /// .with_arg_names(["path"]);
+    /// # }
+    /// ```
+    pub async fn from_path(path: impl AsRef<std::path::Path>) -> Result<Self, Error> {
+        let path = std::path::absolute(path.as_ref())?;
+        let (path, data) = tokio::task::spawn_blocking(move || {
+            std::fs::read(&path).map(|data| (path, Bytes::from(data)))
+        })
+        .await
+        .map_err(|err| Error::new(ErrorCode::InternalError, err))??;
+
+        if let Some(content) = Self::media(&data) {
+            return Ok(content);
+        }
+        let uri = media::file_uri(&path);
+        let mime = media::sniff(&data).or_else(|| media::mime_by_extension(&path));
+        let resource = match String::from_utf8(data.into()) {
+            Ok(text) => ResourceContents::from(
+                TextResourceContents::new(uri, text).with_mime(mime.unwrap_or("text/plain")),
+            ),
+            Err(err) => ResourceContents::from(
+                BlobResourceContents::new(uri, err.into_bytes())
+                    .with_mime(mime.unwrap_or("application/octet-stream")),
+            ),
+        };
+        Ok(Self::resource(resource))
+    }
+
+    /// Reads `reader` to its end into a [`Content`]: an image or an audio clip
+    /// if its first bytes say it is one, or else text.
+    ///
+    /// Fails if it is neither a known image or audio format nor UTF-8 text.
+    pub async fn from_reader<R>(mut reader: R) -> Result<Self, Error>
+    where
+        R: tokio::io::AsyncRead + Unpin,
+    {
+        use tokio::io::AsyncReadExt;
+
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).await?;
+        if let Some(content) = Self::media(&data) {
+            return Ok(content);
+        }
+        match String::from_utf8(data) {
+            Ok(text) => Ok(Self::text(text)),
+            Err(_) => Err(Error::new(
+                ErrorCode::InvalidParams,
+                "Content is neither a known image or audio format nor UTF-8 text",
+            )),
+        }
+    }
+
+    /// Creates an image or audio [`Content`] if the first bytes of `data` say
+    /// it is one.
+    fn media(data: &[u8]) -> Option<Self> {
+        let mime = media::sniff(data)?;
+        if mime.starts_with("image/") && mime != "image/svg+xml" {
+            Some(Self::Image(ImageContent::new(data.to_vec())))
+        } else if mime.starts_with("audio/") {
+            Some(Self::Audio(AudioContent::new(data.to_vec())))
+        } else {
+            None
+        }
+    }
+
     /// Creates an embedded resource [`Content`]
     #[inline]
     pub fn resource(resource: impl Into<ResourceContents>) -> Self {
@@ -634,11 +734,19 @@ impl TextContent {
 
 impl AudioContent {
     /// Creates a new [`AudioContent`]
+    ///
+    /// The MIME type is read off the clip's first bytes -- WAV, MP3, AAC, MP4,
+    /// Ogg and FLAC are known -- and is `audio/wav` for anything else; see
+    /// [`Self::with_mime`].
     #[inline]
     pub fn new(data: impl Into<Bytes>) -> Self {
+        let data = data.into();
+        let mime = media::sniff(&data)
+            .filter(|mime| mime.starts_with("audio/"))
+            .unwrap_or("audio/wav");
         Self {
-            data: data.into(),
-            mime: "audio/wav".into(),
+            data,
+            mime: mime.into(),
             annotations: None,
             meta: None,
         }
@@ -679,11 +787,19 @@ impl AudioContent {
 
 impl ImageContent {
     /// Creates a new [`ImageContent`]
+    ///
+    /// The MIME type is read off the image's first bytes -- PNG, JPEG, GIF,
+    /// WebP, BMP, TIFF, AVIF, HEIC, ICO and SVG are known -- and is
+    /// `image/jpg` for anything else; see [`Self::with_mime`].
     #[inline]
     pub fn new(data: impl Into<Bytes>) -> Self {
+        let data = data.into();
+        let mime = media::sniff(&data)
+            .filter(|mime| mime.starts_with("image/"))
+            .unwrap_or("image/jpg");
         Self {
-            data: data.into(),
-            mime: "image/jpg".into(),
+            data,
+            mime: mime.into(),
             annotations: None,
             meta: None,
         }
@@ -709,6 +825,23 @@ impl ImageContent {
         &self.data
     }
 
+    /// Returns the width and height of the image in pixels, read off its
+    /// header.
+    ///
+    /// `None` unless it is a PNG, JPEG, GIF, WebP or BMP image.
+    pub fn dimensions(&self) -> Option<(u32, u32)> {
+        media::dimensions(&self.data)
+    }
+
+    /// Fits the image within `limits`: returns it as it is if it fits, scaled
+    /// down by the limits' resizer if it does not, or fails if there is no
+    /// resizer or what it returns still does not fit.
+    ///
+    /// See [`ImageLimits`].
+    pub fn fit(self, limits: &ImageLimits) -> Result<Self, Error> {
+        limits.fit(self)
+    }
+
     /// Turns this [`ImageContent`] into a chunked stream of bytes
     pub fn into_stream(self) -> impl futures_util::Stream<Item = Bytes> {
         futures_util::stream::unfold(self.data, |mut remaining_data| async move {
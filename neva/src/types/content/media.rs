@@ -0,0 +1,369 @@
+//! Media types read off raw bytes, and the limits an image is fitted to
+
+use super::ImageContent;
+use crate::error::{Error, ErrorCode};
+use std::fmt::{Debug, Formatter};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Reads the MIME type of an image, audio clip or document off its first bytes.
+pub(crate) fn sniff(data: &[u8]) -> Option<&'static str> {
+    const SIGNATURES: &[(&[u8], &str)] = &[
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"II*\0", "image/tiff"),
+        (b"MM\0*", "image/tiff"),
+        (b"\0\0\x01\0", "image/x-icon"),
+        (b"ID3", "audio/mpeg"),
+        (b"OggS", "audio/ogg"),
+        (b"fLaC", "audio/flac"),
+        (b"%PDF-", "application/pdf"),
+        (b"PK\x03\x04", "application/zip"),
+    ];
+    if let Some((_, mime)) = SIGNATURES.iter().find(|(sig, _)| data.starts_with(sig)) {
+        return Some(mime);
+    }
+    match data {
+        // "BM" alone starts plenty of text; the DIB header size pins it down
+        [b'B', b'M', rest @ ..]
+            if matches!(
+                rest.get(12..16),
+                Some([12 | 40 | 52 | 56 | 64 | 108 | 124, 0, 0, 0])
+            ) =>
+        {
+            Some("image/bmp")
+        }
+        [b'R', b'I', b'F', b'F', rest @ ..] => match rest.get(4..8)? {
+            b"WEBP" => Some("image/webp"),
+            b"WAVE" => Some("audio/wav"),
+            _ => None,
+        },
+        [_, _, _, _, b'f', b't', b'y', b'p', brand @ ..] => match brand.get(..4)? {
+            b"avif" | b"avis" => Some("image/avif"),
+            b"heic" | b"heix" | b"mif1" => Some("image/heic"),
+            b"M4A " | b"M4B " => Some("audio/mp4"),
+            _ => Some("video/mp4"),
+        },
+        // MPEG audio frame sync: ADTS AAC has layer bits 00, MP3 does not
+        [0xff, b, ..] if b & 0xf6 == 0xf0 => Some("audio/aac"),
+        [0xff, b, ..] if b & 0xe0 == 0xe0 => Some("audio/mpeg"),
+        _ if is_svg(data) => Some("image/svg+xml"),
+        _ => None,
+    }
+}
+
+fn is_svg(data: &[u8]) -> bool {
+    let head = &data[..data.len().min(512)];
+    let Ok(head) = std::str::from_utf8(head.trim_ascii_start()) else {
+        return false;
+    };
+    (head.starts_with("<?xml") || head.starts_with("<svg")) && head.contains("<svg")
+}
+
+/// Guesses the MIME type of a file off its extension, for the formats a tool
+/// commonly hands back as text.
+pub(crate) fn mime_by_extension(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    let mime = match ext.as_str() {
+        "txt" | "log" => "text/plain",
+        "md" | "markdown" => "text/markdown",
+        "csv" => "text/csv",
+        "tsv" => "text/tab-separated-values",
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "js" | "mjs" => "text/javascript",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "yaml" | "yml" => "application/yaml",
+        "toml" => "application/toml",
+        "svg" => "image/svg+xml",
+        _ => return None,
+    };
+    Some(mime)
+}
+
+/// Reads the width and height of an image off its header.
+///
+/// Knows PNG, GIF, BMP, JPEG and WebP; `None` for anything else.
+pub(crate) fn dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |at: usize| Some(u16::from_be_bytes(data.get(at..at + 2)?.try_into().ok()?) as u32);
+    let le16 = |at: usize| Some(u16::from_le_bytes(data.get(at..at + 2)?.try_into().ok()?) as u32);
+    let be32 = |at: usize| Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?));
+    let le32 = |at: usize| Some(u32::from_le_bytes(data.get(at..at + 4)?.try_into().ok()?));
+    let le24 = |at: usize| Some(le32(at)? & 0x00ff_ffff);
+
+    match sniff(data)? {
+        "image/png" => Some((be32(16)?, be32(20)?)),
+        "image/gif" => Some((le16(6)?, le16(8)?)),
+        "image/bmp" => Some((le32(18)?, (le32(22)? as i32).unsigned_abs())),
+        "image/webp" => match data.get(12..16)? {
+            b"VP8X" => Some((le24(24)? + 1, le24(27)? + 1)),
+            b"VP8L" => {
+                let bits = le32(21)?;
+                Some(((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1))
+            }
+            b"VP8 " => Some((le16(26)? & 0x3fff, le16(28)? & 0x3fff)),
+            _ => None,
+        },
+        "image/jpeg" => {
+            // Walk the segments up to the start-of-frame one
+            let mut at = 2;
+            loop {
+                while *data.get(at)? != 0xff {
+                    at += 1;
+                }
+                while *data.get(at)? == 0xff {
+                    at += 1;
+                }
+                let marker = *data.get(at)?;
+                at += 1;
+                match marker {
+                    0xd8 | 0x01 | 0xd0..=0xd7 => continue,
+                    0xc0..=0xcf if !matches!(marker, 0xc4 | 0xc8 | 0xcc) => {
+                        return Some((be16(at + 5)?, be16(at + 3)?));
+                    }
+                    _ => at += be16(at)? as usize,
+                }
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Turns an image into one that fits within a width and height.
+type Resizer = Arc<dyn Fn(&ImageContent, u32, u32) -> Result<ImageContent, Error> + Send + Sync>;
+
+/// The size an image has to fit before it is sent, see [`ImageContent::fit`].
+///
+/// neva reads an image's dimensions off its header but does not link an image
+/// codec, so an image over the limits is refused unless a resizer -- backed by
+/// whichever codec the server uses -- is given to scale it down.
+///
+/// # Example
+/// ```no_run
+/// use neva::types::{Content, ImageContent, ImageLimits};
+/// # fn shrink(img: &ImageContent, width: u32, height: u32) -> ImageContent { img.clone() }
+///
+/// let limits = ImageLimits::new()
+///     .with_max_dimensions(1568, 1568)
+///     .with_max_bytes(5 * 1024 * 1024)
+///     .with_resizer(|img, width, height| Ok(shrink(img, width, height)));
+///
+/// # let bytes: Vec<u8> = Vec::new();
+/// let image = ImageContent::new(bytes).fit(&limits);
+/// ```
+#[derive(Clone, Default)]
+pub struct ImageLimits {
+    max_bytes: Option<usize>,
+    max_dimensions: Option<(u32, u32)>,
+    resizer: Option<Resizer>,
+}
+
+impl Debug for ImageLimits {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ImageLimits")
+            .field("max_bytes", &self.max_bytes)
+            .field("max_dimensions", &self.max_dimensions)
+            .field("resizer", &self.resizer.is_some())
+            .finish()
+    }
+}
+
+impl ImageLimits {
+    /// Creates a new [`ImageLimits`] that lets any image through
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the largest an image may be, in bytes.
+    ///
+    /// Default: no limit
+    pub fn with_max_bytes(mut self, bytes: usize) -> Self {
+        self.max_bytes = Some(bytes);
+        self
+    }
+
+    /// Sets the largest width and height an image may have, in pixels.
+    ///
+    /// An image whose dimensions cannot be read off its header is let through
+    /// on its size in bytes alone.
+    ///
+    /// Default: no limit
+    pub fn with_max_dimensions(mut self, width: u32, height: u32) -> Self {
+        self.max_dimensions = Some((width, height));
+        self
+    }
+
+    /// Sets the function that scales an image over the limits down to fit
+    /// within the width and height it is given.
+    ///
+    /// The aspect ratio is kept in the width and height it is asked for. What
+    /// it returns is checked against the limits again, and refused if it still
+    /// does not fit.
+    ///
+    /// Default: none, an image over the limits is refused
+    pub fn with_resizer<F>(mut self, resizer: F) -> Self
+    where
+        F: Fn(&ImageContent, u32, u32) -> Result<ImageContent, Error> + Send + Sync + 'static,
+    {
+        self.resizer = Some(Arc::new(resizer));
+        self
+    }
+
+    /// The width and height `image` has to be scaled to, if it does not fit.
+    fn target(&self, image: &ImageContent) -> Option<(u32, u32)> {
+        let dims = dimensions(&image.data);
+        let (mut width, mut height) = dims.unwrap_or((0, 0));
+        let mut scale = 1f64;
+        if let (Some((max_w, max_h)), Some((w, h))) = (self.max_dimensions, dims) {
+            scale = scale
+                .min(max_w as f64 / w.max(1) as f64)
+                .min(max_h as f64 / h.max(1) as f64);
+        }
+        if let Some(max) = self.max_bytes {
+            // Bytes go roughly with the pixel count, so with the square of a side
+            scale = scale.min((max as f64 / image.data.len().max(1) as f64).sqrt());
+        }
+        if scale >= 1.0 {
+            return None;
+        }
+        width = ((width as f64 * scale).floor() as u32).max(1);
+        height = ((height as f64 * scale).floor() as u32).max(1);
+        Some((width, height))
+    }
+
+    /// Returns why `image` does not fit, if it does not.
+    fn check(&self, image: &ImageContent) -> Result<(), Error> {
+        let len = image.data.len();
+        if let Some(max) = self.max_bytes.filter(|max| len > *max) {
+            return Err(Error::new(
+                ErrorCode::InvalidParams,
+                format!("Image of {len} bytes is over the {max} byte limit"),
+            ));
+        }
+        if let (Some((max_w, max_h)), Some((w, h))) = (self.max_dimensions, dimensions(&image.data))
+            && (w > max_w || h > max_h)
+        {
+            return Err(Error::new(
+                ErrorCode::InvalidParams,
+                format!("Image of {w}x{h} pixels is over the {max_w}x{max_h} limit"),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Fits `image` within these limits: as it is, scaled down, or not at all.
+    pub(super) fn fit(&self, image: ImageContent) -> Result<ImageContent, Error> {
+        let Err(err) = self.check(&image) else {
+            return Ok(image);
+        };
+        let (Some(resizer), Some((width, height))) = (&self.resizer, self.target(&image)) else {
+            return Err(err);
+        };
+        let resized = resizer(&image, width, height)?;
+        self.check(&resized)?;
+        Ok(resized)
+    }
+}
+
+/// Builds a `file://` URI for an absolute path, percent-encoding what a URI
+/// path may not hold.
+pub(crate) fn file_uri(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    let mut uri = String::with_capacity(path.len() + 8);
+    uri.push_str("file://");
+    for segment in path.split(['/', '\\']) {
+        if !uri.ends_with("//") || !segment.is_empty() {
+            uri.push('/');
+        }
+        for byte in segment.bytes() {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b':' => {
+                    uri.push(byte as char)
+                }
+                _ => uri.push_str(&format!("%{byte:02X}")),
+            }
+        }
+    }
+    uri
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut data = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+        data.extend(width.to_be_bytes());
+        data.extend(height.to_be_bytes());
+        data.extend([8, 6, 0, 0, 0]);
+        data
+    }
+
+    #[test]
+    fn it_sniffs_media_types() {
+        assert_eq!(sniff(&png(1, 1)), Some("image/png"));
+        assert_eq!(sniff(b"\xff\xd8\xff\xe0"), Some("image/jpeg"));
+        assert_eq!(sniff(b"RIFF\0\0\0\0WEBPVP8 "), Some("image/webp"));
+        assert_eq!(sniff(b"RIFF\0\0\0\0WAVEfmt "), Some("audio/wav"));
+        assert_eq!(sniff(b"ID3\x04"), Some("audio/mpeg"));
+        assert_eq!(sniff(b"\xff\xfb\x90\x00"), Some("audio/mpeg"));
+        assert_eq!(sniff(b"\xff\xf1\x50\x80"), Some("audio/aac"));
+        assert_eq!(sniff(b"\0\0\0\x20ftypM4A \0\0"), Some("audio/mp4"));
+        assert_eq!(sniff(b"  <svg xmlns=\"\"></svg>"), Some("image/svg+xml"));
+        assert_eq!(sniff(b"%PDF-1.7"), Some("application/pdf"));
+        assert_eq!(sniff(b"hello"), None);
+    }
+
+    #[test]
+    fn it_reads_image_dimensions() {
+        assert_eq!(dimensions(&png(640, 480)), Some((640, 480)));
+        assert_eq!(dimensions(b"GIF89a\x20\x03\x58\x02"), Some((800, 600)));
+
+        let mut jpeg = b"\xff\xd8\xff\xe0\0\x04ab".to_vec();
+        jpeg.extend(b"\xff\xc0\0\x11\x08\x01\xe0\x02\x80");
+        assert_eq!(dimensions(&jpeg), Some((640, 480)));
+
+        assert_eq!(dimensions(b"hello"), None);
+    }
+
+    #[test]
+    fn it_refuses_an_image_over_the_limits() {
+        let limits = ImageLimits::new().with_max_dimensions(100, 100);
+
+        assert!(limits.fit(ImageContent::new(png(100, 50))).is_ok());
+        let err = limits.fit(ImageContent::new(png(400, 200))).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Image of 400x200 pixels is over the 100x100 limit"
+        );
+        assert!(
+            ImageLimits::new()
+                .with_max_bytes(8)
+                .fit(ImageContent::new(png(1, 1)))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn it_scales_an_image_down_to_fit() {
+        let limits = ImageLimits::new()
+            .with_max_dimensions(100, 100)
+            .with_resizer(|_, width, height| Ok(ImageContent::new(png(width, height))));
+
+        let image = limits.fit(ImageContent::new(png(400, 200))).unwrap();
+        assert_eq!(dimensions(&image.data), Some((100, 50)));
+    }
+
+    #[test]
+    fn it_builds_file_uris() {
+        assert_eq!(
+            file_uri(Path::new("/tmp/my report.pdf")),
+            "file:///tmp/my%20report.pdf"
+        );
+    }
+}
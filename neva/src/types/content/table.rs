@@ -0,0 +1,370 @@
+//! Tabular data rendered as text, with its rows alongside as structured content
+
+use crate::error::{Error, ErrorCode};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fmt::Write;
+
+#[cfg(feature = "server")]
+use crate::types::{CallToolResponse, Content};
+
+/// How a [`Table`] is rendered as text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TableFormat {
+    /// A GitHub-flavored Markdown table.
+    #[default]
+    Markdown,
+
+    /// Comma-separated values, with a header row, as RFC 4180 has them.
+    Csv,
+}
+
+/// Rows of data under named columns, for a tool to return.
+///
+/// Returned from a tool, it reaches the client as text content in its
+/// [`format`](Self::with_format) and as structured content that holds the same
+/// rows as JSON objects:
+///
+/// ```json
+/// { "columns": ["city", "temp"], "rows": [{ "city": "London", "temp": 12.5 }] }
+/// ```
+///
+/// # Example
+/// ```no_run
+/// # #[cfg(feature = "server")] {
+/// use neva::prelude::*;
+///
+/// let mut app = App::new();
+/// app.map_tool("forecast", || async {
+///     Table::new(["city", "temp"])
+///         .with_row([json!("London"), json!(12.5)])
+///         .with_row([json!("Paris"), json!(15)])
+/// });
+/// # use serde_json::json;
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+    columns: Vec<String>,
+    rows: Vec<Vec<Value>>,
+    format: TableFormat,
+}
+
+impl Table {
+    /// Creates a new [`Table`] with these columns and no rows
+    pub fn new<I, S>(columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            columns: columns.into_iter().map(Into::into).collect(),
+            rows: Vec::new(),
+            format: TableFormat::default(),
+        }
+    }
+
+    /// Creates a [`Table`] from rows that serialize to JSON objects.
+    ///
+    /// Its columns are every key any row has, in the order `serde_json` keeps
+    /// an object's keys in -- alphabetical -- so [`Self::with_columns`] is the
+    /// way to choose and order the ones shown. A row without a key has `null`
+    /// under it.
+    ///
+    /// # Example
+    /// ```
+    /// use neva::types::Table;
+    ///
+    /// #[derive(serde::Serialize)]
+    /// struct City { name: &'static str, temp: f32 }
+    ///
+    /// let table = Table::from_rows([City { name: "London", temp: 12.5 }])
+    ///     .unwrap()
+    ///     .with_columns(["temp", "name"]);
+    ///
+    /// assert_eq!(table.to_csv(), "temp,name\r\n12.5,London\r\n");
+    /// ```
+    pub fn from_rows<T, I>(rows: I) -> Result<Self, Error>
+    where
+        T: Serialize,
+        I: IntoIterator<Item = T>,
+    {
+        let objects = rows
+            .into_iter()
+            .enumerate()
+            .map(|(i, row)| match serde_json::to_value(row)? {
+                Value::Object(object) => Ok(object),
+                _ => Err(Error::new(
+                    ErrorCode::InvalidParams,
+                    format!("Table row {i} is not an object"),
+                )),
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let mut columns: Vec<String> = Vec::new();
+        for key in objects.iter().flat_map(Map::keys) {
+            if !columns.contains(key) {
+                columns.push(key.clone());
+            }
+        }
+        let rows = objects
+            .into_iter()
+            .map(|mut object| {
+                columns
+                    .iter()
+                    .map(|column| object.remove(column).unwrap_or_default())
+                    .collect()
+            })
+            .collect();
+        Ok(Self {
+            columns,
+            rows,
+            format: TableFormat::default(),
+        })
+    }
+
+    /// Adds a row, its cells in the order of the columns.
+    ///
+    /// A row shorter than the columns is padded with `null`; cells past the
+    /// last column are dropped.
+    pub fn with_row<I, V>(mut self, cells: I) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<Value>,
+    {
+        let mut row: Vec<Value> = cells
+            .into_iter()
+            .take(self.columns.len())
+            .map(Into::into)
+            .collect();
+        row.resize(self.columns.len(), Value::Null);
+        self.rows.push(row);
+        self
+    }
+
+    /// Keeps only these columns, in this order.
+    ///
+    /// A column the table does not have is added, with `null` in every row.
+    pub fn with_columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let columns: Vec<String> = columns.into_iter().map(Into::into).collect();
+        let picks: Vec<Option<usize>> = columns
+            .iter()
+            .map(|column| self.columns.iter().position(|c| c == column))
+            .collect();
+        for row in &mut self.rows {
+            *row = picks
+                .iter()
+                .map(|pick| {
+                    pick.map(|i| std::mem::take(&mut row[i]))
+                        .unwrap_or_default()
+                })
+                .collect();
+        }
+        self.columns = columns;
+        self
+    }
+
+    /// Sets how the table is rendered as text.
+    ///
+    /// Default: [`TableFormat::Markdown`]
+    pub fn with_format(mut self, format: TableFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Returns the names of the columns.
+    #[inline]
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Returns the rows, their cells in the order of the columns.
+    #[inline]
+    pub fn rows(&self) -> &[Vec<Value>] {
+        &self.rows
+    }
+
+    /// Renders the table as a Markdown table.
+    ///
+    /// A `|` in a cell is escaped and a line break becomes `<br>`, so a cell
+    /// never breaks the table.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let line = |out: &mut String, cells: &mut dyn Iterator<Item = String>| {
+            out.push('|');
+            for cell in cells {
+                let cell = cell
+                    .replace('|', "\\|")
+                    .replace("\r\n", "<br>")
+                    .replace('\n', "<br>");
+                let _ = write!(out, " {cell} |");
+            }
+            out.push('\n');
+        };
+        line(&mut out, &mut self.columns.iter().cloned());
+        line(&mut out, &mut self.columns.iter().map(|_| "---".into()));
+        for row in &self.rows {
+            line(&mut out, &mut row.iter().map(cell));
+        }
+        out
+    }
+
+    /// Renders the table as CSV, with a header row and CRLF line endings.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::new();
+        let line = |out: &mut String, cells: &mut dyn Iterator<Item = String>| {
+            for (i, cell) in cells.enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                if cell.contains([',', '"', '\r', '\n']) {
+                    let _ = write!(out, "\"{}\"", cell.replace('"', "\"\""));
+                } else {
+                    out.push_str(&cell);
+                }
+            }
+            out.push_str("\r\n");
+        };
+        line(&mut out, &mut self.columns.iter().cloned());
+        for row in &self.rows {
+            line(&mut out, &mut row.iter().map(cell));
+        }
+        out
+    }
+
+    /// Returns the columns and the rows as JSON objects, the structured
+    /// content the table is sent with.
+    pub fn to_json(&self) -> Value {
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                let object: Map<String, Value> = self
+                    .columns
+                    .iter()
+                    .cloned()
+                    .zip(row.iter().cloned())
+                    .collect();
+                Value::Object(object)
+            })
+            .collect();
+        serde_json::json!({ "columns": self.columns, "rows": Value::Array(rows) })
+    }
+
+    /// Renders the table in its format.
+    pub(super) fn render(&self) -> String {
+        match self.format {
+            TableFormat::Markdown => self.to_markdown(),
+            TableFormat::Csv => self.to_csv(),
+        }
+    }
+}
+
+/// The text of a cell: a string as it is, `null` as nothing, anything else as
+/// JSON.
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(feature = "server")]
+impl From<Table> for CallToolResponse {
+    #[inline]
+    fn from(table: Table) -> Self {
+        Self {
+            content: vec![Content::table(&table)],
+            struct_content: Some(table.to_json()),
+            is_error: false,
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl crate::types::tool::IntoToolResponse for Table {
+    #[inline]
+    fn into_tool_response(self) -> Result<CallToolResponse, crate::error::Error> {
+        Ok(self.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn table() -> Table {
+        Table::new(["city", "note"])
+            .with_row([json!("London"), json!("rain | wind")])
+            .with_row([json!("Paris, FR"), json!("say \"hi\"\nthen go")])
+            .with_row([json!("Oslo")])
+    }
+
+    #[test]
+    fn it_renders_markdown() {
+        assert_eq!(
+            table().to_markdown(),
+            "| city | note |\n\
+             | --- | --- |\n\
+             | London | rain \\| wind |\n\
+             | Paris, FR | say \"hi\"<br>then go |\n\
+             | Oslo |  |\n"
+        );
+    }
+
+    #[test]
+    fn it_renders_csv() {
+        assert_eq!(
+            table().to_csv(),
+            "city,note\r\n\
+             London,rain | wind\r\n\
+             \"Paris, FR\",\"say \"\"hi\"\"\nthen go\"\r\n\
+             Oslo,\r\n"
+        );
+    }
+
+    #[test]
+    fn it_builds_from_serialized_rows() {
+        let table = Table::from_rows([json!({ "b": 1, "a": true }), json!({ "c": null })])
+            .unwrap()
+            .with_columns(["b", "c", "z"]);
+
+        assert_eq!(table.columns(), ["b", "c", "z"]);
+        assert_eq!(
+            table.to_json(),
+            json!({
+                "columns": ["b", "c", "z"],
+                "rows": [
+                    { "b": 1, "c": null, "z": null },
+                    { "b": null, "c": null, "z": null }
+                ]
+            })
+        );
+        assert!(Table::from_rows([1, 2]).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "server")]
+    fn it_converts_into_a_tool_response() {
+        let resp = CallToolResponse::from(table().with_format(TableFormat::Csv));
+
+        assert!(!resp.is_error);
+        assert!(
+            resp.content[0]
+                .as_text()
+                .unwrap()
+                .text
+                .starts_with("city,note\r\n")
+        );
+        assert_eq!(
+            resp.struct_content.unwrap()["rows"][2],
+            json!({ "city": "Oslo", "note": null })
+        );
+    }
+}
@@ -18,6 +18,10 @@ use crate::types::Uri;
 #[cfg(feature = "tasks")]
 use crate::types::{RelatedTaskMetadata, TaskMetadata};
 
+pub use form::{ElicitForm, FormEnum};
+
+pub mod form;
+
 /// List of commands for Elicitation
 pub mod commands {
     /// Command name for creating a new elicitation request
@@ -318,6 +322,16 @@ impl Validator {
             .and_then(|c| self.validate_content_constraints(&c).map(|_| c))
     }
 
+    /// Validates elicitation content that is already a JSON value
+    ///
+    /// [`validate`](Self::validate) also checks that `T`'s own schema matches
+    /// the requested one; content that was never a Rust type -- a form filled
+    /// in by hand, say -- has only its values to check.
+    #[inline]
+    pub fn validate_value(&self, content: &Value) -> Result<(), Error> {
+        self.validate_content_constraints(content)
+    }
+
     /// Validates that the source schema is compatible with the target schema
     fn validate_schema_compatibility(&self, source: &schemars::Schema) -> Result<(), Error> {
         const PROP: &str = "properties";
@@ -386,27 +400,12 @@ impl Validator {
         // Validate each property against its schema
         for (prop_name, prop_schema) in &schema.properties {
             if let Some(prop_value) = content_obj.get(prop_name) {
-                self.validate_property_value(prop_value, prop_schema)?;
+                prop_schema.validate(prop_value)?;
             }
         }
 
         Ok(())
     }
-
-    /// Validates a single property value against its schema
-    #[inline]
-    fn validate_property_value(&self, value: &Value, schema: &Schema) -> Result<(), Error> {
-        match schema {
-            Schema::String(string_schema) => string_schema.validate(value),
-            Schema::Number(number_schema) => number_schema.validate(value),
-            Schema::Boolean(boolean_schema) => boolean_schema.validate(value),
-            Schema::SingleUntitledEnum(e) => e.validate(value),
-            Schema::SingleTitledEnum(e) => e.validate(value),
-            Schema::MultiUntitledEnum(e) => e.validate(value),
-            Schema::MultiTitledEnum(e) => e.validate(value),
-            Schema::LegacyEnum(e) => e.validate(value),
-        }
-    }
 }
 
 impl ElicitRequestParams {
@@ -535,6 +534,16 @@ impl ElicitRequestFormParams {
         self
     }
 
+    /// Sets the schema to the form `T` describes
+    ///
+    /// Unlike [`with_schema`](Self::with_schema), every field maps onto a
+    /// primitive [`Schema`] by construction; see [`form`].
+    #[inline]
+    pub fn with_form<T: ElicitForm>(mut self) -> Self {
+        self.schema = T::schema();
+        self
+    }
+
     /// Sets the related task metadata
     #[inline]
     #[cfg(feature = "tasks")]
@@ -765,6 +774,15 @@ impl ElicitResult {
     }
 }
 
+impl std::fmt::Display for UrlElicitationRequiredError {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", ErrorCode::UrlElicitationRequiredError)
+    }
+}
+
+impl std::error::Error for UrlElicitationRequiredError {}
+
 impl UrlElicitationRequiredError {
     /// Creates a new [`UrlElicitationRequiredError`]
     #[inline]
@@ -0,0 +1,201 @@
+//! Typed elicitation forms.
+//!
+//! Form elicitation accepts far less than JSON Schema: a flat object whose
+//! properties are strings, numbers, booleans or enums of strings. A type that
+//! derives `JsonSchema` can describe much more than that, and
+//! [`RequestSchema::of`] has to guess at whatever does not fit. A type that
+//! derives `ElicitForm` cannot: the derive maps each field onto one of the
+//! primitive [`Schema`] variants, and a field it has no variant for is a
+//! compile error rather than a form the client renders wrong.
+//!
+//! # Example
+//! ```
+//! # #[cfg(feature = "macros")] {
+//! use neva::prelude::*;
+//!
+//! #[derive(serde::Deserialize, ElicitForm)]
+//! #[serde(rename_all = "lowercase")]
+//! enum Room {
+//!     #[elicit(title = "Single room")]
+//!     Single,
+//!     #[elicit(title = "Double room")]
+//!     Double,
+//! }
+//!
+//! #[derive(serde::Deserialize, ElicitForm)]
+//! struct Booking {
+//!     #[elicit(title = "Email", format = "email")]
+//!     email: String,
+//!     #[elicit(title = "Nights", min = 1, max = 14, default = 2)]
+//!     nights: u32,
+//!     room: Room,
+//!     #[elicit(descr = "Anything we should know")]
+//!     notes: Option<String>,
+//! }
+//!
+//! let params = ElicitRequestParams::form("Where should we send the booking?")
+//!     .with_form::<Booking>();
+//! assert_eq!(params.schema.required.as_ref().map(Vec::len), Some(3));
+//!
+//! let result = ElicitResult::accept().with_content(serde_json::json!({
+//!     "email": "guest@example.com",
+//!     "nights": 3,
+//!     "room": "double"
+//! }));
+//! let booking: Booking = result.content().expect("a submitted booking");
+//! assert!(matches!(booking.room, Room::Double));
+//! # }
+//! ```
+//!
+//! A nested struct is rejected where it is used:
+//! ```compile_fail
+//! use neva::prelude::*;
+//!
+//! #[derive(serde::Deserialize)]
+//! struct Address {
+//!     city: String,
+//! }
+//!
+//! #[derive(serde::Deserialize, ElicitForm)]
+//! struct Contact {
+//!     address: Address,
+//! }
+//! ```
+
+use super::RequestSchema;
+use crate::types::schema::{EnumItems, EnumOption, EnumOptions};
+use crate::types::{
+    Schema, TitledMultiSelectEnumSchema, TitledSingleSelectEnumSchema,
+    UntitledMultiSelectEnumSchema, UntitledSingleSelectEnumSchema,
+};
+use serde::de::DeserializeOwned;
+
+/// A type whose fields make up an elicitation form.
+///
+/// Derive it with `#[derive(ElicitForm)]` rather than implementing it by hand:
+/// the derive keeps [`schema`](ElicitForm::schema) in step with the way serde
+/// names the fields, which is what lets [`super::ElicitResult::content`] read
+/// the submitted form back as `Self`.
+pub trait ElicitForm: DeserializeOwned {
+    /// The schema the client renders the form from.
+    fn schema() -> RequestSchema;
+}
+
+/// An enum a form field can select from.
+///
+/// Implemented by `#[derive(ElicitForm)]` on an enum of unit variants; a field
+/// of this type becomes a single-select, and a `Vec` of it a multi-select.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be an elicitation form field",
+    label = "not a string, number, boolean or `#[derive(ElicitForm)]` enum",
+    note = "form elicitation only allows a flat object of primitive fields: nested objects and arrays of anything but an enum have no schema there"
+)]
+pub trait FormEnum {
+    /// Whether any variant has a display title.
+    const TITLED: bool;
+
+    /// The variants as serde names them, with their display titles.
+    ///
+    /// An untitled variant is titled with its own value.
+    fn options() -> Vec<EnumOption>;
+}
+
+impl Schema {
+    /// Creates a single-select [`Schema`] over the variants of `T`.
+    ///
+    /// Titled when any variant is, untitled otherwise.
+    pub fn select<T: FormEnum>() -> Self {
+        let options = T::options();
+        if T::TITLED {
+            Self::SingleTitledEnum(TitledSingleSelectEnumSchema {
+                one_of: options,
+                ..Default::default()
+            })
+        } else {
+            Self::SingleUntitledEnum(UntitledSingleSelectEnumSchema {
+                r#enum: options.into_iter().map(|o| o.value).collect(),
+                ..Default::default()
+            })
+        }
+    }
+
+    /// Creates a multi-select [`Schema`] over the variants of `T`.
+    ///
+    /// Titled when any variant is, untitled otherwise.
+    pub fn multi_select<T: FormEnum>(min_items: Option<usize>, max_items: Option<usize>) -> Self {
+        let options = T::options();
+        if T::TITLED {
+            Self::MultiTitledEnum(TitledMultiSelectEnumSchema {
+                items: EnumOptions::new(options),
+                min_items,
+                max_items,
+                ..Default::default()
+            })
+        } else {
+            Self::MultiUntitledEnum(UntitledMultiSelectEnumSchema {
+                items: EnumItems::new(options.into_iter().map(|o| o.value)),
+                min_items,
+                max_items,
+                ..Default::default()
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    enum Size {}
+
+    impl FormEnum for Size {
+        const TITLED: bool = false;
+
+        fn options() -> Vec<EnumOption> {
+            vec![EnumOption::new("s", "s"), EnumOption::new("m", "m")]
+        }
+    }
+
+    enum Room {}
+
+    impl FormEnum for Room {
+        const TITLED: bool = true;
+
+        fn options() -> Vec<EnumOption> {
+            vec![
+                EnumOption::new("single", "Single room"),
+                EnumOption::new("double", "double"),
+            ]
+        }
+    }
+
+    #[test]
+    fn an_untitled_enum_selects_by_value() {
+        let schema = serde_json::to_value(Schema::select::<Size>()).unwrap();
+        assert_eq!(schema, json!({ "type": "string", "enum": ["s", "m"] }));
+    }
+
+    #[test]
+    fn a_titled_enum_selects_by_option() {
+        let schema = serde_json::to_value(Schema::select::<Room>()).unwrap();
+        assert_eq!(
+            schema["oneOf"][0],
+            json!({ "const": "single", "title": "Single room" })
+        );
+        assert_eq!(schema["oneOf"][1]["title"], "double");
+    }
+
+    #[test]
+    fn a_multi_select_keeps_its_item_bounds() {
+        let schema = serde_json::to_value(Schema::multi_select::<Size>(Some(1), None)).unwrap();
+        assert_eq!(
+            schema,
+            json!({ "type": "array", "items": { "type": "string", "enum": ["s", "m"] }, "minItems": 1 })
+        );
+
+        let schema = serde_json::to_value(Schema::multi_select::<Room>(None, Some(2))).unwrap();
+        assert_eq!(schema["items"]["anyOf"][1]["const"], "double");
+        assert_eq!(schema["maxItems"], 2);
+    }
+}
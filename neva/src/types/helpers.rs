@@ -190,6 +190,16 @@ pub(crate) trait TypeCategory: sealed::TypeCategorySealed {
     fn is_optional() -> bool {
         false
     }
+
+    /// Whether extracting this argument needs the client's roots loaded
+    /// first.
+    ///
+    /// True for [`crate::app::context::SandboxedPath`], which is resolved
+    /// against them.
+    #[inline]
+    fn reads_roots() -> bool {
+        false
+    }
 }
 
 /// Wraps JSON-typed data
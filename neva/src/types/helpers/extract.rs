@@ -1,7 +1,7 @@
 //! Traits and helpers for type extraction from request arguments
 
 use crate::Context;
-use crate::error::{Error, ErrorCode};
+use crate::error::{Error, ErrorCode, FieldError};
 use crate::shared::{ArcSlice, ArcStr};
 use crate::types::helpers::TypeCategory;
 use crate::types::request::RequestParamsMeta;
@@ -249,6 +249,9 @@ pub(crate) enum Payload<'a> {
 
     /// Request metadata ("_meta")
     Meta(&'a Option<RequestParamsMeta>),
+
+    /// Tool or Prompt argument, read against the request metadata
+    Scoped(serde_json::Value, &'a Option<RequestParamsMeta>),
 }
 
 /// Represents an extraction sources
@@ -257,6 +260,8 @@ pub(crate) enum Source {
     Args,
     /// Request metadata ("_meta")
     Meta,
+    /// Tool or Prompt arguments that need the request metadata to resolve
+    Scoped,
 }
 
 /// A trait that type needs to implement to be extractable from [`crate::types::Request`]
@@ -327,6 +332,16 @@ impl<'a> Payload<'a> {
             _ => unreachable!("Expected Meta variant"),
         }
     }
+
+    /// Returns arguments value along with the [`RequestParamsMeta`] it is
+    /// resolved against
+    #[inline]
+    pub(crate) fn expect_scoped(self) -> (serde_json::Value, &'a Option<RequestParamsMeta>) {
+        match self {
+            Payload::Scoped(val, meta) => (val, meta),
+            _ => unreachable!("Expected Scoped variant"),
+        }
+    }
 }
 
 impl<T: DeserializeOwned> RequestArgument for T {
@@ -419,9 +434,10 @@ impl RequestArgument for Context {
 /// Extracts one handler argument.
 ///
 /// Metadata-sourced types read `meta` and leave `slot` alone; everything else
-/// consumes the next name and reads the value a peer sent under it. An absent
-/// key is offered to the type as `null`, so an `Option<T>` argument resolves
-/// to `None` instead of failing.
+/// consumes the next name and reads the value a peer sent under it -- a scoped
+/// type reads it against `meta`, the way a path is resolved against the
+/// client's roots. An absent key is offered to the type as `null`, so an
+/// `Option<T>` argument resolves to `None` instead of failing.
 ///
 /// Whether an argument may be omitted is decided by its *type*
 /// ([`TypeCategory::is_optional`]), never by whether a synthetic `null`
@@ -435,28 +451,38 @@ pub(crate) fn extract_arg<T: RequestArgument<Error = Error> + TypeCategory>(
     names: &ArgNames,
     slot: &mut usize,
 ) -> Result<T, Error> {
-    match T::source() {
-        Source::Meta => T::extract(Payload::Meta(meta)),
-        Source::Args => {
-            let name = names.get(*slot);
-            *slot += 1;
-            match args.and_then(|args| args.get(name)) {
-                Some(value) => T::extract(Payload::Args(value.clone())).map_err(|err| {
-                    Error::new(
-                        ErrorCode::InvalidParams,
-                        format!("invalid value for argument `{name}`: {err}"),
-                    )
-                }),
-                None if T::is_optional() => T::extract(Payload::Args(Value::Null)),
-                None => Err(Error::new(
-                    ErrorCode::InvalidParams,
-                    format!("missing required argument `{name}`"),
-                )),
-            }
-        }
+    let scoped = match T::source() {
+        Source::Meta => return T::extract(Payload::Meta(meta)),
+        Source::Args => false,
+        Source::Scoped => true,
+    };
+    let payload = |value| match scoped {
+        true => Payload::Scoped(value, meta),
+        false => Payload::Args(value),
+    };
+    let name = names.get(*slot);
+    *slot += 1;
+    match args.and_then(|args| args.get(name)) {
+        Some(value) => T::extract(payload(value.clone())).map_err(|err| {
+            Error::invalid_params(
+                format!("invalid value for argument `{name}`: {err}"),
+                [FieldError::new(pointer(name), err.to_string())],
+            )
+        }),
+        None if T::is_optional() => T::extract(payload(Value::Null)),
+        None => Err(Error::invalid_params(
+            format!("missing required argument `{name}`"),
+            [FieldError::new(pointer(name), "is required")],
+        )),
     }
 }
 
+/// The JSON pointer to the argument `name`.
+#[inline]
+fn pointer(name: &str) -> String {
+    format!("/{}", name.replace('~', "~0").replace('/', "~1"))
+}
+
 impl<P: HandlerArgs> FromHandlerArgs<P> for () {
     #[inline]
     fn from_args(_: P, _: &ArgNames) -> Result<Self, Error> {
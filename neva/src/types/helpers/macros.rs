@@ -92,5 +92,20 @@ impl<T: Send + Sync> TypeCategory for crate::di::Dc<T> {
     }
 }
 
+// A path resolved against the client's roots: sent as a string, and the one
+// argument that has the roots fetched before the handler runs.
+impl super::sealed::TypeCategorySealed for crate::app::context::SandboxedPath {}
+impl TypeCategory for crate::app::context::SandboxedPath {
+    #[inline]
+    fn category() -> PropertyType {
+        PropertyType::String
+    }
+
+    #[inline]
+    fn reads_roots() -> bool {
+        true
+    }
+}
+
 impl_type_category!(Value, PropertyType::Object);
 impl_type_category!(Json<T>, T, PropertyType::Object);
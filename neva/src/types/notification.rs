@@ -76,6 +76,13 @@ pub struct Notification {
     /// Current MCP Session ID
     #[serde(skip)]
     pub session_id: Option<uuid::Uuid>,
+
+    /// The request on whose response stream this notification arrived, when
+    /// the transport carries request-scoped notifications that way.
+    ///
+    /// Set on receipt by the client transport; never sent.
+    #[serde(skip)]
+    pub request_id: Option<RequestId>,
 }
 
 /// This notification can be sent by either side to indicate that it is cancelling
@@ -125,6 +132,7 @@ impl Notification {
         Self {
             jsonrpc: JSONRPC_VERSION.into(),
             session_id: None,
+            request_id: None,
             method: method.into(),
             params,
         }
@@ -21,6 +21,18 @@ pub struct ProgressNotification {
     /// Total number of items to a process (or total progress required), if known.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub total: Option<f64>,
+
+    /// An optional message describing the current progress.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl ProgressNotification {
+    /// Sets a message describing the current progress.
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
 }
 
 impl From<ProgressNotification> for Notification {
@@ -122,6 +122,7 @@ impl ProgressToken {
             progress_token: self.clone(),
             progress,
             total,
+            message: None,
         }
     }
 }
@@ -454,6 +454,33 @@ impl Prompt {
         }
     }
 
+    /// Creates a [`Prompt`] served by a ready-made handler rather than a
+    /// function, publishing `args` as its arguments.
+    ///
+    /// The handler reads the request's `arguments` itself, so the names here
+    /// are only what peers are told to send.
+    #[cfg(feature = "manifest")]
+    pub(crate) fn from_handler(
+        name: impl Into<String>,
+        args: Vec<PromptArgument>,
+        handler: RequestHandler<GetPromptResult>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            title: None,
+            descr: None,
+            meta: None,
+            arg_names: arg_names(&args),
+            args: (!args.is_empty()).then_some(args),
+            handler: Some(handler),
+            #[cfg(feature = "http-server")]
+            roles: None,
+            #[cfg(feature = "http-server")]
+            permissions: None,
+            icons: None,
+        }
+    }
+
     /// Sets a [`Prompt`] title
     pub fn with_title(&mut self, title: impl Into<String>) -> &mut Self {
         self.title = Some(title.into());
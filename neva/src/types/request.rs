@@ -485,18 +485,12 @@ impl Request {
     /// ```
     #[cfg(not(feature = "legacy-spec"))]
     pub fn unsupported_version_error(&self) -> Option<crate::error::Error> {
-        use crate::error::{Error, ErrorCode};
-
         let stated = self.stated_protocol_version()?;
         (stated != crate::LATEST_PROTOCOL_VERSION).then(|| {
-            Error::new(
-                ErrorCode::UnsupportedProtocolVersion,
-                format!("Unsupported MCP protocol version: {stated}"),
+            crate::error::Error::unsupported_protocol_version(
+                stated,
+                [crate::LATEST_PROTOCOL_VERSION],
             )
-            .with_data(serde_json::json!({
-                "supported": [crate::LATEST_PROTOCOL_VERSION],
-                "requested": stated,
-            }))
         })
     }
 
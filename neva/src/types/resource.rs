@@ -15,6 +15,9 @@ use crate::types::{Annotations, Cursor, Icon};
 use crate::types::{IntoResponse, Page, Request, RequestId, Response};
 use serde::{Deserialize, Serialize};
 
+pub use blob_stream::BlobStream;
+#[cfg(feature = "server")]
+pub use enumerator::TemplateArgs;
 pub use read_resource_result::{
     BlobResourceContents, EmptyResourceContents, JsonResourceContents, ReadResourceResult,
     ResourceContents, TextResourceContents,
@@ -30,6 +33,9 @@ pub(crate) use from_request::{Payload, ResourceArgument, Source};
 #[cfg(feature = "server")]
 pub(crate) use route::Route;
 
+mod blob_stream;
+#[cfg(feature = "server")]
+pub(crate) mod enumerator;
 #[cfg(feature = "server")]
 mod from_request;
 mod read_resource_result;
@@ -0,0 +1,429 @@
+//! Blob resource contents read from a stream while the response is written
+
+use crate::types::{Annotations, Uri};
+use bytes::{Bytes, BytesMut};
+use futures_util::{Stream, StreamExt, stream::BoxStream};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    fmt::{Debug, Formatter},
+    io,
+    sync::Mutex,
+};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+#[cfg(feature = "server")]
+use {
+    crate::error::{Error, ErrorCode},
+    base64::{Engine, engine::general_purpose::STANDARD},
+};
+
+/// How much is read from the source at a time.
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+/// A blob resource whose bytes are read from an [`AsyncRead`] or a byte stream
+/// as the response is written, rather than held in memory.
+///
+/// Returned from a resource handler, it reaches the client as an ordinary
+/// [`BlobResourceContents`](super::BlobResourceContents): the plain JSON reply
+/// over HTTP base64-encodes it into the body as it is read, so serving a large
+/// file costs a read buffer, not the file and its base64 twice over. Stdio
+/// encodes it onto its line the same way.
+/// Where a response has to be whole before it is sent -- a batch, a
+/// request-scoped SSE stream -- the blob is read into memory instead, within
+/// the server's [`max blob size`](crate::App::with_max_blob_size).
+///
+/// A blob whose size is known up front should say so with [`Self::with_size`]:
+/// one over the limit is then refused with an error before anything is sent,
+/// and one that turns out shorter or longer fails rather than reaching the
+/// client truncated.
+///
+/// The bytes can be read only once, so only the original has them: reading a
+/// clone fails. Serializing one writes its metadata without a `blob`, which
+/// suits a log line; the bytes reach the wire only through the transport.
+///
+/// # Example
+/// ```no_run
+/// # #[cfg(feature = "server")] {
+/// use neva::prelude::*;
+///
+/// #[resource(uri = "file://{name}")]
+/// async fn artifact(uri: Uri, name: String) -> Result<BlobStream, Error> {
+///     let file = tokio::fs::File::open(&name).await?;
+///     let size = file.metadata().await?.len();
+///     Ok(BlobStream::new(uri, file)
+///         .with_size(size)
+///         .with_mime("application/octet-stream"))
+/// }
+/// # }
+/// ```
+pub struct BlobStream {
+    /// The URI of the resource.
+    pub uri: Uri,
+
+    /// Intended for UI and end-user contexts - optimized to be human-readable and easily understood,
+    /// even by those unfamiliar with domain-specific terminology.
+    pub title: Option<String>,
+
+    /// The MIME type of content.
+    pub mime: Option<String>,
+
+    /// Optional annotations for the client.
+    pub annotations: Option<Annotations>,
+
+    /// Metadata reserved by MCP for protocol-level metadata.
+    pub meta: Option<serde_json::Value>,
+
+    /// The size of the blob in bytes, if known up front.
+    size: Option<u64>,
+
+    /// The most bytes the server lets through, set when the handler returns.
+    limit: Option<u64>,
+
+    /// The bytes; `None` in a clone.
+    source: Mutex<Option<BoxStream<'static, io::Result<Bytes>>>>,
+}
+
+impl Clone for BlobStream {
+    /// Clones everything but the bytes, which stay with the original.
+    fn clone(&self) -> Self {
+        Self {
+            uri: self.uri.clone(),
+            title: self.title.clone(),
+            mime: self.mime.clone(),
+            annotations: self.annotations.clone(),
+            meta: self.meta.clone(),
+            size: self.size,
+            limit: self.limit,
+            source: Mutex::new(None),
+        }
+    }
+}
+
+impl Debug for BlobStream {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlobStream")
+            .field("uri", &self.uri)
+            .field("title", &self.title)
+            .field("mime", &self.mime)
+            .field("size", &self.size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Serialize for BlobStream {
+    /// Writes the metadata without a `blob`: the bytes reach the wire only
+    /// through a `resources/read` response, which the transport writes with
+    /// the blob read into it.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("BlobStream", 6)?;
+        state.serialize_field("uri", &self.uri)?;
+        if let Some(title) = &self.title {
+            state.serialize_field("title", title)?;
+        }
+        if let Some(mime) = &self.mime {
+            state.serialize_field("mimeType", mime)?;
+        }
+        if let Some(size) = self.size {
+            state.serialize_field("size", &size)?;
+        }
+        if let Some(annotations) = &self.annotations {
+            state.serialize_field("annotations", annotations)?;
+        }
+        if let Some(meta) = &self.meta {
+            state.serialize_field("_meta", meta)?;
+        }
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for BlobStream {
+    /// Always fails: on the wire a streamed blob is an ordinary blob.
+    fn deserialize<D: Deserializer<'de>>(_: D) -> Result<Self, D::Error> {
+        Err(serde::de::Error::custom(
+            "a streamed blob is never received",
+        ))
+    }
+}
+
+impl BlobStream {
+    /// Creates a blob resource content read from `reader`
+    pub fn new<R>(uri: impl Into<Uri>, reader: R) -> Self
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        let reader = Box::pin(reader);
+        let chunks = futures_util::stream::unfold(Some(reader), |reader| async move {
+            let mut reader = reader?;
+            let mut buf = BytesMut::with_capacity(READ_CHUNK_SIZE);
+            match reader.read_buf(&mut buf).await {
+                Ok(0) => None,
+                Ok(_) => Some((Ok(buf.freeze()), Some(reader))),
+                Err(err) => Some((Err(err), None)),
+            }
+        });
+        Self::from_stream(uri, chunks)
+    }
+
+    /// Creates a blob resource content read from a stream of byte chunks
+    pub fn from_stream<S>(uri: impl Into<Uri>, stream: S) -> Self
+    where
+        S: Stream<Item = io::Result<Bytes>> + Send + 'static,
+    {
+        Self {
+            uri: uri.into(),
+            title: None,
+            mime: None,
+            annotations: None,
+            meta: None,
+            size: None,
+            limit: None,
+            source: Mutex::new(Some(stream.boxed())),
+        }
+    }
+
+    /// Declares the size of the blob in bytes.
+    ///
+    /// Default: unknown
+    #[inline]
+    pub fn with_size(mut self, size: u64) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Sets the mime type of the blob resource content
+    #[inline]
+    pub fn with_mime(mut self, mime: impl Into<String>) -> Self {
+        self.mime = Some(mime.into());
+        self
+    }
+
+    /// Sets the title of the resource
+    #[inline]
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets annotations for the client
+    pub fn with_annotations<F>(mut self, config: F) -> Self
+    where
+        F: FnOnce(Annotations) -> Annotations,
+    {
+        self.annotations = Some(config(Default::default()));
+        self
+    }
+
+    /// Returns the declared size of the blob in bytes
+    #[inline]
+    pub fn size(&self) -> Option<u64> {
+        self.size
+    }
+
+    /// Turns this [`BlobStream`] into the stream of its raw bytes.
+    ///
+    /// The stream fails if the blob outgrows the server's limit or turns out
+    /// a different size than declared, and right away for a clone, which has
+    /// no bytes to read.
+    pub fn into_stream(self) -> impl Stream<Item = io::Result<Bytes>> + Send + 'static {
+        let source = self
+            .source
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take();
+        let Some(source) = source else {
+            let err = io::Error::other(
+                "a clone of a streamed blob has no bytes: only the original can be read",
+            );
+            return futures_util::stream::once(async { Err(err) }).left_stream();
+        };
+        let (size, limit) = (self.size, self.limit);
+        futures_util::stream::unfold((Some(source), 0u64), move |(source, read)| async move {
+            let mut source = source?;
+            let (read, chunk) = match source.next().await {
+                Some(Ok(chunk)) => (read + chunk.len() as u64, chunk),
+                Some(Err(err)) => return Some((Err(err), (None, read))),
+                None if size.is_some_and(|size| read < size) => {
+                    let err = io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "the streamed blob is shorter than its declared size",
+                    );
+                    return Some((Err(err), (None, read)));
+                }
+                None => return None,
+            };
+            if let Some(limit) = limit.filter(|&limit| read > limit) {
+                let err = io::Error::other(format!(
+                    "the streamed blob exceeds the {limit} bytes allowed"
+                ));
+                return Some((Err(err), (None, read)));
+            }
+            if size.is_some_and(|size| read > size) {
+                let err = io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "the streamed blob is longer than its declared size",
+                );
+                return Some((Err(err), (None, read)));
+            }
+            Some((Ok(chunk), (Some(source), read)))
+        })
+        .right_stream()
+    }
+
+    /// Reads the whole blob into memory as [`BlobResourceContents`](super::BlobResourceContents).
+    pub async fn into_contents(self) -> io::Result<super::BlobResourceContents> {
+        // The declared size is the handler's word, not a measurement: reserve
+        // up to a bound and let the rest grow as it is read.
+        let reserve = self.size.unwrap_or_default().min(1 << 24) as usize;
+        let mut blob = BytesMut::with_capacity(reserve);
+        let contents = self.contents();
+        let mut stream = std::pin::pin!(self.into_stream());
+        while let Some(chunk) = stream.next().await {
+            blob.extend_from_slice(&chunk?);
+        }
+        Ok(super::BlobResourceContents {
+            blob: blob.freeze(),
+            ..contents
+        })
+    }
+
+    /// Applies the server's size limit: a blob declared larger is refused
+    /// right away, any other is cut off once it reads past it.
+    #[cfg(feature = "server")]
+    pub(crate) fn limit(&mut self, limit: Option<u64>) -> Result<(), Error> {
+        self.limit = limit;
+        match (self.size, limit) {
+            (Some(size), Some(limit)) if size > limit => Err(Error::new(
+                ErrorCode::InvalidRequest,
+                format!("The resource is {size} bytes, more than the {limit} bytes allowed"),
+            )
+            .with_data(serde_json::json!({
+                "uri": self.uri.to_string(),
+                "size": size,
+                "limit": limit,
+            }))),
+            _ => Ok(()),
+        }
+    }
+
+    /// The blob resource contents this blob is sent as, without its bytes.
+    pub(crate) fn contents(&self) -> super::BlobResourceContents {
+        super::BlobResourceContents {
+            uri: self.uri.clone(),
+            blob: Bytes::new(),
+            title: self.title.clone(),
+            mime: self.mime.clone(),
+            annotations: self.annotations.clone(),
+            meta: self.meta.clone(),
+        }
+    }
+
+    /// Turns this [`BlobStream`] into the stream of its base64 encoding,
+    /// chunk by chunk as the bytes are read.
+    #[cfg(feature = "server")]
+    pub(crate) fn encode(self) -> impl Stream<Item = io::Result<Bytes>> + Send + 'static {
+        let raw = self.into_stream().boxed();
+        // Base64 encodes three bytes at a time; the up to two left over from a
+        // chunk are carried into the next.
+        futures_util::stream::unfold(Some((raw, Vec::new())), |state| async move {
+            let (mut raw, mut carry) = state?;
+            match raw.next().await {
+                Some(Ok(chunk)) => {
+                    carry.extend_from_slice(&chunk);
+                    let whole = carry.len() / 3 * 3;
+                    let encoded = STANDARD.encode(&carry[..whole]);
+                    carry.drain(..whole);
+                    Some((Ok(Bytes::from(encoded)), Some((raw, carry))))
+                }
+                Some(Err(err)) => Some((Err(err), None)),
+                None if carry.is_empty() => None,
+                None => Some((Ok(Bytes::from(STANDARD.encode(&carry))), None)),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "server")]
+mod tests {
+    use super::*;
+
+    async fn collect(stream: impl Stream<Item = io::Result<Bytes>>) -> io::Result<Vec<u8>> {
+        let mut stream = std::pin::pin!(stream);
+        let mut out = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            out.extend_from_slice(&chunk?);
+        }
+        Ok(out)
+    }
+
+    fn chunked(data: &'static [u8], size: usize) -> BlobStream {
+        let chunks = data.chunks(size).map(|c| Ok(Bytes::from_static(c)));
+        BlobStream::from_stream("file://data", futures_util::stream::iter(chunks))
+    }
+
+    #[tokio::test]
+    async fn it_encodes_base64_across_chunk_boundaries() {
+        let data: &[u8] = b"streamed blobs are encoded as they are read";
+        for size in 1..=7 {
+            let encoded = collect(chunked(data, size).encode()).await.unwrap();
+            assert_eq!(
+                encoded,
+                STANDARD.encode(data).into_bytes(),
+                "chunks of {size}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn it_reads_from_an_async_reader() {
+        let blob = BlobStream::new("file://data", &b"hello world"[..]).with_size(11);
+        let contents = blob.into_contents().await.unwrap();
+        assert_eq!(&contents.blob[..], b"hello world");
+    }
+
+    #[tokio::test]
+    async fn it_fails_a_blob_of_another_size_than_declared() {
+        let short = chunked(b"abc", 2).with_size(4);
+        assert_eq!(
+            collect(short.into_stream()).await.unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+
+        let long = chunked(b"abcde", 2).with_size(4);
+        assert_eq!(
+            collect(long.into_stream()).await.unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[tokio::test]
+    async fn only_the_original_has_the_bytes() {
+        let blob = chunked(b"abc", 2);
+        let clone = blob.clone();
+        assert!(collect(clone.into_stream()).await.is_err());
+        assert_eq!(collect(blob.into_stream()).await.unwrap(), b"abc");
+    }
+
+    #[test]
+    fn it_never_serializes_its_bytes() {
+        let blob = chunked(b"abc", 2)
+            .with_size(3)
+            .with_mime("application/octet-stream");
+        let json = serde_json::to_value(&blob).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "uri": "file://data",
+                "mimeType": "application/octet-stream",
+                "size": 3
+            })
+        );
+
+        let json = serde_json::to_value(blob.contents()).unwrap();
+        assert_eq!(json["blob"], "");
+        assert_eq!(json["mimeType"], "application/octet-stream");
+    }
+}
@@ -0,0 +1,330 @@
+//! Concrete resources enumerated from a resource template
+
+use super::{Resource, ResourceTemplate};
+use crate::error::Error;
+use crate::shared::BoxFuture;
+use crate::types::Completion;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The most values a completion may carry, as the spec has it
+const MAX_COMPLETION_VALUES: usize = 100;
+
+/// How many bindings a completion asks an enumerator for at a time
+const COMPLETION_PAGE_SIZE: usize = 100;
+
+/// How many pages a completion scans at most; past them it only says that
+/// there may be more
+const MAX_COMPLETION_PAGES: usize = 10;
+
+/// Lists the bindings of a template's variables, at most `limit` of them
+/// starting at `offset`.
+pub(crate) type Enumerator =
+    Arc<dyn Fn(usize, usize) -> BoxFuture<'static, Result<Vec<TemplateArgs>, Error>> + Send + Sync>;
+
+/// Values for the variables of a [`ResourceTemplate`], naming one of the
+/// concrete resources it covers.
+///
+/// A value given without a variable name goes to the first variable not named
+/// otherwise, so a bare string is enough for a template with one variable.
+///
+/// # Example
+/// ```
+/// use neva::types::TemplateArgs;
+///
+/// let one: TemplateArgs = "monday".into();
+/// let both: TemplateArgs = [("year", "2024"), ("month", "05")].into();
+/// let titled = TemplateArgs::from("monday").with_title("Monday's notes");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TemplateArgs {
+    values: Vec<(Option<String>, String)>,
+    name: Option<String>,
+    title: Option<String>,
+    descr: Option<String>,
+}
+
+impl TemplateArgs {
+    /// Creates an empty [`TemplateArgs`]
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the value of the variable `name`.
+    pub fn with_arg(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.values.push((Some(name.into()), value.into()));
+        self
+    }
+
+    /// Sets the name of the resource.
+    ///
+    /// Default: its URI
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the title of the resource.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the description of the resource.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.descr = Some(description.into());
+        self
+    }
+
+    /// Returns the value of each of `vars`, in their order.
+    fn resolve(&self, vars: &[&str]) -> Vec<Option<&str>> {
+        let mut positional = self
+            .values
+            .iter()
+            .filter(|(name, _)| name.is_none())
+            .map(|(_, value)| value.as_str());
+        vars.iter()
+            .map(|var| {
+                self.values
+                    .iter()
+                    .find(|(name, _)| name.as_deref() == Some(var))
+                    .map(|(_, value)| value.as_str())
+                    .or_else(|| positional.next())
+            })
+            .collect()
+    }
+}
+
+impl From<String> for TemplateArgs {
+    #[inline]
+    fn from(value: String) -> Self {
+        Self {
+            values: vec![(None, value)],
+            ..Self::default()
+        }
+    }
+}
+
+impl From<&str> for TemplateArgs {
+    #[inline]
+    fn from(value: &str) -> Self {
+        value.to_owned().into()
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> From<(K, V)> for TemplateArgs {
+    #[inline]
+    fn from((name, value): (K, V)) -> Self {
+        Self::new().with_arg(name, value)
+    }
+}
+
+impl<K: Into<String>, V: Into<String>, const N: usize> From<[(K, V); N]> for TemplateArgs {
+    #[inline]
+    fn from(values: [(K, V); N]) -> Self {
+        values.into_iter().fold(Self::new(), |args, (name, value)| {
+            args.with_arg(name, value)
+        })
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> From<Vec<(K, V)>> for TemplateArgs {
+    #[inline]
+    fn from(values: Vec<(K, V)>) -> Self {
+        values.into_iter().fold(Self::new(), |args, (name, value)| {
+            args.with_arg(name, value)
+        })
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> From<HashMap<K, V>> for TemplateArgs {
+    #[inline]
+    fn from(values: HashMap<K, V>) -> Self {
+        values.into_iter().fold(Self::new(), |args, (name, value)| {
+            args.with_arg(name, value)
+        })
+    }
+}
+
+impl ResourceTemplate {
+    /// Whether the template lists the resources it covers
+    #[inline]
+    pub(crate) fn is_enumerable(&self) -> bool {
+        self.enumerator.is_some()
+    }
+
+    /// Returns the resources the template covers, at most `limit` of them
+    /// starting at `offset`, one for each binding the enumerator gives.
+    ///
+    /// A binding that leaves a variable without a value has no resource.
+    pub(crate) async fn enumerate(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<Option<Resource>>, Error> {
+        let Some(enumerator) = &self.enumerator else {
+            return Ok(Vec::new());
+        };
+        let page = enumerator(offset, limit).await?;
+        Ok(page.into_iter().map(|args| self.resource(args)).collect())
+    }
+
+    /// Completes the variable `var` from the values the enumerator gives it
+    /// that start with `prefix`, scanning at most [`MAX_COMPLETION_PAGES`]
+    /// pages of bindings.
+    pub(crate) async fn complete(&self, var: &str, prefix: &str) -> Result<Completion, Error> {
+        let vars = variables(&self.uri_template);
+        let (Some(enumerator), Some(pos)) = (&self.enumerator, vars.iter().position(|v| *v == var))
+        else {
+            return Ok(Completion::default());
+        };
+
+        let mut values: Vec<String> = Vec::new();
+        let mut has_more = false;
+        let mut offset = 0;
+        'pages: for scanned in 1.. {
+            let page = enumerator(offset, COMPLETION_PAGE_SIZE).await?;
+            let count = page.len();
+            for args in &page {
+                let Some(value) = args.resolve(&vars)[pos] else {
+                    continue;
+                };
+                if !value.starts_with(prefix) || values.iter().any(|v| v == value) {
+                    continue;
+                }
+                if values.len() == MAX_COMPLETION_VALUES {
+                    has_more = true;
+                    break 'pages;
+                }
+                values.push(value.to_owned());
+            }
+            if count < COMPLETION_PAGE_SIZE {
+                break;
+            }
+            if scanned == MAX_COMPLETION_PAGES {
+                has_more = true;
+                break;
+            }
+            offset += count;
+        }
+
+        Ok(Completion {
+            total: (!has_more).then_some(values.len()),
+            has_more: Some(has_more),
+            values,
+        })
+    }
+
+    /// Fills the template in with `args`, the resource taking the template's
+    /// MIME type, annotations and icons.
+    fn resource(&self, args: TemplateArgs) -> Option<Resource> {
+        let values = args.resolve(&variables(&self.uri_template));
+        let uri = expand(&self.uri_template, &values)?;
+        let name = args.name.unwrap_or_else(|| uri.clone());
+        let mut resource = Resource::new(uri, name);
+        resource.title = args.title;
+        resource.descr = args.descr;
+        resource.mime = self.mime.clone();
+        resource.annotations = self.annotations.clone();
+        resource.icons = self.icons.clone();
+        Some(resource)
+    }
+}
+
+/// Returns the names of the variables in a URI template, in their order.
+fn variables(template: &str) -> Vec<&str> {
+    let mut vars = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        vars.push(&rest[start + 1..start + len]);
+        rest = &rest[start + len + 1..];
+    }
+    vars
+}
+
+/// Replaces the variables in a URI template with `values`, in their order.
+fn expand(template: &str, values: &[Option<&str>]) -> Option<String> {
+    let mut uri = String::with_capacity(template.len());
+    let mut values = values.iter();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        uri.push_str(&rest[..start]);
+        uri.push_str((*values.next()?)?);
+        rest = &rest[start + len + 1..];
+    }
+    uri.push_str(rest);
+    Some(uri)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template() -> ResourceTemplate {
+        let mut template = ResourceTemplate::new("notes://{year}/{month}", "notes");
+        template.with_mime("text/markdown");
+        template
+    }
+
+    #[test]
+    fn it_fills_variables_by_name_then_by_position() {
+        let args = TemplateArgs::from("05").with_arg("year", "2024");
+        assert_eq!(args.resolve(&["year", "month"]), [Some("2024"), Some("05")]);
+
+        let resource = template().resource(args).unwrap();
+        assert_eq!(resource.uri.to_string(), "notes://2024/05");
+        assert_eq!(resource.name, "notes://2024/05");
+        assert_eq!(resource.mime.as_deref(), Some("text/markdown"));
+    }
+
+    #[test]
+    fn it_skips_bindings_missing_a_variable() {
+        assert!(template().resource("2024".into()).is_none());
+        assert_eq!(variables("res://{a}/x/{b}"), ["a", "b"]);
+        assert_eq!(expand("res://static", &[]).as_deref(), Some("res://static"));
+    }
+
+    #[tokio::test]
+    async fn it_completes_from_the_enumerated_values() {
+        let mut template = template();
+        template.with_enumerator(|| {
+            (0..250).map(|i| {
+                [
+                    ("year", format!("{}", 2000 + i % 3)),
+                    ("month", format!("{i}")),
+                ]
+            })
+        });
+
+        let years = template.complete("year", "200").await.unwrap();
+        assert_eq!(years.values, ["2000", "2001", "2002"]);
+        assert_eq!(years.total, Some(3));
+
+        let months = template.complete("month", "").await.unwrap();
+        assert_eq!(months.values.len(), 100);
+        assert_eq!(months.has_more, Some(true));
+
+        let none = template.complete("day", "").await.unwrap();
+        assert!(none.values.is_empty());
+    }
+
+    #[tokio::test]
+    async fn it_stops_scanning_after_the_page_limit() {
+        let mut template = template();
+        template.with_enumerator(|| {
+            (0..).map(|i| [("year", format!("{i}")), ("month", "01".to_owned())])
+        });
+
+        let years = template.complete("year", "x").await.unwrap();
+        assert!(years.values.is_empty());
+        assert_eq!(years.has_more, Some(true));
+        assert_eq!(years.total, None);
+    }
+}
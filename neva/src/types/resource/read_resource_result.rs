@@ -1,5 +1,6 @@
 //! Types and utils for handling read resource results
 
+use super::BlobStream;
 use crate::types::helpers::{
     deserialize_base64_as_bytes, deserialize_value_from_string, serialize_bytes_as_base64,
     serialize_value_as_string,
@@ -62,6 +63,14 @@ pub enum ResourceContents {
 
     /// Represents an empty/unknown resource content
     Empty(EmptyResourceContents),
+
+    /// Represents a blob resource content read from a stream as the response
+    /// is written
+    ///
+    /// > **Note:** last, since it is never read off the wire: the client
+    /// > receives it as [`ResourceContents::Blob`]. A clone has no bytes, and
+    /// > serializing one writes no `blob`; see [`BlobStream`].
+    Stream(BlobStream),
 }
 
 /// Represents a blob resource content
@@ -203,9 +212,20 @@ pub struct EmptyResourceContents {
 #[cfg(feature = "server")]
 impl IntoResponse for ReadResourceResult {
     #[inline]
-    fn into_response(self, req_id: RequestId) -> Response {
+    fn into_response(mut self, req_id: RequestId) -> Response {
+        // Streamed blobs ride along with the response, each in place of the
+        // contents it leaves without bytes, for the transport to read in.
+        let mut blobs = Vec::new();
+        for (index, content) in self.contents.iter_mut().enumerate() {
+            if let ResourceContents::Stream(blob) = content {
+                let contents = ResourceContents::Blob(blob.contents());
+                if let ResourceContents::Stream(blob) = std::mem::replace(content, contents) {
+                    blobs.push((index, blob));
+                }
+            }
+        }
         match serde_json::to_value(self) {
-            Ok(v) => Response::success(req_id, v),
+            Ok(v) => Response::success(req_id, v).with_blobs(blobs),
             Err(err) => Response::error(req_id, err.into()),
         }
     }
@@ -245,6 +265,13 @@ impl From<BlobResourceContents> for ResourceContents {
     }
 }
 
+impl From<BlobStream> for ResourceContents {
+    #[inline]
+    fn from(value: BlobStream) -> Self {
+        Self::Stream(value)
+    }
+}
+
 #[cfg(feature = "server")]
 impl<T1, T2> From<(T1, T2)> for TextResourceContents
 where
@@ -419,6 +446,7 @@ impl ResourceContents {
             Self::Json(json) => &json.uri,
             Self::Blob(blob) => &blob.uri,
             Self::Empty(empty) => &empty.uri,
+            Self::Stream(stream) => &stream.uri,
         }
     }
 
@@ -430,6 +458,7 @@ impl ResourceContents {
             Self::Json(json) => json.value.as_str(),
             Self::Blob(_) => None,
             Self::Empty(_) => None,
+            Self::Stream(_) => None,
         }
     }
 
@@ -441,6 +470,7 @@ impl ResourceContents {
             Self::Json(json) => json.title.as_deref(),
             Self::Blob(blob) => blob.title.as_deref(),
             Self::Empty(empty) => empty.title.as_deref(),
+            Self::Stream(stream) => stream.title.as_deref(),
         }
     }
 
@@ -452,6 +482,7 @@ impl ResourceContents {
             Self::Json(json) => json.annotations.as_ref(),
             Self::Blob(blob) => blob.annotations.as_ref(),
             Self::Empty(empty) => empty.annotations.as_ref(),
+            Self::Stream(stream) => stream.annotations.as_ref(),
         }
     }
 
@@ -463,6 +494,7 @@ impl ResourceContents {
             Self::Json(_) => None,
             Self::Text(_) => None,
             Self::Empty(_) => None,
+            Self::Stream(_) => None,
         }
     }
 
@@ -472,7 +504,7 @@ impl ResourceContents {
         match self {
             Self::Text(text) => serde_json::from_str(&text.text).map_err(Error::from),
             Self::Json(json) => serde_json::from_value(json.value.clone()).map_err(Error::from),
-            Self::Blob(_) => Err(Error::new(
+            Self::Blob(_) | Self::Stream(_) => Err(Error::new(
                 ErrorCode::InvalidRequest,
                 "Cannot deserialize blob",
             )),
@@ -491,6 +523,7 @@ impl ResourceContents {
             Self::Json(json) => json.mime.as_deref(),
             Self::Blob(blob) => blob.mime.as_deref(),
             Self::Empty(empty) => empty.mime.as_deref(),
+            Self::Stream(stream) => stream.mime.as_deref(),
         }
     }
 
@@ -502,6 +535,7 @@ impl ResourceContents {
             Self::Json(ref mut json) => json.mime = Some(mime.into()),
             Self::Blob(ref mut blob) => blob.mime = Some(mime.into()),
             Self::Empty(ref mut empty) => empty.mime = Some(mime.into()),
+            Self::Stream(ref mut stream) => stream.mime = Some(mime.into()),
         }
         self
     }
@@ -514,6 +548,7 @@ impl ResourceContents {
             Self::Json(ref mut json) => json.title = Some(title.into()),
             Self::Blob(ref mut blob) => blob.title = Some(title.into()),
             Self::Empty(ref mut empty) => empty.title = Some(title.into()),
+            Self::Stream(ref mut stream) => stream.title = Some(title.into()),
         }
         self
     }
@@ -529,6 +564,7 @@ impl ResourceContents {
             Self::Json(json) => Self::Json(json.with_annotations(config)),
             Self::Blob(blob) => Self::Blob(blob.with_annotations(config)),
             Self::Empty(empty) => Self::Empty(empty.with_annotations(config)),
+            Self::Stream(stream) => Self::Stream(stream.with_annotations(config)),
         }
     }
 
@@ -561,6 +597,14 @@ impl ResourceContents {
                 meta: content.meta,
                 text,
             }),
+            Self::Stream(content) => Self::Text(TextResourceContents {
+                uri: content.uri,
+                mime: Some("text/plain".into()),
+                title: content.title,
+                annotations: content.annotations,
+                meta: content.meta,
+                text,
+            }),
             Self::Empty(content) => Self::Text(TextResourceContents {
                 uri: content.uri,
                 mime: content.mime.or_else(|| Some("text/plain".into())),
@@ -601,6 +645,14 @@ impl ResourceContents {
                 meta: content.meta,
                 blob,
             }),
+            Self::Stream(content) => Self::Blob(BlobResourceContents {
+                uri: content.uri,
+                mime: content.mime,
+                title: content.title,
+                annotations: content.annotations,
+                meta: content.meta,
+                blob,
+            }),
             Self::Empty(content) => Self::Blob(BlobResourceContents {
                 uri: content.uri,
                 mime: None,
@@ -641,6 +693,14 @@ impl ResourceContents {
                 meta: content.meta,
                 value,
             }),
+            Self::Stream(content) => Self::Json(JsonResourceContents {
+                uri: content.uri,
+                mime: Some("application/json".into()),
+                title: content.title,
+                annotations: content.annotations,
+                meta: content.meta,
+                value,
+            }),
             Self::Empty(content) => Self::Json(JsonResourceContents {
                 uri: content.uri,
                 mime: content.mime.or_else(|| Some("application/json".into())),
@@ -23,7 +23,7 @@ pub(crate) struct Route {
 
 /// A handler function for a resource route
 pub(crate) struct ResourceHandler {
-    #[cfg(feature = "http-server")]
+    /// The name of the resource template
     pub(crate) template: String,
     handler: RequestHandler<ReadResourceResult>,
 }
@@ -76,7 +76,7 @@ impl Route {
     pub(crate) fn insert(
         &mut self,
         path: &Uri,
-        _template: String,
+        template: String,
         handler: RequestHandler<ReadResourceResult>,
     ) {
         let mut current = self;
@@ -91,8 +91,7 @@ impl Route {
         }
 
         current.handler = Some(ResourceHandler {
-            #[cfg(feature = "http-server")]
-            template: _template.clone(),
+            template,
             handler: handler.clone(),
         });
     }
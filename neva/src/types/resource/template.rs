@@ -17,7 +17,10 @@ use crate::types::{
 };
 
 #[cfg(feature = "server")]
-use crate::types::{FromRequest, ReadResourceRequestParams, ReadResourceResult, Request};
+use crate::types::{
+    FromRequest, ReadResourceRequestParams, ReadResourceResult, Request,
+    resource::enumerator::{Enumerator, TemplateArgs},
+};
 
 /// Represents a known resource template that the server is capable of reading.
 ///
@@ -75,6 +78,11 @@ pub struct ResourceTemplate {
     #[serde(skip)]
     #[cfg(feature = "http-server")]
     pub(crate) permissions: Option<Vec<String>>,
+
+    /// Lists the concrete resources the template covers
+    #[serde(skip)]
+    #[cfg(feature = "server")]
+    pub(crate) enumerator: Option<Enumerator>,
 }
 
 /// Sent from the client to request a list of resource templates the server has.
@@ -259,6 +267,7 @@ impl ResourceTemplate {
             roles: None,
             #[cfg(feature = "http-server")]
             permissions: None,
+            enumerator: None,
         }
     }
 
@@ -316,6 +325,83 @@ impl ResourceTemplate {
         self.icons = Some(icons.into_iter().collect());
         self
     }
+
+    /// Lists the concrete resources the template covers.
+    ///
+    /// They appear in `resources/list` after the static resources, and the
+    /// values they give a variable are offered when a client asks to complete
+    /// it. The closure returns every binding of the template's variables; it
+    /// runs again for each page, so a large or costly source is better served
+    /// by [`Self::with_paged_enumerator`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// # #[cfg(feature = "server")] {
+    /// use neva::prelude::*;
+    ///
+    /// let mut app = App::new();
+    /// app.map_resource("notes://{day}", "note", |uri: Uri, day: String| async move {
+    ///     ResourceContents::new(uri).with_text(format!("notes of {day}"))
+    /// })
+    /// .with_enumerator(|| ["monday", "tuesday"]);
+    /// # }
+    /// ```
+    pub fn with_enumerator<F, I>(&mut self, enumerator: F) -> &mut Self
+    where
+        F: Fn() -> I + Send + Sync + 'static,
+        I: IntoIterator,
+        I::Item: Into<TemplateArgs>,
+    {
+        self.enumerator = Some(Arc::new(move |offset, limit| {
+            let page = enumerator()
+                .into_iter()
+                .skip(offset)
+                .take(limit)
+                .map(Into::into)
+                .collect();
+            Box::pin(std::future::ready(Ok(page)))
+        }));
+        self
+    }
+
+    /// Lists the concrete resources the template covers, a page at a time.
+    ///
+    /// The closure gets the offset of the first binding wanted and how many
+    /// are wanted at most; a page shorter than that ends the list. Otherwise
+    /// it works as [`Self::with_enumerator`] does.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # #[cfg(feature = "server")] {
+    /// use neva::prelude::*;
+    ///
+    /// let mut app = App::new();
+    /// app.map_resource("users://{id}", "user", |uri: Uri, id: String| async move {
+    ///     ResourceContents::new(uri).with_text(format!("user {id}"))
+    /// })
+    /// .with_paged_enumerator(|offset, limit| async move {
+    ///     let ids = (offset..offset + limit).take_while(|id| *id < 42);
+    ///     Ok::<_, Error>(ids.map(|id| id.to_string()).collect::<Vec<_>>())
+    /// });
+    /// # }
+    /// ```
+    pub fn with_paged_enumerator<F, Fut, I, E>(&mut self, enumerator: F) -> &mut Self
+    where
+        F: Fn(usize, usize) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<I, E>> + Send + 'static,
+        I: IntoIterator,
+        I::Item: Into<TemplateArgs>,
+        E: Into<Error>,
+    {
+        self.enumerator = Some(Arc::new(move |offset, limit| {
+            let page = enumerator(offset, limit);
+            Box::pin(async move {
+                let page = page.await.map_err(Into::into)?;
+                Ok(page.into_iter().map(Into::into).collect())
+            })
+        }));
+        self
+    }
 }
 
 #[cfg(test)]
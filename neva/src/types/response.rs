@@ -9,6 +9,9 @@ use serde_json::{Value, json};
 #[cfg(feature = "http-server")]
 use http::HeaderMap;
 
+#[cfg(feature = "server")]
+use crate::types::BlobStream;
+
 pub use error_details::ErrorDetails;
 pub use into_response::IntoResponse;
 
@@ -96,6 +99,7 @@ pub enum Response {
 
 /// A successful response message in the JSON-RPC protocol.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct OkResponse {
     /// JSON-RPC protocol version.
     ///
@@ -117,6 +121,12 @@ pub struct OkResponse {
     #[serde(skip)]
     #[cfg(feature = "http-server")]
     pub headers: HeaderMap,
+
+    /// Streamed blobs the transport reads into `result`, each with the index
+    /// of the entry of `result.contents` whose `blob` it fills in.
+    #[serde(skip)]
+    #[cfg(feature = "server")]
+    pub(crate) blobs: Vec<(usize, BlobStream)>,
 }
 
 /// A response to a request that indicates an error occurred.
@@ -171,6 +181,8 @@ impl Response {
             session_id: None,
             #[cfg(feature = "http-server")]
             headers: HeaderMap::with_capacity(8),
+            #[cfg(feature = "server")]
+            blobs: Vec::new(),
             id,
             result,
         })
@@ -187,6 +199,8 @@ impl Response {
             session_id: None,
             #[cfg(feature = "http-server")]
             headers: HeaderMap::new(),
+            #[cfg(feature = "server")]
+            blobs: Vec::new(),
             id,
             result,
         })
@@ -369,6 +383,15 @@ impl Response {
         self
     }
 
+    /// Attaches the streamed blobs the result carries placeholders for
+    #[cfg(feature = "server")]
+    pub(crate) fn with_blobs(mut self, blobs: Vec<(usize, BlobStream)>) -> Self {
+        if let Response::Ok(ok) = &mut self {
+            ok.blobs = blobs;
+        }
+        self
+    }
+
     /// Unwraps the [`Response`] into either result of `T` or [`Error`]
     pub fn into_result<T: DeserializeOwned>(self) -> Result<T, Error> {
         match self {
@@ -734,6 +734,124 @@ impl Schema {
     pub fn multi_titled_enum() -> Self {
         MultiTitledEnum(Default::default())
     }
+
+    /// Sets a title for the property.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        let title = Some(title.into());
+        match &mut self {
+            Self::String(s) => s.title = title,
+            Self::Number(s) => s.title = title,
+            Self::Boolean(s) => s.title = title,
+            Self::SingleUntitledEnum(s) => s.title = title,
+            Self::SingleTitledEnum(s) => s.title = title,
+            Self::MultiUntitledEnum(s) => s.title = title,
+            Self::MultiTitledEnum(s) => s.title = title,
+            Self::LegacyEnum(s) => s.title = title,
+        }
+        self
+    }
+
+    /// Sets a human-readable description of the property.
+    pub fn with_descr(mut self, descr: impl Into<String>) -> Self {
+        let descr = Some(descr.into());
+        match &mut self {
+            Self::String(s) => s.descr = descr,
+            Self::Number(s) => s.descr = descr,
+            Self::Boolean(s) => s.descr = descr,
+            Self::SingleUntitledEnum(s) => s.descr = descr,
+            Self::SingleTitledEnum(s) => s.descr = descr,
+            Self::MultiUntitledEnum(s) => s.descr = descr,
+            Self::MultiTitledEnum(s) => s.descr = descr,
+            Self::LegacyEnum(s) => s.descr = descr,
+        }
+        self
+    }
+
+    /// Sets the default value of the property.
+    ///
+    /// A schema that models `default` takes it there when the value has the
+    /// modelled type; anything else is kept verbatim in `extra`, which is how
+    /// the peer receives it either way.
+    ///
+    /// # Examples
+    /// ```
+    /// use neva::types::Schema;
+    ///
+    /// let schema = serde_json::to_value(Schema::integer().with_default(2))?;
+    /// assert_eq!(schema["default"], 2);
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    pub fn with_default(mut self, value: impl Into<Value>) -> Self {
+        let value = value.into();
+        let extra = match &mut self {
+            Self::Boolean(s) => match value.as_bool() {
+                Some(b) => {
+                    s.default = Some(b);
+                    return self;
+                }
+                None => &mut s.extra,
+            },
+            Self::SingleUntitledEnum(s) => match value.as_str() {
+                Some(v) => {
+                    s.default = Some(v.into());
+                    return self;
+                }
+                None => &mut s.extra,
+            },
+            Self::SingleTitledEnum(s) => match value.as_str() {
+                Some(v) => {
+                    s.default = Some(v.into());
+                    return self;
+                }
+                None => &mut s.extra,
+            },
+            Self::MultiUntitledEnum(s) => match string_array(&value) {
+                Some(v) => {
+                    s.default = Some(v);
+                    return self;
+                }
+                None => &mut s.extra,
+            },
+            Self::MultiTitledEnum(s) => match string_array(&value) {
+                Some(v) => {
+                    s.default = Some(v);
+                    return self;
+                }
+                None => &mut s.extra,
+            },
+            Self::String(s) => &mut s.extra,
+            Self::Number(s) => &mut s.extra,
+            Self::LegacyEnum(s) => &mut s.extra,
+        };
+        extra.insert("default".into(), value);
+        self
+    }
+}
+
+impl Schema {
+    /// Validates a property value against the schema.
+    #[inline]
+    pub(crate) fn validate(&self, value: &Value) -> Result<(), Error> {
+        match self {
+            Self::String(s) => s.validate(value),
+            Self::Number(s) => s.validate(value),
+            Self::Boolean(s) => s.validate(value),
+            Self::SingleUntitledEnum(s) => s.validate(value),
+            Self::SingleTitledEnum(s) => s.validate(value),
+            Self::MultiUntitledEnum(s) => s.validate(value),
+            Self::MultiTitledEnum(s) => s.validate(value),
+            Self::LegacyEnum(s) => s.validate(value),
+        }
+    }
+}
+
+/// `value` as a list of strings, if that is what it is.
+fn string_array(value: &Value) -> Option<Vec<String>> {
+    value
+        .as_array()?
+        .iter()
+        .map(|v| v.as_str().map(Into::into))
+        .collect()
 }
 
 impl StringSchema {
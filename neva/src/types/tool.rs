@@ -34,10 +34,18 @@ use crate::types::TaskMetadata;
 use jsonschema::validator_for;
 
 pub use call_tool_response::CallToolResponse;
+#[cfg(feature = "server")]
+pub use call_tool_response::IntoToolResponse;
+pub use tool_error::ToolError;
+#[cfg(feature = "server")]
+pub use tool_error::ToolErrorMode;
+#[cfg(feature = "server")]
+pub(crate) use tool_error::{ToolErrorPolicy, fold};
 
 mod call_tool_response;
 #[cfg(feature = "server")]
 mod from_request;
+mod tool_error;
 
 /// List of commands for Tools
 pub mod commands {
@@ -152,6 +160,11 @@ pub struct Tool {
     #[serde(skip)]
     #[cfg(feature = "server")]
     custom_schema: bool,
+
+    /// Whether the handler needs the client's roots before it runs.
+    #[serde(skip)]
+    #[cfg(feature = "server")]
+    pub(crate) reads_roots: bool,
 }
 
 /// Execution-related properties for a tool.
@@ -757,13 +770,21 @@ pub trait ToolHandler<Args>: GenericHandler<Args> {
     fn args() -> Vec<ToolArg> {
         Vec::new()
     }
+
+    /// Whether the handler takes an argument resolved against the client's
+    /// roots, such as a [`crate::app::context::SandboxedPath`], so the roots
+    /// have to be fetched before it runs.
+    #[inline]
+    fn reads_roots() -> bool {
+        false
+    }
 }
 
 #[cfg(feature = "server")]
 pub(crate) struct ToolFunc<F, R, Args>
 where
     F: ToolHandler<Args, Output = R>,
-    R: Into<CallToolResponse>,
+    R: IntoToolResponse,
     Args: FromHandlerArgs<CallToolRequestParams>,
 {
     func: F,
@@ -774,7 +795,7 @@ where
 impl<F, R, Args> ToolFunc<F, R, Args>
 where
     F: ToolHandler<Args, Output = R>,
-    R: Into<CallToolResponse>,
+    R: IntoToolResponse,
     Args: FromHandlerArgs<CallToolRequestParams>,
 {
     /// Creates a new [`ToolFunc`] wrapped into [`Arc`]
@@ -791,7 +812,7 @@ where
 impl<F, R, Args> Handler<CallToolResponse> for ToolFunc<F, R, Args>
 where
     F: ToolHandler<Args, Output = R>,
-    R: Into<CallToolResponse>,
+    R: IntoToolResponse,
     Args: FromHandlerArgs<CallToolRequestParams> + Send + Sync,
 {
     #[inline]
@@ -800,8 +821,13 @@ where
             unreachable!()
         };
         Box::pin(async move {
+            let policy = params
+                .meta
+                .as_ref()
+                .and_then(|meta| meta.context.as_ref())
+                .and_then(|ctx| ctx.options.tool_error_policy().cloned());
             let args = Args::from_args(params, &names)?;
-            Ok(self.func.call(args).await.into())
+            fold(self.func.call(args).await, policy)
         })
     }
 }
@@ -1047,7 +1073,7 @@ impl Tool {
     pub fn new<F, Args, R>(name: impl Into<String>, handler: F) -> Self
     where
         F: ToolHandler<Args, Output = R>,
-        R: Into<CallToolResponse> + Send + 'static,
+        R: IntoToolResponse + Send + 'static,
         Args: FromHandlerArgs<CallToolRequestParams> + Send + Sync + 'static,
     {
         let handler = ToolFunc::new(handler);
@@ -1065,6 +1091,36 @@ impl Tool {
             handler: Some(handler),
             arg_names,
             custom_schema: false,
+            reads_roots: F::reads_roots(),
+            icons: None,
+            #[cfg(feature = "http-server")]
+            roles: None,
+            #[cfg(feature = "http-server")]
+            permissions: None,
+            #[cfg(feature = "tasks")]
+            exec: None,
+        }
+    }
+
+    /// Initializes a [`Tool`] around a handler that reads its arguments
+    /// itself, so its input schema is left for the caller to set.
+    #[cfg(feature = "openapi")]
+    pub(crate) fn with_handler(
+        name: impl Into<String>,
+        handler: RequestHandler<CallToolResponse>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            title: None,
+            descr: None,
+            input_schema: Default::default(),
+            output_schema: None,
+            meta: None,
+            annotations: None,
+            handler: Some(handler),
+            arg_names: ArgNames::positional(0),
+            custom_schema: true,
+            reads_roots: false,
             icons: None,
             #[cfg(feature = "http-server")]
             roles: None,
@@ -1351,12 +1407,10 @@ impl Tool {
 impl Tool {
     /// Validates [`CallToolResponse`] against this tool output schema.
     ///
-    /// Under the legacy feature set the schema is the typed `ToolSchema`
-    /// struct and is materialized via [`serde_json::to_value`]. Under
-    /// MCP 2026-07-28 the schema is already a [`serde_json::Value`]
-    /// (wrapped by [`crate::types::schema_2020::InputSchema`]), so we borrow
-    /// it directly via [`crate::types::schema_2020::InputSchema::as_value`]
-    /// -- no re-serialization is needed.
+    /// The schema is compiled on every call. A client calling the same tool
+    /// repeatedly should let it check the results instead -- see
+    /// [`McpOptions::with_output_validation`](crate::client::options::McpOptions::with_output_validation),
+    /// which compiles each schema once per `tools/list`.
     pub fn validate<'a>(&self, resp: &'a CallToolResponse) -> Result<&'a CallToolResponse, Error> {
         let Some(schema_ref) = self.output_schema.as_ref() else {
             return Err(Error::new(
@@ -1365,11 +1419,7 @@ impl Tool {
             ));
         };
 
-        #[cfg(feature = "legacy-spec")]
-        let schema = serde_json::to_value(schema_ref).map_err(Into::<Error>::into)?;
-        #[cfg(not(feature = "legacy-spec"))]
-        let schema = schema_ref.as_value().clone();
-
+        let schema = shared::json_schema::schema_value(schema_ref)?;
         let validator =
             validator_for(&schema).map_err(|err| Error::new(ErrorCode::ParseError, err))?;
 
@@ -1411,6 +1461,13 @@ impl ToolAnnotations {
         self
     }
 
+    /// Sets/Unsets a hint that the tool does not modify its environment.
+    #[inline]
+    pub fn with_readonly(mut self, readonly: bool) -> Self {
+        self.readonly = Some(readonly);
+        self
+    }
+
     /// Sets/Unsets a hint that the tool may perform destructive updates to its environment.
     ///
     /// Also sets the readonly hint to `false`
@@ -1477,6 +1534,11 @@ macro_rules! impl_generic_tool_handler ({ $($param:ident)* } => {
             )*
             args
         }
+
+        #[inline]
+        fn reads_roots() -> bool {
+            false $(|| <$param as TypeCategory>::reads_roots())*
+        }
     }
 });
 
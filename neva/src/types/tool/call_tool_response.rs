@@ -4,6 +4,8 @@
 use crate::error::Error;
 #[cfg(feature = "server")]
 use crate::types::Json;
+#[cfg(any(feature = "server", feature = "client"))]
+use crate::types::ToolError;
 use crate::types::{Content, IntoResponse, RequestId, Response};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -138,6 +140,118 @@ impl_from_for_call_tool_response! {
     f32, f64,
 }
 
+/// What a tool handler returns: the response it makes, or the error it
+/// failed with.
+///
+/// Implemented for everything a [`CallToolResponse`] is made from. An error --
+/// an [`Error`] or the error of a `Result` -- is given back as it is, for the
+/// tool error policy to decide how it reaches the client; see
+/// [`App::with_tool_error_policy`](crate::App::with_tool_error_policy).
+#[cfg(feature = "server")]
+pub trait IntoToolResponse {
+    /// Splits the value into the response it makes or the error it holds.
+    fn into_tool_response(self) -> Result<CallToolResponse, Error>;
+}
+
+#[cfg(feature = "server")]
+impl IntoToolResponse for CallToolResponse {
+    #[inline]
+    fn into_tool_response(self) -> Result<CallToolResponse, Error> {
+        Ok(self)
+    }
+}
+
+#[cfg(feature = "server")]
+impl IntoToolResponse for Error {
+    #[inline]
+    fn into_tool_response(self) -> Result<CallToolResponse, Error> {
+        Err(self)
+    }
+}
+
+#[cfg(feature = "server")]
+impl IntoToolResponse for ToolError {
+    #[inline]
+    fn into_tool_response(self) -> Result<CallToolResponse, Error> {
+        Ok(self.into())
+    }
+}
+
+#[cfg(feature = "server")]
+impl<T, E> IntoToolResponse for Result<T, E>
+where
+    T: IntoToolResponse,
+    E: Into<Error>,
+{
+    #[inline]
+    fn into_tool_response(self) -> Result<CallToolResponse, Error> {
+        self.map_err(Into::into)?.into_tool_response()
+    }
+}
+
+#[cfg(feature = "server")]
+impl<T: IntoToolResponse> IntoToolResponse for Option<T> {
+    #[inline]
+    fn into_tool_response(self) -> Result<CallToolResponse, Error> {
+        match self {
+            Some(value) => value.into_tool_response(),
+            None => Ok(CallToolResponse::empty()),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl IntoToolResponse for () {
+    #[inline]
+    fn into_tool_response(self) -> Result<CallToolResponse, Error> {
+        Ok(CallToolResponse::empty())
+    }
+}
+
+#[cfg(feature = "server")]
+impl<T: Into<Content>> IntoToolResponse for T {
+    #[inline]
+    fn into_tool_response(self) -> Result<CallToolResponse, Error> {
+        Ok(CallToolResponse::new(self))
+    }
+}
+
+#[cfg(feature = "server")]
+impl<T: Serialize> IntoToolResponse for Json<T> {
+    #[inline]
+    fn into_tool_response(self) -> Result<CallToolResponse, Error> {
+        Ok(self.into())
+    }
+}
+
+#[cfg(feature = "server")]
+impl<T: Into<Content>> IntoToolResponse for Vec<T> {
+    #[inline]
+    fn into_tool_response(self) -> Result<CallToolResponse, Error> {
+        Ok(self.into())
+    }
+}
+
+#[cfg(feature = "server")]
+macro_rules! impl_into_tool_response {
+    { $($type:ident),* $(,)? } => {
+        $(impl IntoToolResponse for $type {
+            #[inline]
+            fn into_tool_response(self) -> Result<CallToolResponse, Error> {
+                Ok(self.into())
+            }
+        })*
+    };
+}
+
+#[cfg(feature = "server")]
+impl_into_tool_response! {
+    bool,
+    i8, i16, i32, i64, i128, isize,
+    u8, u16, u32, u64, u128, usize,
+    f32, f64,
+}
+
 #[cfg(feature = "server")]
 impl CallToolResponse {
     /// Creates a single response
@@ -200,8 +314,15 @@ impl CallToolResponse {
     }
 
     /// Creates an error response
+    ///
+    /// An error caused by a [`ToolError`] keeps its content and structured
+    /// details.
     #[inline]
     pub fn error(error: Error) -> Self {
+        let error = match error.downcast::<ToolError>() {
+            Ok(err) => return err.into(),
+            Err(error) => error,
+        };
         Self {
             content: vec![Content::text(error.to_string())],
             struct_content: None,
@@ -252,6 +373,49 @@ impl CallToolResponse {
 
 #[cfg(feature = "client")]
 impl CallToolResponse {
+    /// Turns a result with `isError` set into an [`Error`] whose kind is
+    /// [`ErrorKind::Tool`](crate::error::ErrorKind::Tool), so a failed tool and
+    /// a failed call can be handled in one place.
+    ///
+    /// The first text content becomes the [`ToolError`] message and the rest
+    /// of the content follows it, as the server built it.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use neva::prelude::*;
+    /// use neva::error::ErrorKind;
+    ///
+    /// # async fn f(client: &mut Client) {
+    /// let result = client
+    ///     .call_tool("divide", [("a", 1), ("b", 0)])
+    ///     .await
+    ///     .and_then(CallToolResponse::into_result);
+    /// match result {
+    ///     Ok(resp) => println!("{:?}", resp.content),
+    ///     Err(err) => match err.kind() {
+    ///         ErrorKind::Tool(err) => println!("the tool failed: {err}"),
+    ///         _ => println!("the call failed: {err}"),
+    ///     },
+    /// }
+    /// # }
+    /// ```
+    pub fn into_result(self) -> Result<Self, Error> {
+        if !self.is_error {
+            return Ok(self);
+        }
+        let mut content = self.content.into_iter().peekable();
+        let message = match content.next_if(|item| item.as_text().is_some()) {
+            Some(Content::Text(text)) => text.text,
+            _ => String::new(),
+        };
+        Err(ToolError {
+            message,
+            content: content.collect(),
+            struct_content: self.struct_content,
+        }
+        .into())
+    }
+
     /// Turns [`CallToolResponse`]'s structured content into `T`
     pub fn as_json<T: DeserializeOwned>(&self) -> Result<T, Error> {
         self.struct_content()
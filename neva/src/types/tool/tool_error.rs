@@ -0,0 +1,259 @@
+//! Types for failures a tool reports to the model
+
+use crate::error::{Error, ErrorCode};
+use crate::types::{CallToolResponse, Content, IntoResponse, RequestId, Response};
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt;
+
+#[cfg(feature = "server")]
+use {super::IntoToolResponse, crate::error::ErrorKind, std::sync::Arc};
+
+/// A tool that failed at its task, as opposed to a call that could not be made.
+///
+/// It reaches the client as a [`CallToolResponse`] with `isError` set -- the
+/// message as its first text content, followed by any extra content, and the
+/// structured content if there is any -- so the model sees what went wrong and
+/// can try again. A tool returns it directly, as the error of a `Result`, or
+/// through an [`Error`] with `?`.
+///
+/// On the client, [`CallToolResponse::into_result`] turns an `isError` result
+/// back into one.
+///
+/// # Example
+/// ```no_run
+/// # #[cfg(feature = "server")] {
+/// use neva::prelude::*;
+///
+/// let mut app = App::new();
+/// app.map_tool("divide", |a: f64, b: f64| async move {
+///     if b == 0.0 {
+///         return Err(ToolError::new("cannot divide by zero")
+///             .with_structured(serde_json::json!({ "argument": "b" })));
+///     }
+///     Ok(a / b)
+/// });
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ToolError {
+    /// What went wrong, in words the model reads.
+    pub message: String,
+
+    /// Further content sent after the message.
+    pub content: Vec<Content>,
+
+    /// Structured details of the failure.
+    pub struct_content: Option<Value>,
+}
+
+impl fmt::Display for ToolError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ToolError {}
+
+impl ToolError {
+    /// Creates a new [`ToolError`]
+    #[inline]
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            content: Vec::new(),
+            struct_content: None,
+        }
+    }
+
+    /// Adds content to send after the message.
+    #[inline]
+    pub fn with_content(mut self, content: impl Into<Content>) -> Self {
+        self.content.push(content.into());
+        self
+    }
+
+    /// Attaches structured details of the failure.
+    ///
+    /// Details that do not serialize are left out.
+    #[inline]
+    pub fn with_structured<T: Serialize>(mut self, data: T) -> Self {
+        self.struct_content = serde_json::to_value(data).ok();
+        self
+    }
+}
+
+/// How an error a tool returns reaches the client.
+///
+/// See [`App::with_tool_error_policy`](crate::App::with_tool_error_policy).
+#[cfg(feature = "server")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolErrorMode {
+    /// As a [`CallToolResponse`] with `isError` set, for the model to see.
+    Result,
+
+    /// As a JSON-RPC error response, for the client to handle.
+    Protocol,
+}
+
+#[cfg(feature = "server")]
+impl ToolErrorMode {
+    /// How an error of `kind` is sent unless a policy says otherwise.
+    ///
+    /// The errors that ask something of the client rather than the model --
+    /// a URL to visit, a capability or protocol version it lacks, a header it
+    /// got wrong -- and a cancelled call are protocol errors, since the spec
+    /// gives them a code a client acts on. Everything else is the tool failing
+    /// at its task, and the model sees it.
+    pub fn for_kind(kind: &ErrorKind) -> Self {
+        match kind {
+            ErrorKind::UrlElicitationRequired(_) | ErrorKind::Cancelled => Self::Protocol,
+            #[cfg(not(feature = "legacy-spec"))]
+            ErrorKind::HeaderMismatch
+            | ErrorKind::MissingClientCapability(_)
+            | ErrorKind::UnsupportedProtocolVersion { .. }
+            | ErrorKind::InputRequired => Self::Protocol,
+            _ => Self::Result,
+        }
+    }
+}
+
+/// Decides how an error a tool returns reaches the client.
+#[cfg(feature = "server")]
+pub(crate) type ToolErrorPolicy = Arc<dyn Fn(&ErrorKind) -> ToolErrorMode + Send + Sync>;
+
+/// Folds a tool's return value into its response, unless it holds an error the
+/// policy sends as a protocol error.
+#[cfg(feature = "server")]
+pub(crate) fn fold<R: IntoToolResponse>(
+    value: R,
+    policy: Option<ToolErrorPolicy>,
+) -> Result<CallToolResponse, Error> {
+    let err = match value.into_tool_response() {
+        Ok(resp) => return Ok(resp),
+        Err(err) => err,
+    };
+    let kind = err.kind();
+    let mode = match policy {
+        Some(policy) => policy(&kind),
+        None => ToolErrorMode::for_kind(&kind),
+    };
+    match mode {
+        ToolErrorMode::Result => Ok(CallToolResponse::error(err)),
+        ToolErrorMode::Protocol => Err(err),
+    }
+}
+
+impl From<ToolError> for CallToolResponse {
+    #[inline]
+    fn from(err: ToolError) -> Self {
+        let mut content = Vec::with_capacity(err.content.len() + 1);
+        content.push(Content::text(err.message));
+        content.extend(err.content);
+        Self {
+            content,
+            struct_content: err.struct_content,
+            is_error: true,
+        }
+    }
+}
+
+impl IntoResponse for ToolError {
+    #[inline]
+    fn into_response(self, req_id: RequestId) -> Response {
+        CallToolResponse::from(self).into_response(req_id)
+    }
+}
+
+impl From<ToolError> for Error {
+    #[inline]
+    fn from(err: ToolError) -> Self {
+        Error::new(ErrorCode::InternalError, err)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "server")]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn it_renders_as_an_error_result() {
+        let resp = CallToolResponse::from(
+            ToolError::new("city not found")
+                .with_content("try a nearby one")
+                .with_structured(json!({ "city": "Atlantis" })),
+        );
+
+        assert_eq!(
+            serde_json::to_value(resp).unwrap(),
+            json!({
+                "content": [
+                    { "type": "text", "text": "city not found" },
+                    { "type": "text", "text": "try a nearby one" }
+                ],
+                "structuredContent": { "city": "Atlantis" },
+                "isError": true
+            })
+        );
+    }
+
+    #[test]
+    fn it_keeps_its_details_through_an_error() {
+        let err = Error::from(ToolError::new("no").with_structured(json!({ "why": "because" })));
+        assert!(matches!(err.kind(), ErrorKind::Tool(_)));
+
+        let resp = CallToolResponse::error(err);
+        assert!(resp.is_error);
+        assert_eq!(resp.struct_content, Some(json!({ "why": "because" })));
+    }
+
+    #[test]
+    fn it_escalates_an_error_only_while_folding() {
+        let url = || Error::url_elicitation_required("visit", []);
+
+        let resp = CallToolResponse::from(Err::<(), _>(url()));
+        assert!(resp.is_error);
+
+        let err = fold(Ok::<_, Error>(Some(Err::<(), _>(url()))), None).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::UrlElicitationRequiredError);
+
+        let err = fold(Err::<(), _>(url()), None).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::UrlElicitationRequiredError);
+
+        let policy: ToolErrorPolicy = Arc::new(|_| ToolErrorMode::Protocol);
+        let err = fold(Err::<(), _>(ToolError::new("no")), Some(policy)).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::Tool(_)));
+
+        let resp = fold(Err::<(), _>(ToolError::new("no")), None).unwrap();
+        assert!(resp.is_error);
+    }
+
+    #[test]
+    fn only_errors_for_the_client_are_protocol_errors_by_default() {
+        let mode = |err: Error| ToolErrorMode::for_kind(&err.kind());
+
+        assert_eq!(
+            mode(Error::from(ToolError::new("no"))),
+            ToolErrorMode::Result
+        );
+        assert_eq!(
+            mode(Error::new(ErrorCode::InvalidParams, "no")),
+            ToolErrorMode::Result
+        );
+        assert_eq!(
+            mode(Error::from(ErrorCode::InternalError)),
+            ToolErrorMode::Result
+        );
+        assert_eq!(
+            mode(Error::url_elicitation_required("visit", [])),
+            ToolErrorMode::Protocol
+        );
+        assert_eq!(
+            mode(Error::from(ErrorCode::RequestCancelled)),
+            ToolErrorMode::Protocol
+        );
+    }
+}
@@ -0,0 +1,166 @@
+//! `Context::run_agent` end to end: a tool lets the client's model call other
+//! tools of the server over MRTR sampling rounds, driven by the real client.
+#![cfg(all(
+    not(feature = "legacy-spec"),
+    feature = "http-server-volga",
+    feature = "http-client"
+))]
+#![allow(deprecated)]
+
+use neva::{
+    App, Context,
+    app::context::AgentLoop,
+    client::Client,
+    error::Error,
+    types::sampling::{CreateMessageRequestParams, CreateMessageResult, ToolChoiceMode},
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// A server whose `plan` tool runs an agent loop offering `get_weather` only,
+/// and which also serves a `secret` tool the model is not offered.
+fn app(addr: &str, max_steps: usize, weather_calls: Arc<AtomicUsize>) -> App {
+    let mut app = App::new()
+        .with_request_state_secret(b"test-secret")
+        .with_options(|o| o.with_http(|h| h.bind(addr).with_endpoint("/mcp")));
+
+    app.map_tool("get_weather", move |city: String| {
+        let weather_calls = weather_calls.clone();
+        async move {
+            weather_calls.fetch_add(1, Ordering::SeqCst);
+            format!("Sunny in {city}")
+        }
+    })
+    .with_arg_names(["city"]);
+    app.map_tool("secret", || async { "the launch codes" });
+    app.map_tool("plan", move |mut ctx: Context| async move {
+        let params = CreateMessageRequestParams::new().with_message("Plan a day in Paris");
+        let agent = AgentLoop::new(params)
+            .with_tools(["get_weather"])
+            .with_max_steps(max_steps);
+
+        let run = ctx.run_agent("plan", agent).await?;
+        Ok::<String, Error>(format!(
+            "{} after {} step(s), exhausted: {}, transcript: {}",
+            run.text(),
+            run.steps,
+            run.exhausted,
+            run.transcript.len()
+        ))
+    });
+    app
+}
+
+async fn call_plan(addr: &str, client: &mut Client) -> String {
+    client
+        .connect()
+        .await
+        .unwrap_or_else(|err| panic!("client connects to {addr}: {err}"));
+    let resp = client
+        .call_tool("plan", ())
+        .await
+        .expect("the loop completes through the MRTR rounds");
+    assert!(!resp.is_error, "the loop must not fail: {resp:?}");
+    resp.content
+        .first()
+        .and_then(|c| c.as_text())
+        .map(|t| t.text.clone())
+        .unwrap_or_default()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn the_model_calls_offered_tools_until_it_answers() {
+    let addr = format!("127.0.0.1:{}", pick_free_port());
+    let weather_calls = Arc::new(AtomicUsize::new(0));
+    let app = app(&addr, 4, weather_calls.clone());
+    let handle = tokio::spawn(async move { app.run().await });
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+
+    let mut client =
+        Client::new().with_options(|o| o.with_http(|h| h.bind(&addr).with_endpoint("/mcp")));
+    client.map_sampling(|params: CreateMessageRequestParams| async move {
+        let results: Vec<_> = params.results().collect();
+        if results.is_empty() {
+            let offered: Vec<_> = params.tools.iter().flatten().map(|t| &t.name).collect();
+            assert_eq!(
+                offered,
+                ["get_weather"],
+                "only the allow-listed tool is offered"
+            );
+
+            return CreateMessageResult::assistant().use_tools([
+                ("get_weather", [("city", "Paris")]),
+                ("secret", [("city", "")]),
+            ]);
+        }
+
+        let weather = &results[0];
+        assert!(!weather.is_error, "the offered tool runs: {weather:?}");
+        assert!(
+            results[1].is_error,
+            "the other tool is refused: {:?}",
+            results[1]
+        );
+
+        let text = weather.content[0].as_text().unwrap().text.clone();
+        CreateMessageResult::assistant()
+            .with_content(format!("Take a walk: {text}"))
+            .end_turn()
+    });
+
+    let text = call_plan(&addr, &mut client).await;
+    assert_eq!(
+        text,
+        "Take a walk: Sunny in Paris after 2 step(s), exhausted: false, transcript: 4"
+    );
+    assert_eq!(
+        weather_calls.load(Ordering::SeqCst),
+        1,
+        "a replayed step must not run its tools again"
+    );
+
+    client.disconnect().await.ok();
+    handle.abort();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn the_last_step_offers_no_tools() {
+    let addr = format!("127.0.0.1:{}", pick_free_port());
+    let app = app(&addr, 2, Arc::new(AtomicUsize::new(0)));
+    let handle = tokio::spawn(async move { app.run().await });
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+
+    let choices = Arc::new(Mutex::new(Vec::new()));
+    let seen = choices.clone();
+    let mut client =
+        Client::new().with_options(|o| o.with_http(|h| h.bind(&addr).with_endpoint("/mcp")));
+    client.map_sampling(move |params: CreateMessageRequestParams| {
+        let seen = seen.clone();
+        async move {
+            let step = params.results().count();
+            let mut seen = seen.lock().unwrap();
+            if seen.len() == step {
+                seen.push(params.tool_choice.map(|choice| choice.mode));
+            }
+            // A model that never stops asking for the weather.
+            CreateMessageResult::assistant().use_tool("get_weather", [("city", "Paris")])
+        }
+    });
+
+    let text = call_plan(&addr, &mut client).await;
+    assert_eq!(text, " after 2 step(s), exhausted: true, transcript: 4");
+    assert_eq!(
+        *choices.lock().unwrap(),
+        [Some(ToolChoiceMode::Auto), Some(ToolChoiceMode::None)]
+    );
+
+    client.disconnect().await.ok();
+    handle.abort();
+}
+
+fn pick_free_port() -> u16 {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    drop(listener);
+    port
+}
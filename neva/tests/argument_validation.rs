@@ -0,0 +1,153 @@
+//! Argument validation end-to-end over the 2026-07-28 HTTP transport.
+//!
+//! A `tools/call` whose arguments break the tool's input schema is refused
+//! before the handler runs, with every violation named by JSON pointer.
+#![cfg(all(
+    not(feature = "legacy-spec"),
+    feature = "server-validation",
+    feature = "http-server-volga",
+    feature = "http-client"
+))]
+
+use neva::App;
+use neva::types::schema_2020::InputSchema;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn arguments_that_break_the_schema_never_reach_the_handler() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let addr = format!("127.0.0.1:{}", pick_free_port());
+    let mut app = App::new().with_options(|opt| {
+        opt.with_http(|http| http.bind(&addr).with_endpoint("/mcp"))
+            .with_argument_validation()
+    });
+    let counter = calls.clone();
+    app.map_tool("book", move |nights: i64, room: String| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        async move { format!("{nights} nights in a {room} room") }
+    })
+    .with_arg_names(["nights", "room"])
+    .with_input_schema(|_| {
+        InputSchema::from_json_str(
+            r#"{
+                "type": "object",
+                "properties": {
+                    "nights": { "type": "integer", "minimum": 1 },
+                    "room": { "type": "string", "enum": ["single", "double"] }
+                },
+                "required": ["nights", "room"]
+            }"#,
+        )
+        .expect("a valid schema")
+    });
+    let handle = tokio::spawn(async move { app.run().await });
+    await_reachable(&addr).await;
+
+    let client = reqwest::Client::builder()
+        .no_proxy()
+        .build()
+        .expect("test client");
+    let url = format!("http://{addr}/mcp");
+
+    let refused = book(
+        &client,
+        &url,
+        serde_json::json!({ "nights": 0, "room": "suite" }),
+    )
+    .await;
+    assert_eq!(refused["error"]["code"], -32602, "got: {refused}");
+    let mut pointers: Vec<_> = refused["error"]["data"]["errors"]
+        .as_array()
+        .unwrap_or_else(|| panic!("a list of violations, got: {refused}"))
+        .iter()
+        .map(|err| err["pointer"].as_str().unwrap_or_default().to_owned())
+        .collect();
+    pointers.sort();
+    assert_eq!(pointers, ["/nights", "/room"]);
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+    let booked = book(
+        &client,
+        &url,
+        serde_json::json!({ "nights": 2, "room": "double" }),
+    )
+    .await;
+    assert_eq!(
+        booked["result"]["content"][0]["text"], "2 nights in a double room",
+        "got: {booked}"
+    );
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    handle.abort();
+}
+
+async fn book(client: &reqwest::Client, url: &str, args: serde_json::Value) -> serde_json::Value {
+    post(
+        client,
+        url,
+        serde_json::json!({
+            "jsonrpc": "2.0", "id": 1, "method": "tools/call",
+            "params": { "name": "book", "arguments": args, "_meta": meta() }
+        }),
+    )
+    .await
+}
+
+async fn post(client: &reqwest::Client, url: &str, body: serde_json::Value) -> serde_json::Value {
+    routed(client.post(url), &body)
+        .json(&body)
+        .send()
+        .await
+        .expect("send")
+        .json::<serde_json::Value>()
+        .await
+        .expect("json")
+}
+
+async fn await_reachable(addr: &str) {
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+    loop {
+        match tokio::net::TcpStream::connect(addr).await {
+            Ok(_) => break,
+            Err(_) if tokio::time::Instant::now() < deadline => {
+                tokio::time::sleep(Duration::from_millis(50)).await
+            }
+            Err(err) => panic!("server never became reachable: {err}"),
+        }
+    }
+}
+
+fn pick_free_port() -> u16 {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    drop(listener);
+    port
+}
+
+/// The `_meta` MCP 2026-07-28 requires on every request.
+fn meta() -> serde_json::Value {
+    serde_json::json!({
+        "io.modelcontextprotocol/protocolVersion": "2026-07-28",
+        "io.modelcontextprotocol/clientCapabilities": {}
+    })
+}
+
+/// Attaches the routing headers MCP 2026-07-28 requires on every request.
+fn routed(req: reqwest::RequestBuilder, body: &serde_json::Value) -> reqwest::RequestBuilder {
+    let req = req.header("MCP-Protocol-Version", "2026-07-28");
+    let Some(method) = body["method"].as_str() else {
+        return req;
+    };
+    let req = req.header("Mcp-Method", method);
+    let name = match method {
+        "tools/call" | "prompts/get" => body.pointer("/params/name"),
+        "resources/read" => body.pointer("/params/uri"),
+        _ => None,
+    };
+    match name.and_then(|v| v.as_str()) {
+        Some(name) => req.header("Mcp-Name", name),
+        None => req,
+    }
+}
@@ -0,0 +1,30 @@
+//! Blob resources streamed from their source end to end through
+//! `neva::testing`, within and past the app's blob size limit.
+
+#![cfg(all(not(feature = "legacy-spec"), feature = "testing"))]
+
+use neva::prelude::*;
+use neva::testing::TestClient;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn it_reads_streamed_blobs_within_the_limit() {
+    let mut app = App::new().with_max_blob_size(16);
+    app.map_resource(
+        "blob://{size}",
+        "blob",
+        |uri: Uri, size: usize| async move {
+            let data = bytes::Bytes::from(vec![b'x'; size]);
+            let chunks = data.chunks(3).map(|c| Ok(bytes::Bytes::copy_from_slice(c)));
+            BlobStream::from_stream(uri, futures_util::stream::iter(chunks.collect::<Vec<_>>()))
+        },
+    );
+    let mut client = TestClient::new(app);
+    client.connect().await.unwrap();
+
+    let small = client.read_resource("blob://10").await.unwrap();
+    assert_eq!(small.contents[0].blob(), Some(&b"xxxxxxxxxx"[..]));
+
+    // Of unknown size, so it fails once it reads past the limit.
+    assert!(client.read_resource("blob://20").await.is_err());
+    client.shutdown().await;
+}
@@ -0,0 +1,79 @@
+//! Per-call progress and log streams on the `Client`, end to end through
+//! `neva::testing`.
+
+#![cfg(all(not(feature = "legacy-spec"), feature = "testing", feature = "tracing"))]
+
+use futures_util::StreamExt;
+use neva::client::CallEvent;
+use neva::prelude::*;
+use neva::testing::{CallToolResponseExt, TestClient};
+use neva::types::notification::LoggingLevel;
+
+fn app() -> App {
+    let mut app = App::new();
+    app.map_tool("busy", |token: Meta<ProgressToken>| async move {
+        for step in 1..=3 {
+            tracing::info!(target: "progress", token = %*token, value = step, total = 3);
+        }
+        tracing::warn!(logger = "busy", "almost done");
+        "done"
+    });
+    app.map_prompt("hello", |name: String| async move {
+        PromptMessage::user().with(format!("Say hello to {name}"))
+    })
+    .with_args(["name"]);
+    app
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn it_streams_the_progress_and_logs_of_one_call() {
+    let mut client = TestClient::new(app()).with_tracing_capture();
+    client.connect().await.unwrap();
+
+    let mut call = client.client_mut().call_tool_streaming("busy", ()).unwrap();
+    let mut progress = Vec::new();
+    let mut logs = Vec::new();
+    while let Some(event) = call.next().await {
+        match event {
+            CallEvent::Progress(p) => progress.push(p.progress),
+            CallEvent::Log(log) => logs.push(log),
+        }
+    }
+    call.result().await.unwrap().assert_text("done");
+
+    assert_eq!(progress, [1.0, 2.0, 3.0]);
+    assert_eq!(logs.len(), 1, "got {logs:?}");
+    assert_eq!(logs[0].logger.as_deref(), Some("busy"));
+
+    let prompt = client
+        .client_mut()
+        .get_prompt_streaming("hello", [("name", "Ada")])
+        .unwrap()
+        .result()
+        .await
+        .unwrap();
+    assert_eq!(prompt.messages.len(), 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[allow(deprecated)]
+async fn it_asks_for_one_calls_logs_at_its_own_level() {
+    let mut client = TestClient::new(app())
+        .with_options(|opt| opt.with_log_level(LoggingLevel::Debug))
+        .with_tracing_capture();
+    client.connect().await.unwrap();
+
+    let mut call = client
+        .client_mut()
+        .call_tool_streaming("busy", ())
+        .unwrap()
+        .with_log_level(LoggingLevel::Error);
+    let mut logs = Vec::new();
+    while let Some(event) = call.next().await {
+        if let CallEvent::Log(log) = event {
+            logs.push(log);
+        }
+    }
+    call.result().await.unwrap().assert_text("done");
+    assert!(logs.is_empty(), "got {logs:?}");
+}
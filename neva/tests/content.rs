@@ -0,0 +1,75 @@
+//! Content builders end to end through `neva::testing`: files by media type,
+//! tables, and links to and embeds of the app's own resources.
+
+#![cfg(all(not(feature = "legacy-spec"), feature = "testing"))]
+
+use neva::prelude::*;
+use neva::testing::{CallToolResponseExt, TestClient};
+use serde_json::json;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn it_builds_content_from_files_tables_and_resources() {
+    let dir = std::env::temp_dir().join(format!("neva-content-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("dot.png"), b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR").unwrap();
+    std::fs::write(dir.join("notes.md"), "# Notes").unwrap();
+
+    let mut app = App::new();
+    app.add_resource("docs://guide", "guide").mime = Some("text/markdown".into());
+    app.map_resource(
+        "docs://{name}",
+        "doc",
+        |uri: Uri, name: String| async move { TextResourceContents::new(uri, format!("# {name}")) },
+    );
+    app.map_tool("file", |path: String| async move {
+        Content::from_path(path).await
+    })
+    .with_arg_names(["path"]);
+    app.map_tool("cities", || async {
+        Table::new(["city", "temp"]).with_row([json!("London"), json!(12.5)])
+    });
+    app.map_tool("docs", |ctx: Context| async move {
+        let mut content = vec![ctx.resource_link("docs://guide").await?];
+        content.extend(ctx.embed_resource("docs://guide").await?);
+        Ok::<_, Error>(content)
+    });
+    let mut client = TestClient::new(app);
+    client.connect().await.unwrap();
+
+    let path = |name: &str| dir.join(name).display().to_string();
+    let image = client
+        .call_tool("file", [("path", path("dot.png"))])
+        .await
+        .unwrap();
+    assert_eq!(image.content[0].as_image().unwrap().mime, "image/png");
+    let notes = client
+        .call_tool("file", [("path", path("notes.md"))])
+        .await
+        .unwrap();
+    let notes = &notes.content[0].as_resource().unwrap().resource;
+    assert_eq!(notes.mime(), Some("text/markdown"));
+    assert_eq!(notes.text(), Some("# Notes"));
+
+    client
+        .call_tool("cities", ())
+        .await
+        .unwrap()
+        .assert_text("| city | temp |\n| --- | --- |\n| London | 12.5 |\n")
+        .assert_structured(json!({
+            "columns": ["city", "temp"],
+            "rows": [{ "city": "London", "temp": 12.5 }]
+        }));
+
+    let docs = client.call_tool("docs", ()).await.unwrap();
+    docs.assert_success();
+    let link = docs.content[0].as_link().unwrap();
+    assert_eq!(
+        (link.name.as_str(), link.mime.as_deref()),
+        ("guide", Some("text/markdown"))
+    );
+    let embedded = &docs.content[1].as_resource().unwrap().resource;
+    assert_eq!(embedded.text(), Some("# guide"));
+
+    client.shutdown().await;
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,137 @@
+//! A directory served as resources, end to end through `neva::testing`.
+//!
+//! Files are listed and read like any other resource, and a change on disk
+//! reaches a running server -- and a client listening for it -- without a
+//! restart.
+
+#![cfg(all(
+    not(feature = "legacy-spec"),
+    feature = "testing",
+    feature = "directory"
+))]
+
+use neva::prelude::*;
+use neva::testing::TestClient;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn a_served_directory_follows_the_disk() {
+    let dir = TempDir::new();
+    dir.write("notes.md", "# v1");
+    dir.write("assets/logo.png", [0x89, b'P', b'N', b'G']);
+    dir.write("target/build.log", "ignored");
+
+    let app = App::new().with_directory(&dir.0, |dir| {
+        dir.with_exclude("target")
+            .with_poll_interval(Duration::from_millis(20))
+    });
+    let mut test = TestClient::new(app);
+    test.connect().await.expect("connect");
+
+    let mut uris = listed(&mut test).await;
+    uris.sort();
+    assert_eq!(uris, ["file://assets/logo.png", "file://notes.md"]);
+
+    let notes = test.read_resource("file://notes.md").await.unwrap();
+    assert_eq!(notes.contents[0].text(), Some("# v1"));
+    let logo = test.read_resource("file://assets/logo.png").await.unwrap();
+    assert_eq!(logo.contents[0].blob(), Some(&[0x89, b'P', b'N', b'G'][..]));
+    assert!(test.read_resource("file://../escape").await.is_err());
+
+    let list_changed = Arc::new(AtomicUsize::new(0));
+    let updated = Arc::new(AtomicUsize::new(0));
+    let client = test.client_mut();
+    client.on_resources_changed({
+        let list_changed = list_changed.clone();
+        move |_| {
+            let list_changed = list_changed.clone();
+            async move {
+                list_changed.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+    });
+    client.on_resource_changed({
+        let updated = updated.clone();
+        move |_| {
+            let updated = updated.clone();
+            async move {
+                updated.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+    });
+    let _subscription = client
+        .listen(
+            SubscriptionFilter::new()
+                .with_resources_changed()
+                .with_resource("file://notes.md"),
+        )
+        .await
+        .expect("listen");
+
+    dir.write("notes.md", "# v2, longer");
+    eventually(|| updated.load(Ordering::SeqCst) > 0).await;
+    let notes = test.read_resource("file://notes.md").await.unwrap();
+    assert_eq!(notes.contents[0].text(), Some("# v2, longer"));
+
+    dir.write("guide/new.md", "new");
+    std::fs::remove_file(dir.0.join("assets/logo.png")).unwrap();
+    eventually(|| list_changed.load(Ordering::SeqCst) > 0).await;
+    // Both changes may take more than one rescan to land.
+    let mut uris = Vec::new();
+    for _ in 0..100 {
+        uris = listed(&mut test).await;
+        uris.sort();
+        if uris == ["file://guide/new.md", "file://notes.md"] {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(uris, ["file://guide/new.md", "file://notes.md"]);
+
+    test.shutdown().await;
+}
+
+async fn listed(test: &mut TestClient) -> Vec<String> {
+    test.list_resources()
+        .await
+        .expect("resources/list")
+        .resources
+        .into_iter()
+        .map(|resource| resource.uri.to_string())
+        .collect()
+}
+
+async fn eventually(done: impl Fn() -> bool) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while !done() {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .expect("the change was not picked up in time");
+}
+
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new() -> Self {
+        let dir = std::env::temp_dir().join(format!("neva-directory-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        Self(dir)
+    }
+
+    fn write(&self, name: &str, contents: impl AsRef<[u8]>) {
+        let path = self.0.join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
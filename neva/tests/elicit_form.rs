@@ -0,0 +1,176 @@
+//! `#[derive(ElicitForm)]` end to end: the schema a struct publishes, and the
+//! submitted form read back as that struct.
+
+#![cfg(feature = "macros")]
+
+use neva::ElicitForm;
+use neva::types::{ElicitRequestParams, ElicitResult};
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Debug, PartialEq, Deserialize, ElicitForm)]
+#[serde(rename_all = "kebab-case")]
+enum Room {
+    #[elicit(title = "Single room")]
+    Single,
+    #[elicit(title = "Double room")]
+    Double,
+    FamilySuite,
+}
+
+#[derive(Debug, PartialEq, Deserialize, ElicitForm)]
+#[serde(rename_all = "lowercase")]
+enum Extra {
+    Breakfast,
+    Parking,
+}
+
+#[derive(Debug, Deserialize, ElicitForm)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+struct Booking {
+    #[elicit(
+        title = "Email",
+        descr = "Where the confirmation goes",
+        format = "email"
+    )]
+    email: String,
+    #[elicit(min_length = 2, max_length = 64)]
+    guest_name: String,
+    #[elicit(min = 1, max = 14, default = 2)]
+    nights: u32,
+    rating: u8,
+    #[elicit(min = -5.5)]
+    budget: Option<f64>,
+    #[elicit(default = true)]
+    newsletter: bool,
+    #[elicit(default = "double")]
+    room: Room,
+    #[elicit(min_items = 1, default = ["breakfast"])]
+    extras: Vec<Extra>,
+    #[serde(rename = "check_in")]
+    #[elicit(format = "date")]
+    check_in_date: String,
+    #[serde(default)]
+    notes: String,
+    #[serde(skip)]
+    internal: Vec<u8>,
+}
+
+fn schema() -> serde_json::Value {
+    let params = ElicitRequestParams::form("Book a room").with_form::<Booking>();
+    serde_json::to_value(&params.schema).expect("a serializable schema")
+}
+
+#[test]
+fn it_maps_fields_onto_primitive_schemas() {
+    let schema = schema();
+    let props = &schema["properties"];
+
+    assert_eq!(
+        props["email"],
+        json!({
+            "type": "string",
+            "title": "Email",
+            "description": "Where the confirmation goes",
+            "format": "email"
+        })
+    );
+    assert_eq!(
+        props["guestName"],
+        json!({ "type": "string", "minLength": 2, "maxLength": 64 })
+    );
+    assert_eq!(
+        props["nights"],
+        json!({ "type": "integer", "minimum": 1.0, "maximum": 14.0, "default": 2 })
+    );
+    assert_eq!(
+        props["rating"],
+        json!({ "type": "integer", "minimum": 0.0, "maximum": 255.0 })
+    );
+    assert_eq!(
+        props["budget"],
+        json!({ "type": "number", "minimum": -5.5 })
+    );
+    assert_eq!(
+        props["newsletter"],
+        json!({ "type": "boolean", "default": true })
+    );
+    assert_eq!(props["check_in"]["format"], "date");
+    assert!(props.get("internal").is_none(), "got: {schema}");
+}
+
+#[test]
+fn it_maps_enums_onto_selects() {
+    let schema = schema();
+    let props = &schema["properties"];
+
+    assert_eq!(
+        props["room"],
+        json!({
+            "type": "string",
+            "oneOf": [
+                { "const": "single", "title": "Single room" },
+                { "const": "double", "title": "Double room" },
+                { "const": "family-suite", "title": "family-suite" }
+            ],
+            "default": "double"
+        })
+    );
+    assert_eq!(
+        props["extras"],
+        json!({
+            "type": "array",
+            "items": { "type": "string", "enum": ["breakfast", "parking"] },
+            "minItems": 1,
+            "default": ["breakfast"]
+        })
+    );
+}
+
+#[test]
+fn optional_and_defaulted_fields_are_not_required() {
+    let schema = schema();
+    let mut required: Vec<_> = schema["required"]
+        .as_array()
+        .expect("required fields")
+        .iter()
+        .map(|v| v.as_str().unwrap())
+        .collect();
+    required.sort_unstable();
+
+    assert_eq!(
+        required,
+        [
+            "check_in",
+            "email",
+            "extras",
+            "guestName",
+            "newsletter",
+            "nights",
+            "rating",
+            "room"
+        ]
+    );
+}
+
+#[test]
+fn a_submitted_form_reads_back_as_the_struct() {
+    let result = ElicitResult::accept().with_content(json!({
+        "email": "guest@example.com",
+        "guestName": "Ada",
+        "nights": 3,
+        "rating": 5,
+        "newsletter": false,
+        "room": "family-suite",
+        "extras": ["breakfast", "parking"],
+        "check_in": "2026-11-02"
+    }));
+
+    let booking: Booking = result.content().expect("a submitted booking");
+    assert_eq!(booking.guest_name, "Ada");
+    assert_eq!(booking.room, Room::FamilySuite);
+    assert_eq!(booking.extras, [Extra::Breakfast, Extra::Parking]);
+    assert_eq!(booking.budget, None);
+    assert_eq!(booking.notes, "");
+}
@@ -0,0 +1,37 @@
+//! Typed JSON-RPC errors end to end through `neva::testing`: a failed call
+//! comes back as the `ErrorKind` the server raised, with its data.
+
+#![cfg(all(not(feature = "legacy-spec"), feature = "testing"))]
+
+use neva::prelude::*;
+use neva::testing::TestClient;
+use serde_json::json;
+
+fn app() -> App {
+    let mut app = App::new();
+    app.map_tool("add", |a: i64, b: i64| async move { a + b })
+        .with_arg_names(["a", "b"]);
+    app
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn it_surfaces_protocol_errors_by_kind() {
+    let mut client = TestClient::new(app());
+    client.connect().await.unwrap();
+
+    let err = client
+        .call_tool("add", json!({ "a": "two", "b": 3 }))
+        .await
+        .unwrap_err();
+    let ErrorKind::InvalidParams(fields) = err.kind() else {
+        panic!("unexpected {:?}", err.kind());
+    };
+    assert_eq!(fields[0].pointer, "/a");
+
+    let err = client.read_resource("missing://here").await.unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ErrorKind::ResourceNotFound(Some(uri)) if uri == "missing://here"
+    ));
+    client.shutdown().await;
+}
@@ -0,0 +1,340 @@
+//! Graceful shutdown end-to-end over the 2026-07-28 HTTP transport.
+//!
+//! Shutdown refuses new requests from the moment it is requested -- save MRTR
+//! continuations and task polls, which carry on earlier work -- lets the ones
+//! already running finish inside the drain window, fails the tasks that outlive
+//! it, and reports all of that in a `ShutdownSummary`.
+#![cfg(all(
+    not(feature = "legacy-spec"),
+    feature = "tasks",
+    feature = "http-server-volga",
+    feature = "http-client"
+))]
+
+use neva::{App, Context, error::Error, types::elicitation::ElicitRequestParams};
+use std::time::Duration;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn in_flight_requests_finish_and_new_ones_are_refused() {
+    let addr = format!("127.0.0.1:{}", pick_free_port());
+    let mut app = App::new()
+        .with_options(|opt| opt.with_http(|http| http.bind(&addr).with_endpoint("/mcp")))
+        .with_shutdown_drain(Duration::from_secs(10));
+    app.map_tool("slow", || async {
+        tokio::time::sleep(Duration::from_millis(600)).await;
+        "done".to_string()
+    });
+    app.map_tool("ping", || async { "pong".to_string() });
+    let (app, shutdown) = app.with_shutdown();
+
+    let handle = tokio::spawn(async move { app.run().await });
+    await_reachable(&addr).await;
+
+    let client = reqwest::Client::builder()
+        .no_proxy()
+        .build()
+        .expect("test client");
+    let url = format!("http://{addr}/mcp");
+
+    let slow = tokio::spawn(post(client.clone(), url.clone(), call("slow", 1)));
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    shutdown.shutdown();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    // Arrived after the request: refused, not started.
+    let refused = post(client.clone(), url.clone(), call("ping", 2)).await;
+    let message = refused["error"]["message"].as_str().unwrap_or_default();
+    assert!(
+        message.contains("shutting down"),
+        "a request arriving during the drain must be refused, got: {refused}"
+    );
+
+    // Arrived before it: allowed to finish.
+    let slow = slow.await.expect("the slow call panicked");
+    assert_eq!(
+        slow["result"]["content"][0]["text"], "done",
+        "an in-flight request must finish inside the drain, got: {slow}"
+    );
+
+    let summary = tokio::time::timeout(Duration::from_secs(10), shutdown.summary())
+        .await
+        .expect("the summary must be published once the drain is over");
+    assert_eq!(summary.rejected_requests, 1);
+    assert_eq!(summary.abandoned_requests, 0);
+    assert!(summary.is_clean(), "nothing was left behind: {summary:?}");
+
+    tokio::time::timeout(Duration::from_secs(10), handle)
+        .await
+        .expect("the server must stop once the drain is over")
+        .expect("the server task panicked");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn tasks_outliving_the_drain_are_failed() {
+    let addr = format!("127.0.0.1:{}", pick_free_port());
+    let mut app = App::new()
+        .with_options(|opt| {
+            opt.with_http(|http| http.bind(&addr).with_endpoint("/mcp"))
+                .with_tasks()
+        })
+        .with_shutdown_drain(Duration::from_millis(200));
+    app.map_tool("forever", || async {
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        "unreachable".to_string()
+    })
+    .with_task_support("optional");
+    let (app, shutdown) = app.with_shutdown();
+
+    let handle = tokio::spawn(async move { app.run().await });
+    await_reachable(&addr).await;
+
+    let client = reqwest::Client::builder()
+        .no_proxy()
+        .build()
+        .expect("test client");
+    let url = format!("http://{addr}/mcp");
+
+    let mut request = call("forever", 1);
+    request["params"]["task"] = serde_json::json!({ "ttl": 60000 });
+    let created = post(client, url, request).await;
+    let task_id = created["result"]["taskId"]
+        .as_str()
+        .unwrap_or_else(|| panic!("task id present, got: {created}"))
+        .to_string();
+
+    shutdown.shutdown();
+
+    let summary = tokio::time::timeout(Duration::from_secs(10), shutdown.summary())
+        .await
+        .expect("the summary must be published once the drain is over");
+    assert_eq!(
+        summary.abandoned_tasks,
+        vec![task_id],
+        "a task still running when the drain ran out must be reported"
+    );
+    assert!(!summary.is_clean());
+    assert!(
+        summary.elapsed >= Duration::from_millis(200),
+        "a running task must hold the drain open until it runs out"
+    );
+
+    tokio::time::timeout(Duration::from_secs(10), handle)
+        .await
+        .expect("the server must stop once the drain is over")
+        .expect("the server task panicked");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn mrtr_continuations_are_served_while_draining() {
+    let addr = format!("127.0.0.1:{}", pick_free_port());
+    let mut app = App::new()
+        .with_request_state_secret(b"test-secret")
+        .with_options(|opt| opt.with_http(|http| http.bind(&addr).with_endpoint("/mcp")))
+        .with_shutdown_drain(Duration::from_secs(10));
+    app.map_tool("slow", || async {
+        tokio::time::sleep(Duration::from_millis(800)).await;
+        "done".to_string()
+    });
+    app.map_tool("greet", |mut ctx: Context| async move {
+        let params: ElicitRequestParams = ElicitRequestParams::form("Your name?")
+            .with_required("name", "string")
+            .into();
+        let res = ctx.elicit("name", params).await?;
+        let name = res
+            .content
+            .and_then(|c| c.get("name").and_then(|v| v.as_str().map(str::to_owned)))
+            .unwrap_or_else(|| "stranger".into());
+        Ok::<String, Error>(format!("hello {name}"))
+    });
+    let (app, shutdown) = app.with_shutdown();
+
+    let handle = tokio::spawn(async move { app.run().await });
+    await_reachable(&addr).await;
+
+    let client = reqwest::Client::builder()
+        .no_proxy()
+        .build()
+        .expect("test client");
+    let url = format!("http://{addr}/mcp");
+
+    // Holds the drain open while the continuation arrives.
+    let slow = tokio::spawn(post(client.clone(), url.clone(), call("slow", 1)));
+
+    let mut first = call("greet", 2);
+    first["params"]["_meta"] = eliciting_meta();
+    let first = post(client.clone(), url.clone(), first).await;
+    let state = first["result"]["requestState"]
+        .as_str()
+        .unwrap_or_else(|| panic!("requestState present, got: {first}"))
+        .to_string();
+    let key = first["result"]["inputRequests"]
+        .as_object()
+        .and_then(|requests| requests.keys().next())
+        .unwrap_or_else(|| panic!("one input request, got: {first}"))
+        .clone();
+
+    shutdown.shutdown();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    // A fresh round would start new work: refused.
+    let mut fresh = call("greet", 3);
+    fresh["params"]["_meta"] = eliciting_meta();
+    let fresh = post(client.clone(), url.clone(), fresh).await;
+    let message = fresh["error"]["message"].as_str().unwrap_or_default();
+    assert!(
+        message.contains("shutting down"),
+        "a new call arriving during the drain must be refused, got: {fresh}"
+    );
+
+    // The continuation finishes what the first round began: served.
+    let mut retry = call("greet", 4);
+    retry["params"]["requestState"] = state.into();
+    retry["params"]["inputResponses"] = serde_json::json!({
+        key: { "action": "accept", "content": { "name": "octocat" } }
+    });
+    retry["params"]["_meta"] = eliciting_meta();
+    let retry = post(client.clone(), url.clone(), retry).await;
+    assert_eq!(
+        retry["result"]["content"][0]["text"], "hello octocat",
+        "an MRTR continuation must be served during the drain, got: {retry}"
+    );
+
+    slow.await.expect("the slow call panicked");
+    let summary = tokio::time::timeout(Duration::from_secs(10), shutdown.summary())
+        .await
+        .expect("the summary must be published once the drain is over");
+    assert_eq!(summary.rejected_requests, 1);
+
+    tokio::time::timeout(Duration::from_secs(10), handle)
+        .await
+        .expect("the server must stop once the drain is over")
+        .expect("the server task panicked");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn task_polls_are_served_while_draining() {
+    let addr = format!("127.0.0.1:{}", pick_free_port());
+    let mut app = App::new()
+        .with_options(|opt| {
+            opt.with_http(|http| http.bind(&addr).with_endpoint("/mcp"))
+                .with_tasks()
+        })
+        .with_shutdown_drain(Duration::from_millis(500));
+    app.map_tool("forever", || async {
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        "unreachable".to_string()
+    })
+    .with_task_support("optional");
+    let (app, shutdown) = app.with_shutdown();
+
+    let handle = tokio::spawn(async move { app.run().await });
+    await_reachable(&addr).await;
+
+    let client = reqwest::Client::builder()
+        .no_proxy()
+        .build()
+        .expect("test client");
+    let url = format!("http://{addr}/mcp");
+
+    let mut request = call("forever", 1);
+    request["params"]["task"] = serde_json::json!({ "ttl": 60000 });
+    let created = post(client.clone(), url.clone(), request).await;
+    let task_id = created["result"]["taskId"]
+        .as_str()
+        .unwrap_or_else(|| panic!("task id present, got: {created}"))
+        .to_string();
+
+    shutdown.shutdown();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let poll = serde_json::json!({
+        "jsonrpc": "2.0", "id": 2, "method": "tasks/get",
+        "params": { "taskId": task_id, "_meta": meta() }
+    });
+    let poll = post(client, url, poll).await;
+    assert_eq!(
+        poll["result"]["status"], "working",
+        "a task poll must be served during the drain, got: {poll}"
+    );
+
+    let summary = tokio::time::timeout(Duration::from_secs(10), shutdown.summary())
+        .await
+        .expect("the summary must be published once the drain is over");
+    assert_eq!(summary.rejected_requests, 0);
+
+    tokio::time::timeout(Duration::from_secs(10), handle)
+        .await
+        .expect("the server must stop once the drain is over")
+        .expect("the server task panicked");
+}
+
+fn call(tool: &str, id: i64) -> serde_json::Value {
+    serde_json::json!({
+        "jsonrpc": "2.0", "id": id, "method": "tools/call",
+        "params": { "name": tool, "arguments": {}, "_meta": meta() }
+    })
+}
+
+async fn post(client: reqwest::Client, url: String, body: serde_json::Value) -> serde_json::Value {
+    routed(client.post(&url), &body)
+        .json(&body)
+        .send()
+        .await
+        .expect("send")
+        .json::<serde_json::Value>()
+        .await
+        .expect("json")
+}
+
+async fn await_reachable(addr: &str) {
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+    loop {
+        match tokio::net::TcpStream::connect(addr).await {
+            Ok(_) => break,
+            Err(_) if tokio::time::Instant::now() < deadline => {
+                tokio::time::sleep(Duration::from_millis(50)).await
+            }
+            Err(err) => panic!("server never became reachable: {err}"),
+        }
+    }
+}
+
+fn pick_free_port() -> u16 {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    drop(listener);
+    port
+}
+
+/// The `_meta` MCP 2026-07-28 requires on every request.
+fn meta() -> serde_json::Value {
+    serde_json::json!({
+        "io.modelcontextprotocol/protocolVersion": "2026-07-28",
+        "io.modelcontextprotocol/clientCapabilities": {}
+    })
+}
+
+/// The same, declaring the elicitation capability an MRTR round needs.
+fn eliciting_meta() -> serde_json::Value {
+    let mut meta = meta();
+    meta["io.modelcontextprotocol/clientCapabilities"] =
+        serde_json::json!({ "elicitation": { "form": {} } });
+    meta
+}
+
+/// Attaches the routing headers MCP 2026-07-28 requires on every request.
+fn routed(req: reqwest::RequestBuilder, body: &serde_json::Value) -> reqwest::RequestBuilder {
+    let req = req.header("MCP-Protocol-Version", "2026-07-28");
+    let Some(method) = body["method"].as_str() else {
+        return req;
+    };
+    let req = req.header("Mcp-Method", method);
+    match method {
+        "tools/call" => match body.pointer("/params/name").and_then(|v| v.as_str()) {
+            Some(name) => req.header("Mcp-Name", name),
+            None => req,
+        },
+        _ => req,
+    }
+}
@@ -0,0 +1,138 @@
+//! Health probes and the admin endpoint against a running
+//! `HttpServer<DefaultClaims, VolgaEngine>` bound to an ephemeral port.
+
+#![cfg(all(feature = "http-server-volga", feature = "http-client"))]
+
+use neva::App;
+use std::time::Duration;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn probes_and_admin_report_a_running_server() {
+    let port = pick_free_port();
+    let addr = format!("127.0.0.1:{port}");
+
+    let (mut app, shutdown) = App::new()
+        .with_options(|opt| {
+            opt.with_name("probed").with_http(|http| {
+                http.bind(&addr)
+                    .with_health_probes(|probes| probes)
+                    .with_admin(|admin| admin.with_token("t0ken"))
+            })
+        })
+        .with_shutdown();
+    app.map_tool("ping", || async move { "pong".to_string() });
+
+    let handle = tokio::spawn(app.run());
+
+    let client = reqwest::Client::builder()
+        .no_proxy()
+        .build()
+        .expect("test client");
+    let base = format!("http://{addr}");
+
+    let mut ready = false;
+    for _ in 0..50 {
+        if let Ok(resp) = client.get(format!("{base}/readyz")).send().await
+            && resp.status().is_success()
+        {
+            ready = true;
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    assert!(ready, "the server never reported ready");
+
+    let resp = client.get(format!("{base}/healthz")).send().await.unwrap();
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+
+    let resp = client.get(format!("{base}/admin")).send().await.unwrap();
+    assert_eq!(resp.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    let resp = client
+        .get(format!("{base}/admin"))
+        .bearer_auth("t0ken")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    let report: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(report["build"]["name"], "probed");
+    assert_eq!(report["tools"], serde_json::json!(["ping"]));
+    assert_eq!(report["ready"], true);
+    assert!(report["sseSessions"].is_array());
+
+    shutdown.shutdown();
+    tokio::time::timeout(Duration::from_secs(5), handle)
+        .await
+        .expect("the server did not stop")
+        .expect("the server task panicked");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn probes_are_off_unless_enabled() {
+    let port = pick_free_port();
+    let addr = format!("127.0.0.1:{port}");
+
+    let (app, shutdown) = App::new()
+        .with_options(|opt| {
+            opt.with_http(|http| {
+                http.bind(&addr)
+                    .with_admin(|admin| admin.with_token("t0ken"))
+            })
+        })
+        .with_shutdown();
+    let handle = tokio::spawn(app.run());
+
+    let client = reqwest::Client::builder()
+        .no_proxy()
+        .build()
+        .expect("test client");
+    let base = format!("http://{addr}");
+
+    let mut up = false;
+    for _ in 0..50 {
+        if let Ok(resp) = client
+            .get(format!("{base}/admin"))
+            .bearer_auth("t0ken")
+            .send()
+            .await
+            && resp.status().is_success()
+        {
+            up = true;
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    assert!(up, "the server never came up");
+
+    for path in ["/healthz", "/readyz"] {
+        let resp = client.get(format!("{base}{path}")).send().await.unwrap();
+        assert_eq!(resp.status(), reqwest::StatusCode::NOT_FOUND, "{path}");
+    }
+
+    shutdown.shutdown();
+    tokio::time::timeout(Duration::from_secs(5), handle)
+        .await
+        .expect("the server did not stop")
+        .expect("the server task panicked");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn an_admin_endpoint_without_a_token_fails_the_start() {
+    let port = pick_free_port();
+    let addr = format!("127.0.0.1:{port}");
+
+    let app =
+        App::new().with_options(|opt| opt.with_http(|http| http.bind(&addr).with_admin(|a| a)));
+
+    tokio::time::timeout(Duration::from_secs(5), app.run())
+        .await
+        .expect("a misconfigured server must not keep running");
+}
+
+fn pick_free_port() -> u16 {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    drop(listener);
+    port
+}
@@ -0,0 +1,262 @@
+//! A manifest directory end-to-end over the 2026-07-28 HTTP transport.
+//!
+//! What the manifest defines is served like anything registered in code, and
+//! an edit to it reaches a running server -- and its subscribers -- without a
+//! restart.
+#![cfg(all(
+    not(feature = "legacy-spec"),
+    feature = "manifest",
+    feature = "http-server-volga",
+    feature = "http-client"
+))]
+
+use neva::App;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const MOTD: &str = "docs://motd";
+
+#[tokio::test(flavor = "multi_thread")]
+async fn editing_the_manifest_updates_a_running_server() {
+    let dir = TempDir::new();
+    write_manifest(&dir.0, "Hello, {{name}}!", "v1", false);
+
+    let addr = format!("127.0.0.1:{}", pick_free_port());
+    let app = App::new()
+        .with_options(|opt| {
+            opt.with_http(|http| http.bind(&addr).with_endpoint("/mcp"))
+                .with_resources(|res| res.with_subscribe())
+        })
+        .with_manifest(|manifest| {
+            manifest
+                .with_dir(&dir.0)
+                .with_poll_interval(Duration::from_millis(50))
+        });
+    let handle = tokio::spawn(async move { app.run().await });
+    await_reachable(&addr).await;
+
+    let client = reqwest::Client::builder()
+        .no_proxy()
+        .build()
+        .expect("test client");
+    let url = format!("http://{addr}/mcp");
+
+    assert_eq!(greet(&client, &url).await, "Hello, Ada!");
+    assert_eq!(read_motd(&client, &url).await, "v1");
+
+    let listen = serde_json::json!({
+        "jsonrpc": "2.0", "id": "sub-1", "method": "subscriptions/listen",
+        "params": {
+            "notifications": {
+                "promptsListChanged": true,
+                "resourceSubscriptions": [MOTD]
+            },
+            "_meta": meta()
+        }
+    });
+    let mut stream = routed(client.post(&url), &listen)
+        .header("Accept", "application/json, text/event-stream")
+        .json(&listen)
+        .send()
+        .await
+        .expect("listen failed");
+    let mut body = String::new();
+    let ack = next_message(&mut stream, &mut body).await;
+    assert_eq!(ack["method"], "notifications/subscriptions/acknowledged");
+    assert_eq!(ack["params"]["notifications"]["promptsListChanged"], true);
+
+    write_manifest(&dir.0, "Hi, {{name}}!", "v2", true);
+
+    // One `list_changed` per prompt that changed -- `greet` was edited and
+    // `farewell` added -- and an update for the resource whose text did.
+    let mut methods = Vec::new();
+    for _ in 0..3 {
+        let message = next_message(&mut stream, &mut body).await;
+        methods.push(message["method"].as_str().unwrap_or_default().to_owned());
+    }
+    methods.sort();
+    assert_eq!(
+        methods,
+        [
+            "notifications/prompts/list_changed",
+            "notifications/prompts/list_changed",
+            "notifications/resources/updated"
+        ]
+    );
+
+    assert_eq!(greet(&client, &url).await, "Hi, Ada!");
+    assert_eq!(read_motd(&client, &url).await, "v2");
+    let list = post(
+        &client,
+        &url,
+        serde_json::json!({
+            "jsonrpc": "2.0", "id": 4, "method": "prompts/list",
+            "params": { "_meta": meta() }
+        }),
+    )
+    .await;
+    let names: Vec<_> = list["result"]["prompts"]
+        .as_array()
+        .expect("a prompt list")
+        .iter()
+        .map(|prompt| prompt["name"].as_str().unwrap_or_default().to_owned())
+        .collect();
+    assert_eq!(names, ["farewell", "greet"]);
+
+    drop(stream);
+    handle.abort();
+}
+
+/// Writes the whole manifest: one greeting prompt, a farewell prompt when
+/// asked, and the message of the day.
+fn write_manifest(dir: &Path, greeting: &str, motd: &str, farewell: bool) {
+    let mut prompts = vec![serde_json::json!({
+        "name": "greet",
+        "arguments": [{ "name": "name", "required": true }],
+        "messages": [{ "text": greeting }]
+    })];
+    if farewell {
+        prompts.push(serde_json::json!({
+            "name": "farewell",
+            "messages": [{ "text": "Bye!" }]
+        }));
+    }
+    let manifest = serde_json::json!({
+        "prompts": prompts,
+        "resources": [{ "uri": MOTD, "name": "motd", "text": motd }]
+    });
+    std::fs::write(dir.join("manifest.json"), manifest.to_string()).unwrap();
+}
+
+async fn greet(client: &reqwest::Client, url: &str) -> String {
+    let reply = post(
+        client,
+        url,
+        serde_json::json!({
+            "jsonrpc": "2.0", "id": 1, "method": "prompts/get",
+            "params": { "name": "greet", "arguments": { "name": "Ada" }, "_meta": meta() }
+        }),
+    )
+    .await;
+    reply["result"]["messages"][0]["content"]["text"]
+        .as_str()
+        .unwrap_or_else(|| panic!("a rendered prompt, got: {reply}"))
+        .to_owned()
+}
+
+async fn read_motd(client: &reqwest::Client, url: &str) -> String {
+    let reply = post(
+        client,
+        url,
+        serde_json::json!({
+            "jsonrpc": "2.0", "id": 2, "method": "resources/read",
+            "params": { "uri": MOTD, "_meta": meta() }
+        }),
+    )
+    .await;
+    reply["result"]["contents"][0]["text"]
+        .as_str()
+        .unwrap_or_else(|| panic!("the resource's text, got: {reply}"))
+        .to_owned()
+}
+
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new() -> Self {
+        let dir = std::env::temp_dir().join(format!(
+            "neva-manifest-{}-{}",
+            std::process::id(),
+            pick_free_port()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        Self(dir)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+async fn post(client: &reqwest::Client, url: &str, body: serde_json::Value) -> serde_json::Value {
+    routed(client.post(url), &body)
+        .json(&body)
+        .send()
+        .await
+        .expect("send")
+        .json::<serde_json::Value>()
+        .await
+        .expect("json")
+}
+
+/// Pulls chunks off a live SSE body until one more complete `data:` frame is
+/// available, and returns it parsed.
+async fn next_message(resp: &mut reqwest::Response, body: &mut String) -> serde_json::Value {
+    loop {
+        if let Some(end) = body.find("\n\n") {
+            let frame: String = body.drain(..end + 2).collect();
+            if let Some(msg) = frame
+                .lines()
+                .find_map(|line| line.strip_prefix("data:"))
+                .and_then(|data| serde_json::from_str(data.trim()).ok())
+            {
+                return msg;
+            }
+            continue;
+        }
+        let chunk = tokio::time::timeout(Duration::from_secs(5), resp.chunk())
+            .await
+            .expect("timed out waiting for the next subscription message")
+            .expect("stream error")
+            .expect("stream ended before the expected message");
+        body.push_str(&String::from_utf8_lossy(&chunk));
+    }
+}
+
+async fn await_reachable(addr: &str) {
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+    loop {
+        match tokio::net::TcpStream::connect(addr).await {
+            Ok(_) => break,
+            Err(_) if tokio::time::Instant::now() < deadline => {
+                tokio::time::sleep(Duration::from_millis(50)).await
+            }
+            Err(err) => panic!("server never became reachable: {err}"),
+        }
+    }
+}
+
+fn pick_free_port() -> u16 {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    drop(listener);
+    port
+}
+
+/// The `_meta` MCP 2026-07-28 requires on every request.
+fn meta() -> serde_json::Value {
+    serde_json::json!({
+        "io.modelcontextprotocol/protocolVersion": "2026-07-28",
+        "io.modelcontextprotocol/clientCapabilities": {}
+    })
+}
+
+/// Attaches the routing headers MCP 2026-07-28 requires on every request.
+fn routed(req: reqwest::RequestBuilder, body: &serde_json::Value) -> reqwest::RequestBuilder {
+    let req = req.header("MCP-Protocol-Version", "2026-07-28");
+    let Some(method) = body["method"].as_str() else {
+        return req;
+    };
+    let req = req.header("Mcp-Method", method);
+    let name = match method {
+        "tools/call" | "prompts/get" => body.pointer("/params/name"),
+        "resources/read" => body.pointer("/params/uri"),
+        _ => None,
+    };
+    match name.and_then(|v| v.as_str()) {
+        Some(name) => req.header("Mcp-Name", name),
+        None => req,
+    }
+}
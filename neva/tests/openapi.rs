@@ -0,0 +1,175 @@
+//! Tools generated from an OpenAPI document, end to end through
+//! `neva::testing` against a stub of the API they call.
+//!
+//! A call becomes one HTTP request with the arguments in their places -- path,
+//! query, header and body -- and the caller's claims turned into headers; a
+//! failing status comes back as a tool error.
+
+#![cfg(all(not(feature = "legacy-spec"), feature = "testing", feature = "openapi"))]
+
+use neva::app::openapi::OpenApi;
+use neva::auth::DefaultClaims;
+use neva::prelude::*;
+use neva::testing::{CallToolResponseExt, TestClient};
+use serde_json::json;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+
+const SPEC: &str = r##"{
+    "openapi": "3.1.0",
+    "paths": {
+        "/pets/{petId}": {
+            "get": {
+                "operationId": "getPet",
+                "parameters": [
+                    { "name": "petId", "in": "path", "required": true, "schema": { "type": "string" } },
+                    { "name": "fields", "in": "query", "schema": { "type": "array", "items": { "type": "string" } } }
+                ],
+                "responses": {
+                    "200": { "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } } } },
+                    "404": { "description": "no such pet" }
+                }
+            }
+        },
+        "/pets": {
+            "post": {
+                "operationId": "addPet",
+                "parameters": [{ "name": "x-request-id", "in": "header", "schema": { "type": "string" } }],
+                "requestBody": {
+                    "required": true,
+                    "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } } }
+                },
+                "responses": {
+                    "201": { "content": { "application/json": { "schema": { "type": "integer" } } } }
+                }
+            }
+        }
+    },
+    "components": {
+        "schemas": {
+            "Pet": {
+                "type": "object",
+                "properties": { "name": { "type": "string" } },
+                "required": ["name"]
+            }
+        }
+    }
+}"##;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn a_generated_tool_calls_the_api() {
+    let api = StubApi::start();
+    let app = App::new().with_openapi(OpenApi::from_json(SPEC).unwrap(), |spec| {
+        spec.with_base_url(api.url.clone())
+            .with_auth_from_claims(|claims| {
+                let mut headers = http::HeaderMap::new();
+                if let Some(sub) = claims.subject() {
+                    headers.insert("x-user", sub.parse().unwrap());
+                }
+                headers
+            })
+    });
+    let claims = DefaultClaims {
+        sub: Some("alice".into()),
+        ..Default::default()
+    };
+    let mut client = TestClient::new(app).with_claims(claims);
+    client.connect().await.unwrap();
+
+    let tools = client.list_tools().await.unwrap();
+    let get = tools.get("getPet").unwrap();
+    assert_eq!(get.annotations.as_ref().unwrap().readonly, Some(true));
+    let add = serde_json::to_value(&tools.get("addPet").unwrap().input_schema).unwrap();
+    assert_eq!(add["properties"]["body"]["$ref"], "#/$defs/Pet");
+    assert_eq!(add["$defs"]["Pet"]["required"], json!(["name"]));
+
+    client
+        .call_tool(
+            "getPet",
+            json!({ "petId": "rex 1", "fields": ["name", "age"] }),
+        )
+        .await
+        .unwrap()
+        .assert_structured(json!({ "name": "Rex" }));
+    client
+        .call_tool(
+            "addPet",
+            json!({ "body": { "name": "Tom" }, "x-request-id": "42" }),
+        )
+        .await
+        .unwrap()
+        .assert_structured(json!({ "result": 7 }));
+
+    let requests = api.requests();
+    assert!(requests[0].starts_with("GET /pets/rex%201?fields=name&fields=age "));
+    assert!(requests[0].contains("x-user: alice"));
+    assert!(requests[1].starts_with("POST /pets "));
+    assert!(requests[1].contains("x-request-id: 42"));
+    assert!(requests[1].ends_with(r#"{"name":"Tom"}"#));
+
+    client
+        .call_tool("getPet", [("petId", "missing")])
+        .await
+        .unwrap()
+        .assert_error()
+        .assert_text_contains("`GET /pets/{petId}` returned 404 Not Found");
+}
+
+/// An HTTP/1.1 server answering a fixed set of routes, one request per
+/// connection, and keeping what it was sent.
+struct StubApi {
+    url: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl StubApi {
+    fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let log = requests.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                    if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                        length = value.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                request.push_str(&String::from_utf8(body).unwrap());
+
+                let (status, body) = if request.starts_with("GET /pets/missing") {
+                    ("404 Not Found", "no such pet")
+                } else if request.starts_with("GET /pets/") {
+                    ("200 OK", r#"{"name":"Rex"}"#)
+                } else {
+                    ("201 Created", "7")
+                };
+                log.lock().unwrap().push(request);
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\ncontent-type: application/json\r\n\
+                     content-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+            }
+        });
+        Self { url, requests }
+    }
+
+    fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
@@ -0,0 +1,38 @@
+//! The typed progress reporter on `Context` end to end through
+//! `neva::testing`: nested stages report on one scale, without `tracing`.
+
+#![cfg(all(not(feature = "legacy-spec"), feature = "testing"))]
+
+use neva::prelude::*;
+use neva::testing::{CallToolResponseExt, TestClient};
+
+fn app() -> App {
+    let mut app = App::new();
+    app.map_tool("stages", |ctx: Context| async move {
+        let progress = ctx.progress().with_total(10.0);
+        progress.report_with_message(2.0, "fetched").await;
+        let parse = progress.sub(8.0).with_total(2.0);
+        parse.report(1.0).await;
+        parse.finish().await;
+        "parsed"
+    });
+    app
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn it_captures_progress_reported_through_the_context() {
+    let mut client = TestClient::new(app());
+    client.connect().await.unwrap();
+
+    client
+        .call_tool("stages", ())
+        .await
+        .unwrap()
+        .assert_text("parsed");
+
+    let progress = client.progress();
+    let values: Vec<_> = progress.iter().map(|p| p.progress).collect();
+    assert_eq!(values, [2.0, 6.0, 10.0]);
+    assert_eq!(progress[0].message.as_deref(), Some("fetched"));
+    assert!(progress.iter().all(|p| p.total == Some(10.0)));
+}
@@ -0,0 +1,120 @@
+//! Recording a session over HTTP and replaying it: against a fresh server,
+//! against a server whose answers changed, and as a mock server for a client.
+
+#![cfg(all(
+    not(feature = "legacy-spec"),
+    feature = "http-server-volga",
+    feature = "http-client"
+))]
+
+use neva::prelude::*;
+use neva::transport::record::{Peer, Recorder, Recording};
+use neva::transport::replay::Replay;
+use std::time::Duration;
+
+fn app(greeting: &'static str) -> App {
+    let mut app = App::new();
+    app.map_tool("greet", move |name: String| async move {
+        format!("{greeting}, {name}!")
+    })
+    .with_arg_names(["name"]);
+    app
+}
+
+/// Records a client listing and calling the tools of `app("Hello")`.
+async fn record() -> Recording {
+    let recorder = Recorder::in_memory();
+    let addr = format!("127.0.0.1:{}", pick_free_port());
+    let server = app("Hello").with_options(|opt| {
+        opt.with_http(|http| http.bind(&addr).with_endpoint("/mcp"))
+            .with_recorder(recorder.clone())
+    });
+    let handle = tokio::spawn(server.run());
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    let mut client = Client::new().with_options(|opt| {
+        opt.with_http(|http| http.bind(&addr).with_endpoint("/mcp"))
+            .with_timeout(Duration::from_secs(5))
+    });
+    client.connect().await.expect("connect");
+    client.list_tools(None).await.expect("tools/list");
+    let result = client
+        .call_tool("greet", [("name", "Ada")])
+        .await
+        .expect("tools/call");
+    assert_eq!(text(&result), Some("Hello, Ada!".into()));
+    handle.abort();
+
+    recorder.recording().expect("an in-memory recording")
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn a_recorded_session_replays_against_the_server() {
+    let recording = record().await;
+    let from: Vec<_> = recording.messages().iter().map(|m| m.from).collect();
+    assert!(from.contains(&Peer::Client) && from.contains(&Peer::Server));
+
+    // Through the file format and back.
+    let mut lines = Vec::new();
+    recording.write_to(&mut lines).unwrap();
+    let recording = Recording::parse(std::str::from_utf8(&lines).unwrap()).unwrap();
+
+    let replay = Replay::new(recording).with_timeout(Duration::from_secs(2));
+    replay.run(app("Hello")).await.expect("the same server");
+
+    let err = replay.run(app("Goodbye")).await.unwrap_err();
+    assert!(err.to_string().contains("replay diverged"), "{err}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn a_recorded_session_mocks_the_server_for_a_client() {
+    let replay = Replay::new(record().await).with_timeout(Duration::from_secs(2));
+
+    let mut client = Client::new().with_options(|opt| {
+        opt.with_replay(&replay)
+            .with_timeout(Duration::from_secs(5))
+    });
+    client.connect().await.expect("connect");
+    client.list_tools(None).await.expect("tools/list");
+    let result = client
+        .call_tool("greet", [("name", "Ada")])
+        .await
+        .expect("tools/call");
+    assert_eq!(text(&result), Some("Hello, Ada!".into()));
+
+    replay
+        .finish()
+        .await
+        .expect("the client behaved as recorded");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn a_client_that_strays_from_the_recording_fails_the_replay() {
+    let replay = Replay::new(record().await).with_timeout(Duration::from_millis(500));
+
+    let mut client = Client::new().with_options(|opt| {
+        opt.with_replay(&replay)
+            .with_timeout(Duration::from_secs(1))
+    });
+    client.connect().await.expect("connect");
+    client.list_tools(None).await.expect("tools/list");
+    let _ = client.call_tool("greet", [("name", "Grace")]).await;
+
+    let err = replay.finish().await.unwrap_err();
+    assert!(err.to_string().contains("replay diverged"), "{err}");
+}
+
+fn text(result: &CallToolResponse) -> Option<String> {
+    serde_json::to_value(result)
+        .ok()?
+        .pointer("/content/0/text")?
+        .as_str()
+        .map(str::to_owned)
+}
+
+fn pick_free_port() -> u16 {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    drop(listener);
+    port
+}
@@ -0,0 +1,95 @@
+//! Resource templates listed through their enumerators and completed from
+//! them, end to end through `neva::testing`.
+
+#![cfg(all(not(feature = "legacy-spec"), feature = "testing"))]
+
+use neva::prelude::*;
+use neva::testing::TestClient;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn it_lists_and_completes_the_resources_templates_enumerate() {
+    const DAYS: [&str; 7] = [
+        "monday",
+        "tuesday",
+        "wednesday",
+        "thursday",
+        "friday",
+        "saturday",
+        "sunday",
+    ];
+
+    let mut app = App::new();
+    app.add_resource("notes://today", "today");
+    app.map_resource(
+        "notes://{week}/{day}",
+        "note",
+        |uri: Uri, week: String, day: String| async move {
+            ResourceContents::new(uri).with_text(format!("{day} of week {week}"))
+        },
+    )
+    .with_mime("text/plain")
+    .with_enumerator(|| {
+        (1..=2).flat_map(|week| {
+            DAYS.map(|day| TemplateArgs::from(day).with_arg("week", week.to_string()))
+        })
+    });
+    app.map_resource("users://{id}", "user", |uri: Uri, id: String| async move {
+        ResourceContents::new(uri).with_text(format!("user {id}"))
+    })
+    .with_paged_enumerator(|offset, limit| async move {
+        let ids = (offset..offset + limit).take_while(|id| *id < 3);
+        Ok::<_, Error>(
+            ids.map(|id| TemplateArgs::from(id.to_string()).with_name(format!("user {id}")))
+                .collect::<Vec<_>>(),
+        )
+    });
+    let mut client = TestClient::new(app);
+    client.connect().await.unwrap();
+
+    let mut uris = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = client.client_mut().list_resources(cursor).await.unwrap();
+        assert!(page.resources.len() <= 10);
+        uris.extend(
+            page.resources
+                .into_iter()
+                .map(|r| (r.uri.to_string(), r.name, r.mime)),
+        );
+        cursor = page.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    assert_eq!(uris.len(), 1 + 14 + 3);
+    assert_eq!(uris[0].0, "notes://today");
+    assert_eq!(
+        uris[1],
+        (
+            "notes://1/monday".into(),
+            "notes://1/monday".into(),
+            Some("text/plain".into())
+        )
+    );
+    assert_eq!(uris[17], ("users://2".into(), "user 2".into(), None));
+
+    let note = client.read_resource("notes://2/sunday").await.unwrap();
+    assert_eq!(note.contents[0].text(), Some("sunday of week 2"));
+
+    let params = CompleteRequestParams {
+        r#ref: Reference::resource("notes://{week}/{day}"),
+        arg: Argument {
+            name: "day".into(),
+            value: "t".into(),
+        },
+    };
+    let result: CompleteResult = client
+        .client_mut()
+        .command(neva::types::completion::commands::COMPLETE, Some(params))
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+    assert_eq!(result.completion.values, ["tuesday", "thursday"]);
+    assert_eq!(result.completion.total, Some(2));
+}
@@ -0,0 +1,85 @@
+//! Tools confined to the client's roots, end to end through `neva::testing`.
+//!
+//! A `SandboxedPath` argument has the roots fetched on the MRTR loop before
+//! the handler runs, and a path that leaves them fails the call before the
+//! handler sees it.
+
+#![cfg(all(not(feature = "legacy-spec"), feature = "testing"))]
+
+use neva::prelude::*;
+use neva::testing::{CallToolResponseExt, TestClient};
+use std::path::PathBuf;
+
+fn app() -> App {
+    let mut app = App::new();
+    app.map_tool("read", |path: SandboxedPath| async move {
+        std::fs::read_to_string(&path).map_err(Error::from)
+    })
+    .with_arg_names(["path"]);
+    app.map_tool("roots", |mut ctx: Context| async move {
+        let sandbox = ctx.sandbox().await?;
+        Ok::<_, Error>(sandbox.roots().len().to_string())
+    });
+    app
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn a_sandboxed_tool_reads_inside_the_roots_only() {
+    let dir = TempDir::new();
+    dir.write("work/notes.md", "inside");
+    dir.write("secret/key", "outside");
+
+    let root = format!("file://{}", dir.0.join("work").display());
+    let mut client = TestClient::new(app()).with_roots([(root, "work")]);
+    client.connect().await.unwrap();
+
+    client
+        .call_tool("read", [("path", "notes.md")])
+        .await
+        .unwrap()
+        .assert_text("inside");
+    let inside = dir.0.join("work/notes.md").display().to_string();
+    client
+        .call_tool("read", [("path", inside)])
+        .await
+        .unwrap()
+        .assert_text("inside");
+    client
+        .call_tool("roots", ())
+        .await
+        .unwrap()
+        .assert_text("1");
+
+    for escape in [
+        "../secret/key".to_owned(),
+        dir.0.join("secret/key").display().to_string(),
+    ] {
+        let err = client
+            .call_tool("read", [("path", escape)])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("outside the client's roots"));
+    }
+}
+
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new() -> Self {
+        let dir = std::env::temp_dir().join(format!("neva-sandbox-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        Self(dir.canonicalize().unwrap())
+    }
+
+    fn write(&self, name: &str, contents: &str) {
+        let path = self.0.join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,49 @@
+//! Client-side validation of tool calls against the listed schemas, end to
+//! end through `neva::testing`: a stale listing is refreshed only when it
+//! refuses a call.
+
+#![cfg(all(not(feature = "legacy-spec"), feature = "testing"))]
+
+use neva::prelude::*;
+use neva::testing::{CallToolResponseExt, TestClient};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+fn app() -> App {
+    let mut app = App::new();
+    app.map_tool("add", |a: i64, b: i64| async move { a + b })
+        .with_arg_names(["a", "b"]);
+    app
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn it_validates_calls_against_a_listing_stale_on_arrival() {
+    static LISTINGS: AtomicUsize = AtomicUsize::new(0);
+
+    let app = app().wrap_list_tools(|ctx, next| async move {
+        LISTINGS.fetch_add(1, Ordering::SeqCst);
+        next(ctx).await
+    });
+    let mut client = TestClient::new(app).with_options(|opt| opt.with_input_validation());
+    client.connect().await.unwrap();
+
+    // A neva server sends `ttlMs: 0`, so the listing is stale on arrival
+    let tools = client.list_tools().await.unwrap();
+    assert_eq!(tools.ttl_ms, 0);
+    assert_eq!(LISTINGS.load(Ordering::SeqCst), 1);
+
+    // Calls the stale schema accepts go out without listing again
+    for _ in 0..3 {
+        client
+            .call_tool("add", [("a", 2), ("b", 3)])
+            .await
+            .unwrap()
+            .assert_success();
+    }
+    assert_eq!(LISTINGS.load(Ordering::SeqCst), 1);
+
+    // A refusal lists again, and the new listing refuses it too
+    let err = client.call_tool("add", [("a", "two")]).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::InvalidParams);
+    assert_eq!(err.data().unwrap()["tool"], "add");
+    assert_eq!(LISTINGS.load(Ordering::SeqCst), 2);
+}
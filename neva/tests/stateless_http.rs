@@ -11,6 +11,7 @@
 ))]
 
 use neva::App;
+use neva::types::Uri;
 
 #[tokio::test(flavor = "multi_thread")]
 async fn stateless_discover_and_call() {
@@ -184,6 +185,80 @@ async fn stateless_discover_and_call() {
     handle.abort();
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn streamed_blob_resources_are_written_into_the_body() {
+    use neva::types::{BlobStream, ReadResourceResult, ResourceContents};
+
+    const SIZE: u64 = 3 * 1024 * 1024 + 1;
+
+    let port = pick_free_port();
+    let addr = format!("127.0.0.1:{port}");
+    let mut app = App::new()
+        .with_options(|opt| opt.with_http(|http| http.bind(&addr).with_endpoint("/mcp")))
+        .with_max_blob_size(4 * 1024 * 1024);
+    app.map_resource("blob://{size}", "blob", |uri: Uri, size: u64| async move {
+        use tokio::io::AsyncReadExt;
+        let reader = tokio::io::repeat(b'x').take(size);
+        BlobStream::new(uri, reader)
+            .with_size(size)
+            .with_mime("application/octet-stream")
+    });
+    let handle = tokio::spawn(async move { app.run().await });
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+
+    let client = reqwest::Client::builder()
+        .no_proxy()
+        .build()
+        .expect("test client");
+    let url = format!("http://{addr}/mcp");
+    let read = |id: u64, uri: String| {
+        serde_json::json!({
+            "jsonrpc": "2.0", "id": id, "method": "resources/read",
+            "params": { "uri": uri, "_meta": meta() }
+        })
+    };
+
+    // A blob within the limit arrives whole, as an ordinary blob.
+    let body = read(1, format!("blob://{SIZE}"));
+    let resp = routed(client.post(&url), &body)
+        .json(&body)
+        .send()
+        .await
+        .expect("read failed");
+    assert!(resp.status().is_success());
+    assert_eq!(
+        resp.headers()["content-type"].to_str().unwrap(),
+        "application/json"
+    );
+    let mut body: serde_json::Value = resp.json().await.unwrap();
+    let result: ReadResourceResult = serde_json::from_value(body["result"].take()).unwrap();
+    let ResourceContents::Blob(blob) = &result.contents[0] else {
+        panic!("expected a blob, got {:?}", result.contents[0]);
+    };
+    assert_eq!(blob.blob.len() as u64, SIZE);
+    assert!(blob.blob.iter().all(|&b| b == b'x'));
+    assert_eq!(blob.mime.as_deref(), Some("application/octet-stream"));
+
+    // One declared over the limit is refused before anything is sent.
+    let body = read(2, format!("blob://{}", 5 * 1024 * 1024));
+    let body: serde_json::Value = routed(client.post(&url), &body)
+        .json(&body)
+        .send()
+        .await
+        .expect("read failed")
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["id"], 2, "got: {body}");
+    assert_eq!(
+        body["error"]["data"]["limit"],
+        4 * 1024 * 1024,
+        "got: {body}"
+    );
+
+    handle.abort();
+}
+
 /// The `_meta` MCP 2026-07-28 requires on every request: the protocol version,
 /// and the capabilities this request is made under -- empty being the valid
 /// declaration of "no optional capabilities".
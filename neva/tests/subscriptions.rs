@@ -526,11 +526,57 @@ async fn shutting_down_ends_live_subscriptions_gracefully() {
         .expect("the server task panicked");
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn shutting_down_says_why_before_it_closes_the_stream() {
+    // A client that sees its subscription answered cannot tell a shutdown from
+    // any other server-initiated close. The notice ahead of the result is what
+    // tells it not to reconnect to this instance.
+    let addr = format!("127.0.0.1:{}", pick_free_port());
+    let (app, shutdown) = App::new()
+        .with_options(|opt| {
+            opt.with_http(|http| http.bind(&addr).with_endpoint("/mcp"))
+                .with_tools(|t| t.with_list_changed())
+                .with_resources(|r| r.with_list_changed().with_subscribe())
+        })
+        .with_shutdown();
+
+    let handle = tokio::spawn(async move { app.run().await });
+    await_reachable(&addr).await;
+
+    let client = reqwest::Client::builder()
+        .no_proxy()
+        .build()
+        .expect("test client");
+    let (mut stream, mut body) = listen(&client, &addr).await;
+
+    shutdown.shutdown();
+
+    let notice = next_message(&mut stream, &mut body).await;
+    assert_eq!(notice["method"], "notifications/message", "got: {notice}");
+    assert_eq!(notice["params"]["level"], "warning");
+    assert_eq!(notice["params"]["data"]["reason"], "shutdown");
+
+    let result = next_message(&mut stream, &mut body).await;
+    assert_eq!(
+        result["id"], "sub-1",
+        "the notice must be followed by the graceful-close result, got: {result}"
+    );
+
+    let summary = shutdown.summary().await;
+    assert_eq!(summary.closed_subscriptions, 1);
+    assert!(summary.is_clean(), "{summary:?}");
+
+    tokio::time::timeout(Duration::from_secs(10), handle)
+        .await
+        .expect("the server must stop after a shutdown request")
+        .expect("the server task panicked");
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn shutting_down_without_subscriptions_does_not_wait() {
-    // The drain is owed only to live subscriptions. A server that never opened
-    // one must shut down as immediately as it did before the drain existed --
-    // otherwise every server pays a subscription feature it does not use.
+    // The drain is owed only to work under way. A server with nothing running
+    // must shut down as immediately as it did before the drain existed --
+    // otherwise every server pays for a window it does not use.
     let addr = format!("127.0.0.1:{}", pick_free_port());
     let app = App::new()
         .with_options(|opt| opt.with_http(|http| http.bind(&addr).with_endpoint("/mcp")))
@@ -0,0 +1,181 @@
+//! `neva::testing` against an in-process app: typed calls, canned client
+//! answers for MRTR input requests, injected claims and captured
+//! notifications.
+
+#![cfg(all(not(feature = "legacy-spec"), feature = "testing", feature = "tracing"))]
+#![allow(deprecated)]
+
+use neva::auth::DefaultClaims;
+use neva::prelude::*;
+use neva::testing::{CallToolResponseExt, TestClient};
+use neva::types::elicitation::ElicitRequestParams;
+use neva::types::notification::LoggingLevel;
+use neva::types::sampling::{CreateMessageRequestParams, CreateMessageResult};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Sum {
+    total: i64,
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.map_tool(
+        "add",
+        |a: i64, b: i64| async move { Json(Sum { total: a + b }) },
+    )
+    .with_arg_names(["a", "b"]);
+    app.map_tool("fail", || async {
+        Err::<String, Error>(Error::new(ErrorCode::InvalidParams, "no"))
+    });
+    app.map_tool("ask", |mut ctx: Context| async move {
+        let params = ElicitRequestParams::form("Your name?")
+            .with_required("name", "string")
+            .into();
+        let answer = ctx.elicit("name", params).await?;
+        let name = answer.content::<serde_json::Value>().unwrap_or_default()["name"].clone();
+        Ok::<String, Error>(format!("Hi, {}", name.as_str().unwrap_or("?")))
+    });
+    app.map_tool("model", |mut ctx: Context| async move {
+        let params = CreateMessageRequestParams::new().with_message("Say hi");
+        let reply = ctx.sample("model", params).await?;
+        let roots = ctx.list_roots("roots").await?;
+        Ok::<String, Error>(format!(
+            "{:?} / {} root(s)",
+            reply.content,
+            roots.roots.len()
+        ))
+    });
+    app.map_tool("admin", || async { "granted" })
+        .with_roles(["admin"]);
+    app.map_tool("busy", |token: Meta<ProgressToken>| async move {
+        for step in 1..=3 {
+            tracing::info!(target: "progress", token = %*token, value = step, total = 3);
+        }
+        tracing::warn!(logger = "busy", "almost done");
+        "done"
+    });
+    app.map_prompt("hello", |name: String| async move {
+        PromptMessage::user().with(format!("Say hello to {name}"))
+    })
+    .with_args(["name"]);
+    app.add_resource("notes://today", "today");
+    app.map_resource(
+        "notes://{day}",
+        "note",
+        |uri: Uri, day: String| async move {
+            ResourceContents::new(uri).with_text(format!("notes of {day}"))
+        },
+    );
+    app
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn it_calls_tools_prompts_and_resources() {
+    let mut client = TestClient::new(app());
+    client.connect().await.unwrap();
+
+    let tools = client.list_tools().await.unwrap();
+    assert!(tools.tools.iter().any(|tool| tool.name == "add"));
+
+    client
+        .call_tool("add", [("a", 2), ("b", 3)])
+        .await
+        .unwrap()
+        .assert_success()
+        .assert_structured(Sum { total: 5 });
+    let sum: Sum = client
+        .call_tool_as("add", [("a", 1), ("b", 1)])
+        .await
+        .unwrap();
+    assert_eq!(sum, Sum { total: 2 });
+
+    client.call_tool("fail", ()).await.unwrap().assert_error();
+    assert!(
+        client
+            .call_tool_as::<String, _, _>("fail", ())
+            .await
+            .is_err()
+    );
+
+    let prompt = client.get_prompt("hello", [("name", "Ada")]).await.unwrap();
+    assert_eq!(prompt.messages.len(), 1);
+
+    let note = client.read_resource("notes://monday").await.unwrap();
+    assert_eq!(
+        note.contents[0].text(),
+        Some("notes of monday"),
+        "got {note:?}"
+    );
+    client.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn it_answers_input_requests() {
+    let mut client = TestClient::new(app())
+        .with_elicitation_answer(
+            ElicitResult::accept().with_content(serde_json::json!({ "name": "Ada" })),
+        )
+        .with_sampling_answer(CreateMessageResult::assistant().with_content("hi"))
+        .with_roots([("file:///work", "work")]);
+    client.connect().await.unwrap();
+
+    client
+        .call_tool("ask", ())
+        .await
+        .unwrap()
+        .assert_text("Hi, Ada");
+    client
+        .call_tool("model", ())
+        .await
+        .unwrap()
+        .assert_text_contains("hi")
+        .assert_text_contains("1 root(s)");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn it_injects_claims() {
+    let client = TestClient::new(app());
+    let mut client = client.with_claims(DefaultClaims {
+        role: Some("admin".into()),
+        ..Default::default()
+    });
+    client.connect().await.unwrap();
+    client
+        .call_tool("admin", ())
+        .await
+        .unwrap()
+        .assert_text("granted");
+
+    client.clear_claims();
+    assert!(client.call_tool("admin", ()).await.is_err());
+
+    client.set_claims(DefaultClaims {
+        role: Some("guest".into()),
+        ..Default::default()
+    });
+    assert!(client.call_tool("admin", ()).await.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn it_captures_progress_and_logs() {
+    let mut client = TestClient::new(app())
+        .with_options(|opt| opt.with_log_level(LoggingLevel::Info))
+        .with_tracing_capture();
+    client.connect().await.unwrap();
+
+    client
+        .call_tool("busy", ())
+        .await
+        .unwrap()
+        .assert_text("done");
+
+    let progress: Vec<_> = client.progress().iter().map(|p| p.progress).collect();
+    assert_eq!(progress, [1.0, 2.0, 3.0]);
+    let logs = client.logs();
+    assert_eq!(logs.len(), 1, "got {logs:?}");
+    assert_eq!(logs[0].logger.as_deref(), Some("busy"));
+
+    client.clear_notifications();
+    assert!(client.notifications().is_empty());
+}
@@ -0,0 +1,71 @@
+//! Tool errors against protocol errors end to end through `neva::testing`:
+//! what a handler returns as an `isError` result and what fails the request,
+//! under the default and a custom tool error policy.
+
+#![cfg(all(not(feature = "legacy-spec"), feature = "testing"))]
+
+use neva::prelude::*;
+use neva::testing::{CallToolResponseExt, TestClient};
+use serde_json::json;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn it_splits_tool_errors_from_protocol_errors() {
+    let tools = |app: &mut App| {
+        app.map_tool("divide", |a: f64, b: f64| async move {
+            if b == 0.0 {
+                return Err(ToolError::new("cannot divide by zero")
+                    .with_structured(json!({ "argument": "b" })));
+            }
+            Ok(a / b)
+        })
+        .with_arg_names(["a", "b"]);
+        app.map_tool("visit", || async {
+            Err::<(), _>(Error::url_elicitation_required("sign in first", []))
+        });
+    };
+    let mut app = App::new();
+    tools(&mut app);
+    let mut client = TestClient::new(app);
+    client.connect().await.unwrap();
+
+    client
+        .call_tool("divide", json!({ "a": 1, "b": 0 }))
+        .await
+        .unwrap()
+        .assert_error()
+        .assert_text_contains("cannot divide by zero")
+        .assert_structured(json!({ "argument": "b" }));
+    let err = client
+        .call_tool_as::<f64, _, _>("divide", json!({ "a": 1, "b": 0 }))
+        .await
+        .unwrap_err();
+    let ErrorKind::Tool(err) = err.kind() else {
+        panic!("unexpected {:?}", err.kind());
+    };
+    assert_eq!(err.struct_content, Some(json!({ "argument": "b" })));
+
+    let err = client.call_tool("visit", ()).await.unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::UrlElicitationRequired(_)));
+    client.shutdown().await;
+
+    let mut app = App::new().with_tool_error_policy(|kind| match kind {
+        ErrorKind::Tool(_) => ToolErrorMode::Protocol,
+        _ => ToolErrorMode::Result,
+    });
+    tools(&mut app);
+    let mut client = TestClient::new(app);
+    client.connect().await.unwrap();
+
+    let err = client
+        .call_tool("divide", json!({ "a": 1, "b": 0 }))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::InternalError);
+    client
+        .call_tool("visit", ())
+        .await
+        .unwrap()
+        .assert_error()
+        .assert_text_contains("sign in first");
+    client.shutdown().await;
+}
@@ -0,0 +1,176 @@
+//! `#[toolset]` end-to-end over the 2026-07-28 HTTP transport: the methods of
+//! one `impl` block served as prefixed tools, resources and prompts that all
+//! call into the same instance.
+
+#![cfg(all(
+    not(feature = "legacy-spec"),
+    feature = "server-macros",
+    feature = "http-server-volga",
+    feature = "http-client"
+))]
+
+use neva::prelude::*;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Requests the group middleware has seen.
+static WRAPPED: AtomicUsize = AtomicUsize::new(0);
+
+async fn count_requests(ctx: MwContext, next: Next) -> Response {
+    WRAPPED.fetch_add(1, Ordering::SeqCst);
+    next(ctx).await
+}
+
+struct Notes {
+    notes: Mutex<Vec<String>>,
+}
+
+#[toolset(prefix = "notes_", middleware = [count_requests])]
+impl Notes {
+    #[tool(descr = "Adds a note")]
+    async fn add(&self, text: String) -> usize {
+        let mut notes = self.notes.lock().unwrap();
+        notes.push(text);
+        notes.len()
+    }
+
+    #[tool]
+    async fn count(&self) -> usize {
+        self.notes.lock().unwrap().len()
+    }
+
+    #[resource(uri = "notes://{index}", mime = "text/plain")]
+    async fn note(&self, uri: Uri, index: usize) -> ResourceContents {
+        let text = self.notes.lock().unwrap()[index].clone();
+        ResourceContents::new(uri).with_text(text)
+    }
+
+    #[prompt(descr = "Asks for a summary of every note")]
+    async fn summarize(&self, tone: String) -> PromptMessage {
+        let notes = self.notes.lock().unwrap().join("; ");
+        PromptMessage::user().with(format!("Summarize in a {tone} tone: {notes}"))
+    }
+
+    /// Not an item: left alone on the `impl` block.
+    fn is_empty(&self) -> bool {
+        self.notes.lock().unwrap().is_empty()
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn a_toolset_serves_its_methods_from_one_instance() {
+    let notes = Notes {
+        notes: Mutex::new(Vec::new()),
+    };
+    assert!(notes.is_empty());
+
+    let addr = format!("127.0.0.1:{}", pick_free_port());
+    let mut app =
+        App::new().with_options(|opt| opt.with_http(|http| http.bind(&addr).with_endpoint("/mcp")));
+    app.add_toolset(notes);
+    let handle = tokio::spawn(async move { app.run().await });
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+
+    let client = reqwest::Client::builder()
+        .no_proxy()
+        .build()
+        .expect("test client");
+    let url = format!("http://{addr}/mcp");
+    let post = |body: serde_json::Value| {
+        let req = routed(client.post(&url), &body).json(&body);
+        async move {
+            req.send()
+                .await
+                .expect("request failed")
+                .json::<serde_json::Value>()
+                .await
+                .expect("json")
+        }
+    };
+
+    let list = post(serde_json::json!({
+        "jsonrpc": "2.0", "id": 1, "method": "tools/list",
+        "params": { "_meta": meta() }
+    }))
+    .await;
+    let tools = list["result"]["tools"].as_array().expect("a tool list");
+    let mut names: Vec<_> = tools.iter().map(|t| t["name"].as_str().unwrap()).collect();
+    names.sort();
+    assert_eq!(names, ["notes_add", "notes_count"]);
+    let add = tools.iter().find(|t| t["name"] == "notes_add").unwrap();
+    assert_eq!(add["description"], "Adds a note");
+    assert_eq!(add["inputSchema"]["properties"]["text"]["type"], "string");
+    assert_eq!(add["inputSchema"]["required"], serde_json::json!(["text"]));
+
+    for (id, text) in [(2, "milk"), (3, "eggs")] {
+        post(serde_json::json!({
+            "jsonrpc": "2.0", "id": id, "method": "tools/call",
+            "params": { "name": "notes_add", "arguments": { "text": text }, "_meta": meta() }
+        }))
+        .await;
+    }
+    let count = post(serde_json::json!({
+        "jsonrpc": "2.0", "id": 4, "method": "tools/call",
+        "params": { "name": "notes_count", "_meta": meta() }
+    }))
+    .await;
+    assert_eq!(count["result"]["content"][0]["text"], "2", "got: {count}");
+
+    let read = post(serde_json::json!({
+        "jsonrpc": "2.0", "id": 5, "method": "resources/read",
+        "params": { "uri": "notes://1", "_meta": meta() }
+    }))
+    .await;
+    assert_eq!(read["result"]["contents"][0]["text"], "eggs", "got: {read}");
+
+    let prompt = post(serde_json::json!({
+        "jsonrpc": "2.0", "id": 6, "method": "prompts/get",
+        "params": { "name": "notes_summarize", "arguments": { "tone": "dry" }, "_meta": meta() }
+    }))
+    .await;
+    assert_eq!(
+        prompt["result"]["messages"][0]["content"]["text"], "Summarize in a dry tone: milk; eggs",
+        "got: {prompt}"
+    );
+
+    // Three tool calls, a resource read and a prompt, each wrapped once.
+    assert_eq!(WRAPPED.load(Ordering::SeqCst), 5);
+
+    handle.abort();
+}
+
+fn pick_free_port() -> u16 {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    drop(listener);
+    port
+}
+
+/// The `_meta` MCP 2026-07-28 requires on every request: the protocol version,
+/// and the capabilities this request is made under -- empty being the valid
+/// declaration of "no optional capabilities".
+fn meta() -> serde_json::Value {
+    serde_json::json!({
+        "io.modelcontextprotocol/protocolVersion": "2026-07-28",
+        "io.modelcontextprotocol/clientCapabilities": {}
+    })
+}
+
+/// Attaches the routing headers MCP 2026-07-28 requires on every request, the
+/// way a conforming client derives them: from the body it is about to send.
+fn routed(req: reqwest::RequestBuilder, body: &serde_json::Value) -> reqwest::RequestBuilder {
+    let method = body["method"].as_str().unwrap_or_default();
+    let req = req
+        .header("MCP-Protocol-Version", "2026-07-28")
+        .header("Mcp-Method", method);
+    let name = match method {
+        "tools/call" | "prompts/get" => body.pointer("/params/name"),
+        "resources/read" => body.pointer("/params/uri"),
+        "tasks/get" | "tasks/update" | "tasks/cancel" => body.pointer("/params/taskId"),
+        _ => None,
+    };
+    match name.and_then(|v| v.as_str()) {
+        Some(name) => req.header("Mcp-Name", name),
+        None => req,
+    }
+}
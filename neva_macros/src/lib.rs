@@ -105,6 +105,7 @@ pub fn tool(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// * `mime` - Resource MIME type.
 /// * `annotations` - Resource content arbitrary [metadata](https://docs.rs/neva/latest/neva/types/struct.Annotations.html).
 /// * `roles` & `permissions` - Define which users can read the resource when using Streamable HTTP transport with OAuth.
+/// * `middleware` - Middleware list to apply to reads of the resource.
 ///
 /// # Simple Example
 /// ```ignore
@@ -220,6 +221,63 @@ pub fn prompt(attr: TokenStream, item: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Maps the `#[tool]`, `#[resource]` and `#[prompt]` methods of an `impl`
+/// block to items served by one shared instance
+///
+/// Each marked method takes `&self` and accepts the same parameters as the
+/// free-function macro it is marked with. The group is registered with
+/// `app.add_toolset(instance)`.
+///
+/// # Parameters
+/// * `prefix` - Prepended verbatim to every item name, e.g. `"db_"` registers
+///   the `query` method as `db_query`.
+/// * `roles` & `permissions` - Applied to every item that does not state its own.
+/// * `middleware` - Middleware list applied to every tool, resource and
+///   prompt, ahead of any an item states itself.
+///
+/// Any other option is a compile error.
+///
+/// # Example
+/// ```ignore
+/// use neva::prelude::*;
+///
+/// struct Library {
+///     books: Vec<String>,
+/// }
+///
+/// #[toolset(prefix = "library_", roles = ["reader"])]
+/// impl Library {
+///     #[tool(descr = "Counts the books")]
+///     async fn count(&self) -> usize {
+///         self.books.len()
+///     }
+///
+///     #[resource(uri = "books://{index}")]
+///     async fn book(&self, uri: Uri, index: usize) -> ResourceContents {
+///         ResourceContents::new(uri).with_text(self.books[index].clone())
+///     }
+///
+///     #[prompt(descr = "Asks for a summary of a book")]
+///     async fn summarize(&self, title: String) -> PromptMessage {
+///         PromptMessage::user().with(format!("Summarize {title}"))
+///     }
+/// }
+///
+/// let mut app = App::new();
+/// app.add_toolset(Library { books: vec!["Dune".into()] });
+/// ```
+#[proc_macro_attribute]
+#[cfg(feature = "server")]
+pub fn toolset(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(item as syn::ItemImpl);
+    let attr = parse_macro_input!(
+        attr with Punctuated::<syn::Meta, Token![,]>::parse_terminated
+    );
+    server::toolset::expand(&attr, &item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
 /// Maps the function to a command handler
 ///
 /// # Parameters
@@ -342,3 +400,58 @@ pub fn json_schema(attr: TokenStream, item: TokenStream) -> TokenStream {
         .unwrap_or_else(syn::Error::into_compile_error)
         .into()
 }
+
+/// Describes an elicitation form with a struct, or its select options with an enum
+///
+/// On a struct, each named field becomes a property of the form:
+/// `String` a string, the integer and float types a number, `bool` a boolean,
+/// an enum deriving `ElicitForm` a single-select and a `Vec` of one a
+/// multi-select. `Option` makes a field optional. Any other field type is a
+/// compile error, since MCP form elicitation only allows a flat object of
+/// primitives. Property names and option values follow serde's `rename`,
+/// `rename_all`, `skip` and `default`.
+///
+/// # Field parameters
+/// * `title` - Property title.
+/// * `descr` - Property description.
+/// * `default` - Default value, of the field's own type; the option value for a select.
+/// * `format` - String format: `"email"`, `"uri"`, `"date"` or `"date-time"`.
+/// * `min_length` & `max_length` - Bounds on a string's length.
+/// * `min` & `max` - Bounds on a number.
+/// * `min_items` & `max_items` - Bounds on the number of options a multi-select takes.
+///
+/// # Variant parameters
+/// * `title` - Option title. An enum with any titled variant publishes titled options.
+///
+/// # Example
+/// ```ignore
+/// use neva::prelude::*;
+///
+/// #[derive(serde::Deserialize, ElicitForm)]
+/// #[serde(rename_all = "lowercase")]
+/// enum Room {
+///     #[elicit(title = "Single room")]
+///     Single,
+///     #[elicit(title = "Double room")]
+///     Double,
+/// }
+///
+/// #[derive(serde::Deserialize, ElicitForm)]
+/// struct Booking {
+///     #[elicit(title = "Email", format = "email")]
+///     email: String,
+///     #[elicit(min = 1, max = 14, default = 2)]
+///     nights: u32,
+///     room: Room,
+///     notes: Option<String>,
+/// }
+///
+/// let params = ElicitRequestParams::form("Book a room").with_form::<Booking>();
+/// ```
+#[proc_macro_derive(ElicitForm, attributes(elicit))]
+pub fn elicit_form(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as syn::DeriveInput);
+    shared::elicit_form::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -8,6 +8,7 @@ use syn::{ItemFn, Meta, punctuated::Punctuated, token::Comma};
 pub(super) mod prompt;
 pub(crate) mod resource;
 pub(crate) mod tool;
+pub(crate) mod toolset;
 
 pub(super) fn expand_handler(
     attr: &Punctuated<Meta, Comma>,
@@ -146,10 +147,8 @@ pub(super) fn get_arg_type(t: &Type) -> &str {
 /// Handed to `neva::__arg_names!` / `neva::__prompt_args!`, which decide which
 /// of them are arguments from the resolved type -- see the call sites for why
 /// that decision cannot be made here.
-pub(super) fn param_idents_and_types(function: &ItemFn) -> Vec<(syn::Ident, syn::Type)> {
-    function
-        .sig
-        .inputs
+pub(super) fn param_idents_and_types(sig: &syn::Signature) -> Vec<(syn::Ident, syn::Type)> {
+    sig.inputs
         .iter()
         .filter_map(|arg| match arg {
             syn::FnArg::Typed(pat_type) => match &*pat_type.pat {
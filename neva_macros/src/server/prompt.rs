@@ -1,15 +1,50 @@
 //! Macros for MCP prompts
 
+use super::toolset::Group;
 use super::{get_bool_param, get_exprs_arr, get_params_arr, get_str_param, param_idents_and_types};
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{ItemFn, Meta, punctuated::Punctuated, token::Comma};
+use syn::{ItemFn, Meta, Signature, punctuated::Punctuated, token::Comma};
 
 pub(crate) fn expand(
     attr: &Punctuated<Meta, Comma>,
     function: &ItemFn,
 ) -> syn::Result<TokenStream> {
     let func_name = &function.sig.ident;
+    let registration = registration(
+        attr,
+        &function.sig,
+        &func_name.to_string(),
+        quote! { #func_name },
+        &Group::default(),
+    )?;
+    let module_name = syn::Ident::new(&format!("map_{func_name}"), func_name.span());
+
+    // Expand the function and apply the tool functionality
+    let expanded = quote! {
+        // Original function
+        #function
+
+        fn #module_name(app: &mut App) {
+            #registration
+        }
+        neva::macros::inventory::submit! {
+            neva::macros::server::ItemRegistrar(#module_name)
+        }
+    };
+
+    Ok(expanded)
+}
+
+/// The statement registering a prompt named `name` on `app`; see
+/// [`super::tool::registration`].
+pub(crate) fn registration(
+    attr: &Punctuated<Meta, Comma>,
+    sig: &Signature,
+    name: &str,
+    handler: TokenStream,
+    group: &Group,
+) -> syn::Result<TokenStream> {
     let mut description = None;
     let mut args = None;
     let mut title = None;
@@ -76,7 +111,7 @@ pub(crate) fn expand(
         // a type alias this macro cannot see through. The published list is
         // also what extraction reads by, so classifying it syntactically would
         // shift every argument after the mis-classified one.
-        let params = param_idents_and_types(function);
+        let params = param_idents_and_types(sig);
         if params.is_empty() {
             quote! {}
         } else {
@@ -87,44 +122,31 @@ pub(crate) fn expand(
         quote! {}
     };
 
-    let roles_code = roles.map(|roles| {
+    let roles_code = group.roles(roles).map(|roles| {
         let role_literals = roles.iter().map(|r| quote::quote! { #r });
         quote! { .with_roles([#(#role_literals),*]) }
     });
 
-    let permission_code = permissions.map(|permission| {
+    let permission_code = group.permissions(permissions).map(|permission| {
         let permission_literals = permission.iter().map(|r| quote::quote! { #r });
         quote! { .with_permissions([#(#permission_literals),*]) }
     });
 
-    let middleware_code = middleware.map(|mws| {
+    let middleware_code = group.middleware(middleware).map(|mws| {
         let mw_calls = mws.iter().map(|mw| {
-            quote! { .wrap_prompt(stringify!(#func_name), #mw) }
+            quote! { .wrap_prompt(#name, #mw) }
         });
         quote! { #(#mw_calls)* }
     });
 
-    let module_name = syn::Ident::new(&format!("map_{func_name}"), func_name.span());
-
-    // Expand the function and apply the tool functionality
-    let expanded = quote! {
-        // Original function
-        #function
-
-        fn #module_name(app: &mut App) {
-            app
-                #middleware_code
-                .map_prompt(stringify!(#func_name), #func_name)
-                #title_code
-                #description_code
-                #args_code
-                #roles_code
-                #permission_code;
-        }
-        neva::macros::inventory::submit! {
-            neva::macros::server::ItemRegistrar(#module_name)
-        }
-    };
-
-    Ok(expanded)
+    Ok(quote! {
+        app
+            #middleware_code
+            .map_prompt(#name, #handler)
+            #title_code
+            #description_code
+            #args_code
+            #roles_code
+            #permission_code;
+    })
 }
@@ -1,5 +1,6 @@
 //! Macros for MCP server resources
 
+use super::toolset::Group;
 use super::{get_exprs_arr, get_params_arr, get_str_param};
 use proc_macro2::TokenStream;
 use quote::quote;
@@ -10,6 +11,38 @@ pub(crate) fn expand_resource(
     function: &ItemFn,
 ) -> syn::Result<TokenStream> {
     let func_name = &function.sig.ident;
+    let registration = registration(
+        attr,
+        &func_name.to_string(),
+        quote! { #func_name },
+        &Group::default(),
+    )?;
+    let module_name = syn::Ident::new(&format!("map_{func_name}"), func_name.span());
+
+    // Expand the function and apply the tool functionality
+    let expanded = quote! {
+        // Original function
+        #function
+        // Register a resource function
+        fn #module_name(app: &mut neva::App) {
+            #registration
+        }
+        neva::macros::inventory::submit! {
+            neva::macros::server::ItemRegistrar(#module_name)
+        }
+    };
+
+    Ok(expanded)
+}
+
+/// The statement registering a resource template named `name` on `app`; see
+/// [`super::tool::registration`].
+pub(crate) fn registration(
+    attr: &Punctuated<Meta, Comma>,
+    name: &str,
+    handler: TokenStream,
+    group: &Group,
+) -> syn::Result<TokenStream> {
     let mut uri = None;
     let mut title = None;
     let mut description = None;
@@ -17,6 +50,7 @@ pub(crate) fn expand_resource(
     let mut annotations = None;
     let mut roles = None;
     let mut permissions = None;
+    let mut middleware = None;
 
     for meta in attr {
         match &meta {
@@ -46,6 +80,9 @@ pub(crate) fn expand_resource(
                         "permissions" => {
                             permissions = get_params_arr(&nv.value);
                         }
+                        "middleware" => {
+                            middleware = get_exprs_arr(&nv.value);
+                        }
                         _ => {}
                     }
                 }
@@ -76,38 +113,34 @@ pub(crate) fn expand_resource(
         }
     });
 
-    let roles_code = roles.map(|roles| {
+    let roles_code = group.roles(roles).map(|roles| {
         let role_literals = roles.iter().map(|r| quote::quote! { #r });
         quote! { .with_roles([#(#role_literals),*]) }
     });
 
-    let permission_code = permissions.map(|permission| {
+    let permission_code = group.permissions(permissions).map(|permission| {
         let permission_literals = permission.iter().map(|r| quote::quote! { #r });
         quote! { .with_permissions([#(#permission_literals),*]) }
     });
 
-    let module_name = syn::Ident::new(&format!("map_{func_name}"), func_name.span());
-
-    // Expand the function and apply the tool functionality
-    let expanded = quote! {
-        // Original function
-        #function
-        // Register a resource function
-        fn #module_name(app: &mut neva::App) {
-            app.map_resource(#uri_code, stringify!(#func_name), #func_name)
-                #title_code
-                #description_code
-                #mime_code
-                #annotations_code
-                #roles_code
-                #permission_code;
-        }
-        neva::macros::inventory::submit! {
-            neva::macros::server::ItemRegistrar(#module_name)
-        }
-    };
+    let middleware_code = group.middleware(middleware).map(|mws| {
+        let mw_calls = mws.iter().map(|mw| {
+            quote! { .wrap_resource(#name, #mw) }
+        });
+        quote! { #(#mw_calls)* }
+    });
 
-    Ok(expanded)
+    Ok(quote! {
+        app
+            #middleware_code
+            .map_resource(#uri_code, #name, #handler)
+            #title_code
+            #description_code
+            #mime_code
+            #annotations_code
+            #roles_code
+            #permission_code;
+    })
 }
 
 pub(crate) fn expand_resources(
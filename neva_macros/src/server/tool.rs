@@ -20,19 +20,57 @@
 //!   at compile time; malformed JSON is a compile error (on every feature
 //!   configuration).
 
+use super::toolset::Group;
 use super::{
     get_arg_type, get_bool_param, get_exprs_arr, get_inner_type_from_generic, get_option_inner,
     get_param_type, get_params_arr, get_str_param, param_idents_and_types,
 };
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{FnArg, ItemFn, Meta, Pat, ReturnType, punctuated::Punctuated, token::Comma};
+use syn::{FnArg, ItemFn, Meta, Pat, ReturnType, Signature, punctuated::Punctuated, token::Comma};
 
 pub(crate) fn expand(
     attr: &Punctuated<Meta, Comma>,
     function: &ItemFn,
 ) -> syn::Result<TokenStream> {
     let func_name = &function.sig.ident;
+    let registration = registration(
+        attr,
+        &function.sig,
+        &func_name.to_string(),
+        quote! { #func_name },
+        &Group::default(),
+    )?;
+    let module_name = syn::Ident::new(&format!("map_{func_name}"), func_name.span());
+
+    // Expand the function and apply the tool functionality
+    let expanded = quote! {
+        // Original function
+        #function
+        // Register the tool with the app
+        fn #module_name(app: &mut neva::App) {
+            #registration
+        }
+        neva::macros::inventory::submit! {
+            neva::macros::server::ItemRegistrar(#module_name)
+        }
+    };
+
+    Ok(expanded)
+}
+
+/// The statement registering a tool named `name` on `app`, with `handler` as
+/// its handler and `sig` as the signature its schema is derived from.
+///
+/// `group` supplies what a `#[toolset]` shares across its tools; a free
+/// function passes an empty one.
+pub(crate) fn registration(
+    attr: &Punctuated<Meta, Comma>,
+    sig: &Signature,
+    name: &str,
+    handler: TokenStream,
+    group: &Group,
+) -> syn::Result<TokenStream> {
     let mut description = None;
     let mut input_schema = None;
     let mut output_schema = None;
@@ -120,7 +158,7 @@ pub(crate) fn expand(
     // resolution can. Deciding it syntactically would name an argument
     // `ToolHandler::args` does not count, and `App::run` refuses to start on
     // exactly that disagreement.
-    let params = param_idents_and_types(function);
+    let params = param_idents_and_types(sig);
     let arg_names_code = if params.is_empty() {
         quote! {}
     } else {
@@ -150,7 +188,7 @@ pub(crate) fn expand(
             // `primitive_subschema`; object/custom args use
             // `__tool_arg_subschema!` (rich-or-fallback).
             let mut entries = Vec::new();
-            for arg in &function.sig.inputs {
+            for arg in &sig.inputs {
                 if let FnArg::Typed(pat_type) = arg
                     && let Pat::Ident(pat_ident) = &*pat_type.pat
                 {
@@ -205,7 +243,7 @@ pub(crate) fn expand(
             }
         } else {
             let mut schema_entries = Vec::new();
-            for arg in &function.sig.inputs {
+            for arg in &sig.inputs {
                 if let FnArg::Typed(pat_type) = arg
                     && let Pat::Ident(pat_ident) = &*pat_type.pat
                 {
@@ -263,7 +301,7 @@ pub(crate) fn expand(
             }
         }
     } else if !no_schema {
-        match &function.sig.output {
+        match &sig.output {
             ReturnType::Default => quote! {},
             ReturnType::Type(_, return_type) => {
                 let type_str = get_arg_type(return_type);
@@ -303,19 +341,19 @@ pub(crate) fn expand(
         }
     });
 
-    let roles_code = roles.map(|roles| {
+    let roles_code = group.roles(roles).map(|roles| {
         let role_literals = roles.iter().map(|r| quote::quote! { #r });
         quote! { .with_roles([#(#role_literals),*]) }
     });
 
-    let permission_code = permissions.map(|permission| {
+    let permission_code = group.permissions(permissions).map(|permission| {
         let permission_literals = permission.iter().map(|r| quote::quote! { #r });
         quote! { .with_permissions([#(#permission_literals),*]) }
     });
 
-    let middleware_code = middleware.map(|mws| {
+    let middleware_code = group.middleware(middleware).map(|mws| {
         let mw_calls = mws.iter().map(|mw| {
-            quote! { .wrap_tool(stringify!(#func_name), #mw) }
+            quote! { .wrap_tool(#name, #mw) }
         });
         quote! { #(#mw_calls)* }
     });
@@ -324,31 +362,18 @@ pub(crate) fn expand(
         quote! { .with_task_support(#ts) }
     });
 
-    let module_name = syn::Ident::new(&format!("map_{func_name}"), func_name.span());
-
-    // Expand the function and apply the tool functionality
-    let expanded = quote! {
-        // Original function
-        #function
-        // Register the tool with the app
-        fn #module_name(app: &mut neva::App) {
-            app
-                #middleware_code
-                .map_tool(stringify!(#func_name), #func_name)
-                #arg_names_code
-                #title_code
-                #description_code
-                #input_schema_code
-                #output_schema_code
-                #annotations_code
-                #roles_code
-                #permission_code
-                #task_support_code;
-        }
-        neva::macros::inventory::submit! {
-            neva::macros::server::ItemRegistrar(#module_name)
-        }
-    };
-
-    Ok(expanded)
+    Ok(quote! {
+        app
+            #middleware_code
+            .map_tool(#name, #handler)
+            #arg_names_code
+            #title_code
+            #description_code
+            #input_schema_code
+            #output_schema_code
+            #annotations_code
+            #roles_code
+            #permission_code
+            #task_support_code;
+    })
 }
@@ -0,0 +1,223 @@
+//! Macros for groups of tools, resources and prompts served by one instance.
+//!
+//! `#[toolset]` goes on an `impl` block. Each method in it marked `#[tool]`,
+//! `#[resource]` or `#[prompt]` is registered the way the free-function macro
+//! would register it, except that its handler is a closure calling the method
+//! on an `Arc` of the instance handed to `App::add_toolset` -- which is how
+//! every item in the group gets at the same pool, client or configuration.
+
+use super::{get_exprs_arr, get_params_arr, get_str_param, param_idents_and_types};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{
+    Attribute, Expr, FnArg, ImplItem, ItemImpl, Meta, Pat, punctuated::Punctuated, token::Comma,
+};
+
+/// What a `#[toolset]` shares across its items.
+///
+/// A free function registers with an empty group, which leaves everything to
+/// its own attribute.
+#[derive(Default)]
+pub(crate) struct Group {
+    prefix: Option<String>,
+    roles: Option<Vec<String>>,
+    permissions: Option<Vec<String>>,
+    middleware: Option<Vec<Expr>>,
+}
+
+impl Group {
+    /// An item's own `roles`, or the group's when it states none.
+    pub(crate) fn roles(&self, own: Option<Vec<String>>) -> Option<Vec<String>> {
+        own.or_else(|| self.roles.clone())
+    }
+
+    /// An item's own `permissions`, or the group's when it states none.
+    pub(crate) fn permissions(&self, own: Option<Vec<String>>) -> Option<Vec<String>> {
+        own.or_else(|| self.permissions.clone())
+    }
+
+    /// The group's middleware followed by the item's own: unlike roles, a
+    /// middleware list is not a setting one level overrides but a pipeline
+    /// both levels contribute to.
+    pub(crate) fn middleware(&self, own: Option<Vec<Expr>>) -> Option<Vec<Expr>> {
+        match (&self.middleware, own) {
+            (Some(shared), Some(own)) => Some(shared.iter().cloned().chain(own).collect()),
+            (Some(shared), None) => Some(shared.clone()),
+            (None, own) => own,
+        }
+    }
+
+    /// The name an item registers under: the method name, behind the prefix.
+    fn name(&self, method: &syn::Ident) -> String {
+        match &self.prefix {
+            Some(prefix) => format!("{prefix}{method}"),
+            None => method.to_string(),
+        }
+    }
+}
+
+/// Which free-function macro an item attribute stands for.
+#[derive(Clone, Copy)]
+enum Kind {
+    Tool,
+    Resource,
+    Prompt,
+}
+
+impl Kind {
+    fn of(attr: &Attribute) -> Option<Self> {
+        let ident = &attr.path().segments.last()?.ident;
+        if ident == "tool" {
+            Some(Self::Tool)
+        } else if ident == "resource" {
+            Some(Self::Resource)
+        } else if ident == "prompt" {
+            Some(Self::Prompt)
+        } else {
+            None
+        }
+    }
+}
+
+pub(crate) fn expand(attr: &Punctuated<Meta, Comma>, item: &ItemImpl) -> syn::Result<TokenStream> {
+    let mut group = Group::default();
+
+    for meta in attr {
+        let Meta::NameValue(nv) = meta else {
+            return Err(syn::Error::new_spanned(
+                meta.path(),
+                "unknown toolset option",
+            ));
+        };
+        let key = &nv.path;
+        match key.get_ident().map(ToString::to_string).as_deref() {
+            Some("prefix") => {
+                group.prefix = get_str_param(&nv.value);
+            }
+            Some("roles") => {
+                group.roles = get_params_arr(&nv.value);
+            }
+            Some("permissions") => {
+                group.permissions = get_params_arr(&nv.value);
+            }
+            Some("middleware") => {
+                group.middleware = get_exprs_arr(&nv.value);
+            }
+            _ => return Err(syn::Error::new_spanned(key, "unknown toolset option")),
+        }
+    }
+
+    if let Some(trait_) = &item.trait_ {
+        return Err(syn::Error::new_spanned(
+            &trait_.0,
+            "`#[toolset]` goes on an inherent `impl` block, not a trait implementation",
+        ));
+    }
+
+    // The item attributes are taken off the methods: left in place they would
+    // expand as free-function macros, which cannot register a method.
+    let mut item = item.clone();
+    let mut registrations = Vec::new();
+    for impl_item in &mut item.items {
+        let ImplItem::Fn(method) = impl_item else {
+            continue;
+        };
+        let Some(pos) = method.attrs.iter().position(|a| Kind::of(a).is_some()) else {
+            continue;
+        };
+        let attr = method.attrs.remove(pos);
+        let kind = Kind::of(&attr).expect("matched above");
+        let args = match &attr.meta {
+            Meta::Path(_) => Punctuated::new(),
+            Meta::List(list) => list.parse_args_with(Punctuated::parse_terminated)?,
+            Meta::NameValue(nv) => {
+                return Err(syn::Error::new_spanned(
+                    nv,
+                    "expected `#[tool(...)]`, `#[resource(...)]` or `#[prompt(...)]`",
+                ));
+            }
+        };
+
+        let sig = &method.sig;
+        match sig.receiver() {
+            Some(syn::Receiver {
+                kind: syn::ReceiverKind::Reference(_, _, None),
+                ..
+            }) => {}
+            _ => {
+                return Err(syn::Error::new(
+                    sig.span(),
+                    "a `#[toolset]` method must take `&self`: every call shares one instance",
+                ));
+            }
+        }
+        for input in &sig.inputs {
+            if let FnArg::Typed(pat_type) = input
+                && !matches!(&*pat_type.pat, Pat::Ident(_))
+            {
+                return Err(syn::Error::new_spanned(
+                    &pat_type.pat,
+                    "a `#[toolset]` method parameter must be a plain identifier: it names the argument",
+                ));
+            }
+        }
+
+        let method_name = &sig.ident;
+        let (idents, types): (Vec<_>, Vec<_>) = param_idents_and_types(sig).into_iter().unzip();
+        let handler = quote! {{
+            let this = ::std::sync::Arc::clone(&self);
+            move |#(#idents: #types),*| {
+                let this = ::std::sync::Arc::clone(&this);
+                async move { this.#method_name(#(#idents),*).await }
+            }
+        }};
+
+        let name = group.name(method_name);
+        let registration = match kind {
+            Kind::Tool => super::tool::registration(&args, sig, &name, handler, &group)?,
+            Kind::Resource => super::resource::registration(&args, &name, handler, &group)?,
+            Kind::Prompt => super::prompt::registration(&args, sig, &name, handler, &group)?,
+        };
+        registrations.push(registration);
+    }
+
+    let self_ty = &item.self_ty;
+    let (impl_generics, _, where_clause) = item.generics.split_for_impl();
+
+    let expanded = quote! {
+        #item
+
+        impl #impl_generics neva::macros::server::Toolset for #self_ty #where_clause {
+            fn register(self: ::std::sync::Arc<Self>, app: &mut neva::App) {
+                #({ #registrations })*
+            }
+        }
+    };
+
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::expand;
+    use syn::{ItemImpl, Meta, parse::Parser, punctuated::Punctuated, token::Comma};
+
+    fn expand_err(attr: &str, item: &str) -> String {
+        let attr = Punctuated::<Meta, Comma>::parse_terminated
+            .parse_str(attr)
+            .unwrap();
+        let item: ItemImpl = syn::parse_str(item).unwrap();
+        expand(&attr, &item).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn it_refuses_an_unknown_option() {
+        let item = "impl Notes {}";
+        assert_eq!(
+            expand_err(r#"prefx = "notes_""#, item),
+            "unknown toolset option"
+        );
+        assert_eq!(expand_err("roles", item), "unknown toolset option");
+    }
+}
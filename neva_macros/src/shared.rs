@@ -4,6 +4,8 @@ use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{Path, punctuated::Punctuated, token::Comma};
 
+pub(crate) mod elicit_form;
+
 pub(super) fn expand_json_schema(
     attr: &Punctuated<Path, Comma>,
     input: &syn::DeriveInput,
@@ -0,0 +1,739 @@
+//! `#[derive(ElicitForm)]`: elicitation forms from plain structs and enums.
+//!
+//! On a struct, each named field becomes one property of the form's
+//! `RequestSchema`, mapped from its type: `String` to a string schema, the
+//! integer and float types to a number schema, `bool` to a boolean one, an
+//! enum deriving `ElicitForm` to a single-select and a `Vec` of one to a
+//! multi-select. `Option` makes a field optional. Anything else has no place
+//! in a form -- MCP only allows a flat object of primitives -- and is a
+//! compile error: a path type the derive does not recognise is taken to be an
+//! enum, and the `FormEnum` bound that puts on it names the field that is not.
+//!
+//! On an enum of unit variants, the derive implements `FormEnum`, with each
+//! variant's option value spelled the way serde spells it.
+//!
+//! Property names and option values follow serde's `rename`, `rename_all`,
+//! `skip` and `default`, so what the client submits reads back as the type
+//! that described it.
+
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::{ToTokens, quote};
+use syn::ext::IdentExt;
+use syn::{
+    Attribute, Data, DeriveInput, Expr, Fields, GenericArgument, Lit, LitStr, PathArguments, Type,
+    UnOp, spanned::Spanned,
+};
+
+pub(crate) fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    match &input.data {
+        Data::Struct(data) => expand_struct(input, &data.fields),
+        Data::Enum(data) => expand_enum(input, data.variants.iter()),
+        Data::Union(data) => Err(syn::Error::new_spanned(
+            data.union_token,
+            "`ElicitForm` describes a struct or an enum of unit variants",
+        )),
+    }
+}
+
+fn expand_struct(input: &DeriveInput, fields: &Fields) -> syn::Result<TokenStream> {
+    let Fields::Named(fields) = fields else {
+        return Err(syn::Error::new_spanned(
+            fields,
+            "an elicitation form needs named fields: they name its properties",
+        ));
+    };
+
+    let container = Serde::of(&input.attrs)?;
+    let mut properties = Vec::with_capacity(fields.named.len());
+    for field in &fields.named {
+        let serde = Serde::of(&field.attrs)?;
+        if serde.skip {
+            continue;
+        }
+        if let Some(span) = serde.flatten {
+            return Err(syn::Error::new(
+                span,
+                "a flattened field nests an object, and an elicitation form is flat",
+            ));
+        }
+
+        let ident = field.ident.as_ref().expect("named fields");
+        let name = match serde.rename {
+            Some(name) => name,
+            None => {
+                let ident = ident.unraw().to_string();
+                match &container.rename_all {
+                    Some(rule) => rule.field(&ident),
+                    None => ident,
+                }
+            }
+        };
+
+        let (ty, optional) = match option_inner(&field.ty) {
+            Some(inner) => (inner, true),
+            None => (&field.ty, false),
+        };
+        let kind = Kind::of(ty)?;
+        let attrs = FieldAttrs::of(&field.attrs, &kind)?;
+        let schema = kind.schema(ty, &attrs);
+
+        let required = !optional && !serde.default && !container.default;
+        let method = if required {
+            quote!(with_required)
+        } else {
+            quote!(with_prop)
+        };
+        properties.push(quote! {
+            let form = form.#method(#name, #schema);
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics neva::types::elicitation::ElicitForm for #ident #ty_generics #where_clause {
+            fn schema() -> neva::types::elicitation::RequestSchema {
+                let form = neva::types::elicitation::RequestSchema::new();
+                #(#properties)*
+                form
+            }
+        }
+    })
+}
+
+fn expand_enum<'a>(
+    input: &DeriveInput,
+    variants: impl Iterator<Item = &'a syn::Variant>,
+) -> syn::Result<TokenStream> {
+    let container = Serde::of(&input.attrs)?;
+    if let Some(span) = container.tagged {
+        return Err(syn::Error::new(
+            span,
+            "a select option is submitted as a bare string, which a tagged or untagged enum does not read",
+        ));
+    }
+
+    let mut titled = false;
+    let mut options = Vec::new();
+    for variant in variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                &variant.fields,
+                "a select option is a single string, so a form enum's variants carry no data",
+            ));
+        }
+        let serde = Serde::of(&variant.attrs)?;
+        if serde.skip {
+            continue;
+        }
+
+        let value = match serde.rename {
+            Some(name) => name,
+            None => {
+                let ident = variant.ident.unraw().to_string();
+                match &container.rename_all {
+                    Some(rule) => rule.variant(&ident),
+                    None => ident,
+                }
+            }
+        };
+        let title = match variant_title(&variant.attrs)? {
+            Some(title) => {
+                titled = true;
+                title.value()
+            }
+            None => value.clone(),
+        };
+        options.push(quote! { neva::types::EnumOption::new(#value, #title) });
+    }
+
+    if options.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "a form enum needs at least one variant to select",
+        ));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics neva::types::elicitation::FormEnum for #ident #ty_generics #where_clause {
+            const TITLED: bool = #titled;
+
+            fn options() -> ::std::vec::Vec<neva::types::EnumOption> {
+                ::std::vec![#(#options),*]
+            }
+        }
+    })
+}
+
+/// The schema a field type maps onto.
+enum Kind {
+    String,
+    /// An integer type, with the bounds it implies when none are given: the
+    /// range of an 8- or 16-bit type, which a user can meaningfully be shown,
+    /// and zero as the floor of a wider unsigned one.
+    Integer(Option<f64>, Option<f64>),
+    Number,
+    Boolean,
+    /// A type taken to be a `FormEnum`.
+    Select,
+    /// A `Vec` of a type taken to be a `FormEnum`.
+    MultiSelect(Box<Type>),
+}
+
+impl Kind {
+    fn of(ty: &Type) -> syn::Result<Self> {
+        let Some(ident) = plain_ident(ty) else {
+            return match ty {
+                Type::Path(path) if path.qself.is_none() => Self::collection(ty),
+                _ => Err(syn::Error::new_spanned(
+                    ty,
+                    "an elicitation form field is a string, number, boolean or `#[derive(ElicitForm)]` enum",
+                )),
+            };
+        };
+        let kind = match ident.to_string().as_str() {
+            "String" => Self::String,
+            "bool" => Self::Boolean,
+            "f32" | "f64" => Self::Number,
+            "u8" => Self::Integer(Some(0.0), Some(u8::MAX.into())),
+            "u16" => Self::Integer(Some(0.0), Some(u16::MAX.into())),
+            "u32" | "u64" | "u128" | "usize" => Self::Integer(Some(0.0), None),
+            "i8" => Self::Integer(Some(i8::MIN.into()), Some(i8::MAX.into())),
+            "i16" => Self::Integer(Some(i16::MIN.into()), Some(i16::MAX.into())),
+            "i32" | "i64" | "i128" | "isize" => Self::Integer(None, None),
+            _ => Self::Select,
+        };
+        Ok(kind)
+    }
+
+    /// A generic path type: only a `Vec` of an enum has a form schema.
+    fn collection(ty: &Type) -> syn::Result<Self> {
+        if let Some(item) = generic_inner(ty, "Vec") {
+            return match Self::of(item)? {
+                Self::Select => Ok(Self::MultiSelect(Box::new(item.clone()))),
+                _ => Err(syn::Error::new_spanned(
+                    item,
+                    "a multi-select field is a `Vec` of a `#[derive(ElicitForm)]` enum: a form has no list of free values",
+                )),
+            };
+        }
+        if generic_inner(ty, "Option").is_some() {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "`Option` makes a form field optional once; it does not nest",
+            ));
+        }
+        Ok(Self::Select)
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::String => "a string",
+            Self::Integer(..) | Self::Number => "a number",
+            Self::Boolean => "a boolean",
+            Self::Select => "a single-select",
+            Self::MultiSelect(_) => "a multi-select",
+        }
+    }
+
+    fn schema(&self, ty: &Type, attrs: &FieldAttrs) -> TokenStream {
+        let mut schema = match self {
+            Self::String => {
+                let min_length = option(attrs.min_length.map(Literal::usize_suffixed));
+                let max_length = option(attrs.max_length.map(Literal::usize_suffixed));
+                let format = option(attrs.format.as_ref().map(|format| {
+                    let variant = syn::Ident::new(format, Span::call_site());
+                    quote!(neva::types::StringFormat::#variant)
+                }));
+                quote! {
+                    neva::types::Schema::String(neva::types::StringSchema {
+                        min_length: #min_length,
+                        max_length: #max_length,
+                        format: #format,
+                        ..::core::default::Default::default()
+                    })
+                }
+            }
+            Self::Integer(min, max) => {
+                let min = option(attrs.min.or(*min).map(Literal::f64_suffixed));
+                let max = option(attrs.max.or(*max).map(Literal::f64_suffixed));
+                quote! {
+                    neva::types::Schema::Number(neva::types::NumberSchema {
+                        r#type: neva::types::PropertyType::Integer,
+                        min: #min,
+                        max: #max,
+                        ..::core::default::Default::default()
+                    })
+                }
+            }
+            Self::Number => {
+                let min = option(attrs.min.map(Literal::f64_suffixed));
+                let max = option(attrs.max.map(Literal::f64_suffixed));
+                quote! {
+                    neva::types::Schema::Number(neva::types::NumberSchema {
+                        min: #min,
+                        max: #max,
+                        ..::core::default::Default::default()
+                    })
+                }
+            }
+            Self::Boolean => quote!(neva::types::Schema::boolean()),
+            Self::Select => quote!(neva::types::Schema::select::<#ty>()),
+            Self::MultiSelect(item) => {
+                let min_items = option(attrs.min_items.map(Literal::usize_suffixed));
+                let max_items = option(attrs.max_items.map(Literal::usize_suffixed));
+                quote!(neva::types::Schema::multi_select::<#item>(#min_items, #max_items))
+            }
+        };
+
+        if let Some(title) = &attrs.title {
+            schema = quote!(#schema.with_title(#title));
+        }
+        if let Some(descr) = &attrs.descr {
+            schema = quote!(#schema.with_descr(#descr));
+        }
+        if let Some(default) = &attrs.default {
+            schema = quote!(#schema.with_default(#default));
+        }
+        schema
+    }
+}
+
+/// What a field's `#[elicit(...)]` says, checked against the field's kind.
+#[derive(Default)]
+struct FieldAttrs {
+    title: Option<LitStr>,
+    descr: Option<LitStr>,
+    /// The `StringFormat` variant.
+    format: Option<String>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    min: Option<f64>,
+    max: Option<f64>,
+    min_items: Option<usize>,
+    max_items: Option<usize>,
+    /// A value of the field's own type, ready to pass to `with_default`.
+    default: Option<TokenStream>,
+}
+
+impl FieldAttrs {
+    fn of(attrs: &[Attribute], kind: &Kind) -> syn::Result<Self> {
+        let mut this = Self::default();
+        for attr in attrs.iter().filter(|a| a.path().is_ident("elicit")) {
+            attr.parse_nested_meta(|meta| {
+                let key = meta
+                    .path
+                    .get_ident()
+                    .map(ToString::to_string)
+                    .unwrap_or_default();
+                let misplaced = |expected: &str| {
+                    meta.error(format!(
+                        "`{key}` applies to {expected} field, and this is {}",
+                        kind.name()
+                    ))
+                };
+                match key.as_str() {
+                    "title" => this.title = Some(meta.value()?.parse()?),
+                    "descr" => this.descr = Some(meta.value()?.parse()?),
+                    "format" => {
+                        if !matches!(kind, Kind::String) {
+                            return Err(misplaced("a string"));
+                        }
+                        let format: LitStr = meta.value()?.parse()?;
+                        let variant = match format.value().as_str() {
+                            "email" => "Email",
+                            "uri" => "Uri",
+                            "date" => "Date",
+                            "date-time" => "DateTime",
+                            _ => {
+                                return Err(syn::Error::new_spanned(
+                                    format,
+                                    "a form string's format is \"email\", \"uri\", \"date\" or \"date-time\"",
+                                ));
+                            }
+                        };
+                        this.format = Some(variant.into());
+                    }
+                    "min_length" | "max_length" => {
+                        if !matches!(kind, Kind::String) {
+                            return Err(misplaced("a string"));
+                        }
+                        let value = Some(meta.value()?.parse::<syn::LitInt>()?.base10_parse()?);
+                        if key == "min_length" {
+                            this.min_length = value;
+                        } else {
+                            this.max_length = value;
+                        }
+                    }
+                    "min" | "max" => {
+                        if !matches!(kind, Kind::Integer(..) | Kind::Number) {
+                            return Err(misplaced("a number"));
+                        }
+                        let expr: Expr = meta.value()?.parse()?;
+                        let value = Some(number(&expr, kind)?);
+                        if key == "min" {
+                            this.min = value;
+                        } else {
+                            this.max = value;
+                        }
+                    }
+                    "min_items" | "max_items" => {
+                        if !matches!(kind, Kind::MultiSelect(_)) {
+                            return Err(misplaced("a multi-select"));
+                        }
+                        let value = Some(meta.value()?.parse::<syn::LitInt>()?.base10_parse()?);
+                        if key == "min_items" {
+                            this.min_items = value;
+                        } else {
+                            this.max_items = value;
+                        }
+                    }
+                    "default" => {
+                        let expr: Expr = meta.value()?.parse()?;
+                        this.default = Some(default(&expr, kind)?);
+                    }
+                    _ => {
+                        return Err(meta.error(
+                            "expected `title`, `descr`, `default`, `format`, `min_length`, `max_length`, `min`, `max`, `min_items` or `max_items`",
+                        ));
+                    }
+                }
+                Ok(())
+            })?;
+        }
+
+        if let (Some(min), Some(max)) = (this.min_length, this.max_length)
+            && min > max
+        {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                format!("`min_length = {min}` is above `max_length = {max}`"),
+            ));
+        }
+        if let (Some(min), Some(max)) = (this.min, this.max)
+            && min > max
+        {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                format!("`min = {min}` is above `max = {max}`"),
+            ));
+        }
+        Ok(this)
+    }
+}
+
+/// A `min` or `max` bound: a number literal, negated or not, and a whole one
+/// for an integer field.
+fn number(expr: &Expr, kind: &Kind) -> syn::Result<f64> {
+    let (negative, lit) = match expr {
+        Expr::Unary(unary) if matches!(unary.op, UnOp::Neg(_)) => (true, &*unary.expr),
+        expr => (false, expr),
+    };
+    let value = match lit {
+        Expr::Lit(syn::ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_parse::<f64>()?,
+        Expr::Lit(syn::ExprLit {
+            lit: Lit::Float(float),
+            ..
+        }) if !matches!(kind, Kind::Integer(..)) => float.base10_parse::<f64>()?,
+        _ => {
+            let expected = match kind {
+                Kind::Integer(..) => "an integer literal",
+                _ => "a number literal",
+            };
+            return Err(syn::Error::new_spanned(
+                expr,
+                format!("expected {expected}"),
+            ));
+        }
+    };
+    Ok(if negative { -value } else { value })
+}
+
+/// A `default` of the field's own type, as tokens for `with_default`.
+fn default(expr: &Expr, kind: &Kind) -> syn::Result<TokenStream> {
+    let lit = match expr {
+        Expr::Lit(syn::ExprLit { lit, .. }) => Some(lit),
+        _ => None,
+    };
+    match (kind, lit) {
+        (Kind::String | Kind::Select, Some(Lit::Str(s))) => Ok(s.to_token_stream()),
+        (Kind::Boolean, Some(Lit::Bool(b))) => Ok(b.to_token_stream()),
+        (Kind::Integer(..), _) => {
+            let value = number(expr, kind)?;
+            Ok(Literal::i64_suffixed(value as i64).into_token_stream())
+        }
+        (Kind::Number, _) => Ok(Literal::f64_suffixed(number(expr, kind)?).into_token_stream()),
+        (Kind::MultiSelect(_), None) => {
+            let Expr::Array(array) = expr else {
+                return Err(syn::Error::new_spanned(
+                    expr,
+                    "a multi-select default is an array of option values",
+                ));
+            };
+            let mut values = Vec::with_capacity(array.elems.len());
+            for elem in &array.elems {
+                match elem {
+                    Expr::Lit(syn::ExprLit {
+                        lit: Lit::Str(s), ..
+                    }) => values.push(s),
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            elem,
+                            "a multi-select default is an array of option values",
+                        ));
+                    }
+                }
+            }
+            Ok(quote!(::std::vec![#(#values),*]))
+        }
+        _ => Err(syn::Error::new_spanned(
+            expr,
+            format!("expected a default for {} field", kind.name()),
+        )),
+    }
+}
+
+/// A variant's `#[elicit(title = "...")]`.
+fn variant_title(attrs: &[Attribute]) -> syn::Result<Option<LitStr>> {
+    let mut title = None;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("elicit")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("title") {
+                title = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("a form enum variant takes only `title`"))
+            }
+        })?;
+    }
+    Ok(title)
+}
+
+/// The serde attributes that decide what a form is submitted as.
+#[derive(Default)]
+struct Serde {
+    rename: Option<String>,
+    rename_all: Option<RenameRule>,
+    skip: bool,
+    default: bool,
+    flatten: Option<Span>,
+    tagged: Option<Span>,
+}
+
+impl Serde {
+    fn of(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut this = Self::default();
+        for attr in attrs.iter().filter(|a| a.path().is_ident("serde")) {
+            attr.parse_nested_meta(|meta| {
+                let path = &meta.path;
+                if path.is_ident("rename") {
+                    this.rename = deserialize_name(&meta)?.map(|s| s.value());
+                } else if path.is_ident("rename_all") {
+                    if let Some(rule) = deserialize_name(&meta)? {
+                        this.rename_all = Some(RenameRule::parse(&rule)?);
+                    }
+                } else if path.is_ident("skip")
+                    || path.is_ident("skip_deserializing")
+                    || path.is_ident("other")
+                {
+                    this.skip = true;
+                } else if path.is_ident("default") {
+                    this.default = true;
+                    skip_value(&meta)?;
+                } else if path.is_ident("flatten") {
+                    this.flatten = Some(path.span());
+                } else if path.is_ident("tag") || path.is_ident("untagged") {
+                    this.tagged = Some(path.span());
+                    skip_value(&meta)?;
+                } else {
+                    skip_value(&meta)?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(this)
+    }
+}
+
+/// The name a `rename` or `rename_all` gives when deserializing: either the
+/// one name given, or its `deserialize = "..."` half.
+fn deserialize_name(meta: &syn::meta::ParseNestedMeta<'_>) -> syn::Result<Option<LitStr>> {
+    if meta.input.peek(syn::Token![=]) {
+        return Ok(Some(meta.value()?.parse()?));
+    }
+    let mut name = None;
+    meta.parse_nested_meta(|inner| {
+        let value: LitStr = inner.value()?.parse()?;
+        if inner.path.is_ident("deserialize") {
+            name = Some(value);
+        }
+        Ok(())
+    })?;
+    Ok(name)
+}
+
+/// Steps over the value of a serde attribute this derive does not read.
+fn skip_value(meta: &syn::meta::ParseNestedMeta<'_>) -> syn::Result<()> {
+    if meta.input.peek(syn::Token![=]) {
+        meta.value()?.parse::<Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        let content;
+        syn::parenthesized!(content in meta.input);
+        content.parse::<TokenStream>()?;
+    }
+    Ok(())
+}
+
+/// serde's `rename_all` rules, applied the way serde applies them.
+#[derive(Clone, Copy)]
+enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn parse(lit: &LitStr) -> syn::Result<Self> {
+        Ok(match lit.value().as_str() {
+            "lowercase" => Self::Lower,
+            "UPPERCASE" => Self::Upper,
+            "PascalCase" => Self::Pascal,
+            "camelCase" => Self::Camel,
+            "snake_case" => Self::Snake,
+            "SCREAMING_SNAKE_CASE" => Self::ScreamingSnake,
+            "kebab-case" => Self::Kebab,
+            "SCREAMING-KEBAB-CASE" => Self::ScreamingKebab,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    "unknown serde `rename_all` rule",
+                ));
+            }
+        })
+    }
+
+    /// A `PascalCase` variant name under this rule.
+    fn variant(self, name: &str) -> String {
+        match self {
+            Self::Pascal => name.to_owned(),
+            Self::Lower => name.to_ascii_lowercase(),
+            Self::Upper => name.to_ascii_uppercase(),
+            Self::Camel => name[..1].to_ascii_lowercase() + &name[1..],
+            Self::Snake => {
+                let mut snake = String::with_capacity(name.len() + 4);
+                for (i, ch) in name.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                snake
+            }
+            Self::ScreamingSnake => Self::Snake.variant(name).to_ascii_uppercase(),
+            Self::Kebab => Self::Snake.variant(name).replace('_', "-"),
+            Self::ScreamingKebab => Self::ScreamingSnake.variant(name).replace('_', "-"),
+        }
+    }
+
+    /// A `snake_case` field name under this rule.
+    fn field(self, name: &str) -> String {
+        match self {
+            Self::Lower | Self::Snake => name.to_owned(),
+            Self::Upper | Self::ScreamingSnake => name.to_ascii_uppercase(),
+            Self::Pascal => {
+                let mut pascal = String::with_capacity(name.len());
+                let mut capitalize = true;
+                for ch in name.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            Self::Camel => {
+                let pascal = Self::Pascal.field(name);
+                pascal[..1].to_ascii_lowercase() + &pascal[1..]
+            }
+            Self::Kebab => name.replace('_', "-"),
+            Self::ScreamingKebab => Self::ScreamingSnake.field(name).replace('_', "-"),
+        }
+    }
+}
+
+/// `Some(#value)` or `None`, as tokens.
+fn option<T: ToTokens>(value: Option<T>) -> TokenStream {
+    match value {
+        Some(value) => quote!(::core::option::Option::Some(#value)),
+        None => quote!(::core::option::Option::None),
+    }
+}
+
+/// The `T` of an `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    generic_inner(ty, "Option")
+}
+
+/// The single type argument of `ty` when its last segment is `wrapper`.
+fn generic_inner<'a>(ty: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != wrapper {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(inner) if args.args.len() == 1 => Some(inner),
+        _ => None,
+    }
+}
+
+/// The identifier of a path type with no generic arguments.
+fn plain_ident(ty: &Type) -> Option<&syn::Ident> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    if path.qself.is_some() {
+        return None;
+    }
+    let segment = path.path.segments.last()?;
+    matches!(segment.arguments, PathArguments::None).then_some(&segment.ident)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RenameRule;
+
+    #[test]
+    fn it_renames_variants_the_way_serde_does() {
+        assert_eq!(RenameRule::Snake.variant("DateTime"), "date_time");
+        assert_eq!(RenameRule::ScreamingKebab.variant("DateTime"), "DATE-TIME");
+        assert_eq!(RenameRule::Camel.variant("DateTime"), "dateTime");
+        assert_eq!(RenameRule::Lower.variant("DateTime"), "datetime");
+    }
+
+    #[test]
+    fn it_renames_fields_the_way_serde_does() {
+        assert_eq!(RenameRule::Camel.field("check_in_date"), "checkInDate");
+        assert_eq!(RenameRule::Pascal.field("check_in_date"), "CheckInDate");
+        assert_eq!(RenameRule::Kebab.field("check_in_date"), "check-in-date");
+        assert_eq!(RenameRule::Lower.field("check_in_date"), "check_in_date");
+    }
+}